@@ -0,0 +1,140 @@
+-- Compares ballot queries that find cohort peers through epeers()
+-- with the cohort-tagged ballots that replaced them.
+-- Run it against a scratch database that has the API's tables,
+-- for example after starting the API against it once:
+--
+--     psql -d ehall_bench -v attendees=600 -f api/bench/cohort_queries.sql
+--
+-- Everything happens in a transaction that is rolled back.
+
+\if :{?attendees}
+\else
+\set attendees 600
+\endif
+\set meeting -1
+\set voter '''a1@example.com'''
+\timing on
+
+begin;
+
+insert into user_topics (email, topic, score)
+select format('a%s@example.com', a), format('topic %s of a%s', t, a), t
+from generate_series(1, :attendees) a, generate_series(1, 3) t;
+
+insert into meeting_attendees (meeting, email)
+select :meeting, format('a%s@example.com', a)
+from generate_series(1, :attendees) a;
+
+-- As when each attendee joins after everyone else, so every ballot is full
+insert into meeting_topics (email, meeting, topic, score)
+select a.email, :meeting, u.id, row_number() over (partition by a.email order by u.id) - 1
+from meeting_attendees a
+join user_topics u on u.email like 'a%@example.com'
+where a.meeting = :meeting;
+
+-- Cohorts of five, like election::cohorts for a large meeting
+with grp as (
+    insert into cohort_groups (meeting) values (:meeting) returning id
+)
+insert into cohort_members (cohort_group, cohort, email)
+select grp.id, (a - 1) / 5, format('a%s@example.com', a)
+from grp, generate_series(1, :attendees) a;
+
+insert into meeting_votes (meeting, email, topic, score)
+select meeting, email, topic, score from meeting_topics
+where meeting = :meeting;
+
+analyze;
+
+\echo before: ballot
+select count(*) from
+    (select topic as text, id, false as seeded from user_topics
+        where email in (select epeers(:voter, :meeting))
+    union all
+    select topic as text, id, true as seeded from seed_topics
+        where meeting = :meeting) u
+    join
+    (select topic as id, score from meeting_topics
+        where meeting = :meeting and email = :voter) m
+    on u.id = m.id;
+
+\echo before: tally
+select count(*) from
+    (select email, topic, score from meeting_votes
+        where meeting = :meeting and email in (select epeers(:voter, :meeting))) as m
+    join
+    (select topic as text, id, false as seeded from user_topics
+        where email in (select epeers(:voter, :meeting))
+    union all
+    select topic as text, id, true as seeded from seed_topics
+        where meeting = :meeting) u
+    on m.topic = u.id;
+
+\echo tagging ballots (TAG_BALLOT_COHORTS)
+update meeting_topics b
+set cohort = eligible.cohort
+from (
+    select voter.email, ut.id as topic, voter.cohort
+    from cohort_groups cg
+    join cohort_members voter on voter.cohort_group = cg.id
+    join cohort_members proposer
+        on proposer.cohort_group = cg.id and proposer.cohort = voter.cohort
+    join user_topics ut on ut.email = proposer.email
+    where cg.meeting = :meeting
+    union all
+    select voter.email, s.id as topic, voter.cohort
+    from cohort_groups cg
+    join cohort_members voter on voter.cohort_group = cg.id
+    join seed_topics s on s.meeting = cg.meeting
+    where cg.meeting = :meeting
+) eligible
+where b.meeting = :meeting and b.email = eligible.email and b.topic = eligible.topic;
+update meeting_votes b
+set cohort = eligible.cohort
+from (
+    select voter.email, ut.id as topic, voter.cohort
+    from cohort_groups cg
+    join cohort_members voter on voter.cohort_group = cg.id
+    join cohort_members proposer
+        on proposer.cohort_group = cg.id and proposer.cohort = voter.cohort
+    join user_topics ut on ut.email = proposer.email
+    where cg.meeting = :meeting
+    union all
+    select voter.email, s.id as topic, voter.cohort
+    from cohort_groups cg
+    join cohort_members voter on voter.cohort_group = cg.id
+    join seed_topics s on s.meeting = cg.meeting
+    where cg.meeting = :meeting
+) eligible
+where b.meeting = :meeting and b.email = eligible.email and b.topic = eligible.topic;
+
+analyze;
+
+\echo after: ballot
+select count(*)
+from meeting_topics m
+join
+(select topic as text, id, false as seeded from user_topics
+union all
+select topic as text, id, true as seeded from seed_topics
+    where meeting = :meeting) u
+on m.topic = u.id
+where m.meeting = :meeting and m.email = :voter and m.cohort is not null;
+
+\echo after: tally
+select count(*)
+from meeting_votes m
+join
+(select topic as text, id, false as seeded from user_topics
+union all
+select topic as text, id, true as seeded from seed_topics
+    where meeting = :meeting) u
+on m.topic = u.id
+where m.meeting = :meeting and m.cohort = (
+    select cm.cohort
+    from cohort_groups cg
+    join cohort_members cm on cm.cohort_group = cg.id
+    where cg.meeting = :meeting and cm.email = :voter
+);
+
+rollback;
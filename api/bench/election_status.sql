@@ -0,0 +1,13 @@
+-- The single query a poll issues until the results are in, from get_election_results.
+-- Run with pgbench; see the README for the invocation.
+select s.meeting_name, s.email, s.voted,
+    g.id is not null, g.round, extract(epoch from g.voting_closes_at)::bigint,
+    m.share_contacts,
+    (
+        select me.cohort from cohort_members me
+        where me.cohort_group = g.id and me.email = :uid
+        limit 1
+    )
+from election_status(:uid, :mtg) s
+join meetings m on m.id = :mtg
+left join cohort_groups g on g.meeting = :mtg;
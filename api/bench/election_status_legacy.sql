@@ -0,0 +1,7 @@
+-- The per-poll queries issued by get_election_results before election_status.
+-- Run with pgbench; see the README for the invocation.
+select n_cohort_peers(:uid, :mtg);
+select epeers(:uid, :mtg);
+select email, voted from meeting_attendees
+    where meeting = :mtg and email in (select epeers(:uid, :mtg));
+select name from meetings where id = :mtg;
@@ -0,0 +1,16 @@
+CREATE or replace FUNCTION n_cohort_peers(uid varchar, mtg bigint) RETURNS table (n bigint) AS $$
+<< outerblock >>
+DECLARE
+    cgrp bigint;
+BEGIN
+    select count(id) as cohort_group into strict cgrp
+    from cohort_groups
+    where meeting = mtg;
+    if not found then
+        return query (select 0);
+    end if;
+RETURN query (
+    select cgrp
+);
+END;
+$$ LANGUAGE plpgsql;
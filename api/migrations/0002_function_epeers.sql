@@ -0,0 +1,19 @@
+CREATE or replace FUNCTION epeers(uid varchar, mtg bigint) RETURNS table (email varchar) AS $$
+<< outerblock >>
+DECLARE
+    cgrp bigint;
+    cht bigint;
+BEGIN
+    select id as cohort_group into strict cgrp
+    from cohort_groups
+    where meeting = mtg;
+    select cohort into strict cht
+    from cohort_members
+    where cohort_group = cgrp and cohort_members.email = uid;
+RETURN query (
+    select cohort_members.email
+        from cohort_members
+    where cohort_group = cgrp and cohort = cht
+);
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,6 @@
+-- id is not a primary key, so that it's not an error to *try*
+-- to create a cohort_group for a meeting that already has one.
+create table if not exists cohort_groups (
+    id bigserial,
+    meeting bigint not null
+);
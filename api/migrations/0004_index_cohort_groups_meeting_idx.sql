@@ -0,0 +1,2 @@
+create unique index if not exists cohort_groups_meeting_idx
+on cohort_groups (meeting);
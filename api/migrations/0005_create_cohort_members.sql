@@ -0,0 +1,5 @@
+create table if not exists cohort_members (
+    cohort_group bigint not null,
+    cohort bigint not null,
+    email varchar (254) not null
+);
@@ -0,0 +1,2 @@
+create index if not exists cohort_members_email_idx
+on cohort_members (cohort_group, email);
@@ -0,0 +1,6 @@
+create table if not exists meeting_topics (
+    email varchar (254) not null,
+    meeting bigint not null,
+    topic bigint not null,
+    score integer default 0
+);
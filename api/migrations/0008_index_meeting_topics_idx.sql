@@ -0,0 +1,2 @@
+create unique index if not exists meeting_topics_idx
+on meeting_topics (meeting, email, topic);
@@ -0,0 +1,2 @@
+-- The voter's cohort, null until the meeting starts
+alter table meeting_topics add column if not exists cohort bigint;
@@ -0,0 +1,8 @@
+-- Each attendee's meeting_topics scores as they were when they voted
+create table if not exists meeting_votes (
+    meeting bigint not null,
+    email varchar (254) not null,
+    topic bigint not null,
+    score integer not null,
+    primary key (meeting, email, topic)
+);
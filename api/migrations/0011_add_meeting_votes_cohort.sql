@@ -0,0 +1 @@
+alter table meeting_votes add column if not exists cohort bigint;
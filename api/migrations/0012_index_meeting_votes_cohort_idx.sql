@@ -0,0 +1,2 @@
+create index if not exists meeting_votes_cohort_idx
+on meeting_votes (meeting, cohort);
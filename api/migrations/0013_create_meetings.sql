@@ -0,0 +1,4 @@
+create table if not exists meetings (
+    name varchar (254) primary key,
+    id bigserial
+);
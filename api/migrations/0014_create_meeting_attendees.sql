@@ -0,0 +1,5 @@
+create table if not exists meeting_attendees (
+    meeting bigint not null,
+    email varchar (254) not null,
+    voted bool default false
+);
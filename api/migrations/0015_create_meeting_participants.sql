@@ -0,0 +1,4 @@
+create table if not exists meeting_participants (
+    meeting bigint not null,
+    email varchar (254) not null
+);
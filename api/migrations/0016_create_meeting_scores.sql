@@ -0,0 +1,5 @@
+create table if not exists meeting_scores (
+    meeting bigint not null,
+    email varchar (254) not null,
+    score integer default 0
+);
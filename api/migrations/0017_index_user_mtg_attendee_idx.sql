@@ -0,0 +1,2 @@
+create unique index if not exists user_mtg_attendee_idx
+on meeting_attendees (meeting, email);
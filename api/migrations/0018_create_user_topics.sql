@@ -0,0 +1,6 @@
+create table if not exists user_topics (
+    email varchar (254) not null,
+    topic varchar (254) not null,
+    id bigserial primary key,
+    score integer default 0
+);
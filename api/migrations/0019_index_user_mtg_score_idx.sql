@@ -0,0 +1,2 @@
+create unique index if not exists user_mtg_score_idx
+on meeting_scores (meeting, email);
@@ -0,0 +1,7 @@
+-- Organizer-seeded topics share the user_topics id sequence,
+-- so meeting_topics.topic can refer to either kind without ambiguity.
+create table if not exists seed_topics (
+    meeting bigint not null,
+    topic varchar (254) not null,
+    id bigint primary key default nextval('user_topics_id_seq')
+);
@@ -0,0 +1,8 @@
+-- Only choices a user has made; everything else uses the channel default.
+create table if not exists notification_preferences (
+    email varchar (254) not null,
+    event varchar (32) not null,
+    channel varchar (32) not null,
+    enabled bool not null,
+    primary key (email, event, channel)
+);
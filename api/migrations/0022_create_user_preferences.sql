@@ -0,0 +1,5 @@
+-- Only users who chose a meetings list order have a row.
+create table if not exists user_preferences (
+    email varchar (254) primary key,
+    meeting_sort varchar (16) not null
+);
@@ -0,0 +1,7 @@
+-- Append-only, so users can see how their interests shift over time
+create table if not exists user_topic_score_history (
+    email varchar (254) not null,
+    topic bigint not null,
+    score integer not null,
+    recorded_at timestamptz not null default now()
+);
@@ -0,0 +1,2 @@
+create index if not exists user_topic_score_history_idx
+on user_topic_score_history (email, recorded_at);
@@ -0,0 +1,6 @@
+-- Organizer questions for registrants, like "what's your team?"
+create table if not exists registration_fields (
+    id bigserial primary key,
+    meeting bigint not null,
+    label varchar (254) not null
+);
@@ -0,0 +1,11 @@
+-- Responses to requests with an Idempotency-Key header, kept for replays.
+-- A null status marks a request that is still being processed.
+create table if not exists idempotency_keys (
+    email varchar (254) not null,
+    key varchar (128) not null,
+    route varchar (254) not null,
+    status integer,
+    body text,
+    created_at timestamptz not null default now(),
+    primary key (email, key)
+);
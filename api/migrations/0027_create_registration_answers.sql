@@ -0,0 +1,6 @@
+create table if not exists registration_answers (
+    field bigint not null,
+    email varchar (254) not null,
+    answer varchar (254) not null,
+    primary key (field, email)
+);
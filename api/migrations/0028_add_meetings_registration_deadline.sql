@@ -0,0 +1,2 @@
+-- Null means registration stays open until the meeting starts.
+alter table meetings add column if not exists registration_deadline timestamptz;
@@ -0,0 +1,7 @@
+-- When each cohort member first opened the cohort's conferencing room
+create table if not exists room_joins (
+    meeting bigint not null,
+    email varchar (254) not null,
+    joined_at timestamptz not null default now(),
+    primary key (meeting, email)
+);
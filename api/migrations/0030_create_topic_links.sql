@@ -0,0 +1,7 @@
+-- Reading material for user and seed topics, which share an id sequence
+create table if not exists topic_links (
+    topic bigint not null,
+    position integer not null,
+    url varchar (2048) not null,
+    primary key (topic, position)
+);
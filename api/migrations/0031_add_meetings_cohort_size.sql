@@ -0,0 +1,2 @@
+-- The default is COHORT_QUORUM, the size before meetings chose their own.
+alter table meetings add column if not exists cohort_size integer not null default 3;
@@ -0,0 +1,2 @@
+-- Null for meetings from before ownership, which every signed-in user may manage
+alter table meetings add column if not exists owner varchar (254);
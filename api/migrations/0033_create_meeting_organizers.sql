@@ -0,0 +1,6 @@
+-- Co-organizers, whom a meeting's owner chooses
+create table if not exists meeting_organizers (
+    meeting bigint not null,
+    email varchar (254) not null,
+    primary key (meeting, email)
+);
@@ -0,0 +1,2 @@
+-- The VetoPolicy, by its as_str name
+alter table meetings add column if not exists veto_policy varchar (16) not null default 'off';
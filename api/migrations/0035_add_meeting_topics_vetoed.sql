@@ -0,0 +1 @@
+alter table meeting_topics add column if not exists vetoed boolean not null default false;
@@ -0,0 +1,3 @@
+-- Each attendee vetoes at most one topic.
+create unique index if not exists meeting_topics_veto_idx
+on meeting_topics (meeting, email) where vetoed;
@@ -0,0 +1,2 @@
+-- The VotingMethod, by its as_str name
+alter table meetings add column if not exists voting_method varchar (16) not null default 'borda';
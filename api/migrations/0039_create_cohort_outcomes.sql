@@ -0,0 +1,9 @@
+-- What each cohort decided to do next, recorded after its conversation
+create table if not exists cohort_outcomes (
+    meeting bigint not null,
+    cohort bigint not null,
+    text varchar (4000) not null,
+    recorded_by varchar (254) not null,
+    recorded_at timestamptz not null default now(),
+    primary key (meeting, cohort)
+);
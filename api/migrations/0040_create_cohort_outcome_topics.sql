@@ -0,0 +1,8 @@
+-- The elected topics each outcome follows up on, in the order given
+create table if not exists cohort_outcome_topics (
+    meeting bigint not null,
+    cohort bigint not null,
+    position integer not null,
+    topic bigint not null,
+    primary key (meeting, cohort, position)
+);
@@ -0,0 +1,2 @@
+-- The default is DEFAULT_N_WINNERS, the count before meetings chose their own.
+alter table meetings add column if not exists n_winners integer not null default 2;
@@ -0,0 +1,7 @@
+-- Topics an organizer merged as duplicates, each pointing at its group's canonical topic
+create table if not exists topic_aliases (
+    meeting bigint not null,
+    topic bigint not null,
+    canonical bigint not null,
+    primary key (meeting, topic)
+);
@@ -0,0 +1,2 @@
+-- Private topics are personal reminders, kept off meeting ballots.
+alter table user_topics add column if not exists private boolean not null default false;
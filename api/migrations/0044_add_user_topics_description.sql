@@ -0,0 +1,2 @@
+-- Markdown from the proposer, shown with the topic on ballots
+alter table user_topics add column if not exists description varchar (4000) not null default '';
@@ -0,0 +1,11 @@
+-- Written once when a cohort finishes voting, so results outlast the ballots,
+-- which go when attendees leave
+create table if not exists election_results (
+    id bigserial primary key,
+    meeting bigint not null,
+    cohort bigint not null,
+    members varchar (254)[] not null,
+    voting_method varchar (16) not null,
+    recorded_at timestamptz not null,
+    unique (meeting, cohort)
+);
@@ -0,0 +1,11 @@
+-- Topic texts are copied, so results survive the topics' deletion.
+create table if not exists election_result_topics (
+    election bigint not null,
+    position integer not null,
+    topic bigint not null,
+    text varchar (254) not null,
+    score integer not null,
+    seeded boolean not null,
+    vetoed boolean not null,
+    primary key (election, position)
+);
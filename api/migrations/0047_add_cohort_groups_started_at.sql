@@ -0,0 +1,2 @@
+-- Existing meetings count as starting now, which only delays their anonymization.
+alter table cohort_groups add column if not exists started_at timestamptz not null default now();
@@ -0,0 +1,2 @@
+-- Set when the meeting's members' emails were replaced with aliases
+alter table meetings add column if not exists anonymized_at timestamptz;
@@ -0,0 +1,6 @@
+-- Accounts an administrator deactivated, which can't log in or join meetings
+create table if not exists disabled_accounts (
+    email varchar (254) primary key,
+    disabled_at timestamptz not null,
+    disabled_by varchar (254) not null
+);
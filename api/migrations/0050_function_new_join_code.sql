@@ -0,0 +1,17 @@
+-- A code for `POST /join_code` that no meeting has,
+-- made longer after repeated collisions so it cannot run out
+CREATE or replace FUNCTION new_join_code() RETURNS varchar AS $$
+DECLARE
+    attempt integer := 0;
+    n_digits integer;
+    code varchar;
+BEGIN
+    LOOP
+        n_digits := 4 + attempt / 10;
+        code := 'HALL-' || lpad(floor(random() * 10 ^ n_digits)::bigint::text, n_digits, '0');
+        EXIT WHEN NOT EXISTS (select 1 from meetings where join_code = code);
+        attempt := attempt + 1;
+    END LOOP;
+    RETURN code;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,2 @@
+-- Short enough to read aloud at an event, like 'HALL-4921'
+alter table meetings add column if not exists join_code varchar (16) unique;
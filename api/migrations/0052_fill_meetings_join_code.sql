@@ -0,0 +1 @@
+update meetings set join_code = new_join_code() where join_code is null;
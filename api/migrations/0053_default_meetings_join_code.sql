@@ -0,0 +1 @@
+alter table meetings alter column join_code set default new_join_code();
@@ -0,0 +1,9 @@
+-- Emails already sent, so each API instance hearing a meeting event
+-- doesn't send them again
+create table if not exists notification_deliveries (
+    meeting bigint not null,
+    email varchar (254) not null,
+    event varchar (32) not null,
+    sent_at timestamptz not null default now(),
+    primary key (meeting, email, event)
+);
@@ -0,0 +1,2 @@
+-- Counts from 1, and each `POST /meeting/<id>/next_round` adds one.
+alter table cohort_groups add column if not exists round integer not null default 1;
@@ -0,0 +1,9 @@
+-- The cohorts of a meeting's earlier rounds, moved here when the next round starts.
+-- Cohort numbers continue from round to round, so they never repeat within a meeting.
+create table if not exists past_cohort_members (
+    meeting bigint not null,
+    round integer not null,
+    cohort bigint not null,
+    email varchar (254) not null,
+    primary key (meeting, round, email)
+);
@@ -0,0 +1 @@
+alter table election_results add column if not exists round integer not null default 1;
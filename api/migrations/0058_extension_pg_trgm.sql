@@ -0,0 +1,2 @@
+-- Trigram indexes serve the meetings list's substring searches.
+create extension if not exists pg_trgm;
@@ -0,0 +1,2 @@
+create index if not exists meetings_name_trgm_idx
+on meetings using gin (name gin_trgm_ops);
@@ -0,0 +1 @@
+alter table meetings add column if not exists match_language boolean not null default false;
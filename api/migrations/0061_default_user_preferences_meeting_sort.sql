@@ -0,0 +1,2 @@
+-- Preferences are now stored one at a time, so the sort may not be chosen yet.
+alter table user_preferences alter column meeting_sort set default 'ranking';
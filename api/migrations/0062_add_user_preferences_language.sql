@@ -0,0 +1,2 @@
+-- A language subtag, like "de", or null for no preference
+alter table user_preferences add column if not exists language varchar (8);
@@ -0,0 +1,2 @@
+-- When the meeting's topic submission phase ends, or null for no such phase
+alter table meetings add column if not exists submission_deadline timestamptz;
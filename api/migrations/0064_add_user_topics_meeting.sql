@@ -0,0 +1,2 @@
+-- Set for topics submitted to one meeting, which stay off the proposer's own list
+alter table user_topics add column if not exists meeting bigint;
@@ -0,0 +1,8 @@
+-- Each registered user's ranking of a meeting's submitted topics, highest first
+create table if not exists topic_submission_scores (
+    meeting bigint not null,
+    email varchar (254) not null,
+    topic bigint not null,
+    score integer not null,
+    primary key (meeting, email, topic)
+);
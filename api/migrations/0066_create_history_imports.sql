@@ -0,0 +1,13 @@
+-- Past meetings queued by admins, added to history one meeting at a time.
+-- The document is emptied when done, since it lists everyone's emails.
+create table if not exists history_imports (
+    id bigserial primary key,
+    submitted_by varchar (254) not null,
+    submitted_at timestamptz not null,
+    document jsonb not null,
+    n_meetings integer not null,
+    n_imported integer not null default 0,
+    state varchar (16) not null default 'queued',
+    error text,
+    finished_at timestamptz
+);
@@ -0,0 +1,20 @@
+-- One row per cohort peer (including uid) with their vote status,
+-- or a single row with null email when uid has no cohort yet.
+-- It must follow the tables above, because SQL function bodies are checked.
+CREATE or replace FUNCTION election_status(uid varchar, mtg bigint)
+RETURNS table (meeting_name varchar, email varchar, voted bool) AS $$
+    select m.name, peers.email, a.voted
+    from meetings m
+    left join (
+        select cg.meeting, cm.email
+        from cohort_groups cg
+        join cohort_members me
+            on me.cohort_group = cg.id and me.email = uid
+        join cohort_members cm
+            on cm.cohort_group = cg.id and cm.cohort = me.cohort
+    ) peers on peers.meeting = m.id
+    left join meeting_attendees a
+        on a.meeting = m.id and a.email = peers.email
+    where m.id = mtg
+    order by peers.email;
+$$ LANGUAGE sql;
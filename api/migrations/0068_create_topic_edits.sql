@@ -0,0 +1,10 @@
+-- Each version of a topic's description, from version 0, the one it was created with,
+-- which is saved with the first edit and has no edit time.
+create table if not exists topic_edits (
+    topic bigint not null,
+    version int not null,
+    description varchar (4000) not null,
+    edited_by varchar (254) not null,
+    edited_at timestamptz,
+    primary key (topic, version)
+);
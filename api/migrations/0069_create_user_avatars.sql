@@ -0,0 +1,7 @@
+-- Images users uploaded to show beside their names, named as stored
+-- in the [avatars] table's upload directory
+create table if not exists user_avatars (
+    email varchar (254) primary key,
+    file varchar (64) not null,
+    uploaded_at timestamptz not null default now()
+);
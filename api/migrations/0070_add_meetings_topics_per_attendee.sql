@@ -0,0 +1,2 @@
+-- The default is DEFAULT_TOPICS_PER_ATTENDEE, the count before meetings chose their own.
+alter table meetings add column if not exists topics_per_attendee integer not null default 3;
@@ -0,0 +1,8 @@
+-- The own topics each user chose to bring to a meeting's ballots.
+-- Users without rows bring their top-ranked topics.
+create table if not exists attendee_topics (
+    meeting bigint not null,
+    email varchar (254) not null,
+    topic bigint not null,
+    primary key (meeting, email, topic)
+);
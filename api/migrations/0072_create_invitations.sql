@@ -0,0 +1,9 @@
+-- Signup links administrators emailed, which work once until they expire
+create table if not exists invitations (
+    token varchar (64) primary key,
+    email varchar (254) not null,
+    invited_by varchar (254) not null,
+    created_at timestamptz not null,
+    expires_at timestamptz not null,
+    accepted_at timestamptz
+);
@@ -0,0 +1,9 @@
+-- Signups without an invitation, when the [membership] table asks for approval.
+-- The account can't log in until an administrator approves it.
+create table if not exists join_requests (
+    email varchar (254) primary key,
+    requested_at timestamptz not null,
+    decided_by varchar (254),
+    decided_at timestamptz,
+    approved boolean
+);
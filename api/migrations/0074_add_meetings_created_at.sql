@@ -0,0 +1,4 @@
+-- When the meeting was created, for digests' new meetings.
+-- Meetings from before this column have none, so they're never new.
+alter table meetings add column if not exists created_at timestamptz;
+alter table meetings alter column created_at set default now();
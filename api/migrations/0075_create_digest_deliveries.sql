@@ -0,0 +1,10 @@
+-- Digests sent, one per person, kind, and period,
+-- so several API instances send each once.
+-- The period is the day for daily digests and the week's Monday for weekly ones.
+create table if not exists digest_deliveries (
+    email varchar (254) not null,
+    event varchar (32) not null,
+    period date not null,
+    sent_at timestamptz not null default now(),
+    primary key (email, event, period)
+);
@@ -0,0 +1,3 @@
+-- 'participant' or 'observer'.  Observers join cohorts without ballots,
+-- and their voted flag is always set, so their cohorts finish without them.
+alter table meeting_attendees add column if not exists role varchar (16) not null default 'participant';
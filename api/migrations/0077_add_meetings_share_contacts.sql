@@ -0,0 +1 @@
+alter table meetings add column if not exists share_contacts boolean not null default false;
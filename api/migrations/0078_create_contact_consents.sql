@@ -0,0 +1,9 @@
+-- Each cohort member's choice whether to share their email with the rest of the cohort,
+-- in meetings that share contacts.  Members without a row haven't chosen.
+create table if not exists contact_consents (
+    meeting bigint not null,
+    email varchar (254) not null,
+    share boolean not null,
+    decided_at timestamptz not null default now(),
+    primary key (meeting, email)
+);
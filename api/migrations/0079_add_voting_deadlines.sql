@@ -0,0 +1,4 @@
+-- A meeting's optional voting time, and when voting closes in its current round.
+-- Null means voting stays open until every cohort member votes.
+alter table meetings add column if not exists voting_minutes integer;
+alter table cohort_groups add column if not exists voting_closes_at timestamptz;
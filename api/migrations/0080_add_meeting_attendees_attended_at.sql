@@ -0,0 +1,4 @@
+-- When the attendee joined the meeting's lobby, for removing stale attendees.
+-- Attendees from before this column have none, so they're never stale.
+alter table meeting_attendees add column if not exists attended_at timestamptz;
+alter table meeting_attendees alter column attended_at set default now();
@@ -0,0 +1,2 @@
+-- Whether the user agrees to their ballots being exported, anonymized, for research.
+alter table user_preferences add column if not exists research_consent boolean not null default false;
@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+
+use tokio_postgres::Error;
+
+use crate::db::Client;
+
+// Every topic in a merged group points at the same canonical topic,
+// which has no row of its own.
+const GET_ALIASES: &str = "select topic, canonical from topic_aliases where meeting = $1";
+
+// The merged topics, and any already merged with them, join $3's group.
+const MERGE: &str = "
+    with roots as (
+        select coalesce(a.canonical, t.id) as root
+        from unnest($2::bigint[]) as t (id)
+        left join topic_aliases a on a.meeting = $1 and a.topic = t.id
+    ), members as (
+        select unnest($2::bigint[]) as topic
+        union
+        select topic from topic_aliases
+        where meeting = $1 and canonical in (select root from roots)
+        union
+        select root from roots
+    )
+    insert into topic_aliases (meeting, topic, canonical)
+    select $1, topic, $3 from members where topic <> $3
+    on conflict (meeting, topic) do update set canonical = excluded.canonical
+";
+
+// When $2 was its group's canonical topic, the lowest remaining id takes over.
+const SPLIT: &str = "
+    with heir as (
+        select min(topic) as topic from topic_aliases
+        where meeting = $1 and canonical = $2
+    ), dropped as (
+        delete from topic_aliases
+        where meeting = $1 and (topic = $2 or topic = (select topic from heir))
+    )
+    update topic_aliases set canonical = (select topic from heir)
+    where meeting = $1 and canonical = $2 and topic <> (select topic from heir)
+";
+
+/// The canonical topic of each merged topic in the meeting, by topic id
+pub async fn topic_aliases(client: &Client, meeting_id: i64) -> Result<HashMap<i64, i64>, Error> {
+    let rows = client.query(GET_ALIASES, &[&meeting_id]).await?;
+    Ok(rows.iter().map(|row| (row.get(0), row.get(1))).collect())
+}
+
+/// Merges `topics` into one group, along with the groups they are already in
+pub async fn merge(client: &Client, meeting_id: i64, topics: &[i64]) -> Result<(), Error> {
+    let aliases = topic_aliases(client, meeting_id).await?;
+    let canonical = aliases.get(&topics[0]).unwrap_or(&topics[0]);
+    client
+        .execute(MERGE, &[&meeting_id, &topics, canonical])
+        .await?;
+    Ok(())
+}
+
+/// Takes `topic` out of its merged group, leaving the rest merged
+pub async fn split(client: &Client, meeting_id: i64, topic: i64) -> Result<(), Error> {
+    client.execute(SPLIT, &[&meeting_id, &topic]).await?;
+    Ok(())
+}
+
+/// Pairs of indices into `topics` whose topics were merged,
+/// as `election::merge_topics` takes them
+pub fn alias_pairs(topics: &[i64], aliases: &HashMap<i64, i64>) -> Vec<(usize, usize)> {
+    let mut first_of_group: HashMap<i64, usize> = HashMap::new();
+    topics
+        .iter()
+        .enumerate()
+        .filter_map(|(i, topic)| {
+            let group = *aliases.get(topic).unwrap_or(topic);
+            let first = *first_of_group.entry(group).or_insert(i);
+            (first != i).then_some((first, i))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::alias_pairs;
+
+    #[test]
+    fn test_alias_pairs() {
+        // 12 and 15 were merged into 11, which is not on this ballot.
+        let aliases = [(12, 11), (15, 11), (14, 13)].into();
+        assert_eq!(
+            alias_pairs(&[12, 13, 14, 15, 16], &aliases),
+            [(1, 2), (0, 3)]
+        );
+        assert!(alias_pairs(&[12, 16], &aliases).is_empty());
+        assert!(alias_pairs(&[], &aliases).is_empty());
+    }
+}
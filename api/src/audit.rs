@@ -0,0 +1,91 @@
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::Result;
+
+use ehall::{CohortAudit, ElectionAudit};
+use ehall_election as election;
+
+use crate::aliases;
+use crate::db::Client;
+
+// Every committed ballot in the meeting, as the tally sees them
+const GET_MEETING_BALLOTS: &str = "
+    select m.cohort, m.email, m.score, m.topic, u.text
+    from meeting_votes m
+    join
+    (select topic as text, id from user_topics
+    union all
+    select topic as text, id from seed_topics
+        where meeting = $1) u
+    on m.topic = u.id
+    where m.meeting = $1 and m.cohort is not null
+    order by m.cohort, m.email, m.topic
+";
+
+/// Recounts each cohort's ballots with `election::recount`
+/// and compares the result with the election's own tally.
+pub async fn audit_meeting(client: &Client, meeting_id: i64) -> Result<ElectionAudit> {
+    let rows = client.query(GET_MEETING_BALLOTS, &[&meeting_id]).await?;
+    let mut cohorts: BTreeMap<i64, BTreeMap<String, Vec<BallotRow>>> = BTreeMap::new();
+    for row in rows {
+        cohorts
+            .entry(row.get::<_, i64>(0))
+            .or_default()
+            .entry(row.get::<_, String>(1))
+            .or_default()
+            .push((
+                row.get::<_, i32>(2) as usize,
+                row.get::<_, i64>(3),
+                row.get::<_, String>(4),
+            ));
+    }
+    let merged = aliases::topic_aliases(client, meeting_id).await?;
+    let cohorts = cohorts
+        .into_iter()
+        .map(|(cohort, ballots)| audit_cohort(cohort, ballots.into_values().collect(), &merged))
+        .collect::<Result<_>>()?;
+    Ok(ElectionAudit {
+        meeting_id: meeting_id as u32,
+        cohorts,
+    })
+}
+
+// A ballot's score, topic id, and topic text for one topic
+type BallotRow = (usize, i64, String);
+
+// Organizer-merged topics are merged here too, as the tally merges them.
+fn audit_cohort(
+    cohort: i64,
+    ballots: Vec<Vec<BallotRow>>,
+    merged: &HashMap<i64, i64>,
+) -> Result<CohortAudit> {
+    let first = ballots.first().map(Vec::as_slice).unwrap_or_default();
+    let topics: Vec<i64> = first.iter().map(|(_, topic, _)| *topic).collect();
+    let texts: Vec<String> = first.iter().map(|(.., text)| text.clone()).collect();
+    let rankings: Vec<_> = ballots
+        .iter()
+        .map(|ballot| election::Ranking {
+            scores: ballot.iter().map(|(score, ..)| *score).collect(),
+        })
+        .collect();
+    let same = aliases::alias_pairs(&topics, merged);
+    let (groups, rankings) = election::merge_topics(&texts, &same, &rankings)?;
+    let reported = election::borda_count(&rankings)?;
+    let recount = election::recount(&rankings);
+    let discrepancies = election::discrepancies(&recount, &reported);
+    let to_u32 = |v: Vec<usize>| v.into_iter().map(|n| n as u32).collect::<Vec<_>>();
+    // Sorting hides which ballot came from which member.
+    let mut ballots: Vec<_> = rankings.into_iter().map(|r| to_u32(r.scores)).collect();
+    ballots.sort();
+    Ok(CohortAudit {
+        cohort: cohort as u32,
+        topics: groups.iter().map(|g| texts[g[0]].clone()).collect(),
+        ballots,
+        reported: to_u32(reported),
+        recount: to_u32(recount),
+        discrepancies: discrepancies
+            .into_iter()
+            .map(|(a, b)| (a as u32, b as u32))
+            .collect(),
+    })
+}
@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::fs;
+use tokio_postgres::Error;
+
+use ehall::AVATAR_CONTENT_TYPES;
+
+use crate::db::Client;
+
+/// Where `GET /avatars/<file>` serves uploaded images from
+pub const ROUTE: &str = "/avatars";
+
+/// The optional `[avatars]` table of the config file.
+/// Without it, cohort members appear by name alone.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct AvatarConfig {
+    /// Shows the Gravatar of users who haven't uploaded an image,
+    /// which browsers fetch from gravatar.com by a hash of the email
+    #[serde(default)]
+    pub gravatar: bool,
+    /// Where uploaded images are stored, like a mounted object-store bucket.
+    /// Without it, uploads are refused.
+    pub upload_directory: Option<PathBuf>,
+}
+
+impl AvatarConfig {
+    pub fn enabled(&self) -> bool {
+        self.gravatar || self.upload_directory.is_some()
+    }
+}
+
+// https://docs.gravatar.com/api/avatars/images/
+// Users without a Gravatar get a generated pattern rather than a broken image.
+fn gravatar_url(email: &str) -> String {
+    let hash = Sha256::digest(email.trim().to_lowercase().as_bytes());
+    let hex: String = hash.iter().map(|b| format!("{b:02x}")).collect();
+    format!("https://www.gravatar.com/avatar/{hex}?s=80&d=identicon")
+}
+
+fn extension(content_type: &str) -> Option<&'static str> {
+    let i = AVATAR_CONTENT_TYPES
+        .iter()
+        .position(|&t| t == content_type)?;
+    Some(["png", "jpg", "webp"][i])
+}
+
+/// The content type for an uploaded image's file name,
+/// or `None` for names uploads are never given, so no other file can be served
+pub fn content_type(file: &str) -> Option<&'static str> {
+    let (stem, ext) = file.split_once('.')?;
+    if stem.len() != 32 || !stem.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    AVATAR_CONTENT_TYPES
+        .iter()
+        .copied()
+        .find(|&t| extension(t) == Some(ext))
+}
+
+/// Each of `emails`' avatar URL, in the same order, or `None` when avatars are off
+pub async fn urls(
+    client: &Client,
+    config: &AvatarConfig,
+    emails: &[String],
+) -> Result<Option<Vec<Option<String>>>, Error> {
+    if !config.enabled() {
+        return Ok(None);
+    }
+    let uploaded: HashMap<String, String> = if config.upload_directory.is_some() {
+        client
+            .query(
+                "select email, file from user_avatars where email = any($1)",
+                &[&emails],
+            )
+            .await?
+            .iter()
+            .map(|row| (row.get(0), row.get(1)))
+            .collect()
+    } else {
+        HashMap::new()
+    };
+    Ok(Some(
+        emails
+            .iter()
+            .map(|email| match uploaded.get(email) {
+                Some(file) => Some(format!("{ROUTE}/{file}")),
+                None if config.gravatar => Some(gravatar_url(email)),
+                None => None,
+            })
+            .collect(),
+    ))
+}
+
+/// Saves `image` as the user's avatar, replacing any they uploaded before,
+/// and returns its URL
+pub async fn store(
+    client: &Client,
+    directory: &Path,
+    email: &str,
+    content_type: &str,
+    image: &[u8],
+) -> Result<String> {
+    let ext = extension(content_type).ok_or_else(|| anyhow!("{content_type} is not allowed"))?;
+    let file = format!("{:032x}.{ext}", rand::thread_rng().gen::<u128>());
+    fs::write(directory.join(&file), image)
+        .await
+        .with_context(|| format!("writing {file} to {}", directory.display()))?;
+    let sql = "
+        with old as (select file from user_avatars where email = $1)
+        insert into user_avatars (email, file) values ($1, $2)
+        on conflict (email) do update set file = excluded.file, uploaded_at = now()
+        returning (select file from old)
+    ";
+    let row = client.query_one(sql, &[&email, &file]).await?;
+    if let Some(old) = row.get::<_, Option<String>>(0) {
+        remove_file(directory, &old).await;
+    }
+    Ok(format!("{ROUTE}/{file}"))
+}
+
+/// Forgets user $1's uploaded avatar, returning its file
+pub const DELETE_AVATAR: &str = "delete from user_avatars where email = $1 returning file";
+
+/// Forgets the user's uploaded avatar and deletes its file,
+/// returning whether there was one
+pub async fn remove(client: &Client, directory: Option<&Path>, email: &str) -> Result<bool> {
+    let row = client.query_opt(DELETE_AVATAR, &[&email]).await?;
+    let Some(row) = row else {
+        return Ok(false);
+    };
+    if let Some(directory) = directory {
+        remove_file(directory, row.get(0)).await;
+    }
+    Ok(true)
+}
+
+/// Deletes the upload directory's `file`, once the database no longer names it.
+/// Failing to delete it only wastes space, so it is only logged.
+pub async fn remove_file(directory: &Path, file: &str) {
+    if let Err(e) = fs::remove_file(directory.join(file)).await {
+        tracing::warn!("removing avatar {file}: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{content_type, gravatar_url};
+
+    #[test]
+    fn test_gravatar_url() {
+        assert_eq!(
+            gravatar_url(" MyEmailAddress@example.com "),
+            gravatar_url("myemailaddress@example.com")
+        );
+        assert!(gravatar_url("a@example.com").starts_with("https://www.gravatar.com/avatar/"));
+    }
+
+    #[test]
+    fn test_content_type() {
+        let stem = "0123456789abcdef0123456789abcdef";
+        assert_eq!(content_type(&format!("{stem}.png")), Some("image/png"));
+        assert_eq!(content_type(&format!("{stem}.jpg")), Some("image/jpeg"));
+        assert_eq!(content_type(&format!("{stem}.webp")), Some("image/webp"));
+        assert_eq!(content_type(&format!("{stem}.svg")), None);
+        assert_eq!(content_type(&format!("{stem}.png.png")), None);
+        assert_eq!(content_type("../../etc/passwd.png"), None);
+        assert_eq!(content_type("short.png"), None);
+    }
+}
@@ -0,0 +1,146 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::{fs, sync::Mutex};
+
+use anyhow::{anyhow, Context, Result};
+use rocket::time::OffsetDateTime;
+use serde::{Deserialize, Serialize};
+
+use ehall::BackupStatus;
+
+use crate::clock::Clock;
+
+const PREFIX: &str = "ehallway-";
+const SUFFIX: &str = ".dump";
+const DEFAULT_KEEP: usize = 7;
+
+fn default_keep() -> usize {
+    DEFAULT_KEEP
+}
+
+/// The optional `[backup]` table of the config file
+#[derive(Clone, Deserialize, Serialize)]
+pub struct BackupConfig {
+    pub directory: PathBuf,
+    /// How many backups to keep, deleting the oldest beyond that
+    #[serde(default = "default_keep")]
+    pub keep: usize,
+}
+
+/// Runs pg_dump for the database the API uses, one backup at a time
+pub struct Backups {
+    config: BackupConfig,
+    postgres_host: String,
+    postgres_user: String,
+    postgres_password: String,
+    clock: Clock,
+    status: Mutex<BackupStatus>,
+}
+
+impl Backups {
+    pub fn new(
+        config: BackupConfig,
+        postgres_host: &str,
+        postgres_user: &str,
+        postgres_password: &str,
+        clock: Clock,
+    ) -> Self {
+        Backups {
+            config,
+            postgres_host: postgres_host.to_owned(),
+            postgres_user: postgres_user.to_owned(),
+            postgres_password: postgres_password.to_owned(),
+            clock,
+            status: Mutex::new(BackupStatus::default()),
+        }
+    }
+
+    pub fn status(&self) -> BackupStatus {
+        let mut status = self.status.lock().unwrap().clone();
+        status.backups = list(&self.config.directory).unwrap_or_default();
+        status
+    }
+
+    /// Dumps the database and prunes old backups, returning the new file's name,
+    /// or None when a backup is already running.
+    pub async fn run(&self) -> Option<Result<String>> {
+        {
+            let mut status = self.status.lock().unwrap();
+            if status.running {
+                return None;
+            }
+            status.running = true;
+        }
+        let result = self.dump().await;
+        let mut status = self.status.lock().unwrap();
+        status.running = false;
+        status.last_finished_at = Some(self.clock.unix_timestamp());
+        status.last_error = result.as_ref().err().map(|e| format!("{e:#}"));
+        Some(result)
+    }
+
+    async fn dump(&self) -> Result<String> {
+        let dir = self.config.directory.clone();
+        let host = self.postgres_host.clone();
+        let user = self.postgres_user.clone();
+        let password = self.postgres_password.clone();
+        let keep = self.config.keep;
+        let now = self.clock.now();
+        rocket::tokio::task::spawn_blocking(move || -> Result<String> {
+            fs::create_dir_all(&dir).with_context(|| format!("creating {}", dir.display()))?;
+            let name = format!("{PREFIX}{}{SUFFIX}", timestamp(now));
+            // Written under another name first, so a failed dump never counts as a backup.
+            let partial = dir.join(format!("{name}.partial"));
+            let output = Command::new("pg_dump")
+                .args(["--host", &host, "--username", &user])
+                .args(["--format", "custom", "--file"])
+                .arg(&partial)
+                .env("PGPASSWORD", &password)
+                .output()
+                .context("running pg_dump")?;
+            if !output.status.success() {
+                let _ = fs::remove_file(&partial);
+                return Err(anyhow!(
+                    "pg_dump {}: {}",
+                    output.status,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ));
+            }
+            fs::rename(&partial, dir.join(&name))?;
+            prune(&dir, keep)?;
+            Ok(name)
+        })
+        .await?
+    }
+}
+
+// UTC times in names sort the same as in time.
+fn timestamp(t: OffsetDateTime) -> String {
+    format!(
+        "{:04}{:02}{:02}T{:02}{:02}{:02}Z",
+        t.year(),
+        t.month() as u8,
+        t.day(),
+        t.hour(),
+        t.minute(),
+        t.second()
+    )
+}
+
+fn list(dir: &Path) -> Result<Vec<String>> {
+    let mut names: Vec<String> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter(|name| name.starts_with(PREFIX) && name.ends_with(SUFFIX))
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+fn prune(dir: &Path, keep: usize) -> Result<()> {
+    let names = list(dir)?;
+    let n_old = names.len().saturating_sub(keep);
+    for name in &names[..n_old] {
+        fs::remove_file(dir.join(name)).with_context(|| format!("removing old backup {name}"))?;
+    }
+    Ok(())
+}
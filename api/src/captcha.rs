@@ -0,0 +1,109 @@
+use std::net::IpAddr;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Provider {
+    HCaptcha,
+    Turnstile,
+}
+
+impl Provider {
+    fn verify_url(self) -> &'static str {
+        match self {
+            Provider::HCaptcha => "https://api.hcaptcha.com/siteverify",
+            Provider::Turnstile => "https://challenges.cloudflare.com/turnstile/v0/siteverify",
+        }
+    }
+
+    fn script_url(self) -> &'static str {
+        match self {
+            Provider::HCaptcha => "https://js.hcaptcha.com/1/api.js",
+            Provider::Turnstile => "https://challenges.cloudflare.com/turnstile/v0/api.js",
+        }
+    }
+
+    // The provider's script renders its widget into elements with this class.
+    fn widget_class(self) -> &'static str {
+        match self {
+            Provider::HCaptcha => "h-captcha",
+            Provider::Turnstile => "cf-turnstile",
+        }
+    }
+}
+
+/// The optional `[captcha]` table of the config file
+#[derive(Clone, Deserialize, Serialize)]
+pub struct CaptchaConfig {
+    pub provider: Provider,
+    pub site_key: String,
+    pub secret_key: String,
+}
+
+#[derive(Deserialize)]
+struct Verification {
+    success: bool,
+}
+
+impl CaptchaConfig {
+    /// Signup template context for the widget, without the secret key
+    pub fn widget(&self) -> Value {
+        json!({
+            "script_url": self.provider.script_url(),
+            "class": self.provider.widget_class(),
+            "site_key": self.site_key,
+        })
+    }
+
+    /// Asks the provider whether the token the widget put in the form is valid.
+    pub async fn verify(&self, token: &str, remote_ip: Option<IpAddr>) -> Result<bool> {
+        let mut form = vec![
+            ("secret", self.secret_key.clone()),
+            ("response", token.to_owned()),
+        ];
+        if let Some(ip) = remote_ip {
+            form.push(("remoteip", ip.to_string()));
+        }
+        let verification: Verification = reqwest::Client::new()
+            .post(self.provider.verify_url())
+            .form(&form)
+            .send()
+            .await?
+            .json()
+            .await?;
+        Ok(verification.success)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CaptchaConfig, Provider};
+
+    #[test]
+    fn test_captcha_config() {
+        let config: CaptchaConfig = toml::from_str(
+            r#"
+            provider = "turnstile"
+            site_key = "public"
+            secret_key = "private"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.provider, Provider::Turnstile);
+        let widget = config.widget();
+        assert_eq!(widget["class"], "cf-turnstile");
+        assert_eq!(widget["site_key"], "public");
+        assert!(widget.get("secret_key").is_none());
+        assert!(toml::from_str::<CaptchaConfig>(
+            r#"
+            provider = "recaptcha"
+            site_key = "public"
+            secret_key = "private"
+            "#,
+        )
+        .is_err());
+    }
+}
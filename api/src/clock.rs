@@ -0,0 +1,76 @@
+use std::sync::{Arc, Mutex};
+
+use rocket::time::{Duration, OffsetDateTime};
+
+/// The time as the API sees it, managed as Rocket state
+/// and shared with everything whose behavior depends on the time,
+/// like registration deadlines and idempotency key expiry.
+/// A mock clock stands still until advanced,
+/// so tests of those don't depend on when they run.
+#[derive(Clone)]
+pub enum Clock {
+    System,
+    Mock(Arc<Mutex<OffsetDateTime>>),
+}
+
+impl Clock {
+    pub fn mock(start: OffsetDateTime) -> Self {
+        Clock::Mock(Arc::new(Mutex::new(start)))
+    }
+
+    pub fn now(&self) -> OffsetDateTime {
+        match self {
+            Clock::System => OffsetDateTime::now_utc(),
+            Clock::Mock(now) => *now.lock().unwrap(),
+        }
+    }
+
+    /// Seconds since the Unix epoch, as timestamps travel in messages
+    pub fn unix_timestamp(&self) -> i64 {
+        self.now().unix_timestamp()
+    }
+
+    /// Whether `deadline`, in seconds since the Unix epoch, has arrived
+    pub fn reached(&self, deadline: i64) -> bool {
+        deadline <= self.unix_timestamp()
+    }
+
+    /// Moves a mock clock forward, returning its new time,
+    /// or None for the system clock, which only time moves
+    pub fn advance(&self, by: Duration) -> Option<OffsetDateTime> {
+        match self {
+            Clock::System => None,
+            Clock::Mock(now) => {
+                let mut now = now.lock().unwrap();
+                *now += by;
+                Some(*now)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rocket::time::{Duration, OffsetDateTime};
+
+    use super::Clock;
+
+    #[test]
+    fn test_mock_clock() {
+        let start = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+        let clock = Clock::mock(start);
+        let shared = clock.clone();
+        let deadline = start.unix_timestamp() + 60;
+        assert!(!clock.reached(deadline));
+        assert_eq!(
+            clock.advance(Duration::seconds(59)),
+            Some(start + Duration::seconds(59))
+        );
+        assert!(!shared.reached(deadline));
+        clock.advance(Duration::SECOND);
+        // Clones share the time, as handlers share the managed clock.
+        assert!(shared.reached(deadline));
+        assert_eq!(shared.unix_timestamp(), deadline);
+        assert_eq!(Clock::System.advance(Duration::SECOND), None);
+    }
+}
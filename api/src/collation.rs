@@ -0,0 +1,22 @@
+use std::convert::Infallible;
+
+use rocket::request::{FromRequest, Outcome, Request};
+
+use ehall::{preferred_language, Collation};
+
+/// Orders names for the language the request's `Accept-Language` header prefers,
+/// or in the root order without one
+pub struct RequestCollation(pub Collation);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for RequestCollation {
+    type Error = Infallible;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let language = request
+            .headers()
+            .get_one("Accept-Language")
+            .and_then(preferred_language);
+        Outcome::Success(RequestCollation(Collation::new(language)))
+    }
+}
@@ -0,0 +1,122 @@
+use tokio_postgres::Error;
+
+use ehall::ContactSharing;
+
+use crate::db::Client;
+
+// A later choice replaces the earlier one, so members can change their minds.
+const CONSENT: &str = "
+    insert into contact_consents (meeting, email, share)
+    values ($1, $2, $3)
+    on conflict (meeting, email) do update
+    set share = excluded.share, decided_at = now()
+";
+
+const GET_CONSENTS: &str = "
+    select email, share from contact_consents
+    where meeting = $1 and email = any($2)
+";
+
+/// Records whether `email` shares their email with their cohort in the meeting
+pub async fn consent(
+    client: &Client,
+    meeting_id: i64,
+    email: &str,
+    share: bool,
+) -> Result<(), Error> {
+    client
+        .execute(CONSENT, &[&meeting_id, &email, &share])
+        .await?;
+    Ok(())
+}
+
+/// What `email` may see of the contacts of `cohort`, the members of their cohort
+pub async fn contact_sharing(
+    client: &Client,
+    meeting_id: i64,
+    email: &str,
+    cohort: &[String],
+) -> Result<ContactSharing, Error> {
+    let rows = client.query(GET_CONSENTS, &[&meeting_id, &cohort]).await?;
+    let choices: Vec<(String, bool)> = rows.iter().map(|row| (row.get(0), row.get(1))).collect();
+    Ok(revealed(email, cohort, &choices))
+}
+
+/// The contacts revealed to `email`: those of the other members of `cohort`
+/// who chose to share, once `email` has chosen to share too
+pub fn revealed(email: &str, cohort: &[String], choices: &[(String, bool)]) -> ContactSharing {
+    let choice = |member: &str| {
+        choices
+            .iter()
+            .find(|(e, _)| e == member)
+            .map(|&(_, share)| share)
+    };
+    let share = choice(email);
+    let contacts = if share == Some(true) {
+        cohort
+            .iter()
+            .filter(|&member| member != email && choice(member) == Some(true))
+            .cloned()
+            .collect()
+    } else {
+        vec![]
+    };
+    ContactSharing { share, contacts }
+}
+
+/// How `member` of `cohort` appears to others in a meeting that shares contacts,
+/// in place of their email: "member 2" for the second of the cohort's emails in order,
+/// so the same member has the same label wherever the cohort is shown
+pub fn label(cohort: &[String], member: &str) -> String {
+    let mut emails: Vec<&str> = cohort.iter().map(String::as_str).collect();
+    emails.sort_unstable();
+    match emails.iter().position(|&email| email == member) {
+        Some(i) => format!("member {}", i + 1),
+        // Like an outcome recorded by someone who has since left
+        None => "a former member".to_owned(),
+    }
+}
+
+/// The labels of `members`, each of `cohort`
+pub fn labels(cohort: &[String], members: &[String]) -> Vec<String> {
+    members.iter().map(|member| label(cohort, member)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{label, labels, revealed};
+
+    #[test]
+    fn test_revealed() {
+        let cohort: Vec<String> = ["ana@example.com", "bo@example.com", "cy@example.com"]
+            .map(str::to_owned)
+            .to_vec();
+        let choices = [
+            ("ana@example.com".to_owned(), true),
+            ("bo@example.com".to_owned(), false),
+            ("cy@example.com".to_owned(), true),
+        ];
+        let ana = revealed("ana@example.com", &cohort, &choices);
+        assert_eq!(ana.share, Some(true));
+        assert_eq!(ana.contacts, vec!["cy@example.com".to_owned()]);
+        // Declining reveals nothing, even of those who shared.
+        let bo = revealed("bo@example.com", &cohort, &choices);
+        assert_eq!(bo.share, Some(false));
+        assert!(bo.contacts.is_empty());
+        let undecided = revealed("ana@example.com", &cohort, &[]);
+        assert_eq!(undecided.share, None);
+        assert!(undecided.contacts.is_empty());
+    }
+
+    #[test]
+    fn test_labels() {
+        let cohort: Vec<String> = ["cy@example.com", "ana@example.com", "bo@example.com"]
+            .map(str::to_owned)
+            .to_vec();
+        assert_eq!(
+            labels(&cohort, &cohort),
+            vec!["member 3", "member 1", "member 2"]
+        );
+        assert_eq!(label(&cohort, "dee@example.com"), "a former member");
+    }
+}
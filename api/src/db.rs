@@ -0,0 +1,396 @@
+use std::future::Future;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+use deadpool_postgres::{Manager, ManagerConfig, Object, Pool, PoolError};
+use rocket::http::Status;
+use rocket::request::{FromRequest, Outcome, Request};
+use tokio_postgres::types::ToSql;
+use tokio_postgres::{Error, NoTls, Row, Statement, ToStatement};
+use tracing::{field, Instrument, Span};
+
+use crate::request_id;
+use crate::sql_log::{one_line, redacted, SqlLog};
+
+// The most statements each hot endpoint should need, by route name.
+// Going over suggests a query per item has crept in, so it is logged,
+// and tests/statement_budgets.rs fails.
+const STATEMENT_BUDGETS: [(&str, usize); 6] = [
+    ("get_election_results", 10),
+    ("get_meetings", 4),
+    ("get_user_topics", 3),
+    ("store_meeting_topic_scores", 1),
+    ("store_user_topic_scores", 1),
+    ("sync_state", 27),
+];
+
+pub fn default_pool_size() -> usize {
+    16
+}
+
+/// Connections for request handlers, opened as requests need them
+/// and kept for reuse, up to `size` at once
+pub fn pool(pg_params: &str, size: usize) -> anyhow::Result<Pool> {
+    let manager = Manager::from_config(pg_params.parse()?, NoTls, ManagerConfig::default());
+    Ok(Pool::builder(manager).max_size(size.max(1)).build()?)
+}
+
+/// Statements a request prepared or ran, and the rows they returned or changed
+#[derive(Debug, Default)]
+pub struct DbStats {
+    statements: AtomicUsize,
+    rows: AtomicUsize,
+}
+
+impl DbStats {
+    fn record(&self, rows: usize) {
+        self.statements.fetch_add(1, Ordering::Relaxed);
+        self.rows.fetch_add(rows, Ordering::Relaxed);
+    }
+
+    pub fn statements(&self) -> usize {
+        self.statements.load(Ordering::Relaxed)
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows.load(Ordering::Relaxed)
+    }
+
+    /// The budget this request's statements went over, if any
+    pub fn over_budget(&self, route: &str) -> Option<usize> {
+        STATEMENT_BUDGETS
+            .iter()
+            .find(|(name, _)| *name == route)
+            .map(|&(_, budget)| budget)
+            .filter(|&budget| self.statements() > budget)
+    }
+}
+
+/// The statistics of every `Db` the request checks out
+pub fn request_stats<'r>(request: &'r Request<'_>) -> &'r Arc<DbStats> {
+    request.local_cache(|| Arc::new(DbStats::default()))
+}
+
+/// What a statement is, for the SQL log.
+/// A prepared statement has no text, which was logged when it was prepared.
+pub trait SqlText {
+    fn sql_text(&self) -> Option<&str>;
+}
+
+impl SqlText for str {
+    fn sql_text(&self) -> Option<&str> {
+        Some(self)
+    }
+}
+
+impl SqlText for String {
+    fn sql_text(&self) -> Option<&str> {
+        Some(self)
+    }
+}
+
+impl SqlText for Statement {
+    fn sql_text(&self) -> Option<&str> {
+        None
+    }
+}
+
+// Where a connection's calls, and those of its transactions, are counted and logged
+#[derive(Clone)]
+struct Recorder {
+    stats: Arc<DbStats>,
+    span: Span,
+    sql_log: Option<SqlLog>,
+}
+
+impl Recorder {
+    fn call_span(&self, call: &'static str) -> Span {
+        tracing::debug_span!(parent: &self.span, "db", call, rows = field::Empty)
+    }
+
+    fn record(&self, span: &Span, rows: usize) {
+        span.record("rows", rows);
+        self.stats.record(rows);
+    }
+
+    // Parameters that could hold user data are left out, and slow statements are warnings.
+    fn log_sql(
+        &self,
+        call: &'static str,
+        sql: Option<&str>,
+        params: &[&(dyn ToSql + Sync)],
+        started: Instant,
+        rows: usize,
+    ) {
+        let Some(sql_log) = self
+            .sql_log
+            .as_ref()
+            .filter(|log| log.remaining().is_some())
+        else {
+            return;
+        };
+        let elapsed = started.elapsed();
+        let ms = elapsed.as_secs_f64() * 1000.0;
+        let statement = sql.map_or_else(|| "(prepared)".to_owned(), one_line);
+        let params = params
+            .iter()
+            .map(|param| redacted(*param))
+            .collect::<Vec<_>>()
+            .join(", ");
+        if elapsed >= sql_log.slow() {
+            tracing::warn!(parent: &self.span, call, ms, rows, params = %params, "slow sql: {statement}");
+        } else {
+            tracing::info!(parent: &self.span, call, ms, rows, params = %params, "sql: {statement}");
+        }
+    }
+
+    /// Runs one call in its `db` span, counting the rows `rows` says it returned or changed
+    async fn run<R>(
+        &self,
+        call: &'static str,
+        sql: Option<&str>,
+        params: &[&(dyn ToSql + Sync)],
+        running: impl Future<Output = Result<R, Error>>,
+        rows: impl FnOnce(&R) -> usize,
+    ) -> Result<R, Error> {
+        let span = self.call_span(call);
+        let started = Instant::now();
+        let result = running.instrument(span.clone()).await;
+        let n = result.as_ref().map_or(0, rows);
+        self.record(&span, n);
+        self.log_sql(call, sql, params, started, n);
+        result
+    }
+}
+
+/// A pooled connection that counts what it runs in `DbStats`
+/// and logs each call in a `db` span, under the span of the request it serves.
+/// While an admin has the `SqlLog` on, each statement is logged too.
+pub struct Client {
+    client: Object,
+    recorder: Recorder,
+}
+
+impl Client {
+    /// A connection for work outside a request's `Db`, whose statistics nobody reads
+    pub fn new(client: Object) -> Self {
+        Client {
+            client,
+            recorder: Recorder {
+                stats: Arc::default(),
+                span: Span::current(),
+                sql_log: None,
+            },
+        }
+    }
+
+    /// A transaction on this connection, rolled back unless committed.
+    /// Beginning it counts as a statement, and what runs in it is counted and logged
+    /// like what runs on the connection.
+    pub async fn transaction(&mut self) -> Result<Transaction<'_>, Error> {
+        let span = self.recorder.call_span("transaction");
+        self.recorder.record(&span, 0);
+        let tx = self.client.transaction().instrument(span).await?;
+        Ok(Transaction {
+            tx,
+            recorder: self.recorder.clone(),
+        })
+    }
+
+    pub async fn prepare(&self, query: &str) -> Result<Statement, Error> {
+        let preparing = self.client.prepare(query);
+        self.recorder
+            .run("prepare", Some(query), &[], preparing, |_| 0)
+            .await
+    }
+
+    pub async fn query<T>(
+        &self,
+        statement: &T,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<Vec<Row>, Error>
+    where
+        T: ?Sized + ToStatement + SqlText,
+    {
+        let running = self.client.query(statement, params);
+        self.recorder
+            .run("query", statement.sql_text(), params, running, Vec::len)
+            .await
+    }
+
+    pub async fn query_one<T>(
+        &self,
+        statement: &T,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<Row, Error>
+    where
+        T: ?Sized + ToStatement + SqlText,
+    {
+        let running = self.client.query_one(statement, params);
+        self.recorder
+            .run("query_one", statement.sql_text(), params, running, |_| 1)
+            .await
+    }
+
+    pub async fn query_opt<T>(
+        &self,
+        statement: &T,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<Option<Row>, Error>
+    where
+        T: ?Sized + ToStatement + SqlText,
+    {
+        let running = self.client.query_opt(statement, params);
+        self.recorder
+            .run("query_opt", statement.sql_text(), params, running, |row| {
+                row.is_some() as usize
+            })
+            .await
+    }
+
+    pub async fn execute<T>(
+        &self,
+        statement: &T,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<u64, Error>
+    where
+        T: ?Sized + ToStatement + SqlText,
+    {
+        let running = self.client.execute(statement, params);
+        self.recorder
+            .run("execute", statement.sql_text(), params, running, |&n| {
+                n as usize
+            })
+            .await
+    }
+}
+
+/// A transaction begun by `Client::transaction`, rolled back when dropped uncommitted.
+/// Its statements go to the same `DbStats` and logs as the connection's.
+pub struct Transaction<'a> {
+    tx: deadpool_postgres::Transaction<'a>,
+    recorder: Recorder,
+}
+
+impl Transaction<'_> {
+    pub async fn commit(self) -> Result<(), Error> {
+        let committing = self.tx.commit();
+        self.recorder
+            .run("commit", Some("commit"), &[], committing, |_| 0)
+            .await
+    }
+
+    pub async fn query_one<T>(
+        &self,
+        statement: &T,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<Row, Error>
+    where
+        T: ?Sized + ToStatement + SqlText,
+    {
+        let running = self.tx.query_one(statement, params);
+        self.recorder
+            .run("query_one", statement.sql_text(), params, running, |_| 1)
+            .await
+    }
+
+    pub async fn query_opt<T>(
+        &self,
+        statement: &T,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<Option<Row>, Error>
+    where
+        T: ?Sized + ToStatement + SqlText,
+    {
+        let running = self.tx.query_opt(statement, params);
+        self.recorder
+            .run("query_opt", statement.sql_text(), params, running, |row| {
+                row.is_some() as usize
+            })
+            .await
+    }
+
+    pub async fn execute<T>(
+        &self,
+        statement: &T,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<u64, Error>
+    where
+        T: ?Sized + ToStatement + SqlText,
+    {
+        let running = self.tx.execute(statement, params);
+        self.recorder
+            .run("execute", statement.sql_text(), params, running, |&n| {
+                n as usize
+            })
+            .await
+    }
+}
+
+/// A connection checked out of the managed `Pool` for one request,
+/// so concurrent requests don't queue behind each other's queries.
+/// It goes back to the pool when the request finishes.
+/// What it runs is counted in the request's `DbStats` and logged under its span.
+pub struct Db(Client);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Db {
+    type Error = PoolError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let pool = request
+            .rocket()
+            .state::<Pool>()
+            .expect("main manages the pool");
+        match pool.get().await {
+            Ok(client) => Outcome::Success(Db(Client {
+                client,
+                recorder: Recorder {
+                    stats: request_stats(request).clone(),
+                    span: request_id::span(request).clone(),
+                    sql_log: request.rocket().state::<SqlLog>().cloned(),
+                },
+            })),
+            Err(e) => {
+                tracing::error!(parent: request_id::span(request), "database connection unavailable: {e}");
+                Outcome::Failure((Status::ServiceUnavailable, e))
+            }
+        }
+    }
+}
+
+impl Deref for Db {
+    type Target = Client;
+
+    fn deref(&self) -> &Client {
+        &self.0
+    }
+}
+
+impl DerefMut for Db {
+    fn deref_mut(&mut self) -> &mut Client {
+        &mut self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DbStats;
+
+    #[test]
+    fn test_statement_budget() {
+        let stats = DbStats::default();
+        for _ in 0..4 {
+            stats.record(2);
+        }
+        assert_eq!(stats.statements(), 4);
+        assert_eq!(stats.rows(), 8);
+        assert_eq!(stats.over_budget("get_meetings"), None);
+        stats.record(0);
+        assert_eq!(stats.over_budget("get_meetings"), Some(4));
+        assert_eq!(stats.over_budget("store_user_topic_scores"), Some(1));
+        assert_eq!(stats.over_budget("get_version"), None);
+    }
+}
@@ -0,0 +1,234 @@
+use std::sync::Mutex;
+
+use anyhow::{anyhow, Result};
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::Header;
+use rocket::time::{Date, Month};
+use rocket::{Request, Response};
+use serde::{Deserialize, Serialize};
+
+use ehall::{DeprecatedRouteUsage, DeprecationReport};
+
+use crate::clock::Clock;
+use crate::log_privacy::request_uri;
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// One `[[deprecations]]` table of the config file
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DeprecationConfig {
+    /// The route's name, which is its handler's name, like "get_user_id"
+    pub route: String,
+    /// When the route was deprecated, as YYYY-MM-DD
+    pub deprecated: String,
+    /// When the route may be removed, as YYYY-MM-DD
+    #[serde(default)]
+    pub sunset: Option<String>,
+    /// The path that replaces the route
+    #[serde(default)]
+    pub successor: Option<String>,
+}
+
+fn parse_date(text: &str) -> Result<Date> {
+    let parts: Vec<&str> = text.split('-').collect();
+    let (year, month, day) = match parts[..] {
+        [year, month, day] => (year, month, day),
+        _ => return Err(anyhow!("\"{text}\" is not a YYYY-MM-DD date")),
+    };
+    let month = Month::try_from(month.parse::<u8>()?)?;
+    Ok(Date::from_calendar_date(
+        year.parse()?,
+        month,
+        day.parse()?,
+    )?)
+}
+
+// The IMF-fixdate form of HTTP dates, which the Sunset header uses
+fn http_date(date: Date) -> String {
+    format!(
+        "{}, {:02} {} {:04} 00:00:00 GMT",
+        WEEKDAYS[date.weekday().number_days_from_monday() as usize],
+        date.day(),
+        MONTHS[date.month() as usize - 1],
+        date.year()
+    )
+}
+
+fn headers(config: &DeprecationConfig) -> Result<Vec<Header<'static>>> {
+    let deprecated = parse_date(&config.deprecated)?;
+    // The Deprecation header takes seconds since the epoch after an "@".
+    let since = deprecated.midnight().assume_utc().unix_timestamp();
+    let mut headers = vec![Header::new("Deprecation", format!("@{since}"))];
+    if let Some(sunset) = &config.sunset {
+        headers.push(Header::new("Sunset", http_date(parse_date(sunset)?)));
+    }
+    if let Some(successor) = &config.successor {
+        let link = format!("<{successor}>; rel=\"successor-version\"");
+        headers.push(Header::new("Link", link));
+    }
+    Ok(headers)
+}
+
+struct DeprecatedRoute {
+    config: DeprecationConfig,
+    headers: Vec<Header<'static>>,
+    uses: u64,
+    last_used_at: Option<i64>,
+}
+
+/// The configured deprecated routes and their use since this instance started
+pub struct Deprecations {
+    clock: Clock,
+    counting_since: i64,
+    routes: Mutex<Vec<DeprecatedRoute>>,
+}
+
+impl Deprecations {
+    pub fn new(configs: Vec<DeprecationConfig>, clock: Clock) -> Result<Self> {
+        let routes = configs
+            .into_iter()
+            .map(|config| -> Result<DeprecatedRoute> {
+                let headers = headers(&config)
+                    .map_err(|e| anyhow!("deprecation of {}: {e}", config.route))?;
+                Ok(DeprecatedRoute {
+                    config,
+                    headers,
+                    uses: 0,
+                    last_used_at: None,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Deprecations {
+            counting_since: clock.unix_timestamp(),
+            clock,
+            routes: Mutex::new(routes),
+        })
+    }
+
+    pub fn route_names(&self) -> Vec<String> {
+        let routes = self.routes.lock().unwrap();
+        routes.iter().map(|r| r.config.route.clone()).collect()
+    }
+
+    pub fn report(&self) -> DeprecationReport {
+        let routes = self.routes.lock().unwrap();
+        DeprecationReport {
+            counting_since: self.counting_since,
+            routes: routes
+                .iter()
+                .map(|r| DeprecatedRouteUsage {
+                    route: r.config.route.clone(),
+                    deprecated: r.config.deprecated.clone(),
+                    sunset: r.config.sunset.clone(),
+                    successor: r.config.successor.clone(),
+                    uses: r.uses,
+                    last_used_at: r.last_used_at,
+                })
+                .collect(),
+        }
+    }
+
+    // Counts one use, returning the route's headers and its use count
+    // when the route is deprecated.
+    fn record(&self, name: &str) -> Option<(Vec<Header<'static>>, u64)> {
+        let mut routes = self.routes.lock().unwrap();
+        let route = routes.iter_mut().find(|r| r.config.route == name)?;
+        route.uses += 1;
+        route.last_used_at = Some(self.clock.unix_timestamp());
+        Some((route.headers.clone(), route.uses))
+    }
+}
+
+/// Marks responses from deprecated routes with Deprecation, Sunset, and Link headers,
+/// and counts and logs each use, so maintainers can tell when a route is safe to remove.
+/// The counts come from the managed `Deprecations`.
+pub struct DeprecationFairing;
+
+#[rocket::async_trait]
+impl Fairing for DeprecationFairing {
+    fn info(&self) -> Info {
+        Info {
+            name: "Route deprecation",
+            kind: Kind::Response,
+        }
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let name = match request.route().and_then(|route| route.name.as_deref()) {
+            Some(name) => name,
+            None => return,
+        };
+        let deprecations = match request.rocket().state::<Deprecations>() {
+            Some(deprecations) => deprecations,
+            None => return,
+        };
+        if let Some((headers, uses)) = deprecations.record(name) {
+            tracing::info!(
+                deprecated_route = name,
+                uses,
+                uri = %request_uri(request),
+                user_agent = request.headers().get_one("User-Agent").unwrap_or(""),
+                "deprecated route used"
+            );
+            for header in headers {
+                response.set_header(header);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rocket::time::{Duration, OffsetDateTime};
+
+    use super::{headers, http_date, parse_date, DeprecationConfig, Deprecations};
+    use crate::clock::Clock;
+
+    #[test]
+    fn test_dates() {
+        assert_eq!(
+            http_date(parse_date("2024-08-01").unwrap()),
+            "Thu, 01 Aug 2024 00:00:00 GMT"
+        );
+        for bad in ["2024-13-01", "2024-02-30", "2024-08", "yesterday"] {
+            assert!(parse_date(bad).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_headers() {
+        let config = DeprecationConfig {
+            route: "get_user_id".to_owned(),
+            deprecated: "2024-05-01".to_owned(),
+            sunset: None,
+            successor: Some("/sync".to_owned()),
+        };
+        let headers = headers(&config).unwrap();
+        assert_eq!(headers[0].value(), "@1714521600");
+        assert_eq!(headers[1].name(), "Link");
+        assert_eq!(headers[1].value(), "</sync>; rel=\"successor-version\"");
+    }
+
+    #[test]
+    fn test_record() {
+        let config = DeprecationConfig {
+            route: "get_user_id".to_owned(),
+            deprecated: "2024-05-01".to_owned(),
+            sunset: None,
+            successor: None,
+        };
+        let start = OffsetDateTime::from_unix_timestamp(1_714_521_600).unwrap();
+        let clock = Clock::mock(start);
+        let deprecations = Deprecations::new(vec![config], clock.clone()).unwrap();
+        assert!(deprecations.record("get_meetings").is_none());
+        clock.advance(Duration::HOUR);
+        assert_eq!(deprecations.record("get_user_id").unwrap().1, 1);
+        let report = deprecations.report();
+        assert_eq!(report.counting_since, 1_714_521_600);
+        assert_eq!(report.routes[0].uses, 1);
+        assert_eq!(report.routes[0].last_used_at, Some(1_714_525_200));
+    }
+}
@@ -0,0 +1,314 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use deadpool_postgres::Pool;
+use rocket::time::{self, OffsetDateTime, Weekday};
+use serde::{Deserialize, Serialize};
+
+use ehall::{NotificationChannel, NotificationEvent};
+
+use crate::clock::Clock;
+use crate::jobs::Jobs;
+use crate::mail::{meeting_url, Mailer, Notice};
+
+/// How often the digest job looks for digests due
+const CHECK_INTERVAL: Duration = Duration::from_secs(600);
+const N_NEW_MEETINGS: i64 = 10;
+const N_POPULAR_TOPICS: i64 = 5;
+
+/// The optional `[digest]` table of the config file.
+/// Digests need an `[smtp]` table, and only go to those who turn them on.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DigestConfig {
+    /// The hour, in UTC, after which each day's digest goes out,
+    /// on Mondays for the weekly one
+    #[serde(default = "default_hour")]
+    pub hour: u8,
+}
+
+fn default_hour() -> u8 {
+    7
+}
+
+impl Default for DigestConfig {
+    fn default() -> Self {
+        DigestConfig {
+            hour: default_hour(),
+        }
+    }
+}
+
+// Opted into explicitly, so users without a preference get none.
+// Claiming records the period's digest before it's sent,
+// so when every API instance checks, each person gets one.
+const CLAIM: &str = "
+    insert into digest_deliveries (email, event, period)
+    select n.email, n.event, (to_timestamp($2::bigint) at time zone 'UTC')::date
+    from notification_preferences n
+    where n.event = $1 and n.channel = $3 and n.enabled
+        and not exists (
+            select 1 from disabled_accounts d where lower(d.email) = lower(n.email)
+        )
+    on conflict do nothing
+    returning email
+";
+
+// Meetings the user registered for that haven't started
+const UPCOMING: &str = "
+    select m.id, m.name from meetings m
+    join meeting_participants p on p.meeting = m.id
+    where p.email = $1 and m.id not in (select meeting from cohort_groups)
+    order by m.registration_deadline nulls last, m.name
+";
+
+// Meetings created since the last digest, and not already the user's
+const NEW_MEETINGS: &str = "
+    select m.id, m.name from meetings m
+    where m.created_at > to_timestamp($2::bigint)
+        and m.id not in (select meeting from cohort_groups)
+        and m.id not in (select meeting from meeting_participants where email = $1)
+    order by m.created_at desc
+    limit $3
+";
+
+// Public topics with the most points in cohorts that finished voting since the last digest
+const POPULAR_TOPICS: &str = "
+    select t.topic from meeting_topics mt
+    join user_topics t on t.id = mt.topic
+    join election_results r on r.meeting = mt.meeting and r.cohort = mt.cohort
+    where r.recorded_at > to_timestamp($1::bigint) and not t.private and not mt.vetoed
+    group by t.id, t.topic
+    order by sum(mt.score) desc, t.topic
+    limit $2
+";
+
+/// A meeting listed in a digest, with its join link
+#[derive(Debug, PartialEq)]
+pub struct DigestMeeting {
+    pub name: String,
+    pub url: String,
+}
+
+/// The digests whose period has arrived at `now`.
+/// Weekly periods start on Mondays.
+pub fn due(now: OffsetDateTime, hour: u8) -> Vec<NotificationEvent> {
+    let mut due = vec![];
+    if now.hour() >= hour {
+        due.push(NotificationEvent::DailyDigest);
+    }
+    if now.weekday() != Weekday::Monday || now.hour() >= hour {
+        due.push(NotificationEvent::WeeklyDigest);
+    }
+    due
+}
+
+/// When the digest period holding `now` started, as midnight UTC
+pub fn period_start(now: OffsetDateTime, event: NotificationEvent) -> OffsetDateTime {
+    let mut date = now.date();
+    if event == NotificationEvent::WeeklyDigest {
+        date -= time::Duration::days(date.weekday().number_days_from_monday() as i64);
+    }
+    date.midnight().assume_utc()
+}
+
+fn period_length(event: NotificationEvent) -> time::Duration {
+    match event {
+        NotificationEvent::WeeklyDigest => time::Duration::weeks(1),
+        _ => time::Duration::days(1),
+    }
+}
+
+/// What to tell someone in their digest, or `None` when there's nothing to tell
+pub fn digest_notice(
+    event: NotificationEvent,
+    upcoming: &[DigestMeeting],
+    new_meetings: &[DigestMeeting],
+    popular_topics: &[String],
+) -> Option<Notice> {
+    if upcoming.is_empty() && new_meetings.is_empty() && popular_topics.is_empty() {
+        return None;
+    }
+    let (subject, since) = match event {
+        NotificationEvent::WeeklyDigest => ("Your eHallway week", "this past week"),
+        _ => ("Your eHallway day", "since yesterday"),
+    };
+    let meetings = |meetings: &[DigestMeeting]| -> String {
+        meetings
+            .iter()
+            .map(|m| format!("- {}: {}\n", m.name, m.url))
+            .collect()
+    };
+    let mut body = String::new();
+    if !upcoming.is_empty() {
+        body.push_str("Meetings you registered for:\n");
+        body.push_str(&meetings(upcoming));
+        body.push('\n');
+    }
+    if !new_meetings.is_empty() {
+        body.push_str(&format!("New meetings {since}:\n"));
+        body.push_str(&meetings(new_meetings));
+        body.push('\n');
+    }
+    if !popular_topics.is_empty() {
+        body.push_str(&format!("Popular topics {since}:\n"));
+        for topic in popular_topics {
+            body.push_str(&format!("- {topic}\n"));
+        }
+        body.push('\n');
+    }
+    body.push_str(
+        "To stop these emails, turn them off under Notifications on the Preferences tab.\n",
+    );
+    Some(Notice {
+        subject: subject.to_owned(),
+        body,
+    })
+}
+
+/// Emails the digests due every few minutes, until the API shuts down
+pub fn register(jobs: &Jobs, mailer: Arc<Mailer>, pool: Pool, clock: Clock, config: DigestConfig) {
+    jobs.register("digest", CHECK_INTERVAL, move || {
+        let (mailer, pool, clock) = (mailer.clone(), pool.clone(), clock.clone());
+        let hour = config.hour;
+        async move {
+            let now = clock.now();
+            // One failed digest doesn't hold up the others.
+            for event in due(now, hour) {
+                if let Err(e) = deliver(&mailer, &pool, now, event).await {
+                    tracing::error!("emailing {}: {e:#}", event.as_str());
+                }
+            }
+            Ok(())
+        }
+    });
+}
+
+async fn deliver(
+    mailer: &Mailer,
+    pool: &Pool,
+    now: OffsetDateTime,
+    event: NotificationEvent,
+) -> Result<()> {
+    let client = pool.get().await?;
+    let period = period_start(now, event).unix_timestamp();
+    let rows = client
+        .query(
+            CLAIM,
+            &[
+                &event.as_str(),
+                &period,
+                &NotificationChannel::Email.as_str(),
+            ],
+        )
+        .await?;
+    if rows.is_empty() {
+        return Ok(());
+    }
+    let since = (now - period_length(event)).unix_timestamp();
+    let popular_topics: Vec<String> = client
+        .query(POPULAR_TOPICS, &[&since, &N_POPULAR_TOPICS])
+        .await?
+        .iter()
+        .map(|row| row.get(0))
+        .collect();
+    let listed = |rows: Vec<tokio_postgres::Row>| -> Vec<DigestMeeting> {
+        rows.iter()
+            .map(|row| DigestMeeting {
+                name: row.get(1),
+                url: meeting_url(mailer.base_url(), row.get::<_, i64>(0) as u32),
+            })
+            .collect()
+    };
+    // Claimed digests are not retried, so nobody gets the same one twice.
+    let (mut n_sent, mut n_failed) = (0, 0);
+    for row in rows.iter() {
+        let email: &str = row.get(0);
+        let upcoming = listed(client.query(UPCOMING, &[&email]).await?);
+        let new_meetings = listed(
+            client
+                .query(NEW_MEETINGS, &[&email, &since, &N_NEW_MEETINGS])
+                .await?,
+        );
+        let Some(notice) = digest_notice(event, &upcoming, &new_meetings, &popular_topics) else {
+            continue;
+        };
+        match mailer.send(email, &notice).await {
+            Ok(()) => n_sent += 1,
+            Err(e) => {
+                tracing::warn!("{} not sent: {e}", event.as_str());
+                n_failed += 1;
+            }
+        }
+    }
+    tracing::info!(
+        "emailed {n_sent} {} and failed {n_failed}, of {} opted in",
+        event.as_str(),
+        rows.len()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use ehall::NotificationEvent;
+    use rocket::time::{Duration, OffsetDateTime};
+
+    use super::{digest_notice, due, period_start, DigestMeeting};
+
+    #[test]
+    fn test_due() {
+        // A Monday at 06:00 UTC
+        let monday = OffsetDateTime::from_unix_timestamp(1_700_460_000).unwrap();
+        assert_eq!(due(monday, 7), vec![]);
+        let later = monday + Duration::hours(1);
+        assert_eq!(
+            due(later, 7),
+            vec![
+                NotificationEvent::DailyDigest,
+                NotificationEvent::WeeklyDigest
+            ]
+        );
+        // Missed Monday's hour, so the week's digest goes out late rather than not at all.
+        let tuesday = monday + Duration::days(1);
+        assert_eq!(due(tuesday, 7), vec![NotificationEvent::WeeklyDigest]);
+    }
+
+    #[test]
+    fn test_period_start() {
+        let monday = OffsetDateTime::from_unix_timestamp(1_700_460_000).unwrap();
+        let midnight = monday - Duration::hours(6);
+        let thursday = monday + Duration::days(3);
+        assert_eq!(
+            period_start(thursday, NotificationEvent::WeeklyDigest),
+            midnight
+        );
+        assert_eq!(
+            period_start(thursday, NotificationEvent::DailyDigest),
+            midnight + Duration::days(3)
+        );
+    }
+
+    #[test]
+    fn test_digest_notice() {
+        assert_eq!(
+            digest_notice(NotificationEvent::DailyDigest, &[], &[], &[]),
+            None
+        );
+        let upcoming = [DigestMeeting {
+            name: "Monday standup".to_owned(),
+            url: "https://hallway.example.com/meeting/7/join".to_owned(),
+        }];
+        let popular = ["Rust at work".to_owned()];
+        let notice =
+            digest_notice(NotificationEvent::WeeklyDigest, &upcoming, &[], &popular).unwrap();
+        assert_eq!(notice.subject, "Your eHallway week");
+        assert!(notice
+            .body
+            .contains("- Monday standup: https://hallway.example.com/meeting/7/join\n"));
+        assert!(notice
+            .body
+            .contains("Popular topics this past week:\n- Rust at work\n"));
+        assert!(!notice.body.contains("New meetings"));
+    }
+}
@@ -0,0 +1,147 @@
+use rocket::http::Status;
+use rocket::request::Request;
+use rocket::response::{self, status::Custom, Responder};
+use serde_json::{json, Value};
+use tokio_postgres::error::SqlState;
+
+use ehall::ErrorCode;
+
+use crate::log_privacy::request_uri;
+
+/// Why an API request failed, sent as a JSON body with the matching status.
+/// Every body has an "error" describing the failure and a "code" naming it
+/// except a `Rejected` one, whose body is whatever the UI expects for that refusal.
+#[derive(Debug)]
+pub enum ApiError {
+    /// A query failed.  The details go to the log, not to the client.
+    Database(tokio_postgres::Error),
+    /// Something that should not happen, like a tally that can't be counted,
+    /// which is also logged rather than described to the client
+    Internal(anyhow::Error),
+    /// No such meeting, topic, or row
+    NotFound,
+    BadRequest(ErrorCode, String),
+    Forbidden(ErrorCode, String),
+    Conflict(ErrorCode, String),
+    /// A refusal with a structured body, like a `StartMeetingError`
+    Rejected(Status, Value),
+}
+
+pub type ApiResult<T> = Result<T, ApiError>;
+
+impl ApiError {
+    pub fn status(&self) -> Status {
+        match self {
+            // A lost connection is worth a retry, while a failed query is a bug.
+            ApiError::Database(e) if e.is_closed() => Status::ServiceUnavailable,
+            ApiError::Database(_) | ApiError::Internal(_) => Status::InternalServerError,
+            ApiError::NotFound => Status::NotFound,
+            ApiError::BadRequest(..) => Status::BadRequest,
+            ApiError::Forbidden(..) => Status::Forbidden,
+            ApiError::Conflict(..) => Status::Conflict,
+            ApiError::Rejected(status, _) => *status,
+        }
+    }
+
+    /// A `BadRequest` for values that failed validation
+    pub fn invalid(message: String) -> Self {
+        ApiError::BadRequest(ErrorCode::InvalidRequest, message)
+    }
+
+    /// A `Conflict` saying `message` if `e` broke a unique constraint,
+    /// like a meeting name already in use, and otherwise a `Database` error
+    pub fn conflict_if_duplicate(
+        e: tokio_postgres::Error,
+        code: ErrorCode,
+        message: String,
+    ) -> Self {
+        if e.code() == Some(&SqlState::UNIQUE_VIOLATION) {
+            ApiError::Conflict(code, message)
+        } else {
+            ApiError::Database(e)
+        }
+    }
+
+    fn body(self) -> Value {
+        match self {
+            ApiError::Database(_) => error_body(ErrorCode::DatabaseError, "database error"),
+            ApiError::Internal(_) => error_body(ErrorCode::InternalError, "internal error"),
+            ApiError::NotFound => error_body(ErrorCode::NotFound, "not found"),
+            ApiError::BadRequest(code, error)
+            | ApiError::Forbidden(code, error)
+            | ApiError::Conflict(code, error) => error_body(code, &error),
+            ApiError::Rejected(_, body) => body,
+        }
+    }
+}
+
+/// The body of a refusal, for those made outside `ApiError` too
+pub fn error_body(code: ErrorCode, error: &str) -> Value {
+    json!({ "error": error, "code": code })
+}
+
+impl From<tokio_postgres::Error> for ApiError {
+    fn from(e: tokio_postgres::Error) -> Self {
+        ApiError::Database(e)
+    }
+}
+
+impl From<anyhow::Error> for ApiError {
+    fn from(e: anyhow::Error) -> Self {
+        ApiError::Internal(e)
+    }
+}
+
+impl<'r> Responder<'r, 'static> for ApiError {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        match &self {
+            ApiError::Database(e) => {
+                tracing::error!(
+                    "database error on {} {}: {e}",
+                    request.method(),
+                    request_uri(request)
+                )
+            }
+            ApiError::Internal(e) => {
+                tracing::error!(
+                    "internal error on {} {}: {e}",
+                    request.method(),
+                    request_uri(request)
+                )
+            }
+            _ => {}
+        }
+        Custom(self.status(), self.body()).respond_to(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rocket::http::Status;
+    use serde_json::json;
+
+    use ehall::ErrorCode;
+
+    use super::ApiError;
+
+    #[test]
+    fn test_status_and_body() {
+        let forbidden = ApiError::Forbidden(
+            ErrorCode::NotOrganizer,
+            "only organizers can do that".to_owned(),
+        );
+        assert_eq!(forbidden.status(), Status::Forbidden);
+        assert_eq!(
+            forbidden.body(),
+            json!({ "error": "only organizers can do that", "code": "not_organizer" })
+        );
+        assert_eq!(ApiError::NotFound.status(), Status::NotFound);
+        assert_eq!(
+            ApiError::NotFound.body(),
+            json!({ "error": "not found", "code": "not_found" })
+        );
+        let rejected = ApiError::Rejected(Status::Forbidden, json!({ "error": "not_organizer" }));
+        assert_eq!(rejected.status(), Status::Forbidden);
+        assert_eq!(rejected.body(), json!({ "error": "not_organizer" }));
+    }
+}
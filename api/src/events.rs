@@ -0,0 +1,197 @@
+use std::future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::time::{sleep, timeout_at, Instant};
+use tokio_postgres::{connect, AsyncMessage, NoTls};
+
+use ehall::{MeetingEvent, MeetingEventKind};
+
+use crate::db;
+
+const CHANNEL: &str = "meeting_events";
+// Subscribers further behind than this miss events and should resync.
+const CAPACITY: usize = 256;
+/// The longest a long poll may wait, short of common proxies' timeouts
+pub const MAX_WAIT_SECS: u64 = 55;
+// Reconnecting waits this long at first, doubling up to MAX_BACKOFF.
+const MIN_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Meeting events from every API instance, received through Postgres
+pub struct MeetingEvents {
+    sender: broadcast::Sender<MeetingEvent>,
+    listening: Arc<AtomicBool>,
+}
+
+impl MeetingEvents {
+    pub fn subscribe(&self) -> broadcast::Receiver<MeetingEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Whether events from other instances are arriving,
+    /// which they don't while the listener reconnects
+    pub fn listening(&self) -> bool {
+        self.listening.load(Ordering::Relaxed)
+    }
+}
+
+/// Keeps a connection dedicated to LISTEN, because notifications arrive
+/// on the connection that listens, and the shared client's connection
+/// is driven without looking at its messages.
+/// A lost connection is reopened, with backoff, and listens again.
+/// Events sent meanwhile are missed, but clients that miss events still poll.
+pub fn listen(pg_params: String) -> MeetingEvents {
+    let (sender, _) = broadcast::channel(CAPACITY);
+    let listening = Arc::new(AtomicBool::new(false));
+    let fan_out = sender.clone();
+    let connected = listening.clone();
+    tokio::spawn(async move {
+        let mut backoff = MIN_BACKOFF;
+        loop {
+            let started = Instant::now();
+            match relay(&pg_params, &fan_out, &connected).await {
+                Ok(()) => tracing::warn!("{CHANNEL} listener connection closed"),
+                Err(e) => tracing::error!("{CHANNEL} listener failed: {e}"),
+            }
+            connected.store(false, Ordering::Relaxed);
+            // A connection that lasted starts the backoff over.
+            if started.elapsed() > MAX_BACKOFF {
+                backoff = MIN_BACKOFF;
+            }
+            sleep(backoff).await;
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+    });
+    MeetingEvents { sender, listening }
+}
+
+// Listens on a new connection and sends on its notifications
+// until the connection fails or closes.
+// The connection is driven while LISTEN runs, since that is what completes it.
+async fn relay(
+    pg_params: &str,
+    fan_out: &broadcast::Sender<MeetingEvent>,
+    connected: &AtomicBool,
+) -> Result<()> {
+    let (client, mut conn) = connect(pg_params, NoTls).await?;
+    let listen = format!("LISTEN {CHANNEL}");
+    let subscribe = client.batch_execute(&listen);
+    tokio::pin!(subscribe);
+    let mut subscribed = false;
+    loop {
+        tokio::select! {
+            result = &mut subscribe, if !subscribed => {
+                result?;
+                subscribed = true;
+                connected.store(true, Ordering::Relaxed);
+                tracing::info!("listening on {CHANNEL}");
+            }
+            message = future::poll_fn(|cx| conn.poll_message(cx)) => match message {
+                Some(Ok(AsyncMessage::Notification(n))) => {
+                    match serde_json::from_str::<MeetingEvent>(n.payload()) {
+                        Ok(event) => {
+                            // No receivers is not an error worth reporting.
+                            let _ = fan_out.send(event);
+                        }
+                        Err(e) => tracing::warn!("bad {CHANNEL} payload {}: {e}", n.payload()),
+                    }
+                }
+                Some(Ok(_)) => {}
+                Some(Err(e)) => return Err(e.into()),
+                None => return Ok(()),
+            },
+        }
+    }
+}
+
+/// Tells every API instance's subscribers about a change to a meeting.
+pub async fn notify(client: &db::Client, meeting: i64, kind: MeetingEventKind) {
+    let event = MeetingEvent {
+        meeting: meeting as u32,
+        kind,
+    };
+    let payload = serde_json::to_string(&event).unwrap();
+    // The change is already made, and clients that miss the event still poll.
+    if let Err(e) = client
+        .execute("select pg_notify($1, $2)", &[&CHANNEL, &payload])
+        .await
+    {
+        tracing::warn!("failed to notify {CHANNEL} of {payload}: {e}");
+    }
+}
+
+/// How long a long poll asks to wait, given like "30s" or "30"
+pub fn parse_wait(wait: &str) -> Result<Duration, String> {
+    let secs: u64 = wait
+        .strip_suffix('s')
+        .unwrap_or(wait)
+        .parse()
+        .map_err(|_| format!("wait is a number of seconds, like 30s, not {wait}"))?;
+    if secs > MAX_WAIT_SECS {
+        return Err(format!("wait is at most {MAX_WAIT_SECS}s"));
+    }
+    Ok(Duration::from_secs(secs))
+}
+
+/// Waits until `deadline` for an event of one of `kinds` in `meeting`,
+/// returning whether one came.
+/// Falling behind counts as one, since a missed event may have been.
+pub async fn wait_for(
+    receiver: &mut broadcast::Receiver<MeetingEvent>,
+    meeting: u32,
+    kinds: &[MeetingEventKind],
+    deadline: Instant,
+) -> bool {
+    loop {
+        match timeout_at(deadline, receiver.recv()).await {
+            Ok(Ok(event)) if event.meeting == meeting && kinds.contains(&event.kind) => {
+                return true
+            }
+            Ok(Ok(_)) => {}
+            Ok(Err(RecvError::Lagged(_))) => return true,
+            Ok(Err(RecvError::Closed)) | Err(_) => return false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::sync::broadcast;
+    use tokio::time::Instant;
+
+    use ehall::{MeetingEvent, MeetingEventKind};
+
+    use super::{parse_wait, wait_for};
+
+    #[test]
+    fn test_parse_wait() {
+        assert_eq!(parse_wait("30s"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_wait("0"), Ok(Duration::ZERO));
+        assert!(parse_wait("56s").is_err());
+        assert!(parse_wait("soon").is_err());
+        assert!(parse_wait("-1s").is_err());
+    }
+
+    #[rocket::async_test]
+    async fn test_wait_for() {
+        let (sender, mut receiver) = broadcast::channel(4);
+        let kinds = [MeetingEventKind::ResultsReady];
+        for (meeting, kind) in [
+            (2, MeetingEventKind::ResultsReady),
+            (1, MeetingEventKind::Voted),
+            (1, MeetingEventKind::ResultsReady),
+        ] {
+            sender.send(MeetingEvent { meeting, kind }).unwrap();
+        }
+        let deadline = Instant::now() + Duration::from_secs(5);
+        assert!(wait_for(&mut receiver, 1, &kinds, deadline).await);
+        let deadline = Instant::now() + Duration::from_millis(10);
+        assert!(!wait_for(&mut receiver, 1, &kinds, deadline).await);
+    }
+}
@@ -0,0 +1,184 @@
+use std::io::Cursor;
+use std::time::Duration;
+
+use rand::Rng;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::{ContentType, Header, Status};
+use rocket::tokio::time::sleep;
+use rocket::{Request, Response};
+use serde::{Deserialize, Serialize};
+
+use ehall::ErrorCode;
+
+use crate::error::error_body;
+
+/// Asks for faults in one request, as "on" for the config file's settings
+/// or like "delay=800,error=0.1,timeout=0.05" to override them
+pub const HEADER: &str = "X-Ehallway-Faults";
+/// Names the fault a response carries
+const INJECTED_HEADER: &str = "X-Ehallway-Fault";
+
+/// The optional `[faults]` table of the config file,
+/// which makes responses slow and unreliable for testing the UI under a bad network.
+/// Only for development: without it, the header is ignored.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FaultConfig {
+    /// Injects faults into every API response, not only those whose request has the header
+    #[serde(default)]
+    pub always: bool,
+    #[serde(flatten)]
+    pub faults: Faults,
+}
+
+/// How a response goes wrong.
+/// Faults are injected after the handler has run, like a response lost on the way back,
+/// so a retried request finds its first attempt's changes.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Faults {
+    /// Each response waits up to this long, chosen at random
+    #[serde(default)]
+    pub max_delay_ms: u64,
+    /// The share of responses replaced with a 500
+    #[serde(default)]
+    pub error_rate: f64,
+    /// The share of responses held for `timeout_secs` and then replaced with a 504,
+    /// as a proxy answers when the API is too slow
+    #[serde(default)]
+    pub timeout_rate: f64,
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_timeout_secs() -> u64 {
+    30
+}
+
+impl Faults {
+    /// These faults with the header's overrides.
+    /// Settings the header doesn't name, or can't parse, are left as they are.
+    pub fn with_header(mut self, header: &str) -> Self {
+        for setting in header.split(',') {
+            let Some((name, value)) = setting.split_once('=') else {
+                continue;
+            };
+            let value = value.trim();
+            match name.trim() {
+                "delay" => self.max_delay_ms = value.parse().unwrap_or(self.max_delay_ms),
+                "error" => self.error_rate = value.parse().unwrap_or(self.error_rate),
+                "timeout" => self.timeout_rate = value.parse().unwrap_or(self.timeout_rate),
+                _ => {}
+            }
+        }
+        self
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Fault {
+    Error,
+    Timeout,
+}
+
+impl Fault {
+    // One draw decides between the two, so the rates add up.
+    fn choose(faults: &Faults, draw: f64) -> Option<Self> {
+        let error_rate = faults.error_rate.clamp(0.0, 1.0);
+        if draw < error_rate {
+            Some(Fault::Error)
+        } else if draw < error_rate + faults.timeout_rate.clamp(0.0, 1.0) {
+            Some(Fault::Timeout)
+        } else {
+            None
+        }
+    }
+}
+
+/// Delays and breaks API responses as the `[faults]` table and request headers ask.
+/// Static files are left alone, so the UI still loads.
+pub struct FaultFairing {
+    config: Option<FaultConfig>,
+}
+
+impl FaultFairing {
+    pub fn new(config: Option<FaultConfig>) -> Self {
+        if let Some(config) = &config {
+            tracing::warn!(
+                "injecting faults into responses, which is only for development: {config:?}"
+            );
+        }
+        FaultFairing { config }
+    }
+
+    fn faults(&self, request: &Request<'_>) -> Option<Faults> {
+        let config = self.config.as_ref()?;
+        let is_static = request
+            .route()
+            .and_then(|route| route.name.as_deref())
+            .is_none_or(|name| name.starts_with("FileServer"));
+        if is_static {
+            return None;
+        }
+        match request.headers().get_one(HEADER) {
+            Some(header) => Some(config.faults.with_header(header)),
+            None if config.always => Some(config.faults),
+            None => None,
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for FaultFairing {
+    fn info(&self) -> Info {
+        Info {
+            name: "Fault injection",
+            kind: Kind::Response,
+        }
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let Some(faults) = self.faults(request) else {
+            return;
+        };
+        let (delay_ms, draw) = {
+            let mut rng = rand::thread_rng();
+            (rng.gen_range(0..=faults.max_delay_ms), rng.gen::<f64>())
+        };
+        sleep(Duration::from_millis(delay_ms)).await;
+        let (status, name) = match Fault::choose(&faults, draw) {
+            Some(Fault::Error) => (Status::InternalServerError, "error"),
+            Some(Fault::Timeout) => {
+                sleep(Duration::from_secs(faults.timeout_secs)).await;
+                (Status::GatewayTimeout, "timeout")
+            }
+            None => return,
+        };
+        let body = error_body(ErrorCode::InternalError, &format!("injected {name}")).to_string();
+        response.set_status(status);
+        response.set_header(ContentType::JSON);
+        response.set_header(Header::new(INJECTED_HEADER, name));
+        response.set_sized_body(body.len(), Cursor::new(body));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Fault, FaultConfig, Faults};
+
+    #[test]
+    fn test_faults() {
+        let config: FaultConfig = toml::from_str("max_delay_ms = 500\nerror_rate = 0.1").unwrap();
+        assert!(!config.always);
+        assert_eq!(config.faults.timeout_secs, 30);
+        let faults = config.faults.with_header("on");
+        assert_eq!(faults, config.faults);
+        let faults = faults.with_header("delay=2000, timeout=0.2,error=oops,bogus");
+        assert_eq!(faults.max_delay_ms, 2000);
+        assert_eq!(faults.error_rate, 0.1);
+        assert_eq!(faults.timeout_rate, 0.2);
+
+        assert_eq!(Fault::choose(&faults, 0.05), Some(Fault::Error));
+        assert_eq!(Fault::choose(&faults, 0.25), Some(Fault::Timeout));
+        assert_eq!(Fault::choose(&faults, 0.35), None);
+        assert_eq!(Fault::choose(&Faults::default(), 0.0), None);
+    }
+}
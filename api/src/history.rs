@@ -0,0 +1,116 @@
+use tokio_postgres::Error;
+
+use ehall::{PastElection, UserTopic};
+
+use crate::clock::Clock;
+use crate::db::Client;
+
+// One statement, so two last voters finishing at once record the cohort once.
+const RECORD: &str = "
+    with election as (
+        insert into election_results (meeting, cohort, members, voting_method, recorded_at, round)
+        select $1, $2, $3, m.voting_method, to_timestamp($4::bigint), coalesce(g.round, 1)
+        from meetings m
+        left join cohort_groups g on g.meeting = m.id
+        where m.id = $1
+        on conflict (meeting, cohort) do nothing
+        returning id
+    )
+    insert into election_result_topics (election, position, topic, text, score, seeded, vetoed)
+    select election.id, (t.position - 1)::int, t.topic, t.text, t.score, t.seeded, t.vetoed
+    from election,
+        unnest($5::bigint[], $6::varchar[], $7::int[], $8::bool[], $9::bool[])
+        with ordinality as t (topic, text, score, seeded, vetoed, position)
+";
+
+// With $2, only the elections of cohorts $2 belonged to
+const GET_PAST_ELECTIONS: &str = "
+    select e.id, e.cohort, e.members, e.voting_method,
+        extract(epoch from e.recorded_at)::bigint,
+        t.topic, t.text, t.score, t.seeded, t.vetoed, e.round
+    from election_results e
+    left join election_result_topics t on t.election = e.id
+    where e.meeting = $1 and ($2::varchar is null or $2 = any(e.members))
+    order by e.recorded_at, e.id, t.position
+";
+
+/// Keeps a cohort's elected topics, unless they were already kept
+pub async fn record(
+    client: &Client,
+    clock: &Clock,
+    meeting_id: i64,
+    cohort: i64,
+    members: &[String],
+    topics: &[UserTopic],
+) -> Result<(), Error> {
+    let ids: Vec<_> = topics.iter().map(|t| t.id as i64).collect();
+    let texts: Vec<_> = topics.iter().map(|t| t.text.as_str()).collect();
+    let scores: Vec<_> = topics.iter().map(|t| t.score as i32).collect();
+    let seeded: Vec<_> = topics.iter().map(|t| t.seeded).collect();
+    let vetoed: Vec<_> = topics.iter().map(|t| t.vetoed).collect();
+    client
+        .execute(
+            RECORD,
+            &[
+                &meeting_id,
+                &cohort,
+                &members,
+                &clock.unix_timestamp(),
+                &ids,
+                &texts,
+                &scores,
+                &seeded,
+                &vetoed,
+            ],
+        )
+        .await?;
+    Ok(())
+}
+
+/// The meeting's recorded elections, oldest first,
+/// or with `member`, only those of cohorts they belonged to
+pub async fn past_elections(
+    client: &Client,
+    meeting_id: i64,
+    member: Option<&str>,
+) -> Result<Vec<PastElection>, Error> {
+    let rows = client
+        .query(GET_PAST_ELECTIONS, &[&meeting_id, &member])
+        .await?;
+    let mut elections: Vec<(i64, PastElection)> = vec![];
+    for row in rows.iter() {
+        let id = row.get::<_, i64>(0);
+        if elections.last().map_or(true, |(last, _)| *last != id) {
+            elections.push((
+                id,
+                PastElection {
+                    round: row.get::<_, i32>(10) as u32,
+                    cohort: row.get::<_, i64>(1) as u32,
+                    members: row.get(2),
+                    voting_method: crate::parse_voting_method(row.get(3)),
+                    topics: vec![],
+                    recorded_at: row.get(4),
+                },
+            ));
+        }
+        // A cohort that elected nothing has a row without a topic.
+        let topic = match row.get::<_, Option<i64>>(5) {
+            Some(topic) => topic,
+            None => continue,
+        };
+        let (_, election) = elections.last_mut().unwrap();
+        election.topics.push(UserTopic {
+            text: row.get(6),
+            score: row.get::<_, i32>(7) as u32,
+            id: topic as u32,
+            seeded: row.get(8),
+            position: election.topics.len() as u32,
+            links: vec![],
+            proposers: vec![],
+            vetoed: row.get(9),
+            private: false,
+            description: "".to_owned(),
+        });
+    }
+    Ok(elections.into_iter().map(|(_, e)| e).collect())
+}
@@ -0,0 +1,152 @@
+use std::future::Future;
+
+use rocket::http::Status;
+use rocket::request::{FromRequest, Outcome, Request};
+use rocket::response::status::Custom;
+use rocket::time::Duration;
+use serde_json::Value;
+
+use ehall::ErrorCode;
+
+use crate::clock::Clock;
+use crate::db::Client;
+use crate::error::{error_body, ApiResult};
+
+pub const HEADER: &str = "Idempotency-Key";
+const MAX_KEY_LEN: usize = 128;
+
+// Clients retry within seconds, so a day is plenty.
+const KEEP: Duration = Duration::DAY;
+const PRUNE: &str = "delete from idempotency_keys where created_at < to_timestamp($1::bigint)";
+
+// Keys created before this, in seconds since the Unix epoch, have expired.
+fn expired_before(clock: &Clock) -> i64 {
+    (clock.now() - KEEP).unix_timestamp()
+}
+
+/// The optional `Idempotency-Key` request header
+pub struct IdempotencyKey(Option<String>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for IdempotencyKey {
+    type Error = &'static str;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        match request.headers().get_one(HEADER) {
+            None => Outcome::Success(IdempotencyKey(None)),
+            Some(key) if !key.is_empty() && key.len() <= MAX_KEY_LEN => {
+                Outcome::Success(IdempotencyKey(Some(key.to_owned())))
+            }
+            Some(_) => Outcome::Failure((Status::BadRequest, "bad Idempotency-Key")),
+        }
+    }
+}
+
+/// Runs `handler` unless the user already sent `key` for `route`,
+/// in which case the first response is returned again.
+/// Requests without a key always run, and so do retries of a request that failed.
+pub async fn once<F>(
+    client: &Client,
+    clock: &Clock,
+    email: &str,
+    key: &IdempotencyKey,
+    route: &str,
+    handler: F,
+) -> ApiResult<Custom<Value>>
+where
+    F: Future<Output = ApiResult<Custom<Value>>>,
+{
+    let key = match &key.0 {
+        Some(key) => key,
+        None => return handler.await,
+    };
+    client.execute(PRUNE, &[&expired_before(clock)]).await?;
+    let reserved = client
+        .execute(
+            "
+            insert into idempotency_keys (email, key, route, created_at)
+            values ($1, $2, $3, to_timestamp($4::bigint))
+            on conflict (email, key) do nothing
+            ",
+            &[&email, key, &route, &clock.unix_timestamp()],
+        )
+        .await?;
+    if reserved == 0 {
+        let rows = client
+            .query(
+                "select route, status, body from idempotency_keys where email = $1 and key = $2",
+                &[&email, key],
+            )
+            .await?;
+        let (first_route, status, body) = match rows.first() {
+            Some(row) => (
+                row.get::<_, String>(0),
+                row.get::<_, Option<i32>>(1),
+                row.get::<_, Option<String>>(2),
+            ),
+            // Pruned in the meantime, which only happens to very slow retries
+            None => return handler.await,
+        };
+        if first_route != route {
+            return Ok(Custom(
+                Status::UnprocessableEntity,
+                error_body(
+                    ErrorCode::KeyReused,
+                    "Idempotency-Key was used for another request",
+                ),
+            ));
+        }
+        return Ok(match (status, body) {
+            (Some(status), Some(body)) => Custom(
+                Status::from_code(status as u16).unwrap_or(Status::Ok),
+                serde_json::from_str(&body).unwrap_or_default(),
+            ),
+            _ => Custom(
+                Status::Conflict,
+                error_body(
+                    ErrorCode::RequestInProgress,
+                    "a request with this Idempotency-Key is in progress",
+                ),
+            ),
+        });
+    }
+    let response = match handler.await {
+        Ok(response) => response,
+        Err(e) => {
+            // There is no response to replay, so let a retry run the handler.
+            client
+                .execute(
+                    "delete from idempotency_keys where email = $1 and key = $2",
+                    &[&email, key],
+                )
+                .await?;
+            return Err(e);
+        }
+    };
+    let status = response.0.code as i32;
+    client
+        .execute(
+            "update idempotency_keys set status = $3, body = $4 where email = $1 and key = $2",
+            &[&email, key, &status, &response.1.to_string()],
+        )
+        .await?;
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use rocket::time::{Duration, OffsetDateTime};
+
+    use super::{expired_before, KEEP};
+    use crate::clock::Clock;
+
+    #[test]
+    fn test_keys_expire_after_a_day() {
+        let clock = Clock::mock(OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap());
+        let created_at = clock.unix_timestamp();
+        clock.advance(KEEP);
+        assert!(created_at >= expired_before(&clock));
+        clock.advance(Duration::SECOND);
+        assert!(created_at < expired_before(&clock));
+    }
+}
@@ -0,0 +1,271 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use deadpool_postgres::{Pool, Transaction};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
+use tokio::time::sleep;
+use tokio_postgres::{Error, Row};
+
+use ehall::{HistoryImport, ImportState, ImportStatus, ImportedMeeting};
+
+use crate::clock::Clock;
+use crate::db::Client;
+use crate::jobs::Jobs;
+
+/// The optional `[imports]` table of the config file
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ImportConfig {
+    /// The pause after each imported meeting,
+    /// so a big import doesn't crowd out meetings in progress
+    #[serde(default = "default_pause_ms")]
+    pub pause_ms: u64,
+}
+
+fn default_pause_ms() -> u64 {
+    200
+}
+
+impl Default for ImportConfig {
+    fn default() -> Self {
+        ImportConfig {
+            pause_ms: default_pause_ms(),
+        }
+    }
+}
+
+/// Wakes this instance's importer when an import is queued or resumed
+#[derive(Clone, Default)]
+pub struct ImportQueue(Arc<Notify>);
+
+impl ImportQueue {
+    pub fn wake(&self) {
+        self.0.notify_one();
+    }
+}
+
+// Other API instances queue imports too, so an idle importer looks now and then.
+const IDLE_CHECK: Duration = Duration::from_secs(30);
+
+const QUEUE: &str = "
+    insert into history_imports (submitted_by, submitted_at, document, n_meetings)
+    values ($1, to_timestamp($2::bigint), $3::text::jsonb, $4)
+    returning id
+";
+
+const GET_STATUSES: &str = "
+    select id, state, n_meetings, n_imported, error, submitted_by,
+        extract(epoch from submitted_at)::bigint,
+        extract(epoch from finished_at)::bigint
+    from history_imports
+    where $1::bigint is null or id = $1
+    order by id desc
+";
+
+// The oldest unfinished import, locked so two API instances never add the same meeting.
+// Imports keep their order, so a later one never waits on an earlier one's meeting names.
+const NEXT_MEETING: &str = "
+    select id, n_imported, (document->'meetings'->n_imported)::text, submitted_by
+    from history_imports
+    where state in ('queued', 'running')
+    order by id
+    limit 1
+    for update skip locked
+";
+
+// The document holds everyone's emails, so it goes once the meetings are in.
+const IMPORTED: &str = "
+    update history_imports
+    set n_imported = n_imported + 1,
+        state = case when n_imported + 1 = n_meetings then 'done' else 'running' end,
+        finished_at = case when n_imported + 1 = n_meetings then to_timestamp($2::bigint) end,
+        document = case when n_imported + 1 = n_meetings then '{}' else document end
+    where id = $1
+";
+
+const FAILED: &str = "
+    update history_imports
+    set state = 'failed', error = $2, finished_at = to_timestamp($3::bigint)
+    where id = $1
+";
+
+// Failed imports start again at the meeting they stopped at.
+const RESUME: &str = "
+    update history_imports
+    set state = 'queued', error = null, finished_at = null
+    where id = $1 and state = 'failed'
+";
+
+// Imported meetings count as started when they were held,
+// so they can't start again and retention anonymizes them in turn.
+const ADD_MEETING: &str = "
+    with meeting as (
+        insert into meetings (name, owner, voting_method, registration_deadline)
+        values ($1, $2, $3, to_timestamp($4::bigint))
+        returning id
+    ), started as (
+        insert into cohort_groups (meeting, round, started_at)
+        select id, 1, to_timestamp($4::bigint) from meeting
+    )
+    select id from meeting
+";
+
+// Topics get IDs from the sequence that user and seed topics share, so none repeat,
+// and scores that keep their order, the best highest.
+const ADD_COHORT: &str = "
+    with election as (
+        insert into election_results (meeting, cohort, members, voting_method, recorded_at, round)
+        values ($1, $2, $3, $4, to_timestamp($5::bigint), 1)
+        returning id
+    ), topics as (
+        insert into election_result_topics (election, position, topic, text, score, seeded, vetoed)
+        select election.id, (t.position - 1)::int, nextval('user_topics_id_seq'), t.text,
+            (cardinality($6::varchar[]) - t.position + 1)::int, false, false
+        from election, unnest($6::varchar[]) with ordinality as t (text, position)
+    )
+    insert into cohort_outcomes (meeting, cohort, text, recorded_by, recorded_at)
+    select $1, $2, $7, $8, to_timestamp($5::bigint)
+    where $7::varchar is not null
+";
+
+fn status(row: &Row) -> ImportStatus {
+    ImportStatus {
+        id: row.get::<_, i64>(0) as u32,
+        state: ImportState::parse(row.get(1)).unwrap_or(ImportState::Failed),
+        n_meetings: row.get::<_, i32>(2) as u32,
+        n_imported: row.get::<_, i32>(3) as u32,
+        error: row.get(4),
+        submitted_by: row.get(5),
+        submitted_at: row.get(6),
+        finished_at: row.get(7),
+    }
+}
+
+/// Saves a validated import for the importer, which adds its meetings in order
+pub async fn queue(
+    client: &Client,
+    clock: &Clock,
+    email: &str,
+    import: &HistoryImport,
+) -> Result<ImportStatus> {
+    let document = serde_json::to_string(import)?;
+    let row = client
+        .query_one(
+            QUEUE,
+            &[
+                &email,
+                &clock.unix_timestamp(),
+                &document,
+                &(import.meetings.len() as i32),
+            ],
+        )
+        .await?;
+    let id = row.get::<_, i64>(0);
+    let statuses = statuses(client, Some(id)).await?;
+    Ok(statuses
+        .into_iter()
+        .next()
+        .expect("the import was just saved"))
+}
+
+/// Every import, newest first, or with `id`, only that one
+pub async fn statuses(client: &Client, id: Option<i64>) -> Result<Vec<ImportStatus>, Error> {
+    let rows = client.query(GET_STATUSES, &[&id]).await?;
+    Ok(rows.iter().map(status).collect())
+}
+
+/// Queues a failed import again, returning whether it had failed
+pub async fn resume(client: &Client, id: i64) -> Result<bool, Error> {
+    Ok(client.execute(RESUME, &[&id]).await? == 1)
+}
+
+async fn add_meeting(
+    tx: &Transaction<'_>,
+    meeting: &ImportedMeeting,
+    recorded_by: &str,
+) -> Result<(), Error> {
+    let row = tx
+        .query_one(
+            ADD_MEETING,
+            &[
+                &meeting.name,
+                &meeting.owner,
+                &meeting.voting_method.as_str(),
+                &meeting.held_at,
+            ],
+        )
+        .await?;
+    let meeting_id = row.get::<_, i64>(0);
+    let stmt = tx.prepare(ADD_COHORT).await?;
+    for (cohort, imported) in meeting.cohorts.iter().enumerate() {
+        tx.execute(
+            &stmt,
+            &[
+                &meeting_id,
+                &(cohort as i64),
+                &imported.members,
+                &meeting.voting_method.as_str(),
+                &meeting.held_at,
+                &imported.topics,
+                &imported.outcome,
+                &recorded_by,
+            ],
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Adds the next meeting of the oldest unfinished import, returning whether there was one.
+/// Each meeting is added whole, with the import's progress, or not at all,
+/// so an importer stopped anywhere resumes where it left off.
+async fn import_next(pool: &Pool, clock: &Clock) -> Result<bool> {
+    let mut client = pool.get().await?;
+    let mut tx = client.transaction().await?;
+    let Some(row) = tx.query_opt(NEXT_MEETING, &[]).await? else {
+        return Ok(false);
+    };
+    let id = row.get::<_, i64>(0);
+    let position = row.get::<_, i32>(1);
+    // Validated when it was queued
+    let meeting: ImportedMeeting = serde_json::from_str(row.get(2))?;
+    let recorded_by: String = row.get(3);
+    let added = {
+        let savepoint = tx.transaction().await?;
+        match add_meeting(&savepoint, &meeting, &recorded_by).await {
+            Ok(()) => savepoint.commit().await,
+            Err(e) => Err(e),
+        }
+    };
+    match added {
+        Ok(()) => {
+            tx.execute(IMPORTED, &[&id, &clock.unix_timestamp()])
+                .await?;
+        }
+        Err(e) => {
+            let error = format!("meeting {} ({:?}): {e}", position + 1, meeting.name);
+            tracing::warn!("history import {id} failed at {error}");
+            tx.execute(FAILED, &[&id, &error, &clock.unix_timestamp()])
+                .await?;
+        }
+    }
+    tx.commit().await?;
+    Ok(true)
+}
+
+/// Works through queued imports one meeting at a time, until the API shuts down
+pub fn register(jobs: &Jobs, pool: Pool, clock: Clock, queue: ImportQueue, config: ImportConfig) {
+    let pause = Duration::from_millis(config.pause_ms);
+    jobs.register_waking("imports", IDLE_CHECK, queue.0.clone(), move || {
+        let (pool, clock, queue) = (pool.clone(), clock.clone(), queue.clone());
+        // A meeting a run, so shutting down waits for one meeting at most
+        async move {
+            if import_next(&pool, &clock).await? {
+                sleep(pause).await;
+                queue.wake();
+            }
+            Ok(())
+        }
+    });
+}
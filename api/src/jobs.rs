@@ -0,0 +1,187 @@
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::Result;
+use deadpool_postgres::Pool;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::time;
+use rocket::tokio::sync::{watch, Notify};
+use rocket::tokio::task::JoinHandle;
+use rocket::tokio::time::{interval, MissedTickBehavior};
+use rocket::tokio::{self, select};
+use rocket::{Orbit, Rocket};
+use serde::{Deserialize, Serialize};
+
+use ehall::MeetingEventKind;
+
+use crate::clock::Clock;
+use crate::db::Client;
+use crate::events;
+
+/// The optional `[jobs]` table of the config file.
+/// Each job it names runs on its own interval as long as the API runs.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct JobsConfig {
+    /// Removes attendees who have waited too long for a meeting to start
+    pub stale_attendees: Option<StaleAttendeesConfig>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct StaleAttendeesConfig {
+    /// Seconds between looks for stale attendees
+    #[serde(default = "default_every_secs")]
+    pub every_secs: u64,
+    /// Hours after attending a meeting that hasn't started before the attendee is removed
+    #[serde(default = "default_ttl_hours")]
+    pub ttl_hours: u32,
+}
+
+fn default_every_secs() -> u64 {
+    600
+}
+
+fn default_ttl_hours() -> u32 {
+    24
+}
+
+// As if each had left the meeting, with their ballot.
+// Meetings that started keep their attendees, who are in its cohorts.
+const PURGE_STALE_ATTENDEES: &str = "
+    with stale as (
+        delete from meeting_attendees a
+        where a.attended_at < to_timestamp($1::bigint)
+            and a.meeting not in (select meeting from cohort_groups)
+        returning a.meeting, a.email
+    ), ballots as (
+        delete from meeting_topics t using stale s
+        where t.meeting = s.meeting and t.email = s.email
+    ), votes as (
+        delete from meeting_votes v using stale s
+        where v.meeting = s.meeting and v.email = s.email
+    )
+    select meeting, count(*) from stale group by meeting order by meeting
+";
+
+/// The periodic jobs running in the background.
+/// As a fairing, it stops them when Rocket shuts down,
+/// letting runs in progress finish first.
+pub struct Jobs {
+    stop: watch::Sender<bool>,
+    running: Mutex<Vec<(&'static str, JoinHandle<()>)>>,
+}
+
+impl Jobs {
+    fn new() -> Self {
+        Jobs {
+            stop: watch::channel(false).0,
+            running: Mutex::new(vec![]),
+        }
+    }
+
+    /// The jobs the config file names.
+    /// The API's own tasks are registered alongside them.
+    pub fn from_config(config: &JobsConfig, pool: &Pool, clock: &Clock) -> Self {
+        let jobs = Jobs::new();
+        if let Some(stale) = config.stale_attendees.clone() {
+            let (pool, clock) = (pool.clone(), clock.clone());
+            jobs.register(
+                "stale_attendees",
+                Duration::from_secs(stale.every_secs.max(1)),
+                move || purge_stale_attendees(pool.clone(), clock.clone(), stale.ttl_hours),
+            );
+        }
+        jobs
+    }
+
+    /// Runs `job` now and then every `period`, logging its failures
+    pub fn register<F, Fut>(&self, name: &'static str, period: Duration, job: F)
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.register_waking(name, period, Arc::new(Notify::new()), job);
+    }
+
+    /// Runs `job` as `register` does, and also as soon as `wake` is notified
+    pub fn register_waking<F, Fut>(
+        &self,
+        name: &'static str,
+        period: Duration,
+        wake: Arc<Notify>,
+        mut job: F,
+    ) where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        let mut stop = self.stop.subscribe();
+        let handle = tokio::spawn(async move {
+            let mut ticks = interval(period);
+            // A slow run delays the next rather than causing a burst of them.
+            ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                select! {
+                    _ = ticks.tick() => {}
+                    _ = wake.notified() => {}
+                    _ = stop.changed() => break,
+                }
+                if let Err(e) = job().await {
+                    tracing::error!("job {name}: {e:#}");
+                }
+            }
+        });
+        tracing::info!(every = ?period, "started job {name}");
+        self.running.lock().unwrap().push((name, handle));
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for Jobs {
+    fn info(&self) -> Info {
+        Info {
+            name: "Background jobs",
+            kind: Kind::Shutdown,
+        }
+    }
+
+    async fn on_shutdown(&self, _: &Rocket<Orbit>) {
+        self.stop.send_replace(true);
+        let running = std::mem::take(&mut *self.running.lock().unwrap());
+        for (name, handle) in running {
+            if let Err(e) = handle.await {
+                tracing::error!("job {name} ended badly: {e}");
+            }
+        }
+        tracing::info!("stopped background jobs");
+    }
+}
+
+// Every API instance looks, and a removed attendee is only removed once,
+// so instances don't need to take turns.
+async fn purge_stale_attendees(pool: Pool, clock: Clock, ttl_hours: u32) -> Result<()> {
+    let client = Client::new(pool.get().await?);
+    let cutoff = (clock.now() - time::Duration::hours(ttl_hours as i64)).unix_timestamp();
+    let rows = client.query(PURGE_STALE_ATTENDEES, &[&cutoff]).await?;
+    for row in rows.iter() {
+        let meeting: i64 = row.get(0);
+        let n: i64 = row.get(1);
+        tracing::info!(meeting, "removed {n} stale attendees");
+        events::notify(&client, meeting, MeetingEventKind::AttendanceChanged).await;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JobsConfig;
+
+    #[test]
+    fn test_jobs_config() {
+        let config: JobsConfig = toml::from_str("").unwrap();
+        assert!(config.stale_attendees.is_none());
+        let config: JobsConfig = toml::from_str("[stale_attendees]\nttl_hours = 6").unwrap();
+        let stale = config.stale_attendees.unwrap();
+        assert_eq!(stale.ttl_hours, 6);
+        assert_eq!(stale.every_secs, 600);
+    }
+}
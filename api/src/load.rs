@@ -0,0 +1,273 @@
+use std::io::Cursor;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::{ContentType, Header, Status};
+use rocket::request::{FromRequest, Outcome};
+use rocket::tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use rocket::{Data, Request, Response};
+use serde::{Deserialize, Serialize};
+
+use ehall::{CohortWaitReport, ErrorCode};
+
+use crate::clock::Clock;
+use crate::error::error_body;
+use crate::request_id;
+
+/// Seconds the client should wait before polling again
+pub const HEADER: &str = "Retry-After";
+// The routes the UI calls on a timer
+const POLLED_ROUTES: [&str; 2] = ["get_meetings", "get_election_results"];
+
+/// The optional `[polling]` table of the config file
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PollingConfig {
+    /// Requests in progress at which polls are spread out the most
+    #[serde(default = "default_busy_requests")]
+    pub busy_requests: usize,
+    /// The longest interval to suggest between polls, in seconds
+    #[serde(default = "default_max_interval_secs")]
+    pub max_interval_secs: u32,
+    /// The longest a wait for election results is held while the cohort is pending,
+    /// in seconds.  Waits still pending at the end count as over budget.
+    #[serde(default = "default_cohort_wait_secs")]
+    pub cohort_wait_secs: u64,
+}
+
+fn default_busy_requests() -> usize {
+    64
+}
+
+fn default_max_interval_secs() -> u32 {
+    30
+}
+
+fn default_cohort_wait_secs() -> u64 {
+    30
+}
+
+impl Default for PollingConfig {
+    fn default() -> Self {
+        PollingConfig {
+            busy_requests: default_busy_requests(),
+            max_interval_secs: default_max_interval_secs(),
+            cohort_wait_secs: default_cohort_wait_secs(),
+        }
+    }
+}
+
+impl PollingConfig {
+    /// From one second when idle, growing linearly to the maximum when busy
+    pub fn interval_secs(&self, in_flight: usize) -> u32 {
+        let busy = self.busy_requests.max(1);
+        let spread = self.max_interval_secs.saturating_sub(1) as usize;
+        1 + (spread * in_flight.min(busy) / busy) as u32
+    }
+}
+
+/// Waits for election results that began with the cohort pending,
+/// and how many were still pending when their budget ran out, since this instance started.
+/// Many overruns suggest that cohorts take longer to form than users are told to wait.
+pub struct CohortWaits {
+    budget: Duration,
+    counting_since: i64,
+    waits: AtomicU64,
+    over_budget: AtomicU64,
+}
+
+impl CohortWaits {
+    pub fn new(config: &PollingConfig, clock: &Clock) -> Self {
+        CohortWaits {
+            budget: Duration::from_secs(config.cohort_wait_secs),
+            counting_since: clock.unix_timestamp(),
+            waits: AtomicU64::new(0),
+            over_budget: AtomicU64::new(0),
+        }
+    }
+
+    /// The longest a wait is held while the cohort is pending
+    pub fn budget(&self) -> Duration {
+        self.budget
+    }
+
+    pub fn record(&self, over_budget: bool) {
+        self.waits.fetch_add(1, Ordering::Relaxed);
+        if over_budget {
+            self.over_budget.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub fn report(&self) -> CohortWaitReport {
+        CohortWaitReport {
+            counting_since: self.counting_since,
+            budget_secs: self.budget.as_secs(),
+            waits: self.waits.load(Ordering::Relaxed),
+            over_budget: self.over_budget.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// The optional `[limits]` table of the config file
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LimitConfig {
+    /// Expensive requests, like long lists, handled at once.
+    /// Any more are refused with a 503 until one finishes.
+    #[serde(default = "default_expensive_requests")]
+    pub expensive_requests: usize,
+}
+
+fn default_expensive_requests() -> usize {
+    8
+}
+
+impl Default for LimitConfig {
+    fn default() -> Self {
+        LimitConfig {
+            expensive_requests: default_expensive_requests(),
+        }
+    }
+}
+
+/// The permits for expensive requests, shared by every route that takes `Admitted`
+pub struct Limiter(Arc<Semaphore>);
+
+impl Limiter {
+    pub fn new(config: &LimitConfig) -> Self {
+        Limiter(Arc::new(Semaphore::new(config.expensive_requests.max(1))))
+    }
+}
+
+// Cached on requests refused for want of a permit
+struct Shed(bool);
+
+/// A permit to handle an expensive request, held until the handler returns.
+/// Without a permit free, the request fails with a 503,
+/// and `LoadFairing` says when to try again.
+pub struct Admitted {
+    _permit: OwnedSemaphorePermit,
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Admitted {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let limiter = request
+            .rocket()
+            .state::<Limiter>()
+            .expect("main manages the limiter");
+        match limiter.0.clone().try_acquire_owned() {
+            Ok(permit) => Outcome::Success(Admitted { _permit: permit }),
+            Err(_) => {
+                tracing::warn!(parent: request_id::span(request), "too busy, shedding the request");
+                request.local_cache(|| Shed(true));
+                Outcome::Failure((Status::ServiceUnavailable, ()))
+            }
+        }
+    }
+}
+
+/// Counts the requests in progress and, on polled routes,
+/// suggests a longer wait before the next poll as that count grows.
+/// Requests shed for want of a permit get the same suggestion
+/// with an error body.
+pub struct LoadFairing {
+    config: PollingConfig,
+    in_flight: AtomicUsize,
+}
+
+impl LoadFairing {
+    pub fn new(config: PollingConfig) -> Self {
+        LoadFairing {
+            config,
+            in_flight: AtomicUsize::new(0),
+        }
+    }
+}
+
+fn is_polled(request: &Request<'_>) -> bool {
+    request
+        .route()
+        .and_then(|route| route.name.as_deref())
+        .is_some_and(|name| POLLED_ROUTES.contains(&name))
+}
+
+#[rocket::async_trait]
+impl Fairing for LoadFairing {
+    fn info(&self) -> Info {
+        Info {
+            name: "Load shedding",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_request(&self, _: &mut Request<'_>, _: &mut Data<'_>) {
+        self.in_flight.fetch_add(1, Ordering::Relaxed);
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        // The count includes this request, which is finishing.
+        let others = self.in_flight.fetch_sub(1, Ordering::Relaxed) - 1;
+        let shed = request.local_cache(|| Shed(false)).0;
+        if shed {
+            let body = error_body(ErrorCode::Busy, "too busy, try again later").to_string();
+            response.set_status(Status::ServiceUnavailable);
+            response.set_header(ContentType::JSON);
+            response.set_sized_body(body.len(), Cursor::new(body));
+        }
+        if shed || is_polled(request) {
+            let secs = self.config.interval_secs(others);
+            response.set_header(Header::new(HEADER, secs.to_string()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rocket::time::OffsetDateTime;
+
+    use super::{CohortWaits, LimitConfig, PollingConfig};
+    use crate::clock::Clock;
+
+    #[test]
+    fn test_interval_secs() {
+        let config: PollingConfig = toml::from_str("busy_requests = 10").unwrap();
+        assert_eq!(config.max_interval_secs, 30);
+        assert_eq!(config.interval_secs(0), 1);
+        assert_eq!(config.interval_secs(5), 15);
+        assert_eq!(config.interval_secs(10), 30);
+        assert_eq!(config.interval_secs(1000), 30);
+        let config = PollingConfig {
+            busy_requests: 0,
+            max_interval_secs: 0,
+            cohort_wait_secs: 0,
+        };
+        assert_eq!(config.interval_secs(3), 1);
+    }
+
+    #[test]
+    fn test_cohort_waits() {
+        let config: PollingConfig = toml::from_str("cohort_wait_secs = 90").unwrap();
+        let start = OffsetDateTime::from_unix_timestamp(1_714_521_600).unwrap();
+        let waits = CohortWaits::new(&config, &Clock::mock(start));
+        assert_eq!(waits.budget().as_secs(), 90);
+        waits.record(false);
+        waits.record(true);
+        waits.record(false);
+        let report = waits.report();
+        assert_eq!(report.counting_since, 1_714_521_600);
+        assert_eq!(report.budget_secs, 90);
+        assert_eq!(report.waits, 3);
+        assert_eq!(report.over_budget, 1);
+    }
+
+    #[test]
+    fn test_limit_config() {
+        let config: LimitConfig = toml::from_str("").unwrap();
+        assert_eq!(config.expensive_requests, 8);
+        let config: LimitConfig = toml::from_str("expensive_requests = 2").unwrap();
+        assert_eq!(config.expensive_requests, 2);
+    }
+}
@@ -0,0 +1,146 @@
+use rocket::http::RawStr;
+use rocket::Request;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+const BLOCK_LEN: usize = 64;
+// Enough of the hash to tell a day's users apart in the log
+const SHOWN_HEX_LEN: usize = 16;
+
+/// The optional `[log_privacy]` table of the config file
+#[derive(Clone, Deserialize, Serialize)]
+pub struct LogPrivacyConfig {
+    /// Keys the hashes, so they cannot be matched to emails by hashing guesses
+    pub key: String,
+}
+
+/// How the server log shows emails, kept as Rocket state.
+/// With a key, each email is replaced by a hash that stays the same
+/// from line to line and from restart to restart, until the key changes.
+pub struct LogEmails(Option<Vec<u8>>);
+
+// HMAC-SHA256, as in RFC 2104
+fn hmac(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut block = [0u8; BLOCK_LEN];
+    if key.len() > BLOCK_LEN {
+        block[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+    let pad = |byte: u8| block.iter().map(move |b| b ^ byte).collect::<Vec<_>>();
+    let inner = Sha256::new()
+        .chain_update(pad(0x36))
+        .chain_update(message)
+        .finalize();
+    Sha256::new()
+        .chain_update(pad(0x5c))
+        .chain_update(inner)
+        .finalize()
+        .to_vec()
+}
+
+impl LogEmails {
+    pub fn new(config: Option<&LogPrivacyConfig>) -> Self {
+        LogEmails(config.map(|c| c.key.as_bytes().to_vec()))
+    }
+
+    /// Whether emails are hashed
+    pub fn hashing(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// The email as it should appear in the log
+    pub fn show(&self, email: &str) -> String {
+        let key = match &self.0 {
+            Some(key) => key,
+            None => return email.to_owned(),
+        };
+        let hex: String = hmac(key, email.as_bytes())
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect();
+        format!("user:{}", &hex[..SHOWN_HEX_LEN])
+    }
+
+    /// The request URI as it should appear in the log,
+    /// with any emails in its path or query shown as `show` would
+    pub fn uri(&self, uri: &str) -> String {
+        if self.0.is_none() {
+            return uri.to_owned();
+        }
+        let mut shown = String::with_capacity(uri.len());
+        for part in uri.split_inclusive(['/', '?', '&', '=']) {
+            let (text, delimiter) = match part.char_indices().last() {
+                Some((i, c)) if "/?&=".contains(c) => part.split_at(i),
+                _ => (part, ""),
+            };
+            let decoded = RawStr::new(text).percent_decode_lossy();
+            if decoded.contains('@') {
+                shown += &self.show(&decoded);
+            } else {
+                shown += text;
+            }
+            shown += delimiter;
+        }
+        shown
+    }
+}
+
+/// The request's URI as the managed `LogEmails` would show it
+pub fn request_uri(request: &Request<'_>) -> String {
+    let uri = request.uri().to_string();
+    match request.rocket().state::<LogEmails>() {
+        Some(emails) => emails.uri(&uri),
+        None => uri,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hmac, LogEmails, LogPrivacyConfig};
+
+    #[test]
+    fn test_hmac() {
+        // RFC 4231, test case 2
+        let mac: String = hmac(b"Jefe", b"what do ya want for nothing?")
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect();
+        assert_eq!(
+            mac,
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_log_emails() {
+        let plain = LogEmails::new(None);
+        assert_eq!(plain.show("a@example.com"), "a@example.com");
+        assert_eq!(
+            plain.uri("/admin/users/a%40example.com"),
+            "/admin/users/a%40example.com"
+        );
+
+        let config = LogPrivacyConfig {
+            key: "secret".to_owned(),
+        };
+        let hashed = LogEmails::new(Some(&config));
+        let a = hashed.show("a@example.com");
+        assert!(a.starts_with("user:") && !a.contains("example"));
+        assert_eq!(a, LogEmails::new(Some(&config)).show("a@example.com"));
+        assert_ne!(a, hashed.show("b@example.com"));
+        let rekeyed = LogEmails::new(Some(&LogPrivacyConfig {
+            key: "other".to_owned(),
+        }));
+        assert_ne!(a, rekeyed.show("a@example.com"));
+        assert_eq!(
+            hashed.uri("/admin/users/a%40example.com/active"),
+            format!("/admin/users/{a}/active")
+        );
+        assert_eq!(
+            hashed.uri("/meeting/3/organizers?email=a@example.com&x=1"),
+            format!("/meeting/3/organizers?email={a}&x=1")
+        );
+        assert_eq!(hashed.uri("/meeting/3"), "/meeting/3");
+    }
+}
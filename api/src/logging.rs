@@ -0,0 +1,80 @@
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::EnvFilter;
+
+/// The optional `[logging]` table of the config file
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LogConfig {
+    /// What to log, as a level like "debug" or per module,
+    /// like "info,ehallway=debug" to add each database call
+    #[serde(default = "default_level")]
+    pub level: String,
+    #[serde(default)]
+    pub format: LogFormat,
+}
+
+fn default_level() -> String {
+    "info".to_owned()
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        LogConfig {
+            level: default_level(),
+            format: LogFormat::default(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogFormat {
+    /// One line per event, for reading
+    #[default]
+    Text,
+    /// Several indented lines per event, for reading during development
+    Pretty,
+    /// One JSON object per line, for log collectors
+    Json,
+}
+
+impl LogConfig {
+    fn filter(&self) -> Result<EnvFilter> {
+        EnvFilter::try_new(&self.level)
+            .with_context(|| format!("logging level {:?} is not a tracing filter", self.level))
+    }
+}
+
+/// Sends the API's log events and spans to standard output as configured.
+/// Spans are logged as they close, with how long they took.
+pub fn init(config: &LogConfig) -> Result<()> {
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(config.filter()?)
+        .with_span_events(FmtSpan::CLOSE);
+    match config.format {
+        LogFormat::Text => builder.try_init(),
+        LogFormat::Pretty => builder.pretty().try_init(),
+        LogFormat::Json => builder.json().with_current_span(true).try_init(),
+    }
+    .map_err(|e| anyhow!("starting the log: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LogConfig, LogFormat};
+
+    #[test]
+    fn test_log_config() {
+        let config: LogConfig = toml::from_str("format = \"json\"").unwrap();
+        assert_eq!(config.format, LogFormat::Json);
+        assert_eq!(config.level, "info");
+        assert!(config.filter().is_ok());
+        let config: LogConfig = toml::from_str("level = \"warn,ehallway::db=debug\"").unwrap();
+        assert_eq!(config.format, LogFormat::Text);
+        assert!(config.filter().is_ok());
+        let config: LogConfig = toml::from_str("level = \"ehallway=loud\"").unwrap();
+        assert!(config.filter().is_err());
+        assert!(toml::from_str::<LogConfig>("format = \"xml\"").is_err());
+    }
+}
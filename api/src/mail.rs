@@ -0,0 +1,316 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use deadpool_postgres::Pool;
+use lettre::message::{header::ContentType, Mailbox};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::{error::RecvError, Receiver};
+
+use ehall::{MeetingEvent, MeetingEventKind, NotificationChannel, NotificationEvent};
+
+/// The optional `[smtp]` table of the config file
+#[derive(Clone, Deserialize, Serialize)]
+pub struct SmtpConfig {
+    pub host: String,
+    /// Connected to with STARTTLS
+    #[serde(default = "default_port")]
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// The sender, like "eHallway <hallway@example.com>"
+    pub from: String,
+    /// Where the site is, like "https://hallway.example.com", for links in emails
+    pub base_url: String,
+}
+
+fn default_port() -> u16 {
+    587
+}
+
+// Each finds the people to email and records that they were,
+// so when every API instance hears the same event, each person gets one email.
+// People are emailed unless they turned the event off, and deactivated accounts never are.
+const CLAIM_MEETING_STARTED: &str = "
+    insert into notification_deliveries (meeting, email, event)
+    select p.meeting, p.email, $2::varchar from meeting_participants p
+    where p.meeting = $1
+        and coalesce((
+            select n.enabled from notification_preferences n
+            where n.email = p.email and n.event = $2 and n.channel = $3
+        ), $4)
+        and not exists (
+            select 1 from disabled_accounts d where lower(d.email) = lower(p.email)
+        )
+    on conflict do nothing
+    returning email
+";
+
+const CLAIM_RESULTS_READY: &str = "
+    insert into notification_deliveries (meeting, email, event)
+    select distinct r.meeting, r.email, $2::varchar
+    from (
+        select meeting, unnest(members) as email from election_results where meeting = $1
+    ) r
+    where coalesce((
+            select n.enabled from notification_preferences n
+            where n.email = r.email and n.event = $2 and n.channel = $3
+        ), $4)
+        and not exists (
+            select 1 from disabled_accounts d where lower(d.email) = lower(r.email)
+        )
+    on conflict do nothing
+    returning email
+";
+
+/// An email's subject and plain text
+#[derive(Debug, PartialEq)]
+pub struct Notice {
+    pub subject: String,
+    pub body: String,
+}
+
+/// What to tell someone about `event` in the meeting named `meeting_name`,
+/// which they can open at `url`
+pub fn notice(event: NotificationEvent, meeting_name: &str, url: &str) -> Notice {
+    let (subject, news) = match event {
+        NotificationEvent::MeetingStarted => (
+            format!("{meeting_name} has started"),
+            format!("{meeting_name}, which you registered for, has started."),
+        ),
+        NotificationEvent::ResultsReady => (
+            format!("Your topics for {meeting_name}"),
+            format!("Your cohort in {meeting_name} has finished voting, and its topics are ready."),
+        ),
+        NotificationEvent::DailyDigest | NotificationEvent::WeeklyDigest => {
+            unreachable!("digests are about no one meeting")
+        }
+    };
+    Notice {
+        subject,
+        body: format!(
+            "{news}\n\nOpen the meeting: {url}\n\n\
+             To stop these emails, turn them off under Notifications on the Preferences tab.\n"
+        ),
+    }
+}
+
+/// What to tell someone `inviter` invited to sign up at `url`
+pub fn invitation_notice(inviter: &str, url: &str) -> Notice {
+    Notice {
+        subject: "You're invited to eHallway".to_owned(),
+        body: format!(
+            "{inviter} invited you to join them on eHallway.\n\n\
+             Sign up with this email address: {url}\n"
+        ),
+    }
+}
+
+/// What to tell administrators when `email` signs up and waits for approval
+pub fn join_request_notice(email: &str, base_url: &str) -> Notice {
+    Notice {
+        subject: format!("{email} asked to join eHallway"),
+        body: format!(
+            "{email} signed up and is waiting for an administrator.\n\n\
+             Approve or decline under Join requests on the Preferences tab: {}\n",
+            base_url.trim_end_matches('/')
+        ),
+    }
+}
+
+/// What to tell someone whose request to join was decided
+pub fn join_decision_notice(approved: bool, base_url: &str) -> Notice {
+    let base_url = base_url.trim_end_matches('/');
+    if approved {
+        Notice {
+            subject: "Welcome to eHallway".to_owned(),
+            body: format!("Your request to join was approved.\n\nLog in: {base_url}/login\n"),
+        }
+    } else {
+        Notice {
+            subject: "Your eHallway signup".to_owned(),
+            body: "Your request to join was declined, and the account was removed.\n".to_owned(),
+        }
+    }
+}
+
+/// The link that joins the meeting, as the invite QR code does
+pub fn meeting_url(base_url: &str, meeting_id: u32) -> String {
+    format!(
+        "{}/meeting/{meeting_id}/join",
+        base_url.trim_end_matches('/')
+    )
+}
+
+pub struct Mailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+    base_url: String,
+}
+
+impl Mailer {
+    pub fn new(config: &SmtpConfig) -> Result<Self> {
+        let mut transport =
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)?.port(config.port);
+        if let (Some(username), Some(password)) = (&config.username, &config.password) {
+            transport = transport.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+        Ok(Mailer {
+            transport: transport.build(),
+            from: config.from.parse()?,
+            base_url: config.base_url.clone(),
+        })
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Emails `notice` to each of `to` without waiting for the mail server.
+    /// These are about people rather than meetings, so nobody opts out
+    /// and a failure is only logged.
+    pub fn post(self: &Arc<Self>, to: Vec<String>, notice: Notice) {
+        let mailer = self.clone();
+        tokio::spawn(async move {
+            for email in to {
+                if let Err(e) = mailer.send(&email, &notice).await {
+                    tracing::warn!("email \"{}\" not sent: {e}", notice.subject);
+                }
+            }
+        });
+    }
+
+    pub async fn send(&self, to: &str, notice: &Notice) -> Result<()> {
+        let message = Message::builder()
+            .from(self.from.clone())
+            .to(to.parse()?)
+            .subject(&notice.subject)
+            .header(ContentType::TEXT_PLAIN)
+            .body(notice.body.clone())?;
+        self.transport.send(message).await?;
+        Ok(())
+    }
+}
+
+/// Emails the people who want to hear about each started meeting and finished cohort,
+/// as long as the API runs
+pub fn spawn(mailer: Arc<Mailer>, pool: Pool, mut events: Receiver<MeetingEvent>) {
+    tokio::spawn(async move {
+        loop {
+            let event = match events.recv().await {
+                Ok(event) => event,
+                Err(RecvError::Lagged(n)) => {
+                    tracing::warn!("mail fell behind and skipped {n} meeting events");
+                    continue;
+                }
+                Err(RecvError::Closed) => break,
+            };
+            let notification = match event.kind {
+                MeetingEventKind::CohortsCreated => NotificationEvent::MeetingStarted,
+                MeetingEventKind::ResultsReady => NotificationEvent::ResultsReady,
+                _ => continue,
+            };
+            // A slow mail server shouldn't hold up later events.
+            let (mailer, pool) = (mailer.clone(), pool.clone());
+            tokio::spawn(async move {
+                if let Err(e) = deliver(&mailer, &pool, event.meeting, notification).await {
+                    tracing::error!(
+                        "emailing {} for meeting {}: {e}",
+                        notification.as_str(),
+                        event.meeting
+                    );
+                }
+            });
+        }
+    });
+}
+
+async fn deliver(
+    mailer: &Mailer,
+    pool: &Pool,
+    meeting_id: u32,
+    event: NotificationEvent,
+) -> Result<()> {
+    let client = pool.get().await?;
+    let claim = match event {
+        NotificationEvent::MeetingStarted => CLAIM_MEETING_STARTED,
+        NotificationEvent::ResultsReady => CLAIM_RESULTS_READY,
+        NotificationEvent::DailyDigest | NotificationEvent::WeeklyDigest => {
+            unreachable!("digests are sent by the digest job")
+        }
+    };
+    let channel = NotificationChannel::Email;
+    let rows = client
+        .query(
+            claim,
+            &[
+                &(meeting_id as i64),
+                &event.as_str(),
+                &channel.as_str(),
+                &event.enabled_by_default(channel),
+            ],
+        )
+        .await?;
+    if rows.is_empty() {
+        return Ok(());
+    }
+    let name: String = client
+        .query_one(
+            "select name from meetings where id = $1",
+            &[&(meeting_id as i64)],
+        )
+        .await?
+        .get(0);
+    let notice = notice(event, &name, &meeting_url(&mailer.base_url, meeting_id));
+    // Claimed emails are not retried, so nobody gets the same one twice.
+    let mut n_failed = 0;
+    for row in rows.iter() {
+        if let Err(e) = mailer.send(row.get(0), &notice).await {
+            tracing::warn!("email about meeting {meeting_id} not sent: {e}");
+            n_failed += 1;
+        }
+    }
+    tracing::info!(
+        "emailed {} of {} people for {} in meeting {meeting_id}",
+        rows.len() - n_failed,
+        rows.len(),
+        event.as_str()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use ehall::NotificationEvent;
+
+    use super::{
+        invitation_notice, join_decision_notice, join_request_notice, meeting_url, notice,
+    };
+
+    #[test]
+    fn test_notice() {
+        let url = meeting_url("https://hallway.example.com/", 7);
+        assert_eq!(url, "https://hallway.example.com/meeting/7/join");
+        let started = notice(NotificationEvent::MeetingStarted, "Monday standup", &url);
+        assert_eq!(started.subject, "Monday standup has started");
+        assert!(started.body.contains(&url));
+        let results = notice(NotificationEvent::ResultsReady, "Monday standup", &url);
+        assert_eq!(results.subject, "Your topics for Monday standup");
+        assert!(results.body.contains(&url));
+    }
+
+    #[test]
+    fn test_membership_notices() {
+        let url = "https://hallway.example.com/signup?invitation=abc";
+        let invited = invitation_notice("ana@example.com", url);
+        assert!(invited.body.contains("ana@example.com"));
+        assert!(invited.body.contains(url));
+        let request = join_request_notice("bo@example.com", "https://hallway.example.com/");
+        assert_eq!(request.subject, "bo@example.com asked to join eHallway");
+        assert!(request.body.contains("https://hallway.example.com\n"));
+        let approved = join_decision_notice(true, "https://hallway.example.com/");
+        assert!(approved.body.contains("https://hallway.example.com/login"));
+        assert!(join_decision_notice(false, "").body.contains("declined"));
+    }
+}
@@ -1,408 +1,1801 @@
-use std::collections::HashMap;
-use std::{convert::TryInto, path::PathBuf, result::Result};
+use std::collections::{HashMap, HashSet};
+use std::{
+    convert::TryInto,
+    net::IpAddr,
+    path::{Path, PathBuf},
+    result::Result,
+};
 use std::{fs, sync};
 
-use anyhow::Context;
+use anyhow::{anyhow, Context};
 use clap::Parser;
-use rand::Rng;
+use deadpool_postgres::Pool;
+use qrcode::{render::svg, QrCode};
+use rocket::data::ToByteUnit;
+use rocket::figment::{
+    providers::{Env, Format, Toml},
+    Figment,
+};
 use rocket::fs::FileServer;
+use rocket::http::{uri::Host, ContentType, Status};
+use rocket::response::status::Custom;
+use rocket::response::stream::{Event, EventStream};
 use rocket::serde::{
     json::{Json, Value},
-    Deserialize,
+    Deserialize, Serialize,
+};
+use rocket::time::{Date, Duration, OffsetDateTime};
+use rocket::tokio::time::Instant;
+use rocket::tokio::{select, sync::broadcast::error::RecvError};
+use rocket::{
+    delete, form::*, get, post, put, response::Redirect, routes, uri, Responder, Shutdown, State,
 };
-use rocket::{delete, form::*, get, post, put, response::Redirect, routes, State};
 use rocket_auth::{prelude::Error, *};
 use rocket_dyn_templates::Template;
 use serde_json::json;
 use sha2::Digest;
-use tokio::time;
-use tokio_postgres::{connect, Client, NoTls};
+use tokio_postgres::{connect, NoTls};
 
 use ehall::{
-    CohortMessage, ElectionResults, Meeting, MeetingMessage, NewMeeting, NewTopicMessage,
-    ParticipateMeetingMessage, RegisteredMeetingsMessage, ScoreMessage, UserTopic,
-    UserTopicsMessage, COHORT_QUORUM,
+    conversation_language, normalize_join_code, page_limit, paginate, validate_cohort_size,
+    validate_n_winners, validate_topic_description, validate_topics_per_attendee,
+    validate_voting_minutes, AccountActiveMessage, AdminMeeting, AdminMeetingsMessage,
+    AttendMeetingMessage, AttendeeRole, AvatarMessage, BackupStatus, BuildInfo, CandidateTopic,
+    CandidateTopicsMessage, CohortMessage, CohortOutcome, CohortRoomJoins, CohortWaitReport,
+    Collation, ContactSharingMessage, Cursor, DeprecationReport, ElectionAudit, ElectionResults,
+    ElectionStatus, ErrorCode, HistoryImport, ImportStatus, ImportsMessage, InstanceInfo,
+    Invitation, InvitationsMessage, JoinCodeMessage, JoinDecisionMessage, JoinRequestsMessage,
+    JoinedMeetingMessage, LanguageMessage, Meeting, MeetingConfig, MeetingEventKind,
+    MeetingHistoryMessage, MeetingMessage, MeetingOrganizersMessage, MeetingOutcomesMessage,
+    MeetingPermissions, MeetingRole, MeetingSort, MeetingSortMessage, MeetingTemplate,
+    MeetingTemplatesMessage, MeetingsMessage, MergeTopicsMessage, MyTopicsMessage,
+    NewInvitationMessage, NewMeeting, NewOrganizerMessage, NewTopicMessage, NotificationChannel,
+    NotificationEvent, NotificationPreference, NotificationPreferencesMessage, OutcomeMessage,
+    OutcomeTopic, PageInfo, ParticipateMeetingMessage, RegisteredMeetingsMessage,
+    RegistrationAnswer, RegistrationDeadlineMessage, RegistrationError, RegistrationField,
+    RegistrationFieldsMessage, RenameMeetingMessage, ResearchConsentMessage, ResearchExport,
+    RoomJoinsMessage, ScoreMessage, ScoresMessage, SeedTopicsMessage, StartMeetingError,
+    SubmissionDeadlineMessage, SubmittedTopicsMessage, SyncMessage, TopicDescriptionMessage,
+    TopicEdit, TopicEditsMessage, TopicHistoryMessage, TopicLinksMessage, TopicPrivacyMessage,
+    TopicScoreChange, UserAccount, UserAccountsMessage, UserTopic, UserTopicsMessage, VetoMessage,
+    VetoPolicy, VotingMethod, VotingMinutesMessage, AVATAR_CONTENT_TYPES, COHORT_QUORUM,
+    MAX_AVATAR_BYTES, MAX_TEXT_LEN, MEETING_CONFIG_VERSION, SCHEMA_VERSION,
 };
+use ehall_election as election;
+
+use avatars::AvatarConfig;
+use backup::{BackupConfig, Backups};
+use captcha::CaptchaConfig;
+use clock::Clock;
+use collation::RequestCollation;
+use db::{Client, Db};
+use deprecation::{DeprecationConfig, DeprecationFairing, Deprecations};
+use digest::DigestConfig;
+use error::{error_body, ApiError, ApiResult};
+use events::MeetingEvents;
+use faults::{FaultConfig, FaultFairing};
+use idempotency::IdempotencyKey;
+use imports::{ImportConfig, ImportQueue};
+use jobs::{Jobs, JobsConfig};
+use load::{Admitted, CohortWaits, LimitConfig, Limiter, LoadFairing, PollingConfig};
+use log_privacy::{LogEmails, LogPrivacyConfig};
+use logging::LogConfig;
+use mail::{Mailer, SmtpConfig};
+use membership::MembershipConfig;
+use pages::{Page, Templates};
+use request_id::RequestIdFairing;
+use research::ResearchConfig;
+use retention::RetentionConfig;
+use sql_log::{SqlLog, SqlLogConfig};
+use summary::{csv_row, CohortSummary, ResultsExport};
 
-mod chance;
-mod cull;
+mod aliases;
+mod audit;
+mod avatars;
+mod backup;
+mod captcha;
+mod clock;
+mod collation;
+mod contacts;
+mod db;
+mod deprecation;
+mod digest;
+mod error;
+mod events;
+mod faults;
+mod history;
+mod idempotency;
+mod imports;
+mod jobs;
+mod load;
+mod log_privacy;
+mod logging;
+mod mail;
+mod membership;
+mod migrations;
+mod pages;
+mod request_id;
+mod research;
+mod retention;
+mod sql_log;
+mod summary;
+mod voting;
 
-const N_MEETING_TOPIC_WINNERS: usize = 2;
-const N_RETRIES: usize = 10;
-const RETRY_SLEEP_MS: u64 = 100;
+const DEFAULT_ROUND_MINUTES: u32 = 30;
+// Environment variables with this prefix override the config file,
+// with a double underscore between table and key, as in EHALLWAY_CAPTCHA__SITE_KEY.
+const ENV_PREFIX: &str = "EHALLWAY_";
+// Shown by the config subcommand in place of secrets
+const HIDDEN: &str = "<hidden>";
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize)]
 struct Config {
     static_path: String,
+    #[serde(default = "default_postgres_host")]
+    postgres_host: String,
     postgres_user: String,
     postgres_password: String,
+    /// Most database connections open at once for request handlers
+    #[serde(default = "db::default_pool_size")]
+    postgres_pool_size: usize,
+    captcha: Option<CaptchaConfig>,
+    backup: Option<BackupConfig>,
+    retention: Option<RetentionConfig>,
+    /// Logs a keyed hash of each email instead of the address
+    log_privacy: Option<LogPrivacyConfig>,
+    /// Emails registrants when meetings start and results are ready
+    smtp: Option<SmtpConfig>,
+    /// When the daily and weekly digest emails go out
+    #[serde(default)]
+    digest: DigestConfig,
+    #[serde(default)]
+    polling: PollingConfig,
+    /// How many expensive requests are handled at once
+    #[serde(default)]
+    limits: LimitConfig,
+    /// Routes to mark as deprecated in responses and count the use of
+    #[serde(default)]
+    deprecations: Vec<DeprecationConfig>,
+    min_attendees: Option<u32>,
+    round_minutes: Option<u32>,
+    /// Added to the built-in meeting templates
+    #[serde(default)]
+    meeting_templates: Vec<MeetingTemplate>,
+    /// Starts a clock that stands still until an admin advances it,
+    /// for testing deadlines and expiry.  Never for a real instance.
+    #[serde(default)]
+    mock_clock: bool,
+    /// Slows and breaks responses, for testing the UI under a bad network.
+    /// Never for a real instance.
+    faults: Option<FaultConfig>,
+    #[serde(default)]
+    imports: ImportConfig,
+    /// Periodic work, like removing stale attendees
+    #[serde(default)]
+    jobs: JobsConfig,
+    /// Lets consenting users' ballots be exported, anonymized, for research
+    research: Option<ResearchConfig>,
+    /// Pictures beside cohort members' names
+    #[serde(default)]
+    avatars: AvatarConfig,
+    /// Where the log goes and how much of it
+    #[serde(default)]
+    logging: LogConfig,
+    /// How long statement logging lasts once an admin turns it on
+    #[serde(default)]
+    sql_log: SqlLogConfig,
+    /// Whether signups need an invitation or an administrator's approval
+    #[serde(default)]
+    membership: MembershipConfig,
+}
+
+fn default_postgres_host() -> String {
+    "localhost".to_owned()
+}
+
+impl Config {
+    // Later providers win, so the environment overrides the file.
+    fn load(file: Option<&Path>) -> anyhow::Result<Self> {
+        let mut figment = Figment::new();
+        if let Some(file) = file {
+            let text = fs::read_to_string(file).context("reading config file")?;
+            figment = figment.merge(Toml::string(&text));
+        }
+        figment
+            .merge(Env::prefixed(ENV_PREFIX).split("__"))
+            .extract()
+            .context("parsing config")
+    }
+
+    /// The settings as JSON, with passwords and keys replaced
+    fn redacted(&self) -> Value {
+        let mut value = json!(self);
+        value["postgres_password"] = json!(HIDDEN);
+        if let Some(captcha) = value.get_mut("captcha").filter(|c| c.is_object()) {
+            captcha["secret_key"] = json!(HIDDEN);
+        }
+        if let Some(log_privacy) = value.get_mut("log_privacy").filter(|l| l.is_object()) {
+            log_privacy["key"] = json!(HIDDEN);
+        }
+        if let Some(smtp) = value.get_mut("smtp").filter(|s| s.is_object()) {
+            if !smtp["password"].is_null() {
+                smtp["password"] = json!(HIDDEN);
+            }
+        }
+        value
+    }
 }
 
+/// The trunk build hash of the UI bundle in the static path
+struct AssetVersion(Option<String>);
+
+/// Attendees needed before any meeting can start
+struct MinAttendees(u32);
+
+/// Length of a cohort's conversation, divided among its elected topics
+struct RoundMinutes(u32);
+
+/// Built-in meeting templates followed by any from the config file
+struct MeetingTemplates(Vec<MeetingTemplate>);
+
+/// When this API instance started, for the uptime its probes report
+struct StartedAt(Instant);
+
+/// Whether requests can ask for injected faults, so the UI offers its toggle
+struct FaultInjection(bool);
+
+const BUILTIN_MEETING_TEMPLATES: &str = include_str!("../meeting_templates.json");
+
 #[derive(Parser)]
 struct Cli {
+    /// TOML settings, which EHALLWAY_* environment variables override
     #[clap(long, value_name = "FILE")]
-    config_file: PathBuf,
+    config_file: Option<PathBuf>,
+    /// Apply any pending database migrations, then exit
+    #[clap(long)]
+    migrate_only: bool,
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(clap::Subcommand)]
+enum Command {
+    /// Back up the database as the config file's [backup] table says, then exit
+    Backup,
+    /// Print the configuration, with secrets hidden, then exit
+    Config,
+    /// Recount a meeting's ballots independently, print the audit, then exit,
+    /// failing if the election's tally disagrees
+    Audit {
+        /// The meeting's ID
+        meeting: u32,
+    },
+    /// Replace members' emails with aliases in meetings older than
+    /// the config file's [retention] table allows, then exit
+    Anonymize,
+}
+
+// Only follow local paths, so a crafted link cannot send users off-site.
+fn local_redirect(next: Option<String>) -> Redirect {
+    match next {
+        Some(next) if next.starts_with('/') && !next.starts_with("//") && !next.contains('\\') => {
+            Redirect::to(next)
+        }
+        _ => Redirect::to("/"),
+    }
 }
 
-#[get("/login")]
-fn get_login() -> Template {
-    Template::render("login", json!({}))
+#[get("/login?<disabled>&<pending>&<next>")]
+fn get_login(
+    templates: &State<Templates>,
+    disabled: bool,
+    pending: bool,
+    next: Option<String>,
+) -> Page {
+    templates.render(
+        "login",
+        json!({ "disabled": disabled, "pending": pending, "next": next }),
+    )
 }
 
-#[post("/login", data = "<form>")]
-async fn post_login(auth: Auth<'_>, form: Form<Login>) -> Result<Redirect, Error> {
+#[post("/login?<next>", data = "<form>")]
+async fn post_login(
+    auth: Auth<'_>,
+    client: Db,
+    log_emails: &State<LogEmails>,
+    form: Form<Login>,
+    next: Option<String>,
+) -> Result<Redirect, Error> {
+    // If the check fails, so does rocket_auth's own lookup of the account.
+    if account_disabled(&client, &form.email)
+        .await
+        .unwrap_or(false)
+    {
+        return Ok(Redirect::to(uri!(get_login(true, false, next))));
+    }
+    if membership::join_pending(&client, &form.email)
+        .await
+        .unwrap_or(false)
+    {
+        return Ok(Redirect::to(uri!(get_login(false, true, next))));
+    }
     let result = auth.login(&form).await;
-    println!("login attempt: {:?}", result);
+    let shown = log_emails.show(&form.email);
+    match &result {
+        Ok(()) => tracing::info!(user = %shown, "login"),
+        // rocket_auth's errors can quote the email.
+        Err(_) if log_emails.hashing() => tracing::info!(user = %shown, "failed login"),
+        Err(e) => tracing::info!(user = %shown, "failed login: {e:?}"),
+    }
     result?;
-    Ok(Redirect::to("/"))
+    Ok(local_redirect(next))
+}
+
+/// A rocket_auth signup plus the token a CAPTCHA widget adds to the form
+#[derive(FromForm)]
+struct CaptchaSignup {
+    email: String,
+    password: String,
+    #[field(name = "h-captcha-response")]
+    hcaptcha_token: Option<String>,
+    #[field(name = "cf-turnstile-response")]
+    turnstile_token: Option<String>,
+    /// The token of the invitation link the form was opened from
+    invitation: Option<String>,
+}
+
+impl CaptchaSignup {
+    // rocket_auth does not expose Signup's password field, but Signup deserializes.
+    fn signup(&self) -> Signup {
+        serde_json::from_value(json!({ "email": self.email, "password": self.password })).unwrap()
+    }
 }
 
-#[get("/signup")]
-async fn get_signup() -> Template {
-    Template::render("signup", json!({}))
+// An invitation link fills in the address it was sent to.
+#[get("/signup?<captcha_failed>&<invitation>&<next>")]
+async fn get_signup(
+    captcha: &State<Option<CaptchaConfig>>,
+    templates: &State<Templates>,
+    client: Db,
+    clock: &State<Clock>,
+    captcha_failed: bool,
+    invitation: Option<String>,
+    next: Option<String>,
+) -> Page {
+    let widget = captcha.inner().as_ref().map(|c| c.widget());
+    let invited_email = match &invitation {
+        Some(token) => membership::invited_email(&client, token, clock.unix_timestamp())
+            .await
+            .unwrap_or(None),
+        None => None,
+    };
+    templates.render(
+        "signup",
+        json!({
+            "captcha": widget,
+            "captcha_failed": captcha_failed,
+            "invitation": invitation,
+            "invited_email": invited_email,
+            "next": next,
+        }),
+    )
 }
 
-#[post("/signup", data = "<form>")]
-async fn post_signup(auth: Auth<'_>, form: Form<Signup>) -> Result<Redirect, Error> {
-    auth.signup(&form).await?;
-    auth.login(&form.into()).await?;
+// With [membership] approval on, an account made without a valid invitation
+// can't log in until an administrator approves it.
+#[post("/signup?<next>", data = "<form>")]
+async fn post_signup(
+    auth: Auth<'_>,
+    client: Db,
+    clock: &State<Clock>,
+    membership_config: &State<MembershipConfig>,
+    mailer: &State<Option<sync::Arc<Mailer>>>,
+    captcha: &State<Option<CaptchaConfig>>,
+    remote_ip: Option<IpAddr>,
+    form: Form<CaptchaSignup>,
+    next: Option<String>,
+) -> Result<Redirect, Error> {
+    if let Some(captcha) = captcha.inner() {
+        let token = form
+            .hcaptcha_token
+            .as_deref()
+            .or(form.turnstile_token.as_deref())
+            .unwrap_or_default();
+        let verified = match captcha.verify(token, remote_ip).await {
+            Ok(verified) => verified,
+            Err(e) => {
+                tracing::warn!("CAPTCHA verification failed: {e}");
+                false
+            }
+        };
+        if !verified {
+            return Ok(Redirect::to(uri!(get_signup(
+                true,
+                form.invitation.as_deref(),
+                next
+            ))));
+        }
+    }
+    let signup = form.signup();
+    auth.signup(&signup).await?;
+    let now = clock.unix_timestamp();
+    let held = match hold_for_approval(&client, membership_config, &form, now).await {
+        Ok(held) => held,
+        Err(e) => {
+            // Login only refuses accounts with a pending join request,
+            // so an account whose request went unrecorded is deleted,
+            // leaving the address free to sign up again.
+            let deleted = client
+                .execute("delete from users where email = $1", &[&form.email])
+                .await;
+            if let Err(delete_error) = deleted {
+                tracing::error!("deleting an account left unapproved: {delete_error}");
+            }
+            return Err(e.into());
+        }
+    };
+    if held {
+        if let Some(mailer) = mailer.inner() {
+            let notice = mail::join_request_notice(&form.email, mailer.base_url());
+            mailer.post(membership::admin_emails(&client).await?, notice);
+        }
+        return Ok(Redirect::to(uri!(get_login(false, true, next))));
+    }
+    auth.login(&signup.into()).await?;
+
+    Ok(local_redirect(next))
+}
 
-    Ok(Redirect::to("/"))
+// Uses up the signup's invitation or, when signups need approval,
+// records its join request, returning whether the new account waits for approval
+async fn hold_for_approval(
+    client: &Client,
+    membership_config: &MembershipConfig,
+    form: &CaptchaSignup,
+    now: i64,
+) -> Result<bool, tokio_postgres::Error> {
+    let invited = match &form.invitation {
+        Some(token) => membership::accept(client, token, &form.email, now).await?,
+        None => false,
+    };
+    if invited || !membership_config.approval {
+        return Ok(false);
+    }
+    membership::request_join(client, &form.email, now).await?;
+    Ok(true)
 }
 
 #[get("/")]
-async fn index(user: Option<User>) -> Template {
-    Template::render("index", json!({ "user": user }))
+async fn index(
+    user: Option<User>,
+    templates: &State<Templates>,
+    asset_version: &State<AssetVersion>,
+) -> Page {
+    templates.render(
+        "index",
+        json!({ "user": user, "asset_version": asset_version.0 }),
+    )
 }
 
 #[get("/logout")]
-fn logout(auth: Auth<'_>) -> Result<Template, Error> {
+fn logout(auth: Auth<'_>, templates: &State<Templates>) -> Result<Page, Error> {
     auth.logout()?;
-    Ok(Template::render("logout", json!({})))
+    Ok(templates.render("logout", json!({})))
 }
 
 #[get("/delete")]
-async fn delete(auth: Auth<'_>) -> Result<Template, Error> {
+async fn delete(auth: Auth<'_>, templates: &State<Templates>) -> Result<Page, Error> {
     auth.delete().await?;
-    Ok(Template::render("deleted", json!({})))
-}
-
-const CREATE_DB_ASSETS: [&str; 14] = [
-    "
-    CREATE or replace FUNCTION n_cohort_peers(uid varchar, mtg bigint) RETURNS table (n bigint) AS $$
-    << outerblock >>
-    DECLARE
-        cgrp bigint;
-    BEGIN
-        select count(id) as cohort_group into strict cgrp
-        from cohort_groups
-        where meeting = mtg;
-        if not found then
-            return query (select 0);
-        end if;
-    RETURN query (
-        select cgrp
-    );
-    END;
-    $$ LANGUAGE plpgsql;
-    ",
-    "
-    CREATE or replace FUNCTION epeers(uid varchar, mtg bigint) RETURNS table (email varchar) AS $$
-    << outerblock >>
-    DECLARE
-        cgrp bigint;
-        cht bigint;
-    BEGIN
-        select id as cohort_group into strict cgrp
-        from cohort_groups
-        where meeting = mtg;
-        select cohort into strict cht
-        from cohort_members
-        where cohort_group = cgrp and cohort_members.email = uid;
-    RETURN query (
-        select cohort_members.email
-            from cohort_members
-        where cohort_group = cgrp and cohort = cht
-    );
-    END;
-    $$ LANGUAGE plpgsql;
-    ",
-    "
-    -- id is not a primary key, so that it's not an error to *try*
-    -- to create a cohort_group for a meeting that already has one.
-    create table if not exists cohort_groups (
-        id bigserial,
-        meeting bigint not null
-    );
-    ",
-    "
-    create unique index if not exists cohort_groups_meeting_idx
-    on cohort_groups (meeting);
-    ",
-    "
-    create table if not exists cohort_members (
-        cohort_group bigint not null,
-        cohort bigint not null,
-        email varchar (254) not null
-    )
-    ",
-    "
-    create table if not exists meeting_topics (
-        email varchar (254) not null,
-        meeting bigint not null,
-        topic bigint not null,
-        score integer default 0
-    )
-    ",
-    "
-    create unique index if not exists meeting_topics_idx
-    on meeting_topics (meeting, email, topic);
-    ",
-    "
-    create table if not exists meetings (
-        name varchar (254) primary key,
-        id bigserial
-    );
-    ",
-    "
-    create table if not exists meeting_attendees (
-        meeting bigint not null,
-        email varchar (254) not null,
-        voted bool default false
-    );
-    ",
-    "
-    create table if not exists meeting_participants (
-        meeting bigint not null,
-        email varchar (254) not null
-    );
-    ",
-    "
-    create table if not exists meeting_scores (
-        meeting bigint not null,
-        email varchar (254) not null,
-        score integer default 0
-    );
-    ",
-    "
-    create unique index if not exists user_mtg_attendee_idx
-    on meeting_attendees (meeting, email);
-    ",
-    "
-    create table if not exists user_topics (
-        email varchar (254) not null,
-        topic varchar (254) not null,
-        id bigserial primary key,
-        score integer default 0
-    );
-    ",
-    "
-    create unique index if not exists user_mtg_score_idx
-    on meeting_scores (meeting, email);
-    ",
-];
+    Ok(templates.render("deleted", json!({})))
+}
+
+// Probes name the build and how long the instance has been up,
+// so a deployment can tell when its new version is the one answering.
+fn probe_body(started_at: &StartedAt, mut body: Value) -> Value {
+    body["version"] = env!("CARGO_PKG_VERSION").into();
+    body["commit"] = env!("EHALL_GIT_COMMIT").into();
+    body["uptime_secs"] = started_at.0.elapsed().as_secs().into();
+    body
+}
+
+/// 200 whenever the process can answer, for liveness probes
+#[get("/healthz")]
+fn healthz(started_at: &State<StartedAt>) -> Value {
+    probe_body(started_at, json!({ "alive": true }))
+}
+
+/// 503 until the server can reach Postgres, finds its schema up to date,
+/// listens for other instances' meeting events, and can render every page
+#[get("/readyz")]
+async fn readyz(
+    pool: &State<Pool>,
+    meeting_events: &State<MeetingEvents>,
+    templates: &State<Templates>,
+    started_at: &State<StartedAt>,
+) -> Custom<Value> {
+    let mut problems = vec![];
+    match pool.get().await {
+        Ok(client) => match migrations::n_pending(&client).await {
+            Ok(0) => {}
+            Ok(n) => problems.push(format!("database: {n} migrations pending")),
+            Err(e) => problems.push(format!("database: {e}")),
+        },
+        Err(e) => problems.push(format!("database: {e}")),
+    }
+    if !meeting_events.listening() {
+        problems.push("meeting events: not listening, reconnecting".to_owned());
+    }
+    if !templates.available() {
+        problems.push(format!(
+            "missing templates: {}",
+            templates.missing.join(", ")
+        ));
+    }
+    if problems.is_empty() {
+        Custom(Status::Ok, probe_body(started_at, json!({ "ready": true })))
+    } else {
+        Custom(
+            Status::ServiceUnavailable,
+            probe_body(started_at, json!({ "ready": false, "problems": problems })),
+        )
+    }
+}
+
+/// The older name for `/readyz`, kept for deployments that probe it
+#[get("/ready")]
+async fn ready(
+    pool: &State<Pool>,
+    meeting_events: &State<MeetingEvents>,
+    templates: &State<Templates>,
+    started_at: &State<StartedAt>,
+) -> Custom<Value> {
+    readyz(pool, meeting_events, templates, started_at).await
+}
 
 const NEW_TOPIC: &str = "
-    insert into user_topics (email, topic)
-    values ($1, $2)
+    insert into user_topics (email, topic, description)
+    values ($1, $2, $3)
     returning id;
 ";
 
 const NEW_MEETING: &str = "
-    insert into meetings (name)
-    values ($1)
+    insert into meetings (
+        name, cohort_size, veto_policy, voting_method, n_winners, match_language, owner,
+        topics_per_attendee, share_contacts
+    )
+    values ($1, $2, $3, $4, $5, $6, $7, $8, $9)
     returning id;
 ";
 
-async fn store_cohorts_for_group(client: &Client, cohort_group: i64, meeting_id: i64) {
+// Ballot rows carry the voter's cohort, so tallies need not look up peers.
+// A cohort ranks the seeded and submitted topics and those its members proposed
+// that made it onto any member's ballot;
+// the rest stay untagged, because no one in the cohort will discuss them.
+// Every member gets the whole list, ranked last where it is new to them,
+// so members who proposed nothing or arrived before a proposer
+// rank the same topics as everyone else.
+// This works from the few eligible rows rather than the whole meeting,
+// where every attendee's ballot has every attendee's topics.
+// It follows a `members` query giving meeting $1's (cohort, email) rows.
+const TAG_BALLOTS: &str = "
+    cohort_topics as (
+        select proposer.cohort, b.topic
+        from members proposer
+        join user_topics ut on ut.email = proposer.email
+        join members holder on holder.cohort = proposer.cohort
+        join meeting_topics b
+            on b.meeting = $1 and b.email = holder.email and b.topic = ut.id
+        union
+        select m.cohort, s.id
+        from members m
+        join seed_topics s on s.meeting = $1
+        union
+        select m.cohort, ut.id
+        from members m
+        join user_topics ut on ut.meeting = $1
+    ), eligible as (
+        select voter.email, t.topic, voter.cohort
+        from members voter
+        join cohort_topics t on t.cohort = voter.cohort
+        where not exists (
+            select 1 from meeting_attendees a
+            where a.meeting = $1 and a.email = voter.email and a.role = 'observer'
+        )
+    ), ballots as (
+        insert into meeting_topics (meeting, email, topic, score, cohort)
+        select $1, email, topic, 0, cohort from eligible
+        on conflict (meeting, email, topic) do update
+            set cohort = excluded.cohort
+    )
+";
+
+// Votes cast before ballots carried cohorts follow their ballots.
+// It follows TAG_BALLOTS.
+const TAG_VOTES: &str = "
+    votes as (
+        update meeting_votes b
+        set cohort = eligible.cohort
+        from eligible
+        where b.meeting = $1 and b.email = eligible.email and b.topic = eligible.topic
+    )
+";
+
+// The group, its members, and their ballots' cohorts are written by one statement,
+// so no API instance can see a cohort group before its members exist,
+// and a failure part way leaves the meeting unstarted rather than half started.
+// When a concurrent start already made the group, nothing is written.
+const NEW_COHORT_GROUP: &str = "
+    with grp as (
+        insert into cohort_groups (meeting)
+        values ($1)
+        on conflict (meeting) do nothing
+        returning id
+    ), members as (
+        insert into cohort_members (cohort_group, cohort, email)
+        select grp.id, m.cohort, m.email
+        from grp, unnest($2::bigint[], $3::varchar[]) as m (cohort, email)
+        returning cohort, email
+    ), {tag_ballots}, {tag_votes}
+    select count(*) from members
+";
+
+// Like NEW_COHORT_GROUP, but for a meeting already in round $4,
+// whose cohorts are archived and whose votes and room joins are cleared,
+// so every attendee votes again with new peers.
+// Ballots keep their scores, and topics that leave a cohort lose their tag.
+// When a concurrent call already moved the meeting on, nothing is written.
+const NEXT_ROUND_COHORT_GROUP: &str = "
+    with old as (
+        delete from cohort_groups
+        where meeting = $1 and round = $4
+        returning id, round, started_at
+    ), old_members as (
+        delete from cohort_members cm
+        using old
+        where cm.cohort_group = old.id
+        returning cm.cohort, cm.email
+    ), archived as (
+        insert into past_cohort_members (meeting, round, cohort, email)
+        select $1, old.round, m.cohort, m.email
+        from old, old_members m
+    ), grp as (
+        -- The first round's start still decides when retention is due.
+        insert into cohort_groups (meeting, round, started_at)
+        select $1, old.round + 1, old.started_at from old
+        returning id
+    ), members as (
+        insert into cohort_members (cohort_group, cohort, email)
+        select grp.id, m.cohort, m.email
+        from grp, unnest($2::bigint[], $3::varchar[]) as m (cohort, email)
+        returning cohort, email
+    ), unvoted as (
+        update meeting_attendees set voted = (role = 'observer')
+        where meeting = $1 and exists (select 1 from grp)
+    ), old_votes as (
+        delete from meeting_votes
+        where meeting = $1 and exists (select 1 from grp)
+    ), old_room_joins as (
+        delete from room_joins
+        where meeting = $1 and exists (select 1 from grp)
+    ), {tag_ballots}, untagged as (
+        update meeting_topics b
+        set cohort = null
+        where b.meeting = $1 and b.cohort is not null and exists (select 1 from grp)
+            and not exists (
+                select 1 from eligible e where e.email = b.email and e.topic = b.topic
+            )
+    )
+    select count(*) from members
+";
+
+const TAG_BALLOT_COHORTS: &str = "
+    with members as (
+        select cm.cohort, cm.email
+        from cohort_groups cg
+        join cohort_members cm on cm.cohort_group = cg.id
+        where cg.meeting = $1
+    ), {tag_ballots}, {tag_votes}
+    select count(*) from members
+";
+
+async fn assign_topic_cohorts(
+    client: &Client,
+    meeting_id: i64,
+) -> Result<(), tokio_postgres::Error> {
+    let sql = TAG_BALLOT_COHORTS
+        .replace("{tag_ballots}", TAG_BALLOTS)
+        .replace("{tag_votes}", TAG_VOTES);
+    client.execute(&sql, &[&meeting_id]).await?;
+    Ok(())
+}
+
+// Meetings started before ballots carried cohorts
+async fn backfill_topic_cohorts(client: &Client) -> anyhow::Result<()> {
+    let rows = client
+        .query(
+            "
+            select cg.meeting
+            from cohort_groups cg
+            where exists (
+                select 1 from meeting_topics where meeting = cg.meeting
+            ) and not exists (
+                select 1 from meeting_topics
+                where meeting = cg.meeting and cohort is not null
+            )
+            ",
+            &[],
+        )
+        .await?;
+    for row in rows {
+        assign_topic_cohorts(client, row.get::<_, i64>(0)).await?;
+    }
+    Ok(())
+}
+
+fn parse_veto_policy(name: &str) -> VetoPolicy {
+    VetoPolicy::ALL
+        .into_iter()
+        .find(|policy| policy.as_str() == name)
+        .unwrap_or_default()
+}
+
+async fn meeting_veto_policy(client: &Client, meeting_id: i64) -> ApiResult<VetoPolicy> {
+    let rows = client
+        .query(
+            "select veto_policy from meetings where id = $1",
+            &[&meeting_id],
+        )
+        .await?;
+    Ok(rows
+        .first()
+        .map_or(VetoPolicy::Off, |row| parse_veto_policy(row.get(0))))
+}
+
+async fn meeting_shares_contacts(client: &Client, meeting_id: i64) -> ApiResult<bool> {
+    let rows = client
+        .query(
+            "select share_contacts from meetings where id = $1",
+            &[&meeting_id],
+        )
+        .await?;
+    Ok(rows.first().is_some_and(|row| row.get(0)))
+}
+
+fn parse_meeting_sort(name: &str) -> Option<MeetingSort> {
+    MeetingSort::ALL
+        .into_iter()
+        .find(|sort| sort.as_str() == name)
+}
+fn parse_voting_method(name: &str) -> VotingMethod {
+    VotingMethod::ALL
+        .into_iter()
+        .find(|method| method.as_str() == name)
+        .unwrap_or_default()
+}
+
+async fn meeting_voting_method(client: &Client, meeting_id: i64) -> ApiResult<VotingMethod> {
+    let rows = client
+        .query(
+            "select voting_method from meetings where id = $1",
+            &[&meeting_id],
+        )
+        .await?;
+    Ok(rows
+        .first()
+        .map_or(VotingMethod::Borda, |row| parse_voting_method(row.get(0))))
+}
+
+// Each method returns the same kind of ranks, so results and timers work alike.
+fn tally(method: VotingMethod, rankings: &[election::Ranking]) -> anyhow::Result<Vec<usize>> {
+    match method {
+        VotingMethod::Borda => election::borda_count(rankings),
+        VotingMethod::Condorcet => election::condorcet_count(rankings),
+        VotingMethod::InstantRunoff => election::instant_runoff(rankings),
+    }
+}
+
+async fn meeting_cohort_size(client: &Client, meeting_id: i64) -> ApiResult<u32> {
+    let rows = client
+        .query(
+            "select cohort_size from meetings where id = $1",
+            &[&meeting_id],
+        )
+        .await?;
+    let row = rows.first().ok_or(ApiError::NotFound)?;
+    Ok(row.get::<_, i32>(0) as u32)
+}
+
+async fn meeting_n_winners(client: &Client, meeting_id: i64) -> ApiResult<u32> {
+    let rows = client
+        .query(
+            "select n_winners from meetings where id = $1",
+            &[&meeting_id],
+        )
+        .await?;
+    let row = rows.first().ok_or(ApiError::NotFound)?;
+    Ok(row.get::<_, i32>(0) as u32)
+}
+
+// The fewest topics any cohort would rank if these cohorts were formed,
+// counting the topics that TAG_BALLOTS would put on its members' ballots
+async fn smallest_ballot(
+    client: &Client,
+    meeting_id: i64,
+    emails: &[String],
+    cohorts: &[Vec<usize>],
+) -> ApiResult<u32> {
+    let cohort_of: HashMap<&str, usize> = cohorts
+        .iter()
+        .enumerate()
+        .flat_map(|(cohort, members)| members.iter().map(move |&i| (emails[i].as_str(), cohort)))
+        .collect();
+    let mut cohort_topics: Vec<HashSet<i64>> = vec![HashSet::new(); cohorts.len()];
+    let rows = client
+        .query(
+            "
+            select mt.email, mt.topic, case when ut.meeting is null then ut.email end
+            from meeting_topics mt
+            left join user_topics ut on ut.id = mt.topic
+            where mt.meeting = $1
+            ",
+            &[&meeting_id],
+        )
+        .await?;
+    for row in rows.iter() {
+        let voter = row.get::<_, &str>(0);
+        let cohort = match cohort_of.get(voter) {
+            Some(cohort) => cohort,
+            None => continue,
+        };
+        // Seeded and submitted topics have no proposer here and are on every ballot.
+        let eligible = match row.get::<_, Option<&str>>(2) {
+            Some(proposer) => cohort_of.get(proposer) == Some(cohort),
+            None => true,
+        };
+        if eligible {
+            cohort_topics[*cohort].insert(row.get::<_, i64>(1));
+        }
+    }
+    Ok(cohort_topics
+        .iter()
+        .map(|topics| topics.len() as u32)
+        .min()
+        .unwrap_or(0))
+}
+
+/// Whether the meeting's attendees have been assigned to cohorts
+async fn meeting_started(client: &Client, meeting_id: i64) -> ApiResult<bool> {
+    let rows = client
+        .query(
+            "select 1 from cohort_groups where meeting = $1",
+            &[&meeting_id],
+        )
+        .await?;
+    Ok(!rows.is_empty())
+}
+
+/// Randomly divides the meeting's participants into cohorts numbered from `first_cohort`,
+/// then deals its observers out among them,
+/// giving parallel lists of cohort numbers and emails.
+/// Draws that would leave a cohort with fewer topics than winners are refused.
+async fn draw_cohorts(
+    client: &Client,
+    meeting_id: i64,
+    first_cohort: i64,
+) -> ApiResult<(Vec<i64>, Vec<String>)> {
     let sql = "
-        select (email) from meeting_attendees
-        where meeting = $1
+        select a.email, p.language, m.match_language, a.role = 'observer'
+        from meeting_attendees a
+        join meetings m on m.id = a.meeting
+        left join user_preferences p on p.email = a.email
+        where a.meeting = $1
     ";
-    let stmt = client.prepare(sql).await.unwrap();
-    let emails: Vec<String> = client
-        .query(&stmt, &[&meeting_id])
-        .await
-        .unwrap()
+    let stmt = client.prepare(sql).await?;
+    let all_rows = client.query(&stmt, &[&meeting_id]).await?;
+    let (observer_rows, rows): (Vec<_>, Vec<_>) =
+        all_rows.iter().partition(|row| row.get::<_, bool>(3));
+    let emails: Vec<String> = rows.iter().map(|row| row.get(0)).collect();
+    let cohort_size = meeting_cohort_size(client, meeting_id).await? as usize;
+    let match_language = rows.first().is_some_and(|row| row.get::<_, bool>(2));
+    let cohorts = if match_language {
+        let languages: Vec<Option<String>> = rows.iter().map(|row| row.get(1)).collect();
+        election::grouped_cohorts(&languages, cohort_size)
+    } else {
+        election::cohorts(emails.len(), cohort_size)
+    };
+    // Someone may have left since `start` counted the attendees.
+    let cohorts = cohorts.map_err(|e| match e {
+        election::CohortError::NotEnoughParticipants { needed, have } => ApiError::Rejected(
+            Status::Conflict,
+            json!(StartMeetingError::NotEnoughParticipants {
+                needed: needed as u32,
+                have: have as u32,
+            }),
+        ),
+    })?;
+    // Checked before any cohort exists, so a refused start changes nothing.
+    let n_winners = meeting_n_winners(client, meeting_id).await?;
+    let n_topics = smallest_ballot(client, meeting_id, &emails, &cohorts).await?;
+    if n_topics < n_winners {
+        return Err(ApiError::Rejected(
+            Status::Conflict,
+            json!(StartMeetingError::TooFewTopics {
+                n_winners,
+                n_topics,
+            }),
+        ));
+    }
+    let n_cohorts = cohorts.len();
+    let observers = observer_rows
         .iter()
-        .map(|row| row.get::<_, String>(0))
-        .collect();
-    let cohorts = chance::cohorts(emails.len(), COHORT_QUORUM).unwrap();
-    let cohort_rows: Vec<_> = cohorts
+        .enumerate()
+        .map(|(i, row)| (first_cohort + (i % n_cohorts) as i64, row.get(0)));
+    Ok(cohorts
         .into_iter()
         .enumerate()
         .flat_map(|(cohort_id, members)| {
             members
                 .into_iter()
-                .zip(std::iter::repeat(cohort_id))
-                .map(|(email_idx, cohort_id)| {
-                    let cohort_id = cohort_id as i64;
-                    (cohort_id, &emails[email_idx])
-                })
+                .map(move |email_idx| (first_cohort + cohort_id as i64, email_idx))
         })
-        .collect();
-    let sql = "
-        insert into cohort_members
-            (cohort_group, cohort, email)
-        values
-            ($1, $2, $3)
-    ";
-    for (cohort, email) in cohort_rows {
-        client
-            .execute(sql, &[&cohort_group, &cohort, &email])
-            .await
-            .unwrap();
+        .map(|(cohort_id, email_idx)| (cohort_id, emails[email_idx].clone()))
+        .chain(observers)
+        .unzip())
+}
+
+/// Assigns the meeting's attendees to cohorts unless that was already done,
+/// returning whether this call did it
+async fn create_cohort_group(client: &Client, meeting_id: i64) -> ApiResult<bool> {
+    // A fresh draw for a meeting that already started could be refused needlessly.
+    if meeting_started(client, meeting_id).await? {
+        return Ok(false);
     }
+    let (cohort_ids, cohort_emails) = draw_cohorts(client, meeting_id, 0).await?;
+    let sql = NEW_COHORT_GROUP
+        .replace("{tag_ballots}", TAG_BALLOTS)
+        .replace("{tag_votes}", TAG_VOTES);
+    let row = client
+        .query_one(&sql, &[&meeting_id, &cohort_ids, &cohort_emails])
+        .await?;
+    Ok(row.get::<_, i64>(0) > 0)
 }
 
-async fn n_cohort_peers(client: &Client, meeting_id: i64, email: &str) -> i64 {
+async fn n_cohort_peers(client: &Client, meeting_id: i64, email: &str) -> ApiResult<i64> {
     let sql = "select n_cohort_peers($1, $2)";
-    let stmt = client.prepare(sql).await.unwrap();
-    let rows = client.query(&stmt, &[&email, &meeting_id]).await.unwrap();
-    rows[0].get::<_, i64>(0)
+    let stmt = client.prepare(sql).await?;
+    let row = client.query_one(&stmt, &[&email, &meeting_id]).await?;
+    Ok(row.get::<_, i64>(0))
 }
 
-async fn cohort_for_user(client: &Client, meeting_id: i64, email: &str) -> Option<Vec<String>> {
-    if n_cohort_peers(client, meeting_id, email).await == 0 {
-        println!("{} has no cohort peers", email);
-        None
+async fn cohort_for_user(
+    client: &Client,
+    log_emails: &LogEmails,
+    meeting_id: i64,
+    email: &str,
+) -> ApiResult<Option<Vec<String>>> {
+    if n_cohort_peers(client, meeting_id, email).await? == 0 {
+        tracing::debug!(user = %log_emails.show(email), "no cohort peers");
+        Ok(None)
     } else {
         let sql = "
             select epeers($1, $2)
         ";
-        let stmt = client.prepare(sql).await.unwrap();
-        for _ in 0..N_RETRIES {
-            let rows = client.query(&stmt, &[&email, &meeting_id]).await.unwrap();
-            if !rows.is_empty() {
-                return Some(rows.iter().map(|row| row.get::<_, String>(0)).collect());
-            }
-            // Use randomness to disperse timings (overkill, but fun)
-            let sleep_ms = RETRY_SLEEP_MS + rand::thread_rng().gen_range(0..20);
-            time::sleep(time::Duration::from_millis(sleep_ms)).await;
-        }
-        None
+        let rows = client.query(sql, &[&email, &meeting_id]).await?;
+        Ok(Some(
+            rows.iter().map(|row| row.get::<_, String>(0)).collect(),
+        ))
     }
 }
 
 async fn elected_topics(
-    client: &State<sync::Arc<Client>>,
+    client: &Client,
     email: &str,
     meeting_id: i64,
-) -> Vec<UserTopic> {
+) -> ApiResult<Vec<UserTopic>> {
     let sql = "
-    select m.email, topic, score, text from
-    (
-        (select email, topic, score from meeting_topics
-            where meeting = $1 and email in (select epeers($2, $1))) as m
-        join
-        (select topic as text, email, id from user_topics
-            where email in (select epeers('Aa345678@foo.com', 16))) u
-        on m.topic = u.id
+    select m.email, m.topic, m.score, u.text, u.seeded, u.proposer, m.vetoed
+    from meeting_votes m
+    join
+    (select topic as text, id, false as seeded, email as proposer from user_topics
+    union all
+    select topic as text, id, true as seeded, null as proposer from seed_topics
+        where meeting = $1) u
+    on m.topic = u.id
+    where m.meeting = $1 and m.cohort = (
+        select cm.cohort
+        from cohort_groups cg
+        join cohort_members cm on cm.cohort_group = cg.id
+        where cg.meeting = $1 and cm.email = $2
     )
-    order by email, topic
+    order by m.email, m.topic
     ";
-    let stmt = client.prepare(sql).await.unwrap();
-    let rows = client.query(&stmt, &[&meeting_id, &email]).await.unwrap();
+    let rows = client.query(sql, &[&meeting_id, &email]).await?;
     let mut scores: HashMap<_, Vec<_>> = HashMap::new();
+    let mut vetoed: HashSet<i64> = HashSet::new();
     for row in rows.into_iter() {
         let email: String = row.get::<_, String>(0);
         let topic: i64 = row.get::<_, i64>(1);
         let score: i32 = row.get::<_, i32>(2);
         let text: String = row.get::<_, String>(3);
+        let seeded: bool = row.get::<_, bool>(4);
+        let proposer: Option<String> = row.get::<_, Option<String>>(5);
+        if row.get::<_, bool>(6) {
+            vetoed.insert(topic);
+        }
         scores
             .entry(email)
             .or_insert_with(Vec::new)
-            .push((topic, score, text));
+            .push((topic, score, text, seeded, proposer));
     }
     let mut rankings: Vec<_> = vec![];
     let mut topics: Vec<_> = vec![];
     let mut topic_texts: Vec<String> = vec![];
+    let mut topic_seeded: Vec<bool> = vec![];
+    let mut topic_proposers: Vec<Option<String>> = vec![];
     for (_email, user_scores) in scores.iter_mut() {
-        let user_topics: Vec<_> = user_scores.iter().map(|(topic, _, _)| *topic).collect();
+        let user_topics: Vec<_> = user_scores.iter().map(|(topic, ..)| *topic).collect();
         if topics.is_empty() {
             topics.extend(user_topics);
             topic_texts.extend(
                 user_scores
                     .iter()
-                    .map(|(_, _, text)| text.clone())
+                    .map(|(_, _, text, ..)| text.clone())
                     .collect::<Vec<String>>(),
             );
-        } else {
-            // SQL did order by email, topic, so we expect these to be in the same
-            // order for every `_email`.
-            assert_eq!(user_topics, topics);
+            topic_seeded.extend(user_scores.iter().map(|(_, _, _, seeded, _)| *seeded));
+            topic_proposers.extend(user_scores.iter().map(|(.., proposer)| proposer.clone()));
+        } else if user_topics != topics {
+            // SQL did order by email, topic, and starting the meeting gave
+            // every member the same ballot, so this is a bug.
+            return Err(anyhow!("ballots differ in meeting {meeting_id}").into());
         }
-        rankings.push(cull::Ranking {
+        rankings.push(election::Ranking {
             scores: user_scores
                 .iter()
-                .map(|(_topic, score, _text)| *score as usize)
+                .map(|(_topic, score, ..)| *score as usize)
                 .collect(),
         });
     }
-    let result = cull::borda_count(&rankings).unwrap();
+    // Identical proposals from cohort members would otherwise split the vote
+    // and take more than one winner slot, and so would those an organizer merged.
+    let merged = aliases::topic_aliases(client, meeting_id).await?;
+    let same = aliases::alias_pairs(&topics, &merged);
+    let (groups, rankings) = election::merge_topics(&topic_texts, &same, &rankings)?;
+    let row = client
+        .query_opt(
+            "select voting_method, veto_policy, n_winners from meetings where id = $1",
+            &[&meeting_id],
+        )
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    let result = tally(parse_voting_method(row.get(0)), &rankings)?;
     let mut topics: Vec<_> = result
         .into_iter()
+        .zip(groups)
+        .map(|(bscore, group)| {
+            // A host-suggested copy stands for the group, so its links show.
+            let i = *group
+                .iter()
+                .find(|&&i| topic_seeded[i])
+                .unwrap_or(&group[0]);
+            UserTopic {
+                text: topic_texts[i].clone(),
+                id: topics[i] as u32,
+                score: bscore as u32,
+                seeded: topic_seeded[i],
+                position: 0,
+                links: vec![],
+                proposers: group
+                    .iter()
+                    .filter_map(|&i| topic_proposers[i].clone())
+                    .collect(),
+                // Vetoing one copy of a merged topic vetoes them all.
+                vetoed: group.iter().any(|&i| vetoed.contains(&topics[i])),
+                private: false,
+                description: "".to_owned(),
+            }
+        })
+        .collect();
+    parse_veto_policy(row.get(1)).rank(&mut topics);
+    // A cohort with fewer topics than winners elects them all.
+    let mut elected: Vec<_> = topics
+        .into_iter()
+        .take(row.get::<_, i32>(2) as usize)
         .enumerate()
-        .map(|(i, bscore)| UserTopic {
-            text: topic_texts[i].clone(),
-            id: topics[i] as u32,
-            score: bscore as u32,
+        .map(|(position, topic)| UserTopic {
+            position: position as u32,
+            ..topic
         })
         .collect();
-    topics.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
-    topics[..N_MEETING_TOPIC_WINNERS].to_vec()
+    attach_topic_links(client, &mut elected).await?;
+    Ok(elected)
 }
 
-#[get("/meeting/<id>/election_results")]
-async fn get_election_results(
-    client: &State<sync::Arc<Client>>,
-    user: User,
+// Each row also carries what a poll needs of the meeting and its cohorts:
+// whether they have formed, their round and voting deadline,
+// whether the meeting shares contacts, and the user's cohort.
+const GET_ELECTION_STATUS: &str = "
+    select s.meeting_name, s.email, s.voted,
+        g.id is not null, g.round, extract(epoch from g.voting_closes_at)::bigint,
+        m.share_contacts,
+        (
+            select me.cohort from cohort_members me
+            where me.cohort_group = g.id and me.email = $1
+            limit 1
+        )
+    from election_status($1, $2) s
+    join meetings m on m.id = $2
+    left join cohort_groups g on g.meeting = $2
+";
+
+async fn election_results(
+    client: &Client,
+    email: &str,
     id: u32,
-) -> Json<ElectionResults> {
-    let cohort = cohort_for_user(client, id as i64, user.email()).await;
-    let (topics, cohort, status) = if let Some(mut cohort) = cohort {
-        let sql = "
-            select email, voted from meeting_attendees
-            where meeting = $1 and email in (select epeers($2, $1))
-        ";
-        let id = id as i64;
-        let stmt = client.prepare(sql).await.unwrap();
-        let rows = client.query(&stmt, &[&id, &user.email()]).await.unwrap();
-        let mut emails: Vec<_> = rows.iter().map(|row| row.get::<_, String>(0)).collect();
-        let voted: Vec<_> = rows.iter().map(|row| row.get::<_, bool>(1)).collect();
-        if voted.len() != cohort.len() || !voted.iter().all(|v| *v) {
-            (None, None, "Cohort voting not finished".to_owned())
-        } else {
-            cohort.sort();
-            emails.sort();
-            if cohort != emails {
-                (None, None, "Unexpected cohort email mismatch".to_owned())
-            } else {
-                (
-                    Some(elected_topics(client, user.email(), id).await),
-                    Some(cohort),
-                    "Vote finished".to_owned(),
-                )
-            }
-        }
+    round_minutes: &RoundMinutes,
+    avatar_config: &AvatarConfig,
+) -> ApiResult<ElectionResults> {
+    let m_id = id as i64;
+    // The meeting name, cohort, and votes all arrive in one round trip,
+    // so a poll before the results are in is one statement.
+    // There is no retry loop here, because the UI polls this endpoint anyway.
+    let rows = client.query(GET_ELECTION_STATUS, &[&email, &m_id]).await?;
+    let first = rows.first().ok_or(ApiError::NotFound)?;
+    let name = first.get::<_, String>(0);
+    let started = first.get::<_, bool>(3);
+    let round = first
+        .get::<_, Option<i32>>(4)
+        .map_or(0, |round| round as u32);
+    let voting_closes_at = first.get::<_, Option<i64>>(5);
+    let contacts_on = first.get::<_, bool>(6);
+    let own_cohort = first.get::<_, Option<i64>>(7);
+    let cohort: Vec<_> = rows
+        .iter()
+        .filter_map(|row| row.get::<_, Option<String>>(1))
+        .collect();
+    // A cohort member without an attendee row has no vote to wait for.
+    let attending: Vec<Option<bool>> = rows.iter().map(|row| row.get(2)).collect();
+    let voted: Vec<_> = attending.iter().map(|v| v.unwrap_or(false)).collect();
+    let user_voted = cohort
+        .iter()
+        .zip(&voted)
+        .any(|(member, &voted)| member == email && voted);
+    let (topics, cohort, status) = if cohort.is_empty() && !started {
+        // Cohorts form when the meeting starts, so there is nothing wrong yet.
+        (None, None, ElectionStatus::CohortPending)
+    } else if cohort.is_empty() {
+        (None, None, ElectionStatus::NoCohort)
+    } else if attending.iter().any(Option::is_none) {
+        (None, None, ElectionStatus::CohortMismatch)
+    } else if !voted.iter().all(|v| *v) {
+        let n_voted = voted.iter().filter(|v| **v).count() as u32;
+        let status = ElectionStatus::WaitingForVotes {
+            n_voted,
+            n_members: voted.len() as u32,
+        };
+        (None, None, status)
     } else {
-        dbg!("empty cohort for user");
-        (None, None, "Empty cohort for user".to_owned())
+        (
+            Some(elected_topics(client, email, m_id).await?),
+            Some(cohort),
+            ElectionStatus::Finished,
+        )
     };
-    let name = meeting_name(client, id).await;
     let url = meeting_url(id, &name, &topics, &cohort);
-    ElectionResults {
+    let avatars = match &cohort {
+        Some(cohort) => avatars::urls(client, avatar_config, cohort).await?,
+        None => None,
+    };
+    // Borda points plus one, so the last elected topic still gets some time
+    let topic_minutes = topics.as_ref().map(|topics| {
+        let weights: Vec<_> = topics.iter().map(|t| t.score as usize + 1).collect();
+        election::split_minutes(&weights, round_minutes.0)
+    });
+    let shares_contacts = cohort.is_some() && contacts_on;
+    let outcome = match (&topics, own_cohort) {
+        (Some(_), Some(cohort)) => cohort_outcomes(client, m_id, Some(cohort), shares_contacts)
+            .await?
+            .pop(),
+        _ => None,
+    };
+    let contact_sharing = match &cohort {
+        Some(cohort) if shares_contacts => {
+            Some(contacts::contact_sharing(client, m_id, email, cohort).await?)
+        }
+        _ => None,
+    };
+    // Members of a meeting that shares contacts know each other by label,
+    // and learn emails only through `contact_sharing`.
+    let (topics, cohort) = if shares_contacts {
+        (
+            topics.map(without_proposers),
+            cohort.map(|cohort| contacts::labels(&cohort, &cohort)),
+        )
+    } else {
+        (topics, cohort)
+    };
+    Ok(ElectionResults {
         meeting_id: id,
         meeting_name: name,
         topics,
         users: cohort,
+        avatars,
         meeting_url: url,
         status,
+        topic_minutes,
+        outcome,
+        contact_sharing,
+        round,
+        voted: user_voted,
+        voting_closes_at,
+    })
+}
+
+// Lets clients react to changes instead of polling,
+// no matter which API instance made the change.
+#[get("/meeting/<id>/events")]
+fn meeting_events(
+    _user: User,
+    meeting_events: &State<MeetingEvents>,
+    mut shutdown: Shutdown,
+    id: u32,
+) -> EventStream![] {
+    let mut receiver = meeting_events.subscribe();
+    EventStream! {
+        loop {
+            let event = select! {
+                received = receiver.recv() => match received {
+                    Ok(event) => event,
+                    Err(RecvError::Closed) => break,
+                    Err(RecvError::Lagged(_)) => continue,
+                },
+                _ = &mut shutdown => break,
+            };
+            if event.meeting == id {
+                yield Event::json(&event);
+            }
+        }
     }
-    .into()
 }
 
-fn meeting_url(
+// With `wait`, like "30s", the request is held until the user's election status
+// changes or the time is up, for clients that can't keep an event stream open.
+// Its connection goes back to the pool while it waits.
+#[get("/meeting/<id>/election_results?<wait>")]
+async fn get_election_results(
+    client: Db,
+    pool: &State<Pool>,
+    meeting_events: &State<MeetingEvents>,
+    round_minutes: &State<RoundMinutes>,
+    avatar_config: &State<AvatarConfig>,
+    cohort_waits: &State<CohortWaits>,
+    user: User,
+    id: u32,
+    wait: Option<&str>,
+) -> ApiResult<Json<ElectionResults>> {
+    const CHANGES: [MeetingEventKind; 3] = [
+        MeetingEventKind::CohortsCreated,
+        MeetingEventKind::Voted,
+        MeetingEventKind::ResultsReady,
+    ];
+    let wait = wait
+        .map(events::parse_wait)
+        .transpose()
+        .map_err(ApiError::invalid)?;
+    // Subscribed before the first look, so no change slips in between.
+    let mut receiver = meeting_events.subscribe();
+    let mut results =
+        election_results(&client, user.email(), id, round_minutes, avatar_config).await?;
+    let Some(wait) = wait else {
+        return Ok(results.into());
+    };
+    // While the cohort is pending, the wait is held no longer than the budget,
+    // and waits the cohort outlasts are counted.
+    let pending = results.status == ElectionStatus::CohortPending;
+    let budget = cohort_waits.budget();
+    let deadline = Instant::now() + if pending { wait.min(budget) } else { wait };
+    drop(client);
+    while events::wait_for(&mut receiver, id, &CHANGES, deadline).await {
+        let client = Client::new(pool.get().await.map_err(anyhow::Error::from)?);
+        let latest =
+            election_results(&client, user.email(), id, round_minutes, avatar_config).await?;
+        // Others' votes move the cohort's count of ballots in,
+        // and a ballot taken back in another tab changes the user's own.
+        let changed = latest.status != results.status
+            || latest.round != results.round
+            || latest.voted != results.voted;
+        results = latest;
+        if changed {
+            break;
+        }
+    }
+    if pending {
+        let over_budget = results.status == ElectionStatus::CohortPending && wait >= budget;
+        if over_budget {
+            tracing::info!(meeting = id, "cohort still pending after the wait budget");
+        }
+        cohort_waits.record(over_budget);
+    }
+    Ok(results.into())
+}
+
+// The UI reports when someone opens their cohort's room link,
+// because the conferencing service itself tells us nothing.
+// Only the first report from each cohort member is kept.
+#[post("/meeting/<id>/joined_room")]
+async fn joined_room(user: User, client: Db, id: u32) -> ApiResult<Value> {
+    let identifier = id as i64;
+    let n_inserted = client
+        .execute(
+            "
+            insert into room_joins (meeting, email)
+            select cg.meeting, cm.email
+            from cohort_groups cg
+            join cohort_members cm on cm.cohort_group = cg.id
+            where cg.meeting = $1 and cm.email = $2
+            on conflict do nothing
+            ",
+            &[&identifier, &user.email()],
+        )
+        .await?;
+    if n_inserted > 0 {
+        events::notify(&client, identifier, MeetingEventKind::RoomJoined).await;
+    }
+    Ok(json!({ "joined_room": id }))
+}
+
+/// Who in each cohort has opened the room, so hosts can chase cohorts that never convened
+#[get("/meeting/<id>/room_joins")]
+async fn get_room_joins(user: User, client: Db, id: u32) -> ApiResult<Json<RoomJoinsMessage>> {
+    let identifier = id as i64;
+    if !meeting_exists(&client, identifier).await? {
+        return Err(ApiError::NotFound);
+    }
+    if !user_permissions(&client, identifier, user.email())
+        .await?
+        .can_view_room_joins
+    {
+        return Err(ApiError::Forbidden(
+            ErrorCode::NotOrganizer,
+            "only organizers can see room joins".to_owned(),
+        ));
+    }
+    let rows = client
+        .query(
+            "
+            select cm.cohort, cm.email, rj.email is not null as joined
+            from cohort_groups cg
+            join cohort_members cm on cm.cohort_group = cg.id
+            left join room_joins rj on rj.meeting = cg.meeting and rj.email = cm.email
+            where cg.meeting = $1
+            order by cm.cohort, cm.email
+            ",
+            &[&identifier],
+        )
+        .await?;
+    let mut cohorts: Vec<CohortRoomJoins> = vec![];
+    for row in rows.iter() {
+        let cohort = row.get::<_, i64>(0) as u32;
+        let email = row.get::<_, String>(1);
+        if cohorts.last().map(|c| c.cohort) != Some(cohort) {
+            cohorts.push(CohortRoomJoins {
+                cohort,
+                members: vec![],
+                joined: vec![],
+            });
+        }
+        let c = cohorts.last_mut().unwrap();
+        if row.get::<_, bool>(2) {
+            c.joined.push(email.clone());
+        }
+        c.members.push(email);
+    }
+    if meeting_shares_contacts(&client, identifier).await? {
+        for c in cohorts.iter_mut() {
+            c.joined = contacts::labels(&c.members, &c.joined);
+            c.members = contacts::labels(&c.members, &c.members);
+        }
+    }
+    Ok(RoomJoinsMessage { cohorts }.into())
+}
+
+async fn user_cohort(client: &Client, meeting_id: i64, email: &str) -> ApiResult<Option<i64>> {
+    let rows = client
+        .query(
+            "
+            select cm.cohort
+            from cohort_groups cg
+            join cohort_members cm on cm.cohort_group = cg.id
+            where cg.meeting = $1 and cm.email = $2
+            ",
+            &[&meeting_id, &email],
+        )
+        .await?;
+    Ok(rows.first().map(|row| row.get(0)))
+}
+
+// Each cohort with its members and whether all of them have voted, in cohort order
+async fn meeting_cohorts(
+    client: &Client,
+    meeting_id: i64,
+) -> ApiResult<Vec<(i64, Vec<String>, bool)>> {
+    let rows = client
+        .query(
+            "
+            select cm.cohort, cm.email, coalesce(a.voted, false)
+            from cohort_groups cg
+            join cohort_members cm on cm.cohort_group = cg.id
+            left join meeting_attendees a on a.meeting = cg.meeting and a.email = cm.email
+            where cg.meeting = $1
+            order by cm.cohort, cm.email
+            ",
+            &[&meeting_id],
+        )
+        .await?;
+    let mut cohorts: Vec<(i64, Vec<String>, bool)> = vec![];
+    for row in rows.iter() {
+        let cohort = row.get::<_, i64>(0);
+        if cohorts.last().map(|c| c.0) != Some(cohort) {
+            cohorts.push((cohort, vec![], true));
+        }
+        let (_, members, voted) = cohorts.last_mut().unwrap();
+        members.push(row.get(1));
+        *voted &= row.get::<_, bool>(2);
+    }
+    Ok(cohorts)
+}
+
+// The recorded outcomes of one cohort, or of every cohort with None,
+// naming members by label when `labeled`, as meetings that share contacts do
+async fn cohort_outcomes(
+    client: &Client,
+    meeting_id: i64,
+    cohort: Option<i64>,
+    labeled: bool,
+) -> ApiResult<Vec<CohortOutcome>> {
+    let mut members: HashMap<i64, Vec<String>> = meeting_cohorts(client, meeting_id)
+        .await?
+        .into_iter()
+        .map(|(cohort, members, _)| (cohort, members))
+        .collect();
+    let rows = client
+        .query(
+            "
+            select t.cohort, t.topic, u.text
+            from cohort_outcome_topics t
+            join (
+                select topic as text, id from user_topics
+                union all
+                select topic as text, id from seed_topics where meeting = $1
+            ) u on u.id = t.topic
+            where t.meeting = $1 and ($2::bigint is null or t.cohort = $2)
+            order by t.cohort, t.position
+            ",
+            &[&meeting_id, &cohort],
+        )
+        .await?;
+    let mut topics: HashMap<i64, Vec<OutcomeTopic>> = HashMap::new();
+    for row in rows.iter() {
+        topics.entry(row.get(0)).or_default().push(OutcomeTopic {
+            id: row.get::<_, i64>(1) as u32,
+            text: row.get(2),
+        });
+    }
+    let rows = client
+        .query(
+            "
+            select cohort, text, recorded_by, extract(epoch from recorded_at)::bigint
+            from cohort_outcomes
+            where meeting = $1 and ($2::bigint is null or cohort = $2)
+            order by cohort
+            ",
+            &[&meeting_id, &cohort],
+        )
+        .await?;
+    Ok(rows
+        .iter()
+        .map(|row| {
+            let cohort = row.get::<_, i64>(0);
+            let mut members = members.remove(&cohort).unwrap_or_default();
+            let mut recorded_by: String = row.get(2);
+            if labeled {
+                recorded_by = contacts::label(&members, &recorded_by);
+                members = contacts::labels(&members, &members);
+            }
+            CohortOutcome {
+                cohort: cohort as u32,
+                members,
+                text: row.get(1),
+                topics: topics.remove(&cohort).unwrap_or_default(),
+                recorded_by,
+                recorded_at: row.get(3),
+            }
+        })
+        .collect())
+}
+
+// The outcome and its topics, $5 in order, in one statement,
+// so a reader never sees the text with another recording's topics.
+// Positions past the new topics' are deleted and the rest overwritten,
+// since a delete and an insert of the same rows can't share a statement.
+const RECORD_OUTCOME: &str = "
+    with outcome as (
+        insert into cohort_outcomes (meeting, cohort, text, recorded_by)
+        values ($1, $2, $3, $4)
+        on conflict (meeting, cohort) do update
+            set text = excluded.text, recorded_by = excluded.recorded_by, recorded_at = now()
+    ), dropped as (
+        delete from cohort_outcome_topics
+        where meeting = $1 and cohort = $2 and position >= cardinality($5::bigint[])
+    )
+    insert into cohort_outcome_topics (meeting, cohort, position, topic)
+    select $1, $2, t.position - 1, t.topic
+    from unnest($5::bigint[]) with ordinality as t (topic, position)
+    on conflict (meeting, cohort, position) do update
+        set topic = excluded.topic
+";
+
+// Any cohort member may record the outcome, and a later one replaces it,
+// because the cohort decides together and the first try may need fixing.
+#[put("/meeting/<id>/outcome", data = "<msg>", format = "json")]
+async fn record_outcome(
+    user: User,
+    client: Db,
+    id: u32,
+    msg: Json<OutcomeMessage>,
+) -> ApiResult<Value> {
+    let m_id = id as i64;
+    if !meeting_exists(&client, m_id).await? {
+        return Err(ApiError::NotFound);
+    }
+    msg.validate().map_err(ApiError::invalid)?;
+    let cohort = user_cohort(&client, m_id, user.email())
+        .await?
+        .ok_or_else(|| {
+            ApiError::Forbidden(
+                ErrorCode::NotCohortMember,
+                "only cohort members can record an outcome".to_owned(),
+            )
+        })?;
+    let rows = client
+        .query(GET_ELECTION_STATUS, &[&user.email(), &m_id])
+        .await?;
+    if !rows
+        .iter()
+        .all(|row| row.get::<_, Option<bool>>(2).unwrap_or(false))
+    {
+        return Err(ApiError::Conflict(
+            ErrorCode::CohortVoting,
+            "your cohort is still voting".to_owned(),
+        ));
+    }
+    let elected = elected_topics(&client, user.email(), m_id).await?;
+    if let Some(topic) = msg
+        .topics
+        .iter()
+        .find(|&&topic| !elected.iter().any(|t| t.id == topic))
+    {
+        return Err(ApiError::BadRequest(
+            ErrorCode::NotOnBallot,
+            format!("topic {topic} is not one of your cohort's topics"),
+        ));
+    }
+    let topics: Vec<i64> = msg.topics.iter().map(|&topic| topic as i64).collect();
+    client
+        .execute(
+            RECORD_OUTCOME,
+            &[&m_id, &cohort, &msg.text, &user.email(), &topics],
+        )
+        .await?;
+    events::notify(&client, m_id, MeetingEventKind::OutcomeRecorded).await;
+    Ok(json!({ "recorded": cohort as u32 }))
+}
+
+// After voting, each cohort member chooses whether to share their email,
+// and changing their mind later hides it again.
+#[put("/meeting/<id>/contact_sharing", data = "<msg>", format = "json")]
+async fn share_contact(
+    user: User,
+    client: Db,
+    id: u32,
+    msg: Json<ContactSharingMessage>,
+) -> ApiResult<Value> {
+    let m_id = id as i64;
+    if !meeting_exists(&client, m_id).await? {
+        return Err(ApiError::NotFound);
+    }
+    if !meeting_shares_contacts(&client, m_id).await? {
+        return Err(ApiError::BadRequest(
+            ErrorCode::ContactSharingOff,
+            "this meeting does not share contacts".to_owned(),
+        ));
+    }
+    if user_cohort(&client, m_id, user.email()).await?.is_none() {
+        return Err(ApiError::Forbidden(
+            ErrorCode::NotCohortMember,
+            "only cohort members can share contacts".to_owned(),
+        ));
+    }
+    let rows = client
+        .query(GET_ELECTION_STATUS, &[&user.email(), &m_id])
+        .await?;
+    if !rows
+        .iter()
+        .all(|row| row.get::<_, Option<bool>>(2).unwrap_or(false))
+    {
+        return Err(ApiError::Conflict(
+            ErrorCode::CohortVoting,
+            "your cohort is still voting".to_owned(),
+        ));
+    }
+    contacts::consent(&client, m_id, user.email(), msg.share).await?;
+    Ok(json!({ "share": msg.share }))
+}
+
+// The name of a meeting whose outcomes the user may see
+async fn outcomes_meeting_name(client: &Client, meeting_id: i64, email: &str) -> ApiResult<String> {
+    let (name, _) = meeting_summary(client, meeting_id)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    if !user_permissions(client, meeting_id, email)
+        .await?
+        .can_view_outcomes
+    {
+        return Err(ApiError::Forbidden(
+            ErrorCode::NotOrganizer,
+            "only organizers can see every cohort's outcome".to_owned(),
+        ));
+    }
+    Ok(name)
+}
+
+/// Elections as recorded when each cohort finished voting,
+/// for organizers every cohort's, and for others their own cohort's
+#[get("/meeting/<id>/history")]
+async fn get_meeting_history(
+    user: User,
+    client: Db,
+    id: u32,
+) -> ApiResult<Json<MeetingHistoryMessage>> {
+    let m_id = id as i64;
+    if !meeting_exists(&client, m_id).await? {
+        return Err(ApiError::NotFound);
+    }
+    let every_cohort = user_permissions(&client, m_id, user.email())
+        .await?
+        .can_view_outcomes;
+    let member = (!every_cohort).then(|| user.email());
+    let mut elections = history::past_elections(&client, m_id, member).await?;
+    if meeting_shares_contacts(&client, m_id).await? {
+        for election in elections.iter_mut() {
+            election.members = contacts::labels(&election.members, &election.members);
+        }
+    }
+    Ok(MeetingHistoryMessage {
+        meeting_id: id,
+        elections,
+    }
+    .into())
+}
+
+/// Every cohort's recorded outcome, as a record of what the meeting decided
+#[get("/meeting/<id>/outcomes")]
+async fn get_meeting_outcomes(
+    user: User,
+    client: Db,
+    id: u32,
+) -> ApiResult<Json<MeetingOutcomesMessage>> {
+    let m_id = id as i64;
+    let meeting_name = outcomes_meeting_name(&client, m_id, user.email()).await?;
+    let labeled = meeting_shares_contacts(&client, m_id).await?;
+    Ok(MeetingOutcomesMessage {
+        meeting_id: id,
+        meeting_name,
+        outcomes: cohort_outcomes(&client, m_id, None, labeled).await?,
+    }
+    .into())
+}
+
+// Every cohort's members, elected topics, and outcome
+// An anonymized meeting's topics don't name their proposers,
+// and neither do those of a meeting that shares contacts, whose members have labels.
+async fn cohort_summaries(
+    client: &Client,
+    meeting_id: i64,
+    anonymized: bool,
+) -> ApiResult<Vec<CohortSummary>> {
+    let labeled = meeting_shares_contacts(client, meeting_id).await?;
+    let mut outcomes = cohort_outcomes(client, meeting_id, None, labeled).await?;
+    let mut cohorts = vec![];
+    for (cohort, members, voted) in meeting_cohorts(client, meeting_id).await? {
+        // Any member sees the same results, since they share ballots.
+        let mut topics = match members.first() {
+            Some(member) if voted => Some(elected_topics(client, member, meeting_id).await?),
+            _ => None,
+        };
+        if anonymized || labeled {
+            topics = topics.map(without_proposers);
+        }
+        let members = if labeled {
+            contacts::labels(&members, &members)
+        } else {
+            members
+        };
+        let outcome = outcomes
+            .iter()
+            .position(|o| o.cohort as i64 == cohort)
+            .map(|i| outcomes.remove(i));
+        cohorts.push(CohortSummary {
+            cohort: cohort as u32,
+            members,
+            topics,
+            outcome,
+        });
+    }
+    Ok(cohorts)
+}
+
+/// Each cohort's members, elected topics, and outcome, as Markdown for sharing
+#[get("/meeting/<id>/summary.md")]
+async fn export_meeting_summary(
+    user: User,
+    client: Db,
+    id: u32,
+) -> ApiResult<(ContentType, String)> {
+    let m_id = id as i64;
+    let meeting_name = outcomes_meeting_name(&client, m_id, user.email()).await?;
+    let anonymized = retention::anonymized_at(&client, m_id).await?.is_some();
+    let cohorts = cohort_summaries(&client, m_id, anonymized).await?;
+    Ok((
+        ContentType::Markdown,
+        summary::markdown(&meeting_name, anonymized, &cohorts),
+    ))
+}
+
+/// Each cohort's members and elected topics with their scores, for archiving,
+/// as CSV or as JSON that also has the cohorts' outcomes
+#[get("/meeting/<id>/election_results/export?<format>")]
+async fn export_election_results(
+    user: User,
+    client: Db,
+    id: u32,
+    format: &str,
+) -> ApiResult<(ContentType, String)> {
+    let m_id = id as i64;
+    if !["csv", "json"].contains(&format) {
+        return Err(ApiError::BadRequest(
+            ErrorCode::InvalidRequest,
+            "the format is either csv or json".to_owned(),
+        ));
+    }
+    let meeting_name = outcomes_meeting_name(&client, m_id, user.email()).await?;
+    let anonymized_at = retention::anonymized_at(&client, m_id).await?;
+    let cohorts = cohort_summaries(&client, m_id, anonymized_at.is_some()).await?;
+    if format == "csv" {
+        return Ok((ContentType::CSV, summary::results_csv(&cohorts)));
+    }
+    let export = ResultsExport {
+        meeting_id: id,
+        meeting_name: &meeting_name,
+        voting_method: meeting_voting_method(&client, m_id).await?,
+        anonymized_at,
+        cohorts: &cohorts,
+    };
+    let json = serde_json::to_string_pretty(&export).map_err(anyhow::Error::from)?;
+    Ok((ContentType::JSON, json))
+}
+
+fn without_proposers(topics: Vec<UserTopic>) -> Vec<UserTopic> {
+    topics
+        .into_iter()
+        .map(|topic| UserTopic {
+            proposers: vec![],
+            ..topic
+        })
+        .collect()
+}
+
+fn meeting_url(
     meeting_id: u32,
     meeting_name: &str,
     topics: &Option<Vec<UserTopic>>,
@@ -417,58 +1810,266 @@ fn meeting_url(
     format!("https://meet.jit.si/ehallway/{:x}", hasher.finalize())
 }
 
-async fn meeting_name(client: &State<sync::Arc<Client>>, meeting_id: u32) -> String {
-    let id = meeting_id as i64;
+// How the user attends the meeting, or None when they don't
+async fn attendee_role(
+    client: &Client,
+    meeting_id: i64,
+    email: &str,
+) -> ApiResult<Option<AttendeeRole>> {
+    let rows = client
+        .query(
+            "select role from meeting_attendees where meeting = $1 and email = $2",
+            &[&meeting_id, &email],
+        )
+        .await?;
+    Ok(rows.first().map(|row| {
+        let role: &str = row.get(0);
+        AttendeeRole::ALL
+            .into_iter()
+            .find(|r| r.as_str() == role)
+            .unwrap_or_default()
+    }))
+}
+
+async fn is_attending(client: &Client, meeting_id: i64, email: &str) -> ApiResult<bool> {
     let sql = "
-        select name from meetings where id = $1
+        select 1 from meeting_attendees
+        where meeting = $1 and email = $2
     ";
-    let stmt = client.prepare(sql).await.unwrap();
-    let rows = client.query(&stmt, &[&id]).await.unwrap();
-    rows.get(0).unwrap().get::<_, String>(0)
+    let rows = client.query(sql, &[&meeting_id, &email]).await?;
+    Ok(!rows.is_empty())
+}
+
+const COUNT_REGISTERED_JOINED: &str = "
+    select count(*), count(a.email)
+    from meeting_participants p
+    left join meeting_attendees a on a.meeting = p.meeting and a.email = p.email
+    where p.meeting = $1
+";
+
+// Hosts may rather wait when most of those registered are still missing.
+fn start_needs_confirming(n_registered: u32, n_joined: u32) -> bool {
+    n_joined * 2 < n_registered
 }
 
-#[put("/meeting/<id>/start")]
+#[put("/meeting/<id>/start?<confirm>")]
 async fn start_meeting(
-    client: &State<sync::Arc<Client>>,
+    client: Db,
+    clock: &State<Clock>,
+    min_attendees: &State<MinAttendees>,
+    log_emails: &State<LogEmails>,
+    avatar_config: &State<AvatarConfig>,
     user: User,
+    key: IdempotencyKey,
     id: u32,
-) -> Json<CohortMessage> {
-    let id = id as i64;
-    let sql = "
-        insert into cohort_groups
-        (meeting)
-        values
-        ($1)
-        on conflict (meeting) do nothing
-        returning id
-    ";
-    let stmt = client.prepare(sql).await.unwrap();
-    let rows = client.query(&stmt, &[&id]).await.unwrap();
-    if rows.len() == 1 {
-        let cohort_group = rows[0].get::<_, i64>(0);
-        store_cohorts_for_group(client, cohort_group, id).await;
-        eprintln!("created");
-    } else {
-        eprintln!("not created");
+    confirm: Option<bool>,
+) -> ApiResult<Custom<Value>> {
+    let route = format!("/meeting/{id}/start");
+    idempotency::once(&client, clock, user.email(), &key, &route, async {
+        let started = start(
+            &client,
+            clock,
+            min_attendees,
+            log_emails,
+            avatar_config,
+            user.email(),
+            id as i64,
+            confirm.unwrap_or(false),
+        );
+        match started.await {
+            Ok(msg) => Ok(Custom(Status::Ok, json!(msg))),
+            // Replaying a refusal is fine, since nothing changed.
+            Err(ApiError::Rejected(status, e)) => Ok(Custom(status, e)),
+            Err(e) => Err(e),
+        }
+    })
+    .await
+}
+
+async fn start(
+    client: &Client,
+    clock: &Clock,
+    min_attendees: &MinAttendees,
+    log_emails: &LogEmails,
+    avatar_config: &AvatarConfig,
+    email: &str,
+    id: i64,
+    confirm: bool,
+) -> ApiResult<CohortMessage> {
+    let refuse = |status: Status, e: StartMeetingError| -> ApiResult<CohortMessage> {
+        Err(ApiError::Rejected(status, json!(e)))
+    };
+    if !is_attending(client, id, email).await? {
+        return refuse(Status::Forbidden, StartMeetingError::NotAttending);
     }
-    CohortMessage {
-        cohort: cohort_for_user(client, id, user.email()).await,
+    if !user_permissions(client, id, email).await?.can_start {
+        return refuse(Status::Forbidden, StartMeetingError::NotOrganizer);
     }
-    .into()
+    let row = client
+        .query_one(
+            "select count(email) from meeting_attendees where meeting = $1 and role = 'participant'",
+            &[&id],
+        )
+        .await?;
+    // Observers only join the cohorts participants fill.
+    let n_attendees = row.get::<_, i64>(0) as u32;
+    // A meeting cannot start without enough attendees for one full cohort.
+    let min_attendees = min_attendees.0.max(meeting_cohort_size(client, id).await?);
+    if n_attendees < min_attendees {
+        return refuse(
+            Status::Conflict,
+            StartMeetingError::TooFewAttendees {
+                min_attendees,
+                n_attendees,
+            },
+        );
+    }
+    if !confirm && !meeting_started(client, id).await? {
+        let row = client.query_one(COUNT_REGISTERED_JOINED, &[&id]).await?;
+        let n_registered = row.get::<_, i64>(0) as u32;
+        let n_joined = row.get::<_, i64>(1) as u32;
+        if start_needs_confirming(n_registered, n_joined) {
+            return refuse(
+                Status::Conflict,
+                StartMeetingError::ConfirmRequired {
+                    n_registered,
+                    n_joined,
+                },
+            );
+        }
+    }
+    if create_cohort_group(client, id).await? {
+        voting::open(client, clock, id).await?;
+        events::notify(client, id, MeetingEventKind::CohortsCreated).await;
+        tracing::info!(meeting = id, "cohorts created");
+    }
+    let cohort = cohort_for_user(client, log_emails, id, email).await?;
+    let avatars = match &cohort {
+        Some(cohort) => avatars::urls(client, avatar_config, cohort).await?,
+        None => None,
+    };
+    Ok(CohortMessage { cohort, avatars })
+}
+
+// Once every cohort has its topics, organizers can shuffle the attendees
+// into new cohorts for another round of hallway conversations.
+#[post("/meeting/<id>/next_round")]
+async fn next_round(
+    client: Db,
+    clock: &State<Clock>,
+    user: User,
+    key: IdempotencyKey,
+    id: u32,
+) -> ApiResult<Custom<Value>> {
+    let route = format!("/meeting/{id}/next_round");
+    idempotency::once(&client, clock, user.email(), &key, &route, async {
+        let round = start_next_round(&client, clock, user.email(), id as i64).await?;
+        Ok(Custom(Status::Ok, json!({ "round": round })))
+    })
+    .await
+}
+
+async fn start_next_round(client: &Client, clock: &Clock, email: &str, id: i64) -> ApiResult<u32> {
+    meeting_summary(client, id)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    if !user_permissions(client, id, email).await?.can_start {
+        return Err(ApiError::Forbidden(
+            ErrorCode::NotOrganizer,
+            "only organizers attending the meeting can start a new round".to_owned(),
+        ));
+    }
+    let rows = client
+        .query(
+            "
+            select cg.round, coalesce(max(cm.cohort) + 1, 0)
+            from cohort_groups cg
+            left join cohort_members cm on cm.cohort_group = cg.id
+            where cg.meeting = $1
+            group by cg.round
+            ",
+            &[&id],
+        )
+        .await?;
+    let (round, next_cohort) = match rows.first() {
+        Some(row) => (row.get::<_, i32>(0), row.get::<_, i64>(1)),
+        None => {
+            return Err(ApiError::Conflict(
+                ErrorCode::MeetingNotStarted,
+                "the meeting has not started".to_owned(),
+            ))
+        }
+    };
+    if !meeting_cohorts(client, id)
+        .await?
+        .iter()
+        .all(|(_, _, voted)| *voted)
+    {
+        return Err(ApiError::Conflict(
+            ErrorCode::CohortVoting,
+            "some cohorts are still voting".to_owned(),
+        ));
+    }
+    // New cohorts get new numbers, so earlier rounds' results and outcomes stay apart.
+    let (cohort_ids, cohort_emails) = draw_cohorts(client, id, next_cohort).await?;
+    let sql = NEXT_ROUND_COHORT_GROUP.replace("{tag_ballots}", TAG_BALLOTS);
+    let row = client
+        .query_one(&sql, &[&id, &cohort_ids, &cohort_emails, &round])
+        .await?;
+    // Otherwise another organizer started the same round first.
+    if row.get::<_, i64>(0) > 0 {
+        voting::open(client, clock, id).await?;
+        events::notify(client, id, MeetingEventKind::CohortsCreated).await;
+    }
+    Ok(round as u32 + 1)
 }
 
 #[post("/meeting/<id>/participants", data = "<msg>", format = "json")]
 async fn meeting_register(
-    client: &State<sync::Arc<Client>>,
+    client: Db,
+    clock: &State<Clock>,
+    log_emails: &State<LogEmails>,
     user: User,
     id: u32,
     msg: Json<ParticipateMeetingMessage>,
-) -> Result<Value, Error> {
-    eprintln!(
-        "meeting {id} user {} participate? {}",
-        user.email(),
-        msg.participate
+) -> ApiResult<Value> {
+    tracing::debug!(
+        meeting = id,
+        user = %log_emails.show(user.email()),
+        participate = msg.participate,
+        "participation"
     );
+    let identifier = id as i64;
+    if msg.participate {
+        if account_disabled(&client, user.email()).await? {
+            return Err(ApiError::Rejected(
+                Status::Forbidden,
+                json!(RegistrationError::AccountDisabled),
+            ));
+        }
+        // Withdrawing is always allowed, but registering is not after the deadline.
+        if let Some(deadline) = closed_registration(&client, clock, identifier).await? {
+            return Err(ApiError::Rejected(
+                Status::Forbidden,
+                json!(RegistrationError::RegistrationClosed { deadline }),
+            ));
+        }
+        let fields = registration_fields(&client, identifier).await?;
+        if let Err(reason) = validate_registration_answers(&fields, &msg.answers) {
+            return Err(ApiError::Rejected(
+                Status::BadRequest,
+                json!(RegistrationError::InvalidAnswers { reason }),
+            ));
+        }
+        store_registration_answers(&client, user.email(), &msg.answers).await?;
+    } else {
+        let sql = "
+            delete from registration_answers
+            where email = $2
+                and field in (select id from registration_fields where meeting = $1)
+        ";
+        client.execute(sql, &[&identifier, &user.email()]).await?;
+    }
     let sql = if msg.participate {
         "
         insert into meeting_participants
@@ -481,411 +2082,3649 @@ async fn meeting_register(
         where email = $2 and meeting = $1
         "
     };
-    let id = id as i64;
-    client.execute(sql, &[&id, &user.email()]).await.unwrap();
+    client.execute(sql, &[&identifier, &user.email()]).await?;
     Ok(json!({ "updated_meeting": id }))
 }
 
-#[post("/meetings", data = "<meeting>", format = "json")]
-async fn add_new_meeting(
-    client: &State<sync::Arc<Client>>,
-    user: User,
-    meeting: Json<NewMeeting<'_>>,
-) -> Result<Value, Error> {
-    let stmt = client.prepare(NEW_MEETING).await?;
-    let rows = client.query(&stmt, &[&meeting.name]).await?;
-    let id = rows[0].get::<_, i64>(0);
-    println!("new meeting {} with id {id}", &meeting.name);
-    let sql = "
-        insert into meeting_scores (meeting, email, score)
-        values ($1, $2::varchar,
-            (select 1 +
-                (select coalesce(max(score), -1) as score
-                    from meeting_scores where email = $2
-                )
-            )
-        );
-    ";
-    client.execute(sql, &[&id, &user.email()]).await.unwrap();
-    Ok(json!({ "inserted": id as u32 }))
+// The deadline, if registration for the meeting has closed
+async fn closed_registration(
+    client: &Client,
+    clock: &Clock,
+    meeting_id: i64,
+) -> ApiResult<Option<i64>> {
+    let rows = client
+        .query(
+            "
+            select extract(epoch from registration_deadline)::bigint
+            from meetings
+            where id = $1 and registration_deadline is not null
+            ",
+            &[&meeting_id],
+        )
+        .await?;
+    Ok(rows
+        .first()
+        .map(|row| row.get(0))
+        .filter(|&deadline| clock.reached(deadline)))
 }
 
-#[post("/topics", data = "<topic>", format = "json")]
-async fn add_new_topic(
-    client: &State<sync::Arc<Client>>,
+#[put("/meeting/<id>/registration_deadline", data = "<msg>", format = "json")]
+async fn set_registration_deadline(
     user: User,
-    topic: Json<NewTopicMessage>,
-) -> Result<Value, Error> {
-    let stmt = client.prepare(NEW_TOPIC).await?;
-    let rows = client
-        .query(&stmt, &[&user.email(), &topic.new_topic])
-        .await?;
-    let id = rows[0].get::<_, i64>(0);
-    println!("new topic {} with id {id}", &topic.new_topic);
-    let sql = "
-        update user_topics
-            set score = (
-                select 1 + coalesce(max(score), -1)
-                from user_topics where email = $2
+    client: Db,
+    id: u32,
+    msg: Json<RegistrationDeadlineMessage>,
+) -> ApiResult<Value> {
+    let identifier = id as i64;
+    if !meeting_exists(&client, identifier).await? {
+        return Err(ApiError::NotFound);
+    }
+    if !user_permissions(&client, identifier, user.email())
+        .await?
+        .can_manage_registration
+    {
+        return Err(ApiError::Forbidden(
+            ErrorCode::NotOrganizer,
+            "only organizers can change the registration deadline".to_owned(),
+        ));
+    }
+    client
+        .execute(
+            "update meetings set registration_deadline = to_timestamp($2::bigint) where id = $1",
+            &[&identifier, &msg.deadline],
+        )
+        .await?;
+    Ok(json!({ "registration_deadline": msg.deadline }))
+}
+
+async fn registration_fields(
+    client: &Client,
+    meeting_id: i64,
+) -> ApiResult<Vec<RegistrationField>> {
+    let rows = client
+        .query(
+            "select id, label from registration_fields where meeting = $1 order by id",
+            &[&meeting_id],
+        )
+        .await?;
+    Ok(rows
+        .iter()
+        .map(|row| RegistrationField {
+            id: row.get::<_, i64>(0) as u32,
+            label: row.get(1),
+        })
+        .collect())
+}
+
+// Every field needs exactly one answer.
+fn validate_registration_answers(
+    fields: &[RegistrationField],
+    answers: &[RegistrationAnswer],
+) -> Result<(), String> {
+    for field in fields {
+        let n_answers = answers.iter().filter(|a| a.field == field.id).count();
+        if n_answers != 1 {
+            return Err(format!("expected one answer for \"{}\"", field.label));
+        }
+    }
+    for answer in answers {
+        if !fields.iter().any(|f| f.id == answer.field) {
+            return Err(format!("no registration field {}", answer.field));
+        }
+        ehall::validate_registration_text(&answer.answer, "answer")?;
+    }
+    Ok(())
+}
+
+async fn store_registration_answers(
+    client: &Client,
+    email: &str,
+    answers: &[RegistrationAnswer],
+) -> ApiResult<()> {
+    let stmt = client
+        .prepare(
+            "
+            insert into registration_answers (field, email, answer)
+            values ($1, $2, $3)
+            on conflict (field, email) do update
+                set answer = excluded.answer
+            ",
+        )
+        .await?;
+    for answer in answers {
+        let field = answer.field as i64;
+        client
+            .execute(&stmt, &[&field, &email, &answer.answer])
+            .await?;
+    }
+    Ok(())
+}
+
+async fn insert_registration_fields(
+    client: &Client,
+    meeting_id: i64,
+    labels: &[String],
+) -> ApiResult<Vec<u32>> {
+    let stmt = client
+        .prepare(
+            "
+            insert into registration_fields (meeting, label)
+            values ($1, $2)
+            returning id
+            ",
+        )
+        .await?;
+    let mut ids = vec![];
+    for label in labels.iter() {
+        let row = client.query_one(&stmt, &[&meeting_id, label]).await?;
+        ids.push(row.get::<_, i64>(0) as u32);
+    }
+    Ok(ids)
+}
+
+#[post("/meeting/<id>/registration_fields", data = "<msg>", format = "json")]
+async fn add_registration_fields(
+    user: User,
+    client: Db,
+    id: u32,
+    msg: Json<RegistrationFieldsMessage>,
+) -> ApiResult<Value> {
+    let identifier = id as i64;
+    if !meeting_exists(&client, identifier).await? {
+        return Err(ApiError::NotFound);
+    }
+    if !user_permissions(&client, identifier, user.email())
+        .await?
+        .can_manage_registration
+    {
+        return Err(ApiError::Forbidden(
+            ErrorCode::NotOrganizer,
+            "only organizers can add registration fields".to_owned(),
+        ));
+    }
+    for label in msg.labels.iter() {
+        ehall::validate_registration_text(label, "registration field")
+            .map_err(ApiError::invalid)?;
+    }
+    let ids = insert_registration_fields(&client, identifier, &msg.labels).await?;
+    Ok(json!({ "added": ids }))
+}
+
+/// Registrants and their answers, one row per registrant
+#[get("/meeting/<id>/registrations.csv")]
+async fn export_registrations(user: User, client: Db, id: u32) -> ApiResult<(ContentType, String)> {
+    let identifier = id as i64;
+    if !meeting_exists(&client, identifier).await? {
+        return Err(ApiError::NotFound);
+    }
+    if !user_permissions(&client, identifier, user.email())
+        .await?
+        .can_manage_registration
+    {
+        return Err(ApiError::Forbidden(
+            ErrorCode::NotOrganizer,
+            "only organizers can export registrations".to_owned(),
+        ));
+    }
+    let fields = registration_fields(&client, identifier).await?;
+    let rows = client
+        .query(
+            "
+            select a.email, a.field, a.answer
+            from registration_answers a
+            join registration_fields f on f.id = a.field
+            where f.meeting = $1
+            ",
+            &[&identifier],
+        )
+        .await?;
+    let mut answers: HashMap<(String, i64), String> = HashMap::new();
+    for row in rows.iter() {
+        answers.insert((row.get(0), row.get(1)), row.get(2));
+    }
+    let rows = client
+        .query(
+            "select email from meeting_participants where meeting = $1 order by email",
+            &[&identifier],
+        )
+        .await?;
+    let mut csv = csv_row(std::iter::once("email").chain(fields.iter().map(|f| f.label.as_str())));
+    for row in rows.iter() {
+        let email = row.get::<_, String>(0);
+        let row_answers = fields.iter().map(|f| {
+            answers
+                .get(&(email.clone(), f.id as i64))
+                .map_or("", |a| a.as_str())
+        });
+        csv += &csv_row(std::iter::once(email.as_str()).chain(row_answers));
+    }
+    Ok((ContentType::CSV, csv))
+}
+
+/// How a new meeting runs, from `POST /meetings` or an imported config
+struct MeetingSettings {
+    cohort_size: u32,
+    veto_policy: VetoPolicy,
+    voting_method: VotingMethod,
+    n_winners: u32,
+    match_language: bool,
+    topics_per_attendee: u32,
+    share_contacts: bool,
+}
+
+async fn create_meeting(
+    client: &Client,
+    name: &str,
+    settings: &MeetingSettings,
+    email: &str,
+) -> Result<i64, tokio_postgres::Error> {
+    let stmt = client.prepare(NEW_MEETING).await?;
+    let rows = client
+        .query(
+            &stmt,
+            &[
+                &name,
+                &(settings.cohort_size as i32),
+                &settings.veto_policy.as_str(),
+                &settings.voting_method.as_str(),
+                &(settings.n_winners as i32),
+                &settings.match_language,
+                &email,
+                &(settings.topics_per_attendee as i32),
+                &settings.share_contacts,
+            ],
+        )
+        .await?;
+    let id = rows[0].get::<_, i64>(0);
+    tracing::info!(meeting = id, name = %name, "new meeting");
+    let sql = "
+        insert into meeting_scores (meeting, email, score)
+        values ($1, $2::varchar,
+            (select 1 +
+                (select coalesce(max(score), -1) as score
+                    from meeting_scores where email = $2
+                )
             )
-            where id = $1;
+        );
     ";
-    client.execute(sql, &[&id, &user.email()]).await?;
+    client.execute(sql, &[&id, &email]).await?;
+    Ok(id)
+}
+
+#[post("/meetings", data = "<meeting>", format = "json")]
+async fn add_new_meeting(
+    client: Db,
+    user: User,
+    meeting: Json<NewMeeting<'_>>,
+) -> ApiResult<Value> {
+    validate_cohort_size(meeting.cohort_size).map_err(ApiError::invalid)?;
+    validate_n_winners(meeting.n_winners).map_err(ApiError::invalid)?;
+    validate_topics_per_attendee(meeting.topics_per_attendee).map_err(ApiError::invalid)?;
+    // A duplicate name violates the meetings primary key.
+    let settings = MeetingSettings {
+        cohort_size: meeting.cohort_size,
+        veto_policy: meeting.veto_policy,
+        voting_method: meeting.voting_method,
+        n_winners: meeting.n_winners,
+        match_language: meeting.match_language,
+        topics_per_attendee: meeting.topics_per_attendee,
+        share_contacts: meeting.share_contacts,
+    };
+    let id = create_meeting(&client, &meeting.name, &settings, user.email())
+        .await
+        .map_err(|e| {
+            ApiError::conflict_if_duplicate(
+                e,
+                ErrorCode::NameTaken,
+                format!("another meeting is named \"{}\"", meeting.name),
+            )
+        })?;
     Ok(json!({ "inserted": id as u32 }))
 }
 
-#[delete("/meeting/<id>/attendees")]
-async fn leave_meeting(user: User, client: &State<sync::Arc<Client>>, id: u32) -> Value {
+#[get("/meeting_templates")]
+fn get_meeting_templates(
+    _user: User,
+    templates: &State<MeetingTemplates>,
+) -> Json<MeetingTemplatesMessage> {
+    MeetingTemplatesMessage {
+        templates: templates.0.clone(),
+    }
+    .into()
+}
+
+#[get("/meetings/<id>/config_export")]
+async fn export_meeting_config(_user: User, client: Db, id: u32) -> ApiResult<Json<MeetingConfig>> {
     let identifier = id as i64;
+    let rows = client
+        .query(
+            "
+            select name, cohort_size, veto_policy, voting_method, n_winners, match_language,
+                topics_per_attendee, share_contacts
+            from meetings where id = $1
+            ",
+            &[&identifier],
+        )
+        .await?;
+    let row = rows.first().ok_or(ApiError::NotFound)?;
+    let name = row.get::<_, String>(0);
+    let cohort_size = row.get::<_, i32>(1) as u32;
+    let veto_policy = parse_veto_policy(row.get(2));
+    let voting_method = parse_voting_method(row.get(3));
+    let n_winners = row.get::<_, i32>(4) as u32;
+    let match_language = row.get::<_, bool>(5);
+    let topics_per_attendee = row.get::<_, i32>(6) as u32;
+    let share_contacts = row.get::<_, bool>(7);
+    let rows = client
+        .query(
+            "select topic, id from seed_topics where meeting = $1 order by id",
+            &[&identifier],
+        )
+        .await?;
+    let seed_topics: Vec<String> = rows.iter().map(|row| row.get(0)).collect();
+    let ids: Vec<i64> = rows.iter().map(|row| row.get(1)).collect();
+    let mut links = topic_links(&client, &ids).await?;
+    let seed_topic_links = seed_topics
+        .iter()
+        .zip(ids)
+        .filter_map(|(topic, id)| Some((topic.clone(), links.remove(&id)?)))
+        .collect();
+    let registration_fields = registration_fields(&client, identifier)
+        .await?
+        .into_iter()
+        .map(|f| f.label)
+        .collect();
+    Ok(MeetingConfig {
+        version: MEETING_CONFIG_VERSION,
+        name,
+        seed_topics,
+        registration_fields,
+        seed_topic_links,
+        cohort_size,
+        veto_policy,
+        voting_method,
+        n_winners,
+        match_language,
+        topics_per_attendee,
+        share_contacts,
+    }
+    .into())
+}
+
+#[post("/meetings/import", data = "<config>", format = "json")]
+async fn import_meeting_config(
+    client: Db,
+    user: User,
+    config: Json<MeetingConfig>,
+) -> ApiResult<Value> {
+    config.validate().map_err(ApiError::invalid)?;
+    // A duplicate name violates the meetings primary key.
+    let settings = MeetingSettings {
+        cohort_size: config.cohort_size,
+        veto_policy: config.veto_policy,
+        voting_method: config.voting_method,
+        n_winners: config.n_winners,
+        match_language: config.match_language,
+        topics_per_attendee: config.topics_per_attendee,
+        share_contacts: config.share_contacts,
+    };
+    let id = create_meeting(&client, &config.name, &settings, user.email())
+        .await
+        .map_err(|e| {
+            ApiError::conflict_if_duplicate(
+                e,
+                ErrorCode::NameTaken,
+                format!("another meeting is named \"{}\"", config.name),
+            )
+        })?;
+    let seeded = insert_seed_topics(&client, id, &config.seed_topics).await?;
+    for (topic, seed_id) in config.seed_topics.iter().zip(seeded) {
+        if let Some(links) = config.seed_topic_links.get(topic) {
+            replace_topic_links(&client, seed_id as i64, links).await?;
+        }
+    }
+    insert_registration_fields(&client, id, &config.registration_fields).await?;
+    Ok(json!({ "inserted": id as u32 }))
+}
+
+#[post("/topics", data = "<topic>", format = "json")]
+async fn add_new_topic(client: Db, user: User, topic: Json<NewTopicMessage>) -> ApiResult<Value> {
+    topic.validate().map_err(ApiError::invalid)?;
+    let stmt = client.prepare(NEW_TOPIC).await?;
+    let row = client
+        .query_one(
+            &stmt,
+            &[&user.email(), &topic.new_topic, &topic.description],
+        )
+        .await?;
+    let id = row.get::<_, i64>(0);
+    tracing::debug!(topic = id, "new topic");
     let sql = "
-        delete from meeting_attendees
-        where meeting = $1 and email = $2
+        update user_topics
+            set score = (
+                select 1 + coalesce(max(score), -1)
+                from user_topics where email = $2 and meeting is null
+            )
+            where id = $1;
     ";
-    client
-        .execute(sql, &[&identifier, &user.email()])
-        .await
-        .unwrap();
+    client.execute(sql, &[&id, &user.email()]).await?;
+    canonicalize_user_topic_scores(&client, user.email()).await?;
+    // A proposer waiting in a lobby gets the topic on their own ballot,
+    // and starting the meeting shares it with their cohort.
+    // Meetings that took submissions stick to the submitted topics,
+    // and proposers who chose what to bring stick to their choice.
     let sql = "
-        delete from meeting_topics
-        where meeting = $1 and email = $2
+        insert into meeting_topics (email, meeting, topic, score)
+        select a.email, a.meeting, $2, 0
+        from meeting_attendees a
+        join meetings m on m.id = a.meeting
+        where a.email = $1 and a.role = 'participant' and m.submission_deadline is null
+            and not exists (select 1 from cohort_groups g where g.meeting = a.meeting)
+            and not exists (
+                select 1 from attendee_topics c where c.meeting = a.meeting and c.email = a.email
+            )
+        on conflict (email, meeting, topic) do nothing
     ";
-    client
-        .execute(sql, &[&identifier, &user.email()])
-        .await
-        .unwrap();
-    json!({ "left": id })
+    client.execute(sql, &[&user.email(), &id]).await?;
+    Ok(json!({ "inserted": id as u32 }))
 }
 
-#[post("/meeting/<id>/attendees")]
-async fn attend_meeting(user: User, client: &State<sync::Arc<Client>>, id: u32) -> Value {
+#[delete("/meeting/<id>/attendees")]
+async fn leave_meeting(user: User, client: Db, id: u32) -> ApiResult<Value> {
     let identifier = id as i64;
+    for sql in [
+        "delete from meeting_attendees where meeting = $1 and email = $2",
+        "delete from meeting_topics where meeting = $1 and email = $2",
+        "delete from meeting_votes where meeting = $1 and email = $2",
+    ] {
+        client.execute(sql, &[&identifier, &user.email()]).await?;
+    }
+    events::notify(&client, identifier, MeetingEventKind::AttendanceChanged).await;
+    Ok(json!({ "left": id }))
+}
+
+// Without a body, the user attends as a participant.
+// Attending again keeps the first role; leaving and attending again changes it.
+#[post("/meeting/<id>/attendees", data = "<msg>")]
+async fn attend_meeting(
+    user: User,
+    client: Db,
+    id: u32,
+    msg: Option<Json<AttendMeetingMessage>>,
+) -> ApiResult<Value> {
+    // Deactivated accounts are left out of cohorts by never attending.
+    if account_disabled(&client, user.email()).await? {
+        return Err(ApiError::Forbidden(
+            ErrorCode::AccountDisabled,
+            "this account is deactivated".to_owned(),
+        ));
+    }
+    let role = msg.map(|msg| msg.participate_as).unwrap_or_default();
+    add_attendee(&client, id as i64, user.email(), role).await?;
+    Ok(json!({ "attending": id }))
+}
+
+async fn add_attendee(
+    client: &Client,
+    identifier: i64,
+    email: &str,
+    role: AttendeeRole,
+) -> ApiResult<()> {
     let stmt = client
         .prepare(
             "
             insert into meeting_attendees
-            (meeting, email)
+            (meeting, email, role, voted)
             values
-            ($1, $2)
+            ($1, $2, $3, $3 = 'observer')
             on conflict (meeting, email) do nothing
             returning meeting
         ",
         )
-        .await
-        .unwrap();
+        .await?;
+    let rows = client
+        .query(&stmt, &[&identifier, &email, &role.as_str()])
+        .await?;
+    if rows.len() == 1 && role == AttendeeRole::Observer {
+        tracing::debug!(meeting = identifier, "inserted meeting observer");
+        events::notify(client, identifier, MeetingEventKind::AttendanceChanged).await;
+    } else if rows.len() == 1 {
+        tracing::debug!(meeting = identifier, "inserted meeting attendees");
+        // Meetings with a submission phase offer the submitted topics
+        // in place of the topics each attendee brings,
+        // starting the ballot in the attendee's submission ranking.
+        let sql = format!(
+            "
+            with {BROUGHT_TOPICS}
+            insert into meeting_topics
+            (email, meeting, topic, score)
+            (
+                select $2 as email, $1 as meeting, id as topic,
+                    (row_number() over (order by s.score nulls first, random()) - 1) as score
+                from
+                    (select id from brought
+                    union all
+                    select id from user_topics where meeting = $1
+                    union all
+                    select id from seed_topics where meeting = $1
+                    ) candidates
+                left join topic_submission_scores s
+                    on s.meeting = $1 and s.email = $2 and s.topic = candidates.id
+            ) on conflict (email, meeting, topic) do nothing
+            "
+        );
+        client.execute(&sql, &[&identifier, &email]).await?;
+        events::notify(client, identifier, MeetingEventKind::AttendanceChanged).await;
+    } else {
+        tracing::debug!(
+            meeting = identifier,
+            rows = rows.len(),
+            "inserted no meeting attendees"
+        );
+    }
+    Ok(())
+}
+
+// The own topics that meeting $1's participants and user $2 bring to its ballots:
+// those they chose, or without a choice, their top-ranked up to the meeting's limit.
+// Meetings with a submission phase take none.
+const BROUGHT_TOPICS: &str = "
+    brought as (
+        select x.id, x.email from (
+            select t.id, t.email, c.topic is not null as chosen,
+                row_number() over (partition by t.email order by t.score desc) as r
+            from user_topics t
+            left join attendee_topics c
+                on c.meeting = $1 and c.email = t.email and c.topic = t.id
+            where not t.private and t.meeting is null and (t.email = $2 or t.email in
+                (select email from meeting_attendees where meeting = $1 and role = 'participant'))
+        ) x
+        join meetings m on m.id = $1 and m.submission_deadline is null
+        where x.chosen or (x.r <= m.topics_per_attendee and not exists
+            (select 1 from attendee_topics c where c.meeting = $1 and c.email = x.email))
+    )
+";
+
+#[get("/meeting/<id>/my_topics")]
+async fn get_my_topics(user: User, client: Db, id: u32) -> ApiResult<Json<MyTopicsMessage>> {
+    let rows = client
+        .query(
+            "select topic from attendee_topics where meeting = $1 and email = $2 order by topic",
+            &[&(id as i64), &user.email()],
+        )
+        .await?;
+    Ok(MyTopicsMessage {
+        topics: rows.iter().map(|row| row.get::<_, i64>(0) as u32).collect(),
+    }
+    .into())
+}
+
+// Users not yet attending may choose ahead.
+// An attendee's own ballot gains the chosen topics,
+// and every ballot loses the user's other topics,
+// as if they had brought these from the start.
+const REPLACE_MY_TOPICS: &str = "
+    with cleared as (
+        delete from attendee_topics
+        where meeting = $1 and email = $2 and not (topic = any($3))
+    )
+    insert into attendee_topics (meeting, email, topic)
+    select $1, $2, unnest($3::bigint[])
+    on conflict (meeting, email, topic) do nothing
+";
+
+const SYNC_MY_BALLOT: &str = "
+    dropped as (
+        delete from meeting_topics b
+        using user_topics t
+        where b.meeting = $1 and b.topic = t.id and t.email = $2 and t.meeting is null
+            and t.id not in (select id from brought where email = $2)
+    )
+    insert into meeting_topics (email, meeting, topic, score)
+    select a.email, $1, b.id, 0
+    from meeting_attendees a
+    join brought b on b.email = a.email
+    where a.meeting = $1 and a.email = $2 and a.role = 'participant'
+    on conflict (email, meeting, topic) do nothing
+";
+
+#[post("/meeting/<id>/my_topics", data = "<msg>", format = "json")]
+async fn choose_my_topics(
+    user: User,
+    client: Db,
+    id: u32,
+    msg: Json<MyTopicsMessage>,
+) -> ApiResult<Json<MyTopicsMessage>> {
+    let identifier = id as i64;
+    let row = client
+        .query_opt(
+            "select topics_per_attendee, submission_deadline is not null from meetings where id = $1",
+            &[&identifier],
+        )
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    let limit = row.get::<_, i32>(0) as usize;
+    if row.get::<_, bool>(1) {
+        return Err(ApiError::Conflict(
+            ErrorCode::SubmittedTopicsOnly,
+            "the meeting's ballots hold its submitted topics".to_owned(),
+        ));
+    }
+    if meeting_started(&client, identifier).await? {
+        return Err(ApiError::Conflict(
+            ErrorCode::MeetingStarted,
+            "the meeting has started, so its ballots are settled".to_owned(),
+        ));
+    }
+    let mut topics: Vec<i64> = msg.topics.iter().map(|&t| t as i64).collect();
+    topics.sort_unstable();
+    topics.dedup();
+    if topics.len() > limit {
+        return Err(ApiError::invalid(format!(
+            "the meeting takes at most {limit} topics per attendee"
+        )));
+    }
+    let n_own = client
+        .query_one(
+            "
+            select count(*) from user_topics
+            where id = any($1) and email = $2 and meeting is null and not private
+            ",
+            &[&topics, &user.email()],
+        )
+        .await?
+        .get::<_, i64>(0);
+    if n_own as usize != topics.len() {
+        return Err(ApiError::invalid(
+            "only your own topics that aren't private can be brought".to_owned(),
+        ));
+    }
+    client
+        .execute(REPLACE_MY_TOPICS, &[&identifier, &user.email(), &topics])
+        .await?;
+    let sql = format!("with {BROUGHT_TOPICS}, {SYNC_MY_BALLOT}");
+    client.execute(&sql, &[&identifier, &user.email()]).await?;
+    Ok(MyTopicsMessage {
+        topics: topics.iter().map(|&t| t as u32).collect(),
+    }
+    .into())
+}
+
+async fn meeting_exists(client: &Client, id: i64) -> ApiResult<bool> {
+    let rows = client
+        .query("select 1 from meetings where id = $1", &[&id])
+        .await?;
+    Ok(!rows.is_empty())
+}
+
+/// The name and number of registered users, for pages describing a meeting
+async fn meeting_summary(client: &Client, id: i64) -> ApiResult<Option<(String, i64)>> {
+    let rows = client
+        .query(
+            "
+            select m.name, count(p.email)
+            from meetings m
+            left join meeting_participants p on p.meeting = m.id
+            where m.id = $1
+            group by m.name
+            ",
+            &[&id],
+        )
+        .await?;
+    Ok(rows.first().map(|row| (row.get(0), row.get(1))))
+}
+
+// Why the user can't join the meeting by invite link or join code, if they can't.
+// Once registration has closed, newcomers are refused.
+// Meetings with registration questions are only registered for with answers,
+// so newcomers to them are refused until they have registered.
+async fn join_refusal(
+    client: &Client,
+    clock: &Clock,
+    identifier: i64,
+    email: &str,
+) -> ApiResult<Option<RegistrationError>> {
+    let registered = client
+        .query_opt(
+            "select 1 from meeting_participants where meeting = $1 and email = $2",
+            &[&identifier, &email],
+        )
+        .await?
+        .is_some();
+    if registered {
+        return Ok(None);
+    }
+    if let Some(deadline) = closed_registration(client, clock, identifier).await? {
+        return Ok(Some(RegistrationError::RegistrationClosed { deadline }));
+    }
+    if !registration_fields(client, identifier).await?.is_empty() {
+        return Ok(Some(RegistrationError::InvalidAnswers {
+            reason: "answer its registration questions in the Meetings tab first".to_owned(),
+        }));
+    }
+    Ok(None)
+}
+
+// Registers the user and joins them to the meeting, for both invite links and join codes,
+// unless `join_refusal` says why not
+async fn register_and_attend(
+    client: &Client,
+    clock: &Clock,
+    identifier: i64,
+    email: &str,
+) -> ApiResult<Result<(), RegistrationError>> {
+    if account_disabled(client, email).await? {
+        return Err(ApiError::Forbidden(
+            ErrorCode::AccountDisabled,
+            "this account is deactivated".to_owned(),
+        ));
+    }
+    if let Some(e) = join_refusal(client, clock, identifier, email).await? {
+        return Ok(Err(e));
+    }
+    client
+        .execute(
+            "
+            insert into meeting_participants
+            (meeting, email) values
+            ($1, $2) on conflict do nothing
+            ",
+            &[&identifier, &email],
+        )
+        .await?;
+    add_attendee(client, identifier, email, AttendeeRole::Participant).await?;
+    Ok(Ok(()))
+}
+
+// A refused registration, as `POST /meeting/<id>/participants` gives it
+fn registration_refused(e: RegistrationError) -> ApiError {
+    let status = match e {
+        RegistrationError::InvalidAnswers { .. } => Status::BadRequest,
+        RegistrationError::RegistrationClosed { .. } | RegistrationError::AccountDisabled => {
+            Status::Forbidden
+        }
+    };
+    ApiError::Rejected(status, json!(e))
+}
+
+// The invite link's page, whose Open Graph tags let chat tools unfurl the shared link.
+// Visitors who are not logged in are asked to, users who can join get a button to,
+// and users who can't are told why.
+fn join_page(
+    templates: &Templates,
+    host: &Host<'_>,
+    id: u32,
+    (name, n_registered): (String, i64),
+    logged_in: bool,
+    refusal: Option<RegistrationError>,
+) -> Page {
+    let next = format!("/meeting/{id}/join");
+    let description = format!(
+        "Join {name} on EHallway to propose and vote on discussion topics. \
+        {n_registered} registered so far."
+    );
+    let notice = refusal.map(|e| match e {
+        RegistrationError::InvalidAnswers { .. } => {
+            "This meeting asks a few questions of those registering. \
+            Answer them in the Meetings tab to register and join."
+        }
+        RegistrationError::RegistrationClosed { .. } | RegistrationError::AccountDisabled => {
+            "Registration for this meeting has closed, \
+            so only those who registered in time can join."
+        }
+    });
+    templates.render(
+        "meeting",
+        json!({
+            "name": name,
+            "description": description,
+            "url": format!("https://{host}{next}"),
+            "next": next,
+            "logged_in": logged_in,
+            "notice": notice,
+        }),
+    )
+}
+
+// The link behind a meeting's invite QR code,
+// so people in the room can register and join from their phones.
+// Opening it changes nothing, so a link can't join anyone unawares:
+// users confirm with the page's button, which posts to the same URL.
+#[get("/meeting/<id>/join")]
+async fn join_meeting(
+    user: Option<User>,
+    client: Db,
+    clock: &State<Clock>,
+    templates: &State<Templates>,
+    host: &Host<'_>,
+    id: u32,
+) -> ApiResult<Page> {
+    let identifier = id as i64;
+    let summary = meeting_summary(&client, identifier)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    let refusal = match &user {
+        Some(user) => join_refusal(&client, clock, identifier, user.email()).await?,
+        None => None,
+    };
+    Ok(join_page(
+        templates,
+        host,
+        id,
+        summary,
+        user.is_some(),
+        refusal,
+    ))
+}
+
+#[derive(Responder)]
+enum JoinResponse {
+    Refused(Page),
+    Joined(Redirect),
+}
+
+/// Registers the user and joins them to the meeting, from the invite link's page
+#[post("/meeting/<id>/join")]
+async fn join_meeting_confirmed(
+    user: User,
+    client: Db,
+    clock: &State<Clock>,
+    templates: &State<Templates>,
+    host: &Host<'_>,
+    id: u32,
+) -> ApiResult<JoinResponse> {
+    let identifier = id as i64;
+    let summary = meeting_summary(&client, identifier)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    match register_and_attend(&client, clock, identifier, user.email()).await? {
+        Ok(()) => Ok(JoinResponse::Joined(Redirect::to("/"))),
+        Err(e) => Ok(JoinResponse::Refused(join_page(
+            templates,
+            host,
+            id,
+            summary,
+            true,
+            Some(e),
+        ))),
+    }
+}
+
+// The spoken alternative to an invite link, typed into the Meetings tab
+#[post("/join_code", data = "<msg>", format = "json")]
+async fn join_by_code(
+    user: User,
+    client: Db,
+    clock: &State<Clock>,
+    msg: Json<JoinCodeMessage>,
+) -> ApiResult<Json<JoinedMeetingMessage>> {
+    let code = normalize_join_code(&msg.code)
+        .ok_or_else(|| ApiError::invalid(format!("not a join code: {}", msg.code)))?;
+    let rows = client
+        .query(
+            "select id, name from meetings where join_code = $1",
+            &[&code],
+        )
+        .await?;
+    let row = rows.first().ok_or(ApiError::NotFound)?;
+    let identifier: i64 = row.get(0);
+    register_and_attend(&client, clock, identifier, user.email())
+        .await?
+        .map_err(registration_refused)?;
+    Ok(JoinedMeetingMessage {
+        meeting: identifier as u32,
+        name: row.get(1),
+    }
+    .into())
+}
+
+#[get("/meeting/<id>/qr.svg")]
+async fn meeting_qr_code(
+    user: User,
+    client: Db,
+    host: &Host<'_>,
+    id: u32,
+) -> ApiResult<(ContentType, String)> {
+    let identifier = id as i64;
+    if !meeting_exists(&client, identifier).await? {
+        return Err(ApiError::NotFound);
+    }
+    if !user_permissions(&client, identifier, user.email())
+        .await?
+        .can_share_invite
+    {
+        return Err(ApiError::Forbidden(
+            ErrorCode::NotOrganizer,
+            "only organizers can share an invite".to_owned(),
+        ));
+    }
+    let join_url = format!("https://{host}/meeting/{id}/join");
+    let svg = QrCode::new(join_url.as_bytes())
+        .map_err(|e| anyhow!("no QR code for {join_url}: {e}"))?
+        .render::<svg::Color>()
+        .min_dimensions(256, 256)
+        .build();
+    Ok((ContentType::SVG, svg))
+}
+
+async fn topic_links(client: &Client, topics: &[i64]) -> ApiResult<HashMap<i64, Vec<String>>> {
+    let rows = client
+        .query(
+            "
+            select topic, url from topic_links
+            where topic = any($1)
+            order by topic, position
+            ",
+            &[&topics],
+        )
+        .await?;
+    let mut links: HashMap<i64, Vec<String>> = HashMap::new();
+    for row in rows.iter() {
+        links.entry(row.get(0)).or_default().push(row.get(1));
+    }
+    Ok(links)
+}
+
+async fn attach_topic_links(client: &Client, topics: &mut [UserTopic]) -> ApiResult<()> {
+    let ids: Vec<i64> = topics.iter().map(|t| t.id as i64).collect();
+    let mut links = topic_links(client, &ids).await?;
+    for topic in topics.iter_mut() {
+        topic.links = links.remove(&(topic.id as i64)).unwrap_or_default();
+    }
+    Ok(())
+}
+
+async fn replace_topic_links(client: &Client, topic: i64, links: &[String]) -> ApiResult<()> {
+    client
+        .execute("delete from topic_links where topic = $1", &[&topic])
+        .await?;
+    let stmt = client
+        .prepare("insert into topic_links (topic, position, url) values ($1, $2, $3)")
+        .await?;
+    for (position, url) in links.iter().enumerate() {
+        client
+            .execute(&stmt, &[&topic, &(position as i32), url])
+            .await?;
+    }
+    Ok(())
+}
+
+// People link their own topics, and organizers link a meeting's seed topics.
+#[put("/topic/<id>/links", data = "<msg>", format = "json")]
+async fn set_topic_links(
+    user: User,
+    client: Db,
+    id: u32,
+    msg: Json<TopicLinksMessage>,
+) -> ApiResult<Value> {
+    let identifier = id as i64;
+    ehall::validate_topic_links(&msg.links).map_err(ApiError::invalid)?;
+    let rows = client
+        .query(
+            "
+            select email, null::bigint from user_topics where id = $1
+            union all
+            select null, meeting from seed_topics where id = $1
+            ",
+            &[&identifier],
+        )
+        .await?;
+    let row = rows.first().ok_or(ApiError::NotFound)?;
+    let allowed = match (row.get::<_, Option<String>>(0), row.get(1)) {
+        (Some(owner), _) => owner == user.email(),
+        (None, Some(meeting)) => {
+            user_permissions(&client, meeting, user.email())
+                .await?
+                .can_seed_topics
+        }
+        (None, None) => false,
+    };
+    if !allowed {
+        return Err(ApiError::Forbidden(
+            ErrorCode::NotProposer,
+            "only a topic's proposer or organizers can link it".to_owned(),
+        ));
+    }
+    replace_topic_links(&client, identifier, &msg.links).await?;
+    Ok(json!({ "links": msg.links }))
+}
+
+async fn insert_seed_topics(
+    client: &Client,
+    meeting_id: i64,
+    topics: &[String],
+) -> ApiResult<Vec<u32>> {
+    let stmt = client
+        .prepare(
+            "
+            insert into seed_topics (meeting, topic)
+            values ($1, $2)
+            returning id
+        ",
+        )
+        .await?;
+    let mut seeded = vec![];
+    for topic in topics.iter() {
+        let row = client.query_one(&stmt, &[&meeting_id, topic]).await?;
+        seeded.push(row.get::<_, i64>(0) as u32);
+    }
+    Ok(seeded)
+}
+
+#[post("/meeting/<id>/seed_topics", data = "<msg>", format = "json")]
+async fn seed_meeting_topics(
+    user: User,
+    client: Db,
+    id: u32,
+    msg: Json<SeedTopicsMessage>,
+) -> ApiResult<Value> {
+    let identifier = id as i64;
+    if !meeting_exists(&client, identifier).await? {
+        return Err(ApiError::NotFound);
+    }
+    if !user_permissions(&client, identifier, user.email())
+        .await?
+        .can_seed_topics
+    {
+        return Err(ApiError::Forbidden(
+            ErrorCode::NotOrganizer,
+            "only organizers can suggest topics".to_owned(),
+        ));
+    }
+    let seeded = insert_seed_topics(&client, identifier, &msg.topics).await?;
+    // Attendees already in the lobby get the new seeds too, ranked last.
+    let sql = "
+        insert into meeting_topics
+        (email, meeting, topic, score, cohort)
+        (
+            select a.email, $1, s.id, 0, cm.cohort
+            from meeting_attendees a
+            join seed_topics s on s.meeting = a.meeting
+            left join cohort_groups cg on cg.meeting = a.meeting
+            left join cohort_members cm
+                on cm.cohort_group = cg.id and cm.email = a.email
+            where a.meeting = $1
+        ) on conflict (email, meeting, topic) do nothing
+    ";
+    client.execute(sql, &[&identifier]).await?;
+    Ok(json!({ "seeded": seeded }))
+}
+
+// Reopening a phase that has ended is allowed until the meeting starts,
+// since ballots only take the submitted topics as attendees arrive.
+#[put("/meeting/<id>/submission_deadline", data = "<msg>", format = "json")]
+async fn set_submission_deadline(
+    user: User,
+    client: Db,
+    id: u32,
+    msg: Json<SubmissionDeadlineMessage>,
+) -> ApiResult<Value> {
+    let identifier = id as i64;
+    if !meeting_exists(&client, identifier).await? {
+        return Err(ApiError::NotFound);
+    }
+    if !user_permissions(&client, identifier, user.email())
+        .await?
+        .can_seed_topics
+    {
+        return Err(ApiError::Forbidden(
+            ErrorCode::NotOrganizer,
+            "only organizers can change the submission deadline".to_owned(),
+        ));
+    }
+    if meeting_started(&client, identifier).await? {
+        return Err(ApiError::Conflict(
+            ErrorCode::MeetingStarted,
+            "the meeting has started, so its topics are settled".to_owned(),
+        ));
+    }
+    client
+        .execute(
+            "update meetings set submission_deadline = to_timestamp($2::bigint) where id = $1",
+            &[&identifier, &msg.deadline],
+        )
+        .await?;
+    Ok(json!({ "submission_deadline": msg.deadline }))
+}
+
+// Like the cohort size, the limit is read when a round starts,
+// so changing it leaves the current round's deadline alone.
+#[put("/meeting/<id>/voting_minutes", data = "<msg>", format = "json")]
+async fn set_voting_minutes(
+    user: User,
+    client: Db,
+    id: u32,
+    msg: Json<VotingMinutesMessage>,
+) -> ApiResult<Value> {
+    let identifier = id as i64;
+    if !meeting_exists(&client, identifier).await? {
+        return Err(ApiError::NotFound);
+    }
+    if !user_permissions(&client, identifier, user.email())
+        .await?
+        .can_rename
+    {
+        return Err(ApiError::Forbidden(
+            ErrorCode::NotOrganizer,
+            "only organizers can change the voting time".to_owned(),
+        ));
+    }
+    if let Some(minutes) = msg.minutes {
+        validate_voting_minutes(minutes).map_err(ApiError::invalid)?;
+    }
+    client
+        .execute(
+            "update meetings set voting_minutes = $2 where id = $1",
+            &[&identifier, &msg.minutes.map(|m| m as i32)],
+        )
+        .await?;
+    Ok(json!({ "voting_minutes": msg.minutes }))
+}
+
+/// The meeting's submission deadline and whether `email` is registered for it
+async fn submission_access(
+    client: &Client,
+    meeting_id: i64,
+    email: &str,
+) -> ApiResult<(Option<i64>, bool)> {
+    let row = client
+        .query_opt(
+            "
+            select extract(epoch from m.submission_deadline)::bigint, exists(
+                select 1 from meeting_participants p
+                where p.meeting = m.id and p.email = $2
+            )
+            from meetings m
+            where m.id = $1
+            ",
+            &[&meeting_id, &email],
+        )
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    Ok((row.get(0), row.get(1)))
+}
+
+// Registered users may submit and rank topics until the deadline,
+// and only before the meeting starts, however late the deadline.
+async fn check_submissions_open(
+    client: &Client,
+    clock: &Clock,
+    meeting_id: i64,
+    email: &str,
+) -> ApiResult<()> {
+    let (deadline, registered) = submission_access(client, meeting_id, email).await?;
+    if !registered {
+        return Err(ApiError::Forbidden(
+            ErrorCode::NotRegistered,
+            "only those registered for the meeting can submit topics".to_owned(),
+        ));
+    }
+    let open = deadline.is_some_and(|deadline| !clock.reached(deadline));
+    if !open || meeting_started(client, meeting_id).await? {
+        return Err(ApiError::Conflict(
+            ErrorCode::SubmissionsClosed,
+            "the meeting is not taking topic submissions".to_owned(),
+        ));
+    }
+    Ok(())
+}
+
+// Topics the user hasn't ranked, like those submitted since, come last.
+const GET_SUBMITTED_TOPICS: &str = "
+    select t.topic, t.id, t.email, t.description
+    from user_topics t
+    left join topic_submission_scores s
+        on s.meeting = t.meeting and s.email = $2 and s.topic = t.id
+    where t.meeting = $1
+    order by s.score desc nulls last, t.id
+";
+
+#[get("/meeting/<id>/submissions")]
+async fn get_submitted_topics(
+    user: User,
+    client: Db,
+    id: u32,
+) -> ApiResult<Json<SubmittedTopicsMessage>> {
+    let identifier = id as i64;
+    let (deadline, registered) = submission_access(&client, identifier, user.email()).await?;
+    if !registered
+        && !user_permissions(&client, identifier, user.email())
+            .await?
+            .can_seed_topics
+    {
+        return Err(ApiError::Forbidden(
+            ErrorCode::NotRegistered,
+            "only those registered for the meeting can see its submitted topics".to_owned(),
+        ));
+    }
+    let rows = client
+        .query(GET_SUBMITTED_TOPICS, &[&identifier, &user.email()])
+        .await?;
+    // Proposers of a meeting that shares contacts stay unnamed, like its cohort members.
+    let named = !meeting_shares_contacts(&client, identifier).await?;
+    // Scores follow the user's ranking, with unranked topics below the ranked ones.
+    let n_topics = rows.len();
+    let mut topics: Vec<UserTopic> = rows
+        .iter()
+        .enumerate()
+        .map(|(position, row)| UserTopic {
+            text: row.get(0),
+            score: (n_topics - position - 1) as u32,
+            id: row.get::<_, i64>(1) as u32,
+            seeded: false,
+            position: position as u32,
+            links: vec![],
+            proposers: if named { vec![row.get(2)] } else { vec![] },
+            vetoed: false,
+            private: false,
+            description: row.get(3),
+        })
+        .collect();
+    attach_topic_links(&client, &mut topics).await?;
+    Ok(SubmittedTopicsMessage { deadline, topics }.into())
+}
+
+// The submitter ranks a new topic first, as with new personal topics.
+const SUBMIT_TOPIC: &str = "
+    with topic as (
+        insert into user_topics (email, topic, description, meeting)
+        values ($2, $3, $4, $1)
+        returning id
+    ), ranked as (
+        insert into topic_submission_scores (meeting, email, topic, score)
+        select $1, $2, id, (
+            select 1 + coalesce(max(score), -1)
+            from topic_submission_scores where meeting = $1 and email = $2
+        )
+        from topic
+    )
+    select id from topic
+";
+
+#[post("/meeting/<id>/submissions", data = "<topic>", format = "json")]
+async fn submit_meeting_topic(
+    user: User,
+    client: Db,
+    clock: &State<Clock>,
+    id: u32,
+    topic: Json<NewTopicMessage>,
+) -> ApiResult<Value> {
+    topic.validate().map_err(ApiError::invalid)?;
+    let identifier = id as i64;
+    check_submissions_open(&client, clock, identifier, user.email()).await?;
+    let row = client
+        .query_one(
+            SUBMIT_TOPIC,
+            &[
+                &identifier,
+                &user.email(),
+                &topic.new_topic,
+                &topic.description,
+            ],
+        )
+        .await?;
+    let topic_id = row.get::<_, i64>(0);
+    Ok(json!({ "inserted": topic_id as u32 }))
+}
+
+// Like `STORE_MEETING_TOPIC_SCORES`, skipping topics not submitted to the meeting
+const STORE_SUBMISSION_SCORES: &str = "
+    insert into topic_submission_scores (meeting, email, topic, score)
+    select $1, $2, s.topic, s.score
+    from unnest($3::bigint[], $4::int[]) as s (topic, score)
+    join user_topics t on t.id = s.topic and t.meeting = $1
+    on conflict (meeting, email, topic) do update
+        set score = excluded.score
+";
+
+#[put("/meeting/<id>/submissions/scores", format = "json", data = "<msg>")]
+async fn store_submission_scores(
+    user: User,
+    client: Db,
+    clock: &State<Clock>,
+    id: u32,
+    msg: Json<ScoresMessage>,
+) -> ApiResult<Value> {
+    msg.validate().map_err(ApiError::invalid)?;
+    let identifier = id as i64;
+    check_submissions_open(&client, clock, identifier, user.email()).await?;
+    let (topics, scores) = score_columns(&msg.scores);
+    let n_stored = client
+        .execute(
+            STORE_SUBMISSION_SCORES,
+            &[&identifier, &user.email(), &topics, &scores],
+        )
+        .await?;
+    Ok(json!({ "stored": n_stored }))
+}
+
+// Merging changes ballots' meaning, so it stops once cohorts have ballots.
+async fn check_topic_merging(client: &Client, meeting_id: i64, email: &str) -> ApiResult<()> {
+    if !meeting_exists(client, meeting_id).await? {
+        return Err(ApiError::NotFound);
+    }
+    if !user_permissions(client, meeting_id, email)
+        .await?
+        .can_merge_topics
+    {
+        return Err(ApiError::Forbidden(
+            ErrorCode::NotOrganizer,
+            "only organizers can merge topics".to_owned(),
+        ));
+    }
+    if meeting_started(client, meeting_id).await? {
+        return Err(ApiError::Conflict(
+            ErrorCode::MeetingStarted,
+            "the meeting has started, so its topics can no longer be merged".to_owned(),
+        ));
+    }
+    Ok(())
+}
+
+/// Every topic on the meeting's ballots, with the groups organizers merged them into
+#[get("/meeting/<id>/candidate_topics")]
+async fn get_candidate_topics(
+    user: User,
+    client: Db,
+    id: u32,
+) -> ApiResult<Json<CandidateTopicsMessage>> {
+    let m_id = id as i64;
+    if !meeting_exists(&client, m_id).await? {
+        return Err(ApiError::NotFound);
+    }
+    if !user_permissions(&client, m_id, user.email())
+        .await?
+        .can_merge_topics
+    {
+        return Err(ApiError::Forbidden(
+            ErrorCode::NotOrganizer,
+            "only organizers can see the topics before the meeting starts".to_owned(),
+        ));
+    }
+    let sql = "
+        select distinct u.id, u.text, u.seeded
+        from meeting_topics m
+        join
+        (select topic as text, id, false as seeded from user_topics
+        union all
+        select topic as text, id, true as seeded from seed_topics
+            where meeting = $1) u
+        on m.topic = u.id
+        where m.meeting = $1
+        order by u.id
+    ";
+    let rows = client.query(sql, &[&m_id]).await?;
+    let merged = aliases::topic_aliases(&client, m_id).await?;
+    let canonical: HashSet<i64> = merged.values().copied().collect();
+    let topics = rows
+        .iter()
+        .map(|row| {
+            let topic: i64 = row.get(0);
+            let merged_into = match merged.get(&topic) {
+                Some(&group) => Some(group),
+                None => canonical.contains(&topic).then_some(topic),
+            };
+            CandidateTopic {
+                id: topic as u32,
+                text: row.get(1),
+                seeded: row.get(2),
+                merged_into: merged_into.map(|group| group as u32),
+            }
+        })
+        .collect();
+    Ok(CandidateTopicsMessage { topics }.into())
+}
+
+/// Marks topics as duplicates, so the tally counts each voter's best score among them once
+#[post("/meeting/<id>/topics/merge", data = "<msg>", format = "json")]
+async fn merge_meeting_topics(
+    user: User,
+    client: Db,
+    id: u32,
+    msg: Json<MergeTopicsMessage>,
+) -> ApiResult<Value> {
+    let m_id = id as i64;
+    msg.validate().map_err(ApiError::invalid)?;
+    check_topic_merging(&client, m_id, user.email()).await?;
+    let topics: Vec<i64> = msg.topics.iter().map(|&t| t as i64).collect();
+    let row = client
+        .query_one(
+            "
+            select count(distinct topic) from meeting_topics
+            where meeting = $1 and topic = any($2)
+            ",
+            &[&m_id, &topics],
+        )
+        .await?;
+    if row.get::<_, i64>(0) != topics.len() as i64 {
+        return Err(ApiError::BadRequest(
+            ErrorCode::NotOnBallot,
+            "only topics on this meeting's ballots can be merged".to_owned(),
+        ));
+    }
+    aliases::merge(&client, m_id, &topics).await?;
+    Ok(json!({ "merged": msg.topics }))
+}
+
+#[delete("/meeting/<id>/topics/merge/<topic>")]
+async fn split_meeting_topic(user: User, client: Db, id: u32, topic: u32) -> ApiResult<Value> {
+    let m_id = id as i64;
+    check_topic_merging(&client, m_id, user.email()).await?;
+    aliases::split(&client, m_id, topic as i64).await?;
+    Ok(json!({ "split": topic }))
+}
+
+#[delete("/meetings/<id>")]
+async fn delete_meeting(user: User, mut client: Db, id: u32) -> ApiResult<Value> {
+    let identifier = id as i64;
+    if !meeting_exists(&client, identifier).await? {
+        return Err(ApiError::NotFound);
+    }
+    if !user_permissions(&client, identifier, user.email())
+        .await?
+        .can_delete
+    {
+        return Err(ApiError::Forbidden(
+            ErrorCode::NotOrganizer,
+            "only organizers can delete a meeting".to_owned(),
+        ));
+    }
+    // Seed topics and topics submitted to the meeting go with it, links and edits too.
+    let tx = client.transaction().await?;
+    for sql in [
+        "delete from topic_links where topic in (
+            select id from seed_topics where meeting = $1
+            union all select id from user_topics where meeting = $1)",
+        "delete from topic_edits where topic in (
+            select id from seed_topics where meeting = $1
+            union all select id from user_topics where meeting = $1)",
+        "delete from seed_topics where meeting = $1",
+        "delete from user_topics where meeting = $1",
+        "delete from registration_answers
+            where field in (select id from registration_fields where meeting = $1)",
+        "delete from registration_fields where meeting = $1",
+        "delete from meeting_organizers where meeting = $1",
+        "delete from meeting_attendees where meeting = $1",
+        "delete from meeting_participants where meeting = $1",
+        "delete from meeting_scores where meeting = $1",
+        "delete from meeting_topics where meeting = $1",
+        "delete from meeting_votes where meeting = $1",
+        "delete from room_joins where meeting = $1",
+        "delete from notification_deliveries where meeting = $1",
+        "delete from cohort_members
+            where cohort_group in (select id from cohort_groups where meeting = $1)",
+        "delete from cohort_groups where meeting = $1",
+        "delete from topic_aliases where meeting = $1",
+        "delete from topic_submission_scores where meeting = $1",
+        "delete from attendee_topics where meeting = $1",
+        "delete from cohort_outcome_topics where meeting = $1",
+        "delete from cohort_outcomes where meeting = $1",
+        "delete from contact_consents where meeting = $1",
+        "delete from past_cohort_members where meeting = $1",
+        "delete from election_result_topics
+            where election in (select id from election_results where meeting = $1)",
+        "delete from election_results where meeting = $1",
+        "delete from meetings where id = $1",
+    ] {
+        tx.execute(sql, &[&identifier]).await?;
+    }
+    tx.commit().await?;
+    Ok(json!({ "deleted": id }))
+}
+
+#[put("/meetings/<id>", data = "<msg>", format = "json")]
+async fn rename_meeting(
+    user: User,
+    client: Db,
+    id: u32,
+    msg: Json<RenameMeetingMessage>,
+) -> ApiResult<Value> {
+    let identifier = id as i64;
+    if !meeting_exists(&client, identifier).await? {
+        return Err(ApiError::NotFound);
+    }
+    if !user_permissions(&client, identifier, user.email())
+        .await?
+        .can_rename
+    {
+        return Err(ApiError::Forbidden(
+            ErrorCode::NotOrganizer,
+            "only organizers can rename a meeting".to_owned(),
+        ));
+    }
+    let name = msg.name.trim();
+    if name.is_empty() || name.len() > MAX_TEXT_LEN {
+        return Err(ApiError::BadRequest(
+            ErrorCode::InvalidRequest,
+            format!("a meeting name needs 1 to {MAX_TEXT_LEN} bytes"),
+        ));
+    }
+    // Names are the primary key, so a name in use is a conflict.
+    client
+        .execute(
+            "update meetings set name = $2 where id = $1",
+            &[&identifier, &name],
+        )
+        .await
+        .map_err(|e| {
+            ApiError::conflict_if_duplicate(
+                e,
+                ErrorCode::NameTaken,
+                format!("another meeting is named \"{name}\""),
+            )
+        })?;
+    Ok(json!({ "renamed": id }))
+}
+
+async fn meeting_organizers(
+    client: &Client,
+    meeting_id: i64,
+) -> ApiResult<MeetingOrganizersMessage> {
+    let rows = client
+        .query("select owner from meetings where id = $1", &[&meeting_id])
+        .await?;
+    let owner = rows.first().and_then(|row| row.get(0));
+    let rows = client
+        .query(
+            "select email from meeting_organizers where meeting = $1 order by email",
+            &[&meeting_id],
+        )
+        .await?;
+    Ok(MeetingOrganizersMessage {
+        owner,
+        organizers: rows.iter().map(|row| row.get(0)).collect(),
+    })
+}
+
+#[get("/meeting/<id>/organizers")]
+async fn get_meeting_organizers(
+    _user: User,
+    client: Db,
+    id: u32,
+) -> ApiResult<Json<MeetingOrganizersMessage>> {
+    let identifier = id as i64;
+    if !meeting_exists(&client, identifier).await? {
+        return Err(ApiError::NotFound);
+    }
+    Ok(meeting_organizers(&client, identifier).await?.into())
+}
+
+// Only the owner chooses co-organizers, and only people with accounts.
+#[post("/meeting/<id>/organizers", data = "<msg>", format = "json")]
+async fn add_meeting_organizer(
+    user: User,
+    client: Db,
+    id: u32,
+    msg: Json<NewOrganizerMessage>,
+) -> ApiResult<Json<MeetingOrganizersMessage>> {
+    let identifier = id as i64;
+    if !meeting_exists(&client, identifier).await? {
+        return Err(ApiError::NotFound);
+    }
+    if !user_permissions(&client, identifier, user.email())
+        .await?
+        .can_manage_organizers
+    {
+        return Err(ApiError::Forbidden(
+            ErrorCode::NotOwner,
+            "only the meeting's owner can add co-organizers".to_owned(),
+        ));
+    }
+    let email = msg.email.trim();
+    let rows = client
+        .query("select 1 from users where email = $1", &[&email])
+        .await?;
+    if rows.is_empty() {
+        return Err(ApiError::BadRequest(
+            ErrorCode::UnknownAccount,
+            format!("no account uses {email}"),
+        ));
+    }
+    client
+        .execute(
+            "
+            insert into meeting_organizers (meeting, email)
+            values ($1, $2)
+            on conflict do nothing
+            ",
+            &[&identifier, &email],
+        )
+        .await?;
+    Ok(meeting_organizers(&client, identifier).await?.into())
+}
+
+#[delete("/meeting/<id>/organizers/<email>")]
+async fn remove_meeting_organizer(
+    user: User,
+    client: Db,
+    id: u32,
+    email: &str,
+) -> ApiResult<Json<MeetingOrganizersMessage>> {
+    let identifier = id as i64;
+    if !meeting_exists(&client, identifier).await? {
+        return Err(ApiError::NotFound);
+    }
+    if !user_permissions(&client, identifier, user.email())
+        .await?
+        .can_manage_organizers
+    {
+        return Err(ApiError::Forbidden(
+            ErrorCode::NotOwner,
+            "only the meeting's owner can remove co-organizers".to_owned(),
+        ));
+    }
+    client
+        .execute(
+            "delete from meeting_organizers where meeting = $1 and email = $2",
+            &[&identifier, &email],
+        )
+        .await?;
+    Ok(meeting_organizers(&client, identifier).await?.into())
+}
+
+#[put("/topic/<id>/private", data = "<msg>", format = "json")]
+async fn set_topic_private(
+    user: User,
+    client: Db,
+    id: u32,
+    msg: Json<TopicPrivacyMessage>,
+) -> ApiResult<Value> {
+    let n = client
+        .execute(
+            "update user_topics set private = $3 where id = $1 and email = $2",
+            &[&(id as i64), &user.email(), &msg.private],
+        )
+        .await?;
+    if n == 0 {
+        return Err(ApiError::NotFound);
+    }
+    Ok(json!({ "private": msg.private }))
+}
+
+// The first edit also saves the description the topic was created with, as version 0.
+// The new version's number counts the versions before it,
+// since the statement doesn't see the version 0 it adds.
+const EDIT_TOPIC_DESCRIPTION: &str = "
+    with topic as (
+        select id, description from user_topics
+        where id = $1 and email = $2
+        for update
+    ), original as (
+        insert into topic_edits (topic, version, description, edited_by)
+        select id, 0, description, $2 from topic
+        on conflict (topic, version) do nothing
+    ), edited as (
+        update user_topics t set description = $3
+        from topic
+        where t.id = topic.id
+        returning t.id
+    )
+    insert into topic_edits (topic, version, description, edited_by, edited_at)
+    select id, 1 + coalesce((select max(version) from topic_edits where topic = $1), 0), $3, $2, now()
+    from edited
+";
+
+#[put("/topic/<id>/description", data = "<msg>", format = "json")]
+async fn set_topic_description(
+    user: User,
+    client: Db,
+    id: u32,
+    msg: Json<TopicDescriptionMessage>,
+) -> ApiResult<Value> {
+    validate_topic_description(&msg.description).map_err(ApiError::invalid)?;
+    let n = client
+        .execute(
+            EDIT_TOPIC_DESCRIPTION,
+            &[&(id as i64), &user.email(), &msg.description],
+        )
+        .await?;
+    if n == 0 {
+        return Err(ApiError::NotFound);
+    }
+    Ok(json!({ "description": msg.description }))
+}
+
+// Only the topic's owner sees its edits.
+#[get("/topic/<id>/description/edits")]
+async fn get_topic_edits(user: User, client: Db, id: u32) -> ApiResult<Json<TopicEditsMessage>> {
+    let identifier = id as i64;
+    let owned = client
+        .query_opt(
+            "select 1 from user_topics where id = $1 and email = $2",
+            &[&identifier, &user.email()],
+        )
+        .await?;
+    if owned.is_none() {
+        return Err(ApiError::NotFound);
+    }
+    let rows = client
+        .query(
+            "
+            select version, description, edited_by, extract(epoch from edited_at)::bigint
+            from topic_edits
+            where topic = $1
+            order by version desc
+            ",
+            &[&identifier],
+        )
+        .await?;
+    let edits = rows
+        .iter()
+        .map(|row| TopicEdit {
+            version: row.get::<_, i32>(0) as u32,
+            description: row.get(1),
+            edited_by: row.get(2),
+            edited_at: row.get(3),
+        })
+        .collect();
+    Ok(TopicEditsMessage { edits }.into())
+}
+
+#[delete("/topics/<id>")]
+async fn delete_topic(user: User, client: Db, id: u32) -> ApiResult<Value> {
+    let identifier = id as i64;
+    for sql in [
+        "
+        delete from topic_links
+        where topic in (select id from user_topics where id = $1 and email = $2)
+        ",
+        "
+        delete from topic_edits
+        where topic in (select id from user_topics where id = $1 and email = $2)
+        ",
+        "delete from user_topics where id = $1 and email = $2",
+        "delete from user_topic_score_history where topic = $1 and email = $2",
+        "delete from attendee_topics where topic = $1 and email = $2",
+    ] {
+        client.execute(sql, &[&identifier, &user.email()]).await?;
+    }
+    canonicalize_user_topic_scores(&client, user.email()).await?;
+    Ok(json!({ "deleted": id }))
+}
+
+#[put("/meeting/<id>/score", format = "json", data = "<score_msg>")]
+async fn store_meeting_score(
+    user: User,
+    client: Db,
+    id: u32,
+    score_msg: Json<ScoreMessage>,
+) -> ApiResult<Value> {
+    let identifier = id as i64;
+    let score = score_msg.score as i32;
+    client
+        .execute(
+            "insert into meeting_scores
+                (meeting, email, score)
+                values
+                ($1, $2, $3)
+            on conflict (meeting, email) do update
+                set score = excluded.score
+            ",
+            &[&identifier, &user.email(), &score],
+        )
+        .await?;
+    Ok(json!({ "stored": score }))
+}
+
+#[put("/meeting/<meeting_id>/vote")]
+async fn vote_for_meeting_topics(
+    user: User,
+    client: Db,
+    clock: &State<Clock>,
+    key: IdempotencyKey,
+    meeting_id: u32,
+) -> ApiResult<Custom<Value>> {
+    let route = format!("/meeting/{meeting_id}/vote");
+    idempotency::once(&client, clock, user.email(), &key, &route, async {
+        vote(&client, clock, user.email(), meeting_id)
+            .await
+            .map(|()| Custom(Status::Ok, json!({ "voted": meeting_id })))
+    })
+    .await
+}
+
+async fn vote(client: &Client, clock: &Clock, email: &str, meeting_id: u32) -> ApiResult<()> {
+    let m_id = meeting_id as i64;
+    let role = attendee_role(client, m_id, email).await?;
+    if role == Some(AttendeeRole::Observer) {
+        return Err(ApiError::Forbidden(
+            ErrorCode::Observing,
+            "observers have no ballot to vote with".to_owned(),
+        ));
+    }
+    // One statement, so the snapshot and the voted flag commit together.
+    // A ballot already in isn't marked again, so only a new vote is announced.
+    let sql = "
+        with snapshot as (
+            insert into meeting_votes (meeting, email, topic, score, cohort, vetoed)
+            select meeting, email, topic, score, cohort, vetoed from meeting_topics
+            where meeting = $1 and email = $2 and cohort is not null
+            on conflict (meeting, email, topic) do nothing
+        )
+        update meeting_attendees
+        set voted = true
+        where meeting = $1 and email = $2 and voted is not true
+        returning 1
+    ";
+    if client.query_opt(sql, &[&m_id, &email]).await?.is_none() {
+        return Err(match role {
+            None => ApiError::NotFound,
+            Some(_) => ApiError::Conflict(
+                ErrorCode::AlreadyVoted,
+                "your ballot is already in".to_owned(),
+            ),
+        });
+    }
+    events::notify(client, m_id, MeetingEventKind::Voted).await;
+    let sql = "
+        select coalesce(bool_and(voted), false), array_agg(email order by email)
+        from election_status($1, $2)
+        where email is not null
+    ";
+    let row = client.query_one(sql, &[&email, &m_id]).await?;
+    if row.get::<_, bool>(0) {
+        if let Some(cohort) = user_cohort(client, m_id, email).await? {
+            let members: Vec<String> = row.get(1);
+            let topics = elected_topics(client, email, m_id).await?;
+            history::record(client, clock, m_id, cohort, &members, &topics).await?;
+        }
+        events::notify(client, m_id, MeetingEventKind::ResultsReady).await;
+    }
+    Ok(())
+}
+
+// A vote marks its voter's row, so holding every row of the cohort
+// makes a vote wait for a retraction in progress, and the retraction wait for the vote,
+// until the transaction holding them ends.
+const LOCK_COHORT_VOTES: &str = "
+    select 1 from meeting_attendees
+    where meeting = $1
+        and email in (select email from election_status($2, $1) where email is not null)
+    for update
+";
+
+// Until the rest of the cohort votes, nothing has been counted,
+// so a ballot can come back for another look and be submitted again.
+// It runs after LOCK_COHORT_VOTES, in its transaction, so the last member's vote
+// either finishes the cohort before the check, which then refuses,
+// or waits until the retraction commits, and finds the cohort unfinished.
+// Results recorded for the unfinished cohort $3 are stale, left by a retraction
+// that raced a vote before cohorts took turns, since recording them never overwrites,
+// so they go too, and are recorded again when the cohort finishes.
+const RETRACT_VOTE: &str = "
+    with retracted as (
+        update meeting_attendees
+        set voted = false
+        where meeting = $1 and email = $2 and voted
+            and exists (
+                select 1 from election_status($2, $1) where not coalesce(voted, false)
+            )
+        returning email
+    ), snapshot as (
+        delete from meeting_votes
+        where meeting = $1 and email in (select email from retracted)
+    ), stale_topics as (
+        delete from election_result_topics
+        where election in (select id from election_results where meeting = $1 and cohort = $3)
+            and exists (select 1 from retracted)
+    ), stale as (
+        delete from election_results
+        where meeting = $1 and cohort = $3 and exists (select 1 from retracted)
+    )
+    select count(*) from retracted
+";
+
+#[delete("/meeting/<meeting_id>/vote")]
+async fn retract_vote(user: User, mut client: Db, meeting_id: u32) -> ApiResult<Value> {
+    let m_id = meeting_id as i64;
+    let email = user.email();
+    match attendee_role(&client, m_id, email).await? {
+        None => {
+            return Err(ApiError::Forbidden(
+                ErrorCode::NotAttendee,
+                "only attendees can take back a vote".to_owned(),
+            ))
+        }
+        Some(AttendeeRole::Observer) => {
+            return Err(ApiError::Forbidden(
+                ErrorCode::Observing,
+                "observers have no ballot to take back".to_owned(),
+            ))
+        }
+        Some(AttendeeRole::Participant) => {}
+    }
+    let cohort = user_cohort(&client, m_id, email).await?;
+    let tx = client.transaction().await?;
+    tx.execute(LOCK_COHORT_VOTES, &[&m_id, &email]).await?;
+    let row = tx
+        .query_one(RETRACT_VOTE, &[&m_id, &email, &cohort])
+        .await?;
+    tx.commit().await?;
+    if row.get::<_, i64>(0) > 0 {
+        events::notify(&client, m_id, MeetingEventKind::Voted).await;
+        return Ok(json!({ "retracted": meeting_id }));
+    }
+    let rows = client
+        .query(
+            "select voted from meeting_attendees where meeting = $1 and email = $2",
+            &[&m_id, &email],
+        )
+        .await?;
+    if rows.first().is_some_and(|row| row.get::<_, bool>(0)) {
+        return Err(ApiError::Conflict(
+            ErrorCode::CohortFinished,
+            "your cohort has finished voting".to_owned(),
+        ));
+    }
+    // Nothing to take back, which is what was asked for.
+    Ok(json!({ "retracted": meeting_id }))
+}
+
+#[put(
+    "/meeting/<meeting_id>/topic/<topic_id>/score",
+    format = "json",
+    data = "<score_msg>"
+)]
+async fn store_meeting_topic_score(
+    user: User,
+    client: Db,
+    meeting_id: u32,
+    topic_id: u32,
+    score_msg: Json<ScoreMessage>,
+) -> ApiResult<Value> {
+    let m_id = meeting_id as i64;
+    let t_id = topic_id as i64;
+    let score = score_msg.score as i32;
+    client
+        .execute(
+            "insert into meeting_topics
+                (meeting, email, topic, score)
+                values
+                ($1, $2, $3, $4)
+            on conflict (meeting, email, topic) do update
+                set score = excluded.score
+            ",
+            &[&m_id, &user.email(), &t_id, &score],
+        )
+        .await?;
+    Ok(json!({ "stored": score }))
+}
+
+// One statement, so a reordering is stored whole or not at all.
+const STORE_MEETING_TOPIC_SCORES: &str = "
+    insert into meeting_topics (meeting, email, topic, score)
+    select $1, $2, topic, score from unnest($3::bigint[], $4::int[]) as s (topic, score)
+    on conflict (meeting, email, topic) do update
+        set score = excluded.score
+";
+
+#[put("/meeting/<id>/topics/scores", format = "json", data = "<msg>")]
+async fn store_meeting_topic_scores(
+    user: User,
+    client: Db,
+    id: u32,
+    msg: Json<ScoresMessage>,
+) -> ApiResult<Value> {
+    msg.validate().map_err(ApiError::invalid)?;
+    let (topics, scores) = score_columns(&msg.scores);
+    client
+        .execute(
+            STORE_MEETING_TOPIC_SCORES,
+            &[&(id as i64), &user.email(), &topics, &scores],
+        )
+        .await?;
+    Ok(json!({ "stored": msg.scores.len() }))
+}
+
+// Splits (topic id, score) pairs into arrays to bind for unnest.
+fn score_columns(pairs: &[(u32, u32)]) -> (Vec<i64>, Vec<i32>) {
+    pairs
+        .iter()
+        .map(|&(topic, score)| (topic as i64, score as i32))
+        .unzip()
+}
+
+// The veto travels with the ballot when the attendee votes.
+#[put("/meeting/<id>/veto", format = "json", data = "<msg>")]
+async fn veto_meeting_topic(
+    user: User,
+    client: Db,
+    id: u32,
+    msg: Json<VetoMessage>,
+) -> ApiResult<Value> {
+    let m_id = id as i64;
+    if !meeting_exists(&client, m_id).await? {
+        return Err(ApiError::NotFound);
+    }
+    if meeting_veto_policy(&client, m_id).await? == VetoPolicy::Off {
+        return Err(ApiError::BadRequest(
+            ErrorCode::VetoesOff,
+            "this meeting does not allow vetoes".to_owned(),
+        ));
+    }
+    if attendee_role(&client, m_id, user.email()).await? == Some(AttendeeRole::Observer) {
+        return Err(ApiError::Forbidden(
+            ErrorCode::Observing,
+            "observers have no ballot to veto topics on".to_owned(),
+        ));
+    }
+    let rows = client
+        .query(
+            "select voted from meeting_attendees where meeting = $1 and email = $2",
+            &[&m_id, &user.email()],
+        )
+        .await?;
+    match rows.first().map(|row| row.get::<_, bool>(0)) {
+        None => {
+            return Err(ApiError::Forbidden(
+                ErrorCode::NotAttendee,
+                "only attendees can veto topics".to_owned(),
+            ))
+        }
+        Some(true) => {
+            return Err(ApiError::Conflict(
+                ErrorCode::AlreadyVoted,
+                "your ballot is already in".to_owned(),
+            ))
+        }
+        Some(false) => {}
+    }
+    // Clearing first keeps the one-veto index satisfied.
+    client
+        .execute(
+            "
+            update meeting_topics set vetoed = false
+            where meeting = $1 and email = $2 and vetoed
+            ",
+            &[&m_id, &user.email()],
+        )
+        .await?;
+    if let Some(topic) = msg.topic {
+        let n = client
+            .execute(
+                "
+                update meeting_topics set vetoed = true
+                where meeting = $1 and email = $2 and topic = $3 and cohort is not null
+                ",
+                &[&m_id, &user.email(), &(topic as i64)],
+            )
+            .await?;
+        if n == 0 {
+            return Err(ApiError::BadRequest(
+                ErrorCode::NotOnBallot,
+                "that topic is not on your ballot".to_owned(),
+            ));
+        }
+    }
+    Ok(json!({ "vetoed": msg.topic }))
+}
+
+#[put("/topic/<topic_id>/score", format = "json", data = "<score_msg>")]
+async fn store_user_topic_score(
+    user: User,
+    client: Db,
+    topic_id: u32,
+    score_msg: Json<ScoreMessage>,
+) -> ApiResult<Value> {
+    let score = score_msg.score as i32;
+    client
+        .execute(
+            STORE_USER_TOPIC_SCORES,
+            &[&user.email(), &vec![topic_id as i64], &vec![score]],
+        )
+        .await?;
+    Ok(json!({ "stored": score }))
+}
+
+// Stores user $1's scores $3 for topics $2, all in one statement,
+// and renumbers the scores of their own topics to 0..n, keeping their order,
+// so that swapping neighbors' scores always moves a topic.
+// Every score that changes, by either step, is recorded in the history.
+// With no scores given, it only renumbers.
+const STORE_USER_TOPIC_SCORES: &str = "
+    with stored as (
+        select t.id, t.meeting, coalesce(s.score, t.score) as score
+        from user_topics t
+        left join unnest($2::bigint[], $3::int[]) as s (topic, score) on s.topic = t.id
+        where t.email = $1 and (t.meeting is null or s.topic is not null)
+    ), canonical as (
+        select id, (row_number() over (order by score, id desc) - 1)::int as score
+        from stored where meeting is null
+        union all
+        select id, score from stored where meeting is not null
+    ), changed as (
+        update user_topics t
+        set score = c.score
+        from canonical c
+        where t.id = c.id and t.score is distinct from c.score
+        returning t.id, t.score
+    )
+    insert into user_topic_score_history (email, topic, score)
+    select $1, id, score from changed
+";
+
+// After a user's topics come or go
+async fn canonicalize_user_topic_scores(client: &Client, email: &str) -> ApiResult<()> {
+    let none: Vec<i64> = vec![];
+    let scores: Vec<i32> = vec![];
+    client
+        .execute(STORE_USER_TOPIC_SCORES, &[&email, &none, &scores])
+        .await?;
+    Ok(())
+}
+
+#[put("/topics/scores", format = "json", data = "<msg>")]
+async fn store_user_topic_scores(
+    user: User,
+    client: Db,
+    msg: Json<ScoresMessage>,
+) -> ApiResult<Value> {
+    msg.validate().map_err(ApiError::invalid)?;
+    let (topics, scores) = score_columns(&msg.scores);
+    client
+        .execute(STORE_USER_TOPIC_SCORES, &[&user.email(), &topics, &scores])
+        .await?;
+    Ok(json!({ "stored": msg.scores.len() }))
+}
+
+// Dates are bound as text, because the Postgres client lacks date support.
+const GET_TOPIC_HISTORY: &str = "
+    select topic, score, extract(epoch from recorded_at)::bigint
+    from user_topic_score_history
+    where email = $1
+        and ($2::text is null or recorded_at >= $2::text::date)
+        and ($3::text is null or recorded_at < $3::text::date + 1)
+    order by topic, recorded_at
+";
+
+// Both dates are inclusive.
+#[get("/user_topics/history?<since>&<until>")]
+async fn get_topic_history(
+    user: User,
+    client: Db,
+    since: Option<Date>,
+    until: Option<Date>,
+) -> ApiResult<Json<TopicHistoryMessage>> {
+    let since = since.map(|d| d.to_string());
+    let until = until.map(|d| d.to_string());
+    let rows = client
+        .query(GET_TOPIC_HISTORY, &[&user.email(), &since, &until])
+        .await?;
+    let changes = rows
+        .iter()
+        .map(|row| TopicScoreChange {
+            topic: row.get::<_, i64>(0) as u32,
+            score: row.get::<_, i32>(1) as u32,
+            recorded_at: row.get(2),
+        })
+        .collect();
+    Ok(TopicHistoryMessage { changes }.into())
+}
+
+const GET_SCORED_MEETINGS: &str = "
+    select
+        meetings.name,
+        meetings.id,
+        coalesce(meeting_scores.score,0) as score,
+        coalesce(r.n_registered,0) as n_registered,
+        coalesce(a.n_attending,0) as n_attending,
+        exists(
+            select 1 from meeting_attendees
+            where meeting = meetings.id and email = $1
+        ) as attending,
+        extract(epoch from meetings.registration_deadline)::bigint as registration_deadline,
+        meetings.cohort_size,
+        meetings.owner,
+        exists(
+            select 1 from meeting_organizers
+            where meeting = meetings.id and email = $1
+        ) as is_organizer,
+        meetings.veto_policy,
+        meetings.voting_method,
+        meetings.n_winners,
+        meetings.join_code,
+        meetings.match_language,
+        extract(epoch from meetings.submission_deadline)::bigint as submission_deadline,
+        meetings.topics_per_attendee,
+        meetings.share_contacts,
+        meetings.voting_minutes,
+        count(*) over () as total
+    from meetings
+    left outer join meeting_scores
+        on meetings.id = meeting_scores.meeting and meeting_scores.email = $1
+    left join (
+        select meeting, count(email) as n_registered
+        from meeting_participants
+        group by meeting
+    ) r on meetings.id = r.meeting
+    left join (
+        select meeting, count(email) as n_attending
+        from meeting_attendees
+        group by meeting
+    ) a on meetings.id = a.meeting
+    where ($2::varchar is null or meetings.name ilike $2)
+        and ($5::bigint is null or meetings.id = $5)
+    order by {order}
+    limit $3 offset $4
+";
+
+const COUNT_MEETINGS: &str = "
+    select count(*) from meetings where $1::varchar is null or name ilike $1
+";
+
+// How the database orders meetings for `sort`, as `MeetingSort::compare` does,
+// or None for names, whose collation it doesn't share
+fn meeting_order(sort: MeetingSort) -> Option<&'static str> {
+    match sort {
+        MeetingSort::Ranking => Some("score desc, meetings.id desc"),
+        MeetingSort::Name => None,
+        MeetingSort::Registered => Some("n_registered desc, meetings.id desc"),
+        MeetingSort::Deadline => Some("registration_deadline nulls last, meetings.id desc"),
+        MeetingSort::Recent => Some("meetings.id desc"),
+    }
+}
+
+async fn get_meeting_topics_vec(
+    client: &Client,
+    email: &str,
+    meeting: i64,
+) -> ApiResult<Vec<UserTopic>> {
+    // Ballots have no cohort, and so no topics, until the meeting starts.
+    // Descriptions are as they stood then, so edits can't change what voters ranked.
+    let sql = "
+        select u.text, m.topic, m.score, u.seeded, m.vetoed,
+            coalesce(frozen.description, u.description)
+        from meeting_topics m
+        join
+        (select topic as text, id, false as seeded, description from user_topics
+        union all
+        select topic as text, id, true as seeded, '' as description from seed_topics
+            where meeting = $1) u
+        on m.topic = u.id
+        left join lateral (
+            select e.description
+            from topic_edits e
+            where e.topic = u.id and (
+                e.edited_at is null
+                or e.edited_at <= (select min(started_at) from cohort_groups where meeting = $1)
+            )
+            order by e.version desc
+            limit 1
+        ) frozen on true
+        where m.meeting = $1 and m.email = $2 and m.cohort is not null
+        order by m.score desc, m.topic;
+    ";
+    let stmt = client.prepare(sql).await?;
+    let rows = client.query(&stmt, &[&meeting, &email]).await?;
+    let mut topics: Vec<_> = rows
+        .into_iter()
+        .enumerate()
+        .map(|(position, row)| UserTopic {
+            text: row.get::<_, String>(0),
+            score: row.get::<_, i32>(2) as u32,
+            id: row.get::<_, i64>(1) as u32,
+            seeded: row.get::<_, bool>(3),
+            position: position as u32,
+            links: vec![],
+            proposers: vec![],
+            vetoed: row.get::<_, bool>(4),
+            private: false,
+            description: row.get(5),
+        })
+        .collect();
+    attach_topic_links(client, &mut topics).await?;
+    Ok(topics)
+}
+
+#[get("/meeting/<id>/topics")]
+async fn get_meeting_topics(user: User, client: Db, id: u32) -> ApiResult<Json<UserTopicsMessage>> {
+    Ok(UserTopicsMessage {
+        topics: get_meeting_topics_vec(&client, user.email(), id as i64).await?,
+        page: PageInfo::default(),
+    }
+    .into())
+}
+
+async fn registered_meetings(client: &Client, email: &str) -> ApiResult<Vec<u32>> {
+    let stmt = client
+        .prepare(
+            "
+        select meeting from meeting_participants
+        where email = $1
+    ",
+        )
+        .await?;
+    let rows = client.query(&stmt, &[&email]).await?;
+    Ok(rows
+        .iter()
+        .map(|row| {
+            let id = row.get::<_, i64>(0);
+            assert_eq!(id as u32 as i64, id); // XXX: later maybe stringify this ID
+            id as u32
+        })
+        .collect())
+}
+
+#[get("/registered_meetings")]
+async fn get_registered_meetings(
+    user: User,
+    client: Db,
+) -> ApiResult<Json<RegisteredMeetingsMessage>> {
+    let meetings = registered_meetings(&client, user.email()).await?;
+    Ok(RegisteredMeetingsMessage { meetings }.into())
+}
+
+// Meetings from before ownership have no owner, and everyone organizes them.
+fn meeting_role(owner: Option<&str>, is_organizer: bool, email: &str) -> MeetingRole {
+    match owner {
+        Some(owner) if owner == email => MeetingRole::Owner,
+        Some(_) if !is_organizer => MeetingRole::Participant,
+        _ => MeetingRole::Organizer,
+    }
+}
+
+// Organizers manage the meeting, and the one who starts it must be attending.
+fn meeting_permissions(role: MeetingRole, attending: bool) -> MeetingPermissions {
+    let organizing = role != MeetingRole::Participant;
+    MeetingPermissions {
+        can_start: organizing && attending,
+        can_delete: organizing,
+        can_rename: organizing,
+        can_manage_organizers: role == MeetingRole::Owner,
+        can_seed_topics: organizing,
+        can_share_invite: organizing,
+        can_manage_registration: organizing,
+        can_view_room_joins: organizing,
+        can_view_outcomes: organizing,
+        can_merge_topics: organizing,
+    }
+}
+
+async fn user_permissions(
+    client: &Client,
+    meeting_id: i64,
+    email: &str,
+) -> ApiResult<MeetingPermissions> {
+    let rows = client
+        .query(
+            "
+            select m.owner, exists(
+                select 1 from meeting_organizers o
+                where o.meeting = m.id and o.email = $2
+            )
+            from meetings m
+            where m.id = $1
+            ",
+            &[&meeting_id, &email],
+        )
+        .await?;
+    let role = match rows.first() {
+        Some(row) => meeting_role(row.get(0), row.get(1), email),
+        None => MeetingRole::Participant,
+    };
+    Ok(meeting_permissions(
+        role,
+        is_attending(client, meeting_id, email).await?,
+    ))
+}
+
+/// Which meetings `scored_meetings` lists, and in what order
+struct MeetingsFilter<'a> {
+    sort: MeetingSort,
+    /// Text that meeting names contain, ignoring case
+    search: Option<&'a str>,
+    offset: u32,
+    /// At most this many, or every one
+    limit: Option<u32>,
+    /// Only this meeting
+    id: Option<u32>,
+}
+
+impl MeetingsFilter<'_> {
+    fn all(sort: MeetingSort) -> Self {
+        MeetingsFilter {
+            sort,
+            search: None,
+            offset: 0,
+            limit: None,
+            id: None,
+        }
+    }
+}
+
+// A page of the meetings, and how many there are on every page.
+// The database cuts the page, except by name, which only sorts here,
+// among the newest `MAX_UNPAGED_MEETINGS` meetings.
+async fn scored_meetings(
+    client: &Client,
+    email: &str,
+    min_attendees: &MinAttendees,
+    collation: &Collation,
+    filter: MeetingsFilter<'_>,
+) -> ApiResult<(Vec<MeetingMessage>, u32)> {
+    let order = meeting_order(filter.sort);
+    let (offset, limit) = match order {
+        Some(_) => (filter.offset as i64, filter.limit.map(i64::from)),
+        None => (0, Some(i64::from(MAX_UNPAGED_MEETINGS))),
+    };
+    let sql = GET_SCORED_MEETINGS.replace("{order}", order.unwrap_or("meetings.id desc"));
+    let pattern = filter.search.map(name_pattern);
+    let id = filter.id.map(i64::from);
+    let rows = client
+        .query(&sql, &[&email, &pattern, &limit, &offset, &id])
+        .await?;
+    let total = match rows.first() {
+        Some(row) => row.get::<_, i64>(19),
+        // A page past the end still says how many there are.
+        None if offset > 0 => client.query_one(COUNT_MEETINGS, &[&pattern]).await?.get(0),
+        None => 0,
+    };
+    let ids: Vec<i64> = rows.iter().map(|row| row.get(1)).collect();
+    let field_rows = client
+        .query(
+            "
+            select meeting, id, label from registration_fields
+            where meeting = any($1)
+            order by id
+            ",
+            &[&ids],
+        )
+        .await?;
+    let mut fields: HashMap<i64, Vec<RegistrationField>> = HashMap::new();
+    for row in field_rows.iter() {
+        fields
+            .entry(row.get(0))
+            .or_default()
+            .push(RegistrationField {
+                id: row.get::<_, i64>(1) as u32,
+                label: row.get(2),
+            });
+    }
+    let mut meetings: Vec<_> = rows
+        .iter()
+        .map(|row| {
+            let name = row.get::<_, String>(0);
+            let id = row.get::<_, i64>(1);
+            let score = row.get::<_, i32>(2);
+            let n_registered = row.get::<_, i64>(3);
+            let n_attending = row.get::<_, i64>(4);
+            let attending = row.get::<_, bool>(5);
+            let registration_deadline = row.get::<_, Option<i64>>(6);
+            let cohort_size = row.get::<_, i32>(7) as u32;
+            let owner = row.get::<_, Option<String>>(8);
+            let role = meeting_role(owner.as_deref(), row.get::<_, bool>(9), email);
+            let veto_policy = parse_veto_policy(row.get(10));
+            let voting_method = parse_voting_method(row.get(11));
+            let n_winners = row.get::<_, i32>(12) as u32;
+            let permissions = meeting_permissions(role, attending);
+            let join_code = permissions.can_share_invite.then(|| row.get(13));
+            let match_language = row.get::<_, bool>(14);
+            let submission_deadline = row.get::<_, Option<i64>>(15);
+            let topics_per_attendee = row.get::<_, i32>(16) as u32;
+            let share_contacts = row.get::<_, bool>(17);
+            let voting_minutes = row.get::<_, Option<i32>>(18).map(|m| m as u32);
+            assert_eq!(id as u32 as i64, id); // XXX: later maybe stringify this ID
+            MeetingMessage {
+                meeting: Meeting {
+                    name,
+                    id: id as u32,
+                    n_registered: n_registered as u32,
+                    n_joined: n_attending as u32,
+                    min_attendees: min_attendees.0.max(cohort_size),
+                    registration_deadline,
+                    submission_deadline,
+                    voting_minutes,
+                    cohort_size,
+                    veto_policy,
+                    voting_method,
+                    n_winners,
+                    match_language,
+                    topics_per_attendee,
+                    share_contacts,
+                    join_code,
+                },
+                score: score as u32,
+                permissions,
+                registration_fields: fields.remove(&id).unwrap_or_default(),
+                owner,
+                role,
+            }
+        })
+        .collect();
+    if order.is_none() {
+        let sort = filter.sort;
+        meetings
+            .sort_by(|a, b| sort.compare(collation, (&a.meeting, a.score), (&b.meeting, b.score)));
+        meetings = meetings
+            .into_iter()
+            .skip(filter.offset as usize)
+            .take(filter.limit.map_or(usize::MAX, |limit| limit as usize))
+            .collect();
+    }
+    Ok((meetings, total as u32))
+}
+
+/// An `ilike` pattern for names containing `search`, whose own wildcards match literally
+fn name_pattern(search: &str) -> String {
+    let mut pattern = String::from("%");
+    for c in search.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    pattern.push('%');
+    pattern
+}
+
+/// The user's chosen order for the meetings list, or the default
+async fn meeting_sort(client: &Client, email: &str) -> ApiResult<MeetingSort> {
+    let row = client
+        .query_opt(
+            "select meeting_sort from user_preferences where email = $1",
+            &[&email],
+        )
+        .await?;
+    Ok(row
+        .and_then(|row| parse_meeting_sort(row.get(0)))
+        .unwrap_or_default())
+}
+
+/// The most meetings a list holds when the client doesn't ask for pages.
+/// The UI ranks every meeting the user sees, so this is well above a page.
+const MAX_UNPAGED_MEETINGS: u32 = 2_000;
+
+/// The meetings list's query parameters
+#[derive(FromForm)]
+struct MeetingsQuery<'r> {
+    sort: Option<&'r str>,
+    /// Text that meeting names contain, ignoring case
+    q: Option<&'r str>,
+    limit: Option<u32>,
+    offset: Option<u32>,
+}
+
+/// Meetings in the user's chosen order, unless `sort` asks for another.
+/// Names sort for the language the browser prefers.
+/// Every meeting, up to `MAX_UNPAGED_MEETINGS`, unless `limit` or `offset` asks for a page,
+/// and `total` counts the meetings on all pages.
+#[get("/meetings?<query..>")]
+async fn get_meetings(
+    user: User,
+    client: Db,
+    _admitted: Admitted,
+    min_attendees: &State<MinAttendees>,
+    collation: RequestCollation,
+    query: MeetingsQuery<'_>,
+) -> ApiResult<Json<MeetingsMessage>> {
+    let search = query.q.map(str::trim).filter(|q| !q.is_empty());
+    if search.is_some_and(|q| q.len() > MAX_TEXT_LEN) {
+        return Err(ApiError::invalid(format!(
+            "a meeting search has at most {MAX_TEXT_LEN} bytes"
+        )));
+    }
+    let sort = match query.sort {
+        Some(name) => parse_meeting_sort(name).ok_or_else(|| {
+            ApiError::BadRequest(
+                ErrorCode::InvalidRequest,
+                format!("no meetings list order is called {name}"),
+            )
+        })?,
+        None => meeting_sort(&client, user.email()).await?,
+    };
+    let offset = query.offset.unwrap_or(0);
+    let limit = if query.limit.is_some() || query.offset.is_some() {
+        page_limit(query.limit)
+    } else {
+        MAX_UNPAGED_MEETINGS
+    };
+    let filter = MeetingsFilter {
+        search,
+        offset,
+        limit: Some(limit),
+        ..MeetingsFilter::all(sort)
+    };
+    let (meetings, total) =
+        scored_meetings(&client, user.email(), min_attendees, &collation.0, filter).await?;
+    let offset = offset.min(total);
+    Ok(MeetingsMessage {
+        meetings,
+        sort,
+        total,
+        offset,
+    }
+    .into())
+}
+
+/// One meeting as the meetings list gives it,
+/// for clients holding only part of a list too long to send whole
+#[get("/meetings/<id>")]
+async fn get_meeting(
+    user: User,
+    client: Db,
+    _admitted: Admitted,
+    min_attendees: &State<MinAttendees>,
+    id: u32,
+) -> ApiResult<Json<MeetingMessage>> {
+    let filter = MeetingsFilter {
+        id: Some(id),
+        ..MeetingsFilter::all(MeetingSort::Recent)
+    };
+    let (meetings, _) = scored_meetings(
+        &client,
+        user.email(),
+        min_attendees,
+        &Collation::default(),
+        filter,
+    )
+    .await?;
+    Ok(meetings
+        .into_iter()
+        .next()
+        .ok_or(ApiError::NotFound)?
+        .into())
+}
+
+// Pages follow the topics' order, highest score first,
+// so a cursor's key is the score of the topic before the page.
+const GET_USER_TOPICS: &str = "
+    select * from (
+        select topic, id, score,
+            (row_number() over (order by score desc, id) - 1)::int as position,
+            private, description
+        from user_topics where email = $1 and meeting is null
+    ) as t
+    where $4::bigint is null or score < $4 or (score = $4 and id > $5)
+    order by position
+    limit $2 offset $3
+";
+
+async fn user_topics(
+    client: &Client,
+    email: &str,
+    limit: Option<i64>,
+    offset: i64,
+    after: Option<Cursor>,
+) -> ApiResult<Vec<UserTopic>> {
+    let stmt = client.prepare(GET_USER_TOPICS).await?;
+    let key = after.map(|c| c.key);
+    let id = after.map(|c| c.id);
+    let rows = client
+        .query(&stmt, &[&email, &limit, &offset, &key, &id])
+        .await?;
+    let mut topics: Vec<_> = rows
+        .iter()
+        .map(|row| {
+            let text = row.get::<_, String>(0);
+            let id = row.get::<_, i64>(1);
+            let score = row.get::<_, i32>(2);
+            let position = row.get::<_, i32>(3);
+            assert_eq!(id as u32 as i64, id); // XXX: later maybe stringify this ID
+            UserTopic {
+                text,
+                score: score as u32,
+                id: id as u32,
+                seeded: false,
+                position: position as u32,
+                links: vec![],
+                proposers: vec![],
+                vetoed: false,
+                private: row.get(4),
+                description: row.get(5),
+            }
+        })
+        .collect();
+    attach_topic_links(client, &mut topics).await?;
+    Ok(topics)
+}
+
+/// Every topic unless `limit` or `after` asks for a page,
+/// and then the page's `next` cursor, passed back as `after`, gets the following page
+#[get("/user_topics?<limit>&<offset>&<after>")]
+async fn get_user_topics(
+    user: User,
+    client: Db,
+    _admitted: Admitted,
+    limit: Option<u32>,
+    offset: Option<u32>,
+    after: Option<&str>,
+) -> ApiResult<Json<UserTopicsMessage>> {
+    let after = after
+        .map(Cursor::decode)
+        .transpose()
+        .map_err(ApiError::invalid)?;
+    let limit = (limit.is_some() || after.is_some()).then(|| page_limit(limit));
+    let offset = offset.map_or(0, i64::from);
+    let fetch = limit.map(|limit| i64::from(limit) + 1);
+    let mut topics = user_topics(&client, user.email(), fetch, offset, after).await?;
+    let page = paginate(&mut topics, limit, |t| Cursor {
+        key: t.score.into(),
+        id: t.id.into(),
+    });
+    Ok(UserTopicsMessage { topics, page }.into())
+}
+
+/// Every event and channel combination, with defaults filled in,
+/// for the preferences UI and for dispatchers deciding whom to notify
+async fn notification_preferences(
+    client: &Client,
+    email: &str,
+) -> ApiResult<Vec<NotificationPreference>> {
+    let sql = "
+        select event, channel, enabled from notification_preferences
+        where email = $1
+    ";
+    let chosen: HashMap<_, _> = client
+        .query(sql, &[&email])
+        .await?
+        .into_iter()
+        .map(|row| {
+            (
+                (row.get::<_, String>(0), row.get::<_, String>(1)),
+                row.get::<_, bool>(2),
+            )
+        })
+        .collect();
+    let mut preferences = vec![];
+    for event in NotificationEvent::ALL {
+        for channel in NotificationChannel::ALL {
+            let key = (event.as_str().to_owned(), channel.as_str().to_owned());
+            preferences.push(NotificationPreference {
+                event,
+                channel,
+                enabled: *chosen
+                    .get(&key)
+                    .unwrap_or(&event.enabled_by_default(channel)),
+            });
+        }
+    }
+    Ok(preferences)
+}
+
+#[get("/preferences/notifications")]
+async fn get_notification_preferences(
+    user: User,
+    client: Db,
+) -> ApiResult<Json<NotificationPreferencesMessage>> {
+    let preferences = notification_preferences(&client, user.email()).await?;
+    Ok(NotificationPreferencesMessage { preferences }.into())
+}
+
+#[put("/preferences/notifications", format = "json", data = "<msg>")]
+async fn store_notification_preferences(
+    user: User,
+    client: Db,
+    msg: Json<NotificationPreferencesMessage>,
+) -> ApiResult<Json<NotificationPreferencesMessage>> {
+    let sql = "
+        insert into notification_preferences (email, event, channel, enabled)
+        values ($1, $2, $3, $4)
+        on conflict (email, event, channel) do update set enabled = excluded.enabled
+    ";
+    let stmt = client.prepare(sql).await?;
+    for p in msg.preferences.iter() {
+        client
+            .execute(
+                &stmt,
+                &[
+                    &user.email(),
+                    &p.event.as_str(),
+                    &p.channel.as_str(),
+                    &p.enabled,
+                ],
+            )
+            .await?;
+    }
+    let preferences = notification_preferences(&client, user.email()).await?;
+    Ok(NotificationPreferencesMessage { preferences }.into())
+}
+
+#[put("/preferences/meeting_sort", format = "json", data = "<msg>")]
+async fn store_meeting_sort(
+    user: User,
+    client: Db,
+    msg: Json<MeetingSortMessage>,
+) -> ApiResult<Json<MeetingSortMessage>> {
+    let sql = "
+        insert into user_preferences (email, meeting_sort) values ($1, $2)
+        on conflict (email) do update set meeting_sort = excluded.meeting_sort
+    ";
+    client
+        .execute(sql, &[&user.email(), &msg.sort.as_str()])
+        .await?;
+    Ok(msg)
+}
+
+#[get("/preferences/language")]
+async fn get_language(user: User, client: Db) -> ApiResult<Json<LanguageMessage>> {
+    let row = client
+        .query_opt(
+            "select language from user_preferences where email = $1",
+            &[&user.email()],
+        )
+        .await?;
+    let language = row.and_then(|row| row.get(0));
+    Ok(LanguageMessage { language }.into())
+}
+
+/// Stores the user's conversation language, by its language subtag alone,
+/// for meetings that match cohorts by language
+#[put("/preferences/language", format = "json", data = "<msg>")]
+async fn store_language(
+    user: User,
+    client: Db,
+    msg: Json<LanguageMessage>,
+) -> ApiResult<Json<LanguageMessage>> {
+    let language = match msg.language.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some(tag) => Some(conversation_language(tag).map_err(ApiError::invalid)?),
+    };
+    let sql = "
+        insert into user_preferences (email, language) values ($1, $2)
+        on conflict (email) do update set language = excluded.language
+    ";
+    client.execute(sql, &[&user.email(), &language]).await?;
+    Ok(LanguageMessage { language }.into())
+}
+
+#[get("/preferences/research")]
+async fn get_research_consent(user: User, client: Db) -> ApiResult<Json<ResearchConsentMessage>> {
+    let row = client
+        .query_opt(
+            "select research_consent from user_preferences where email = $1",
+            &[&user.email()],
+        )
+        .await?;
+    let consent = row.is_some_and(|row| row.get(0));
+    Ok(ResearchConsentMessage { consent }.into())
+}
+
+/// Stores whether the user's ballots may be exported, anonymized, for research.
+/// Withdrawing consent leaves their ballots out of every later export.
+#[put("/preferences/research", format = "json", data = "<msg>")]
+async fn store_research_consent(
+    user: User,
+    client: Db,
+    research: &State<Option<ResearchConfig>>,
+    msg: Json<ResearchConsentMessage>,
+) -> ApiResult<Json<ResearchConsentMessage>> {
+    if msg.consent {
+        configured_research(research)?;
+    }
+    let sql = "
+        insert into user_preferences (email, research_consent) values ($1, $2)
+        on conflict (email) do update set research_consent = excluded.research_consent
+    ";
+    client.execute(sql, &[&user.email(), &msg.consent]).await?;
+    Ok(msg)
+}
+
+#[get("/avatar")]
+async fn get_avatar(
+    user: User,
+    client: Db,
+    avatar_config: &State<AvatarConfig>,
+) -> ApiResult<Json<AvatarMessage>> {
+    let urls = avatars::urls(&client, avatar_config, &[user.email().to_owned()]).await?;
+    Ok(AvatarMessage {
+        url: urls.and_then(|mut urls| urls.pop().flatten()),
+        uploads: avatar_config.upload_directory.is_some(),
+    }
+    .into())
+}
+
+// The body is the image itself, with its type in the Content-Type header.
+#[put("/avatar", data = "<image>")]
+async fn put_avatar(
+    user: User,
+    client: Db,
+    avatar_config: &State<AvatarConfig>,
+    content_type: &ContentType,
+    image: rocket::Data<'_>,
+) -> ApiResult<Json<AvatarMessage>> {
+    let directory = avatar_config
+        .upload_directory
+        .as_deref()
+        .ok_or(ApiError::NotFound)?;
+    let content_type = format!("{}/{}", content_type.top(), content_type.sub());
+    if !AVATAR_CONTENT_TYPES.contains(&content_type.as_str()) {
+        return Err(ApiError::invalid(format!(
+            "avatars must be one of {}",
+            AVATAR_CONTENT_TYPES.join(", ")
+        )));
+    }
+    let image = image
+        .open(MAX_AVATAR_BYTES.bytes())
+        .into_bytes()
+        .await
+        .map_err(anyhow::Error::from)?;
+    if !image.is_complete() {
+        return Err(ApiError::invalid(format!(
+            "avatars must be at most {} KiB",
+            MAX_AVATAR_BYTES / 1024
+        )));
+    }
+    let url = avatars::store(&client, directory, user.email(), &content_type, &image).await?;
+    Ok(AvatarMessage {
+        url: Some(url),
+        uploads: true,
+    }
+    .into())
+}
+
+// Users with a Gravatar go back to showing it.
+#[delete("/avatar")]
+async fn delete_avatar(
+    user: User,
+    client: Db,
+    avatar_config: &State<AvatarConfig>,
+) -> ApiResult<Json<AvatarMessage>> {
+    let directory = avatar_config.upload_directory.as_deref();
+    avatars::remove(&client, directory, user.email()).await?;
+    get_avatar(user, client, avatar_config).await
+}
+
+// Only signed-in users see avatars, and only by the unguessable names uploads get.
+#[get("/avatars/<file>")]
+async fn get_avatar_image(
+    _user: User,
+    avatar_config: &State<AvatarConfig>,
+    file: &str,
+) -> ApiResult<(ContentType, Vec<u8>)> {
+    let (Some(directory), Some(content_type)) =
+        (&avatar_config.upload_directory, avatars::content_type(file))
+    else {
+        return Err(ApiError::NotFound);
+    };
+    let image = tokio::fs::read(directory.join(file))
+        .await
+        .map_err(|_| ApiError::NotFound)?;
+    let content_type = ContentType::parse_flexible(content_type).expect("a known image type");
+    Ok((content_type, image))
+}
+
+async fn attending_meetings(client: &Client, email: &str) -> ApiResult<Vec<u32>> {
+    let sql = "
+        select meeting from meeting_attendees
+        where email = $1
+    ";
+    let stmt = client.prepare(sql).await?;
+    let rows = client.query(&stmt, &[&email]).await?;
+    Ok(rows.iter().map(|row| row.get::<_, i64>(0) as u32).collect())
+}
+
+/// A consolidated snapshot lets a UI that slept or lost focus catch up at once.
+/// Election results are included for `meeting` when the user attends it.
+#[get("/sync?<meeting>")]
+async fn sync_state(
+    user: User,
+    client: Db,
+    min_attendees: &State<MinAttendees>,
+    round_minutes: &State<RoundMinutes>,
+    avatar_config: &State<AvatarConfig>,
+    collation: RequestCollation,
+    meeting: Option<u32>,
+) -> ApiResult<Json<SyncMessage>> {
+    let email = user.email();
+    let attending = attending_meetings(&client, email).await?;
+    let election = match meeting {
+        Some(id) if attending.contains(&id) => {
+            Some(election_results(&client, email, id, round_minutes, avatar_config).await?)
+        }
+        _ => None,
+    };
+    let sort = meeting_sort(&client, email).await?;
+    Ok(SyncMessage {
+        email: email.to_owned(),
+        attending_meetings: attending,
+        registered_meetings: registered_meetings(&client, email).await?,
+        meetings: scored_meetings(
+            &client,
+            email,
+            min_attendees,
+            &collation.0,
+            MeetingsFilter::all(sort),
+        )
+        .await?
+        .0,
+        sort,
+        user_topics: user_topics(&client, email, None, 0, None).await?,
+        election_results: election,
+    }
+    .into())
+}
+
+#[get("/user_id")]
+async fn get_user_id(user: User) -> Value {
+    json!({ "email": &(*user.email()), "is_admin": user.is_admin })
+}
+
+fn configured_backups(backups: &Option<Backups>) -> ApiResult<&Backups> {
+    backups.as_ref().ok_or_else(|| {
+        ApiError::Rejected(
+            Status::NotFound,
+            error_body(
+                ErrorCode::BackupsOff,
+                "backups need a [backup] table in the config file",
+            ),
+        )
+    })
+}
+
+fn configured_research(research: &Option<ResearchConfig>) -> ApiResult<&ResearchConfig> {
+    research.as_ref().ok_or_else(|| {
+        ApiError::Rejected(
+            Status::NotFound,
+            error_body(
+                ErrorCode::ResearchOff,
+                "research exports need a [research] table in the config file",
+            ),
+        )
+    })
+}
+
+// Admins get the details of failures, which other users never see.
+fn admin_failure(e: anyhow::Error) -> ApiError {
+    ApiError::Rejected(
+        Status::InternalServerError,
+        error_body(ErrorCode::InternalError, &format!("{e:#}")),
+    )
+}
+
+#[get("/admin/sql_log")]
+fn get_sql_log(_admin: AdminUser, sql_log: &State<SqlLog>) -> Value {
+    json!({ "secs_left": sql_log.remaining().map(|left| left.as_secs()) })
+}
+
+// Logs every statement requests run, with their parameters redacted,
+// until `minutes` or the `[sql_log]` table's default have passed.
+#[put("/admin/sql_log?<minutes>")]
+fn enable_sql_log(
+    admin: AdminUser,
+    sql_log: &State<SqlLog>,
+    log_emails: &State<LogEmails>,
+    minutes: Option<u32>,
+) -> Value {
+    let period = sql_log.enable(minutes);
+    tracing::warn!(
+        admin = %log_emails.show(admin.email()),
+        secs = period.as_secs(),
+        "SQL logging turned on"
+    );
+    json!({ "secs_left": period.as_secs() })
+}
+
+#[delete("/admin/sql_log")]
+fn disable_sql_log(
+    admin: AdminUser,
+    sql_log: &State<SqlLog>,
+    log_emails: &State<LogEmails>,
+) -> Value {
+    sql_log.disable();
+    tracing::warn!(admin = %log_emails.show(admin.email()), "SQL logging turned off");
+    json!({ "secs_left": null })
+}
+
+#[get("/admin/backups")]
+fn get_backups(
+    _admin: AdminUser,
+    backups: &State<Option<Backups>>,
+) -> ApiResult<Json<BackupStatus>> {
+    Ok(configured_backups(backups)?.status().into())
+}
+
+// Answers when the backup is done, so a scheduler can check the status code.
+#[post("/admin/backups")]
+async fn run_backup(_admin: AdminUser, backups: &State<Option<Backups>>) -> ApiResult<Value> {
+    match configured_backups(backups)?.run().await {
+        Some(Ok(name)) => Ok(json!({ "backup": name })),
+        Some(Err(e)) => Err(admin_failure(e)),
+        None => Err(ApiError::Conflict(
+            ErrorCode::BackupRunning,
+            "a backup is already running".to_owned(),
+        )),
+    }
+}
+
+// Answers once the import is queued, with its status to poll for progress.
+// Names already taken are caught here; the importer fails on any taken since.
+#[post("/admin/imports", data = "<import>")]
+async fn queue_history_import(
+    admin: AdminUser,
+    client: Db,
+    clock: &State<Clock>,
+    queue: &State<ImportQueue>,
+    import: Json<HistoryImport>,
+) -> ApiResult<Json<ImportStatus>> {
+    import.validate().map_err(ApiError::invalid)?;
+    let names: Vec<&str> = import.meetings.iter().map(|m| m.name.as_str()).collect();
+    let rows = client
+        .query("select name from meetings where name = any($1)", &[&names])
+        .await?;
+    if let Some(row) = rows.first() {
+        return Err(ApiError::Conflict(
+            ErrorCode::NameTaken,
+            format!("another meeting is named \"{}\"", row.get::<_, &str>(0)),
+        ));
+    }
+    let status = imports::queue(&client, clock, admin.email(), &import)
+        .await
+        .map_err(admin_failure)?;
+    queue.wake();
+    Ok(status.into())
+}
+
+// Every import, newest first
+#[get("/admin/imports")]
+async fn get_history_imports(_admin: AdminUser, client: Db) -> ApiResult<Json<ImportsMessage>> {
+    let imports = imports::statuses(&client, None).await?;
+    Ok(ImportsMessage { imports }.into())
+}
+
+// Only ballots of users who consented, with nothing that identifies them,
+// their meetings, or their topics
+#[get("/admin/research/ballots")]
+async fn get_research_ballots(
+    _admin: AdminUser,
+    client: Db,
+    _admitted: Admitted,
+    research: &State<Option<ResearchConfig>>,
+) -> ApiResult<Json<ResearchExport>> {
+    let config = configured_research(research)?;
+    Ok(research::export(&client, config).await?.into())
+}
+
+#[get("/admin/imports/<id>")]
+async fn get_history_import(
+    _admin: AdminUser,
+    client: Db,
+    id: u32,
+) -> ApiResult<Json<ImportStatus>> {
+    let status = imports::statuses(&client, Some(id as i64))
+        .await?
+        .into_iter()
+        .next()
+        .ok_or(ApiError::NotFound)?;
+    Ok(status.into())
+}
+
+// A failed import carries on from the meeting it stopped at,
+// once whatever stopped it, like a name taken since it was queued, is fixed.
+// Imports that haven't failed are left as they are.
+#[post("/admin/imports/<id>/resume")]
+async fn resume_history_import(
+    admin: AdminUser,
+    client: Db,
+    queue: &State<ImportQueue>,
+    id: u32,
+) -> ApiResult<Json<ImportStatus>> {
+    if imports::resume(&client, id as i64).await? {
+        queue.wake();
+    }
+    get_history_import(admin, client, id).await
+}
+
+// Only a mock clock moves on request; the system clock is not found here.
+#[post("/admin/clock/advance?<seconds>")]
+fn advance_clock(_admin: AdminUser, clock: &State<Clock>, seconds: u32) -> ApiResult<Value> {
+    let now = clock
+        .advance(Duration::seconds(seconds as i64))
+        .ok_or(ApiError::NotFound)?;
+    Ok(json!({ "now": now.unix_timestamp() }))
+}
+
+// Anonymized ballots and an independent recount, for checking the tally
+#[get("/admin/meetings/<id>/audit")]
+async fn get_election_audit(
+    _admin: AdminUser,
+    client: Db,
+    id: u32,
+) -> ApiResult<Json<ElectionAudit>> {
+    if !meeting_exists(&client, id as i64).await? {
+        return Err(ApiError::NotFound);
+    }
+    let audit = audit::audit_meeting(&client, id as i64)
+        .await
+        .map_err(admin_failure)?;
+    Ok(audit.into())
+}
+
+// A page of every meeting, newest first, whoever owns it
+#[get("/admin/meetings?<limit>&<offset>")]
+async fn get_admin_meetings(
+    admin: AdminUser,
+    client: Db,
+    _admitted: Admitted,
+    min_attendees: &State<MinAttendees>,
+    limit: Option<u32>,
+    offset: Option<u32>,
+) -> ApiResult<Json<AdminMeetingsMessage>> {
+    let rows = client
+        .query("select meeting from cohort_groups", &[])
+        .await?;
+    let started: HashSet<i64> = rows.iter().map(|row| row.get(0)).collect();
+    let filter = MeetingsFilter {
+        limit: Some(page_limit(limit)),
+        offset: offset.unwrap_or(0),
+        ..MeetingsFilter::all(MeetingSort::Recent)
+    };
+    let (meetings, total) = scored_meetings(
+        &client,
+        admin.email(),
+        min_attendees,
+        &Collation::default(),
+        filter,
+    )
+    .await?;
+    let offset = offset.unwrap_or(0).min(total);
+    let meetings = meetings
+        .into_iter()
+        .map(|mm| AdminMeeting {
+            started: started.contains(&(mm.meeting.id as i64)),
+            meeting: mm.meeting,
+            owner: mm.owner,
+        })
+        .collect();
+    Ok(AdminMeetingsMessage {
+        meetings,
+        total,
+        offset,
+    }
+    .into())
+}
+
+// A page of every account, by email address
+#[get("/admin/users?<limit>&<offset>")]
+async fn get_admin_users(
+    _admin: AdminUser,
+    client: Db,
+    _admitted: Admitted,
+    limit: Option<u32>,
+    offset: Option<u32>,
+) -> ApiResult<Json<UserAccountsMessage>> {
+    let offset = offset.unwrap_or(0);
+    let rows = client
+        .query(
+            "
+            select u.email, u.is_admin, d.email is not null
+            from users u
+            left join disabled_accounts d on d.email = u.email
+            order by u.email
+            limit $1 offset $2
+            ",
+            &[&i64::from(page_limit(limit)), &i64::from(offset)],
+        )
+        .await?;
+    let users = rows
+        .iter()
+        .map(|row| UserAccount {
+            email: row.get(0),
+            is_admin: row.get(1),
+            disabled: row.get(2),
+        })
+        .collect();
+    let total: i64 = client
+        .query_one("select count(*) from users", &[])
+        .await?
+        .get(0);
+    Ok(UserAccountsMessage {
+        users,
+        total: total as u32,
+        offset,
+    }
+    .into())
+}
+
+// The account and everything only it used go, like its topics, scores,
+// registrations, and preferences.  Meetings it owns stay, as do
+// records of past meetings, like cohort memberships and outcomes.
+#[delete("/admin/users/<email>")]
+async fn delete_admin_user(
+    admin: AdminUser,
+    mut client: Db,
+    avatar_config: &State<AvatarConfig>,
+    email: &str,
+) -> ApiResult<Value> {
+    if email == admin.email() {
+        return Err(ApiError::Conflict(
+            ErrorCode::OwnAccount,
+            "administrators cannot delete their own account".to_owned(),
+        ));
+    }
     let rows = client
-        .query(&stmt, &[&identifier, &user.email()])
-        .await
-        .unwrap();
-    if rows.len() == 1 {
-        println!("inserted meeting attendees");
-        let sql = "
-        insert into meeting_topics
-        (email, meeting, topic, score)
-        (
-            select $2 as email, $1 as meeting, id as topic, (row_number() over (order by random()) - 1) as score
-            from
-                (select row_number()
-                    over (partition by email order by score desc)
-                as r, t.* from user_topics t
-                    where t.email in
-                        (select distinct email from meeting_attendees
-                            where meeting = $1)
-                ) x
-            where x.r <= 3
-            order by random()
-        ) on conflict (email, meeting, topic) do nothing
-        ";
-        client
-            .execute(sql, &[&identifier, &user.email()])
-            .await
-            .unwrap();
-    } else {
-        println!("inserted no meeting attendees with {} rows", rows.len());
+        .query("select 1 from users where email = $1", &[&email])
+        .await?;
+    if rows.is_empty() {
+        return Err(ApiError::NotFound);
+    }
+    // The account goes whole or not at all, and its avatar's file once nothing names it.
+    let tx = client.transaction().await?;
+    let avatar = tx.query_opt(avatars::DELETE_AVATAR, &[&email]).await?;
+    for sql in [
+        "delete from topic_links where topic in (select id from user_topics where email = $1)",
+        "delete from topic_edits where topic in (select id from user_topics where email = $1)",
+        "delete from user_topics where email = $1",
+        "delete from user_topic_score_history where email = $1",
+        "delete from topic_submission_scores where email = $1",
+        "delete from attendee_topics where email = $1",
+        "delete from meeting_scores where email = $1",
+        "delete from meeting_participants where email = $1",
+        "delete from registration_answers where email = $1",
+        "delete from meeting_attendees where email = $1",
+        "delete from meeting_topics where email = $1",
+        "delete from meeting_votes where email = $1",
+        "delete from meeting_organizers where email = $1",
+        "delete from room_joins where email = $1",
+        "delete from contact_consents where email = $1",
+        "delete from notification_preferences where email = $1",
+        "delete from digest_deliveries where email = $1",
+        "delete from user_preferences where email = $1",
+        "delete from idempotency_keys where email = $1",
+        "delete from disabled_accounts where email = $1",
+        "delete from join_requests where email = $1",
+        "delete from invitations where email = $1",
+        "delete from users where email = $1",
+    ] {
+        tx.execute(sql, &[&email]).await?;
+    }
+    tx.commit().await?;
+    if let (Some(row), Some(directory)) = (avatar, avatar_config.upload_directory.as_deref()) {
+        avatars::remove_file(directory, row.get(0)).await;
     }
-    json!({ "attending": id })
+    Ok(json!({ "deleted": email }))
 }
 
-#[delete("/meetings/<id>")]
-async fn delete_meeting(_user: User, client: &State<sync::Arc<Client>>, id: u32) -> Value {
-    let identifier = id as i64;
-    client
-        .execute("delete from meetings where id = $1", &[&identifier])
-        .await
-        .unwrap();
-    json!({ "deleted": id })
+async fn account_disabled(client: &Client, email: &str) -> ApiResult<bool> {
+    let rows = client
+        .query(
+            "select 1 from disabled_accounts where lower(email) = lower($1)",
+            &[&email],
+        )
+        .await?;
+    Ok(!rows.is_empty())
 }
 
-#[delete("/topics/<id>")]
-async fn delete_topic(user: User, client: &State<sync::Arc<Client>>, id: u32) -> Value {
-    let identifier = id as i64;
+// Deactivating cancels the account's registrations and attendance
+// for meetings that have not started, which reactivating doesn't restore.
+// Cohorts of started meetings, and everything else the account has, stay.
+#[put("/admin/users/<email>/active", format = "json", data = "<msg>")]
+async fn set_account_active(
+    admin: AdminUser,
+    client: Db,
+    clock: &State<Clock>,
+    email: &str,
+    msg: Json<AccountActiveMessage>,
+) -> ApiResult<Value> {
+    let rows = client
+        .query("select 1 from users where email = $1", &[&email])
+        .await?;
+    if rows.is_empty() {
+        return Err(ApiError::NotFound);
+    }
+    if msg.active {
+        client
+            .execute("delete from disabled_accounts where email = $1", &[&email])
+            .await?;
+        return Ok(json!({ "email": email, "active": true }));
+    }
+    if email == admin.email() {
+        return Err(ApiError::Conflict(
+            ErrorCode::OwnAccount,
+            "administrators cannot deactivate their own account".to_owned(),
+        ));
+    }
     client
         .execute(
-            "delete from user_topics where id = $1 and email = $2",
-            &[&identifier, &user.email()],
+            "
+            insert into disabled_accounts (email, disabled_at, disabled_by)
+            values ($1, to_timestamp($2::bigint), $3)
+            on conflict (email) do nothing
+            ",
+            &[&email, &clock.unix_timestamp(), &admin.email()],
         )
-        .await
-        .unwrap();
-    json!({ "deleted": id })
-}
-
-#[put("/meeting/<id>/score", format = "json", data = "<score_msg>")]
-async fn store_meeting_score(
-    user: User,
-    client: &State<sync::Arc<Client>>,
-    id: u32,
-    score_msg: Json<ScoreMessage>,
-) -> Value {
-    let identifier = id as i64;
-    let score = score_msg.score as i32;
+        .await?;
     client
         .execute(
-            "insert into meeting_scores
-                (meeting, email, score)
-                values
-                ($1, $2, $3)
-            on conflict (meeting, email) do update
-                set score = excluded.score
+            "
+            delete from registration_answers
+            where email = $1 and field in (
+                select id from registration_fields
+                where meeting not in (select meeting from cohort_groups)
+            )
             ",
-            &[&identifier, &user.email(), &score],
+            &[&email],
         )
-        .await
-        .unwrap();
-    json!({ "stored": score })
+        .await?;
+    let registrations = client
+        .execute(
+            "
+            delete from meeting_participants
+            where email = $1 and meeting not in (select meeting from cohort_groups)
+            ",
+            &[&email],
+        )
+        .await?;
+    let attendance = client
+        .execute(
+            "
+            delete from meeting_attendees
+            where email = $1 and meeting not in (select meeting from cohort_groups)
+            ",
+            &[&email],
+        )
+        .await?;
+    Ok(json!({
+        "email": email,
+        "active": false,
+        "registrations_cancelled": registrations,
+        "attendance_removed": attendance,
+    }))
 }
 
-#[put("/meeting/<meeting_id>/vote")]
-async fn vote_for_meeting_topics(
-    user: User,
-    client: &State<sync::Arc<Client>>,
-    meeting_id: u32,
-) -> Value {
-    let m_id = meeting_id as i64;
-    let sql = "
-        update meeting_attendees
-        set voted = true
-        where meeting = $1 and email = $2
-    ";
-    client.execute(sql, &[&m_id, &user.email()]).await.unwrap();
-    json!({ "voted": meeting_id })
+// Invitations neither used nor expired, newest first
+#[get("/admin/invitations")]
+async fn get_invitations(
+    _admin: AdminUser,
+    client: Db,
+    clock: &State<Clock>,
+    mailer: &State<Option<sync::Arc<Mailer>>>,
+) -> ApiResult<Json<InvitationsMessage>> {
+    let base_url = mailer.as_ref().map(|m| m.base_url());
+    let invitations = membership::invitations(&client, base_url, clock.unix_timestamp()).await?;
+    Ok(InvitationsMessage { invitations }.into())
 }
 
-#[put(
-    "/meeting/<meeting_id>/topic/<topic_id>/score",
-    format = "json",
-    data = "<score_msg>"
-)]
-async fn store_meeting_topic_score(
-    user: User,
-    client: &State<sync::Arc<Client>>,
-    meeting_id: u32,
-    topic_id: u32,
-    score_msg: Json<ScoreMessage>,
-) -> Value {
-    let m_id = meeting_id as i64;
-    let t_id = topic_id as i64;
-    let score = score_msg.score as i32;
-    client
-        .execute(
-            "insert into meeting_topics
-                (meeting, email, topic, score)
-                values
-                ($1, $2, $3, $4)
-            on conflict (meeting, email, topic) do update
-                set score = excluded.score
-            ",
-            &[&m_id, &user.email(), &t_id, &score],
+// Emails the signup link when there's an [smtp] table,
+// and answers with it either way, for sharing another way.
+// Signing up with the link skips any approval.
+#[post("/admin/invitations", format = "json", data = "<msg>")]
+async fn invite_member(
+    admin: AdminUser,
+    client: Db,
+    clock: &State<Clock>,
+    membership_config: &State<MembershipConfig>,
+    mailer: &State<Option<sync::Arc<Mailer>>>,
+    msg: Json<NewInvitationMessage>,
+) -> ApiResult<Json<Invitation>> {
+    let email = msg.email.trim();
+    if email.is_empty() || !email.contains('@') || email.len() > MAX_TEXT_LEN {
+        return Err(ApiError::invalid(format!(
+            "{email:?} is not an email address"
+        )));
+    }
+    let rows = client
+        .query(
+            "select 1 from users where lower(email) = lower($1)",
+            &[&email],
         )
-        .await
-        .unwrap();
-    json!({ "stored": score })
+        .await?;
+    if !rows.is_empty() {
+        return Err(ApiError::Conflict(
+            ErrorCode::AccountExists,
+            format!("{email} already has an account"),
+        ));
+    }
+    let invitation = membership::invite(
+        &client,
+        mailer.as_ref().map(|m| m.base_url()),
+        email,
+        admin.email(),
+        clock.unix_timestamp(),
+        membership_config.invitation_days,
+    )
+    .await?;
+    if let Some(mailer) = mailer.inner() {
+        let notice = mail::invitation_notice(admin.email(), &invitation.url);
+        mailer.post(vec![email.to_owned()], notice);
+    }
+    Ok(invitation.into())
 }
 
-#[put("/topic/<topic_id>/score", format = "json", data = "<score_msg>")]
-async fn store_user_topic_score(
-    user: User,
-    client: &State<sync::Arc<Client>>,
-    topic_id: u32,
-    score_msg: Json<ScoreMessage>,
-) -> Value {
-    let t_id = topic_id as i64;
-    let score = score_msg.score as i32;
-    client
-        .execute(
-            "update user_topics
-             set score = $3
-             where email = $1 and id = $2
-            ",
-            &[&user.email(), &t_id, &score],
-        )
-        .await
-        .unwrap();
-    json!({ "stored": score })
+#[delete("/admin/invitations/<email>")]
+async fn revoke_invitation(_admin: AdminUser, client: Db, email: &str) -> ApiResult<Value> {
+    if membership::revoke(&client, email).await? == 0 {
+        return Err(ApiError::NotFound);
+    }
+    Ok(json!({ "revoked": email }))
 }
 
-const GET_SCORED_MEETINGS: &str = "
-    select
-        meetings.name,
-        meetings.id,
-        coalesce(meeting_scores.score,0) as score,
-        coalesce(r.n_registered,0) as n_registered,
-        coalesce(a.n_attending,0) as n_attending
-    from meetings
-    left outer join meeting_scores on meetings.id = meeting_scores.meeting
-    left join (
-        select meeting, count(email) as n_registered
-        from meeting_participants
-        group by meeting
-    ) r on meetings.id = r.meeting
-    left join (
-        select meeting, count(email) as n_attending
-        from meeting_attendees
-        group by meeting
-    ) a on meetings.id = a.meeting;
-";
+// Signups waiting for approval, oldest first
+#[get("/admin/join_requests")]
+async fn get_join_requests(_admin: AdminUser, client: Db) -> ApiResult<Json<JoinRequestsMessage>> {
+    let requests = membership::join_requests(&client).await?;
+    Ok(JoinRequestsMessage { requests }.into())
+}
 
-async fn get_meeting_topics_vec(
-    client: &State<sync::Arc<Client>>,
+// Approving lets the account log in.  Declining deletes it,
+// which it can't have used, so the address can sign up again.
+#[put("/admin/join_requests/<email>", format = "json", data = "<msg>")]
+async fn decide_join_request(
+    admin: AdminUser,
+    client: Db,
+    clock: &State<Clock>,
+    mailer: &State<Option<sync::Arc<Mailer>>>,
     email: &str,
-    meeting: i64,
-) -> Vec<UserTopic> {
-    if n_cohort_peers(client, meeting, email).await == 0 {
-        println!("XXXdebug: no cohort peers, so no topics");
-        return vec![];
+    msg: Json<JoinDecisionMessage>,
+) -> ApiResult<Value> {
+    let decided = membership::decide(
+        &client,
+        email,
+        msg.approved,
+        admin.email(),
+        clock.unix_timestamp(),
+    )
+    .await?;
+    if !decided {
+        return Err(ApiError::NotFound);
     }
-    let sql = "
-        select topic as text, m.id, m.score from user_topics u
-        right join
-        (select topic as id, score from meeting_topics
-        where meeting = $1 and meeting_topics.topic in (
-            select id from user_topics
-            where email in (select epeers($2, $1))
-        )) m
-        on u.id = m.id;
-    ";
-    let stmt = client.prepare(sql).await.unwrap();
-    let rows = client.query(&stmt, &[&meeting, &email]).await.unwrap();
-    rows.into_iter()
-        .map(|row| UserTopic {
-            text: row.get::<_, String>(0),
-            score: row.get::<_, i32>(2) as u32,
-            id: row.get::<_, i64>(1) as u32,
-        })
-        .collect()
+    if let Some(mailer) = mailer.inner() {
+        let notice = mail::join_decision_notice(msg.approved, mailer.base_url());
+        mailer.post(vec![email.to_owned()], notice);
+    }
+    Ok(json!({ "email": email, "approved": msg.approved }))
 }
 
-#[get("/meeting/<id>/topics")]
-async fn get_meeting_topics(
-    user: User,
-    client: &State<sync::Arc<Client>>,
-    id: u32,
-) -> Json<UserTopicsMessage> {
-    UserTopicsMessage {
-        topics: get_meeting_topics_vec(client, user.email(), id as i64).await,
-    }
-    .into()
+// Counts are for this API instance since it started.
+#[get("/admin/cohort_waits")]
+fn get_cohort_waits(
+    _admin: AdminUser,
+    cohort_waits: &State<CohortWaits>,
+) -> Json<CohortWaitReport> {
+    cohort_waits.report().into()
 }
 
-#[get("/registered_meetings")]
-async fn get_registered_meetings(
-    user: User,
-    client: &State<sync::Arc<Client>>,
-) -> Json<RegisteredMeetingsMessage> {
-    let stmt = client
-        .prepare(
-            "
-        select meeting from meeting_participants
-        where email = $1
-    ",
-        )
-        .await
-        .unwrap();
-    let rows = client.query(&stmt, &[&user.email()]).await.unwrap();
-    let meetings: Vec<_> = rows
-        .iter()
-        .map(|row| {
-            let id = row.get::<_, i64>(0);
-            assert_eq!(id as u32 as i64, id); // XXX: later maybe stringify this ID
-            id as u32
-        })
-        .collect();
-    RegisteredMeetingsMessage { meetings }.into()
+// Counts are for this API instance since it started.
+#[get("/admin/deprecations")]
+fn get_deprecations(
+    _admin: AdminUser,
+    deprecations: &State<Deprecations>,
+) -> Json<DeprecationReport> {
+    deprecations.report().into()
 }
 
-#[get("/meetings")]
-async fn get_meetings(_user: User, client: &State<sync::Arc<Client>>) -> Value {
-    let stmt = client.prepare(GET_SCORED_MEETINGS).await.unwrap();
-    let rows = client.query(&stmt, &[]).await.unwrap();
-    let meetings: Vec<_> = rows
-        .iter()
-        .map(|row| {
-            let name = row.get::<_, String>(0);
-            let id = row.get::<_, i64>(1);
-            let score = row.get::<_, i32>(2);
-            let n_registered = row.get::<_, i64>(3);
-            let n_attending = row.get::<_, i64>(4);
-            assert_eq!(id as u32 as i64, id); // XXX: later maybe stringify this ID
-            MeetingMessage {
-                meeting: Meeting {
-                    name,
-                    id: id as u32,
-                    n_registered: n_registered as u32,
-                    n_joined: n_attending as u32,
-                },
-                score: score as u32,
-            }
-        })
-        .collect();
-    json!({ "meetings": meetings })
+fn build_info() -> BuildInfo {
+    BuildInfo::new(
+        env!("EHALL_GIT_COMMIT"),
+        env!("EHALL_BUILD_DATE"),
+        &[
+            (env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")),
+            ("ehall-election", election::VERSION),
+        ],
+    )
 }
 
-#[get("/user_topics")]
-async fn get_user_topics(user: User, client: &State<sync::Arc<Client>>) -> Json<UserTopicsMessage> {
-    let stmt = client
-        .prepare(
-            "
-            select topic, id, score from user_topics where email = $1
-        ",
-        )
-        .await
-        .unwrap();
-    let rows = client.query(&stmt, &[&user.email()]).await.unwrap();
-    let topics: Vec<_> = rows
-        .iter()
-        .map(|row| {
-            let text = row.get::<_, String>(0);
-            let id = row.get::<_, i64>(1);
-            let score = row.get::<_, i32>(2);
-            assert_eq!(id as u32 as i64, id); // XXX: later maybe stringify this ID
-            UserTopic {
-                text,
-                score: score as u32,
-                id: id as u32,
-            }
-        })
-        .collect();
-    UserTopicsMessage { topics }.into()
+#[get("/instance_info")]
+fn get_instance_info(
+    asset_version: &State<AssetVersion>,
+    fault_injection: &State<FaultInjection>,
+    research: &State<Option<ResearchConfig>>,
+) -> Json<InstanceInfo> {
+    InstanceInfo {
+        schema_version: SCHEMA_VERSION,
+        asset_version: asset_version.0.clone(),
+        build: Some(build_info()),
+        fault_injection: fault_injection.0,
+        research: research.is_some(),
+    }
+    .into()
 }
 
-#[get("/user_id")]
-async fn get_user_id(user: User) -> Value {
-    json!({ "email": &(*user.email()) })
+#[get("/version")]
+fn get_version() -> Json<BuildInfo> {
+    build_info().into()
+}
+
+// Like tpt-update.sh, take the hash from the name of the bundle's JavaScript file.
+fn find_asset_version(static_path: &str) -> Option<String> {
+    fs::read_dir(static_path)
+        .ok()?
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .find_map(|name| {
+            name.strip_prefix("index-")?
+                .strip_suffix(".js")
+                .map(str::to_owned)
+        })
 }
 
-#[get("/show_all_users")]
+// Pages follow the accounts' IDs, so a cursor's key and ID are both the last one's.
+const GET_USERS_PAGE: &str = "
+    select * from users
+    where $1::int is null or id > $1
+    order by id
+    limit $2
+";
+
+/// A page of the accounts, with a link to the next
+#[get("/show_all_users?<limit>&<after>")]
 async fn show_all_users(
-    client: &State<sync::Arc<Client>>,
+    _admin: AdminUser,
+    client: Db,
+    _admitted: Admitted,
+    templates: &State<Templates>,
     user: Option<User>,
-) -> Result<Template, Error> {
-    let users: Vec<User> = client
-        .query("select * from users;", &[])
-        .await?
-        .into_iter()
-        .flat_map(TryInto::try_into)
-        .collect();
+    limit: Option<u32>,
+    after: Option<&str>,
+) -> ApiResult<Page> {
+    let after = after
+        .map(Cursor::decode)
+        .transpose()
+        .map_err(ApiError::invalid)?;
+    let limit = page_limit(limit);
+    let mut rows = client
+        .query(
+            GET_USERS_PAGE,
+            &[&after.map(|c| c.id as i32), &(i64::from(limit) + 1)],
+        )
+        .await?;
+    let page = paginate(&mut rows, Some(limit), |row| {
+        let id = row.get::<_, i32>("id").into();
+        Cursor { key: id, id }
+    });
+    let users: Vec<User> = rows.into_iter().flat_map(TryInto::try_into).collect();
 
-    Ok(Template::render(
+    Ok(templates.render(
         "users",
-        json!({"users": users, "user": user}),
+        json!({"users": users, "user": user, "next_page": page.next_query()}),
     ))
 }
 
@@ -893,79 +5732,304 @@ async fn show_all_users(
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
-    println!("reading config file: {}", cli.config_file.display());
-
-    let config: Config =
-        toml::from_str(&fs::read_to_string(cli.config_file).context("reading config file")?)
-            .context("parsing TOML config")?;
-    let (client, conn) = connect(
-        &format!(
-            "host=localhost user={} password={}",
-            config.postgres_user, config.postgres_password
-        ),
-        NoTls,
-    )
-    .await?;
+    let config = Config::load(cli.config_file.as_deref())?;
+    if let Some(Command::Config) = cli.command {
+        println!("{:#}", config.redacted());
+        return Ok(());
+    }
+    logging::init(&config.logging)?;
+    tracing::info!(build = %build_info().summary(), config_file = ?cli.config_file, "ehallway");
+    let pg_params = format!(
+        "host={} user={} password={}",
+        config.postgres_host, config.postgres_user, config.postgres_password
+    );
+    let clock = if config.mock_clock {
+        tracing::warn!("using a mock clock, which only moves when an admin advances it");
+        Clock::mock(OffsetDateTime::now_utc())
+    } else {
+        Clock::System
+    };
+    let backups = config.backup.map(|backup| {
+        Backups::new(
+            backup,
+            &config.postgres_host,
+            &config.postgres_user,
+            &config.postgres_password,
+            clock.clone(),
+        )
+    });
+    if let Some(Command::Backup) = cli.command {
+        let backups = backups.ok_or_else(|| anyhow!("no [backup] table in the config file"))?;
+        let name = backups.run().await.expect("nothing else is backing up")?;
+        println!("backed up to {name}");
+        return Ok(());
+    }
+    let (client, conn) = connect(&pg_params, NoTls).await?;
     let client = sync::Arc::new(client);
+    // rocket_auth keeps this connection, and handlers use the pool.
     let users: Users = client.clone().into();
+    let pool = db::pool(&pg_params, config.postgres_pool_size)?;
+    if let Some(directory) = &config.avatars.upload_directory {
+        fs::create_dir_all(directory)
+            .with_context(|| format!("creating avatar directory {}", directory.display()))?;
+    }
+    let asset_version = AssetVersion(find_asset_version(&config.static_path));
+    let min_attendees = MinAttendees(config.min_attendees.unwrap_or(COHORT_QUORUM as u32));
+    if min_attendees.0 < COHORT_QUORUM as u32 {
+        return Err(anyhow!(
+            "min_attendees must be at least the cohort quorum, {COHORT_QUORUM}"
+        ));
+    }
+    let round_minutes = RoundMinutes(config.round_minutes.unwrap_or(DEFAULT_ROUND_MINUTES));
+    let mut meeting_templates: Vec<MeetingTemplate> =
+        serde_json::from_str(BUILTIN_MEETING_TEMPLATES).context("parsing built-in templates")?;
+    meeting_templates.extend(config.meeting_templates);
+    for template in meeting_templates.iter() {
+        template
+            .config(&template.title)
+            .validate()
+            .map_err(|e| anyhow!("meeting template \"{}\": {e}", template.title))?;
+    }
+    let meeting_templates = MeetingTemplates(meeting_templates);
+    let deprecations = Deprecations::new(config.deprecations, clock.clone())?;
+    if asset_version.0.is_none() {
+        tracing::warn!("no UI bundle found in {}", config.static_path);
+    }
+    let templates = Templates::find(&rocket::Config::figment());
+    if !templates.available() {
+        tracing::warn!("{}", templates.remediation());
+    }
 
     tokio::spawn(async move {
         if let Err(e) = conn.await {
-            eprintln!("TokioPostgresError: {}", e);
+            tracing::error!("TokioPostgresError: {}", e);
         }
     });
+    // Startup work and commands run their queries as handlers do.
+    let pooled = Client::new(pool.get().await?);
+    if let Some(Command::Audit { meeting }) = cli.command {
+        let audit = audit::audit_meeting(&pooled, meeting as i64).await?;
+        println!("{}", serde_json::to_string_pretty(&audit)?);
+        return match audit.n_discrepancies() {
+            0 => Ok(()),
+            n => Err(anyhow!("the recount disagrees with the tally {n} times")),
+        };
+    }
     users.create_table().await?;
-    {
-        let client = client.clone();
-        for sql in CREATE_DB_ASSETS {
-            client.execute(sql, &[]).await?;
-        }
+    let applied = migrations::run(&pool).await?;
+    if !applied.is_empty() {
+        tracing::info!(?applied, "applied migrations");
+    }
+    backfill_topic_cohorts(&pooled).await?;
+    if cli.migrate_only {
+        return Ok(());
     }
-    let ignited = rocket::build()
+    if let Some(Command::Anonymize) = cli.command {
+        let retention = config
+            .retention
+            .ok_or_else(|| anyhow!("no [retention] table in the config file"))?;
+        let meetings = retention::anonymize_due(&pooled, &clock, &retention).await?;
+        println!("anonymized {} meetings: {meetings:?}", meetings.len());
+        return Ok(());
+    }
+    drop(pooled);
+    let meeting_events = events::listen(pg_params);
+    let mailer = config
+        .smtp
+        .as_ref()
+        .map(Mailer::new)
+        .transpose()?
+        .map(sync::Arc::new);
+    let jobs = Jobs::from_config(&config.jobs, &pool, &clock);
+    if let Some(mailer) = &mailer {
+        mail::spawn(mailer.clone(), pool.clone(), meeting_events.subscribe());
+        digest::register(
+            &jobs,
+            mailer.clone(),
+            pool.clone(),
+            clock.clone(),
+            config.digest,
+        );
+    }
+    let import_queue = ImportQueue::default();
+    imports::register(
+        &jobs,
+        pool.clone(),
+        clock.clone(),
+        import_queue.clone(),
+        config.imports,
+    );
+    voting::register(&jobs, pool.clone(), clock.clone());
+    if let Some(retention) = config.retention {
+        retention::register(&jobs, pool.clone(), clock.clone(), retention);
+    }
+    let mut app = rocket::build()
         .mount(
             "/",
             routes![
                 add_new_meeting,
+                add_meeting_organizer,
                 add_new_topic,
+                add_registration_fields,
+                advance_clock,
                 attend_meeting,
+                choose_my_topics,
                 delete,
+                decide_join_request,
+                delete_admin_user,
+                delete_avatar,
                 delete_meeting,
                 delete_topic,
+                disable_sql_log,
+                enable_sql_log,
+                export_election_results,
+                export_meeting_config,
+                export_meeting_summary,
+                export_registrations,
+                get_admin_meetings,
+                get_admin_users,
+                get_meeting_history,
+                get_meeting_organizers,
+                get_meeting_outcomes,
+                get_meeting_templates,
                 get_meeting_topics,
+                get_meeting,
                 get_meetings,
+                get_my_topics,
+                get_language,
+                get_notification_preferences,
                 get_registered_meetings,
+                get_room_joins,
+                get_topic_history,
                 get_user_topics,
                 get_user_id,
+                get_version,
                 get_login,
+                get_avatar,
+                get_avatar_image,
+                get_backups,
+                get_candidate_topics,
+                get_cohort_waits,
+                get_deprecations,
+                get_election_audit,
                 get_election_results,
+                get_history_import,
+                get_history_imports,
+                get_instance_info,
+                get_invitations,
+                get_join_requests,
+                get_research_ballots,
+                get_research_consent,
                 get_signup,
+                get_sql_log,
+                get_submitted_topics,
+                get_topic_edits,
+                healthz,
+                import_meeting_config,
                 index,
+                invite_member,
+                join_by_code,
+                join_meeting,
+                join_meeting_confirmed,
+                joined_room,
                 leave_meeting,
                 logout,
+                meeting_events,
+                meeting_qr_code,
                 meeting_register,
+                merge_meeting_topics,
+                next_round,
                 post_login,
                 post_signup,
+                put_avatar,
+                queue_history_import,
+                ready,
+                readyz,
+                record_outcome,
+                remove_meeting_organizer,
+                rename_meeting,
+                resume_history_import,
+                retract_vote,
+                revoke_invitation,
+                run_backup,
+                seed_meeting_topics,
+                set_account_active,
+                set_registration_deadline,
+                set_submission_deadline,
+                set_topic_description,
+                set_topic_links,
+                set_topic_private,
+                set_voting_minutes,
+                share_contact,
                 start_meeting,
+                store_language,
                 store_meeting_score,
+                store_meeting_sort,
                 store_meeting_topic_score,
+                store_meeting_topic_scores,
+                store_notification_preferences,
+                store_research_consent,
+                store_submission_scores,
                 store_user_topic_score,
+                store_user_topic_scores,
                 show_all_users,
+                split_meeting_topic,
+                submit_meeting_topic,
+                sync_state,
+                veto_meeting_topic,
                 vote_for_meeting_topics
             ],
         )
         .mount("/", FileServer::from(config.static_path))
-        .manage(client)
+        .manage(pool)
         .manage(users)
-        .attach(Template::fairing())
-        .ignite()
-        .await;
+        .manage(asset_version)
+        .manage(StartedAt(Instant::now()))
+        .manage(min_attendees)
+        .manage(round_minutes)
+        .manage(meeting_templates)
+        .manage(meeting_events)
+        .manage(config.captcha)
+        .manage(backups)
+        .manage(LogEmails::new(config.log_privacy.as_ref()))
+        .manage(CohortWaits::new(&config.polling, &clock))
+        .manage(clock)
+        .manage(import_queue)
+        .manage(config.avatars)
+        .manage(config.membership)
+        .manage(mailer)
+        .manage(FaultInjection(config.faults.is_some()))
+        .manage(config.research)
+        .manage(SqlLog::new(&config.sql_log))
+        .manage(Limiter::new(&config.limits))
+        // First, so the request ID and load fairings see injected faults
+        .attach(FaultFairing::new(config.faults))
+        .attach(RequestIdFairing)
+        .attach(LoadFairing::new(config.polling))
+        .attach(DeprecationFairing)
+        .attach(jobs);
+    // A misspelled route name would quietly never match.
+    for name in deprecations.route_names() {
+        if !app
+            .routes()
+            .any(|route| route.name.as_deref() == Some(name.as_str()))
+        {
+            return Err(anyhow!("deprecations: no route is named {name}"));
+        }
+    }
+    app = app.manage(deprecations);
+    // Without its templates, the fairing would abort the launch.
+    if templates.available() {
+        app = app.attach(Template::fairing());
+    }
+    let ignited = app.manage(templates).ignite().await;
     match ignited {
         Ok(ignited) => {
             let _app = ignited.launch().await?;
         }
         Err(e) => {
             if let rocket::error::ErrorKind::Collisions(c) = e.kind() {
-                println!("collisions:{:?}", c);
+                tracing::error!("collisions:{:?}", c);
             }
             return Err(e.into());
         }
@@ -0,0 +1,234 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use tokio_postgres::Error;
+
+use ehall::{Invitation, JoinRequest};
+
+use crate::db::Client;
+
+/// The optional `[membership]` table of the config file.
+/// Without it, anyone can sign up, and invitations only save typing the address.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MembershipConfig {
+    /// Signups without an invitation wait for an administrator's approval
+    #[serde(default)]
+    pub approval: bool,
+    /// Days an invitation can be used
+    #[serde(default = "default_invitation_days")]
+    pub invitation_days: u32,
+}
+
+fn default_invitation_days() -> u32 {
+    14
+}
+
+impl Default for MembershipConfig {
+    fn default() -> Self {
+        MembershipConfig {
+            approval: false,
+            invitation_days: default_invitation_days(),
+        }
+    }
+}
+
+/// The signup link for an invitation,
+/// absolute when the `[smtp]` table gives the site's address
+pub fn invitation_url(base_url: Option<&str>, token: &str) -> String {
+    format!(
+        "{}/signup?invitation={token}",
+        base_url.unwrap_or_default().trim_end_matches('/')
+    )
+}
+
+const INVITE: &str = "
+    insert into invitations (token, email, invited_by, created_at, expires_at)
+    values ($1, $2, $3, to_timestamp($4::bigint),
+        to_timestamp($4::bigint) + make_interval(days => $5))
+    returning extract(epoch from created_at)::bigint, extract(epoch from expires_at)::bigint
+";
+
+pub async fn invite(
+    client: &Client,
+    base_url: Option<&str>,
+    email: &str,
+    invited_by: &str,
+    now: i64,
+    days: u32,
+) -> Result<Invitation, Error> {
+    let token = format!("{:032x}", rand::thread_rng().gen::<u128>());
+    let row = client
+        .query_one(INVITE, &[&token, &email, &invited_by, &now, &(days as i32)])
+        .await?;
+    Ok(Invitation {
+        email: email.to_owned(),
+        invited_by: invited_by.to_owned(),
+        url: invitation_url(base_url, &token),
+        created_at: row.get(0),
+        expires_at: row.get(1),
+    })
+}
+
+/// Invitations neither used nor expired at `now`, newest first
+pub async fn invitations(
+    client: &Client,
+    base_url: Option<&str>,
+    now: i64,
+) -> Result<Vec<Invitation>, Error> {
+    let sql = "
+        select token, email, invited_by,
+            extract(epoch from created_at)::bigint, extract(epoch from expires_at)::bigint
+        from invitations
+        where accepted_at is null and expires_at > to_timestamp($1::bigint)
+        order by created_at desc
+    ";
+    let rows = client.query(sql, &[&now]).await?;
+    Ok(rows
+        .iter()
+        .map(|row| Invitation {
+            email: row.get(1),
+            invited_by: row.get(2),
+            url: invitation_url(base_url, row.get(0)),
+            created_at: row.get(3),
+            expires_at: row.get(4),
+        })
+        .collect())
+}
+
+/// Withdraws `email`'s unused invitations, returning how many there were
+pub async fn revoke(client: &Client, email: &str) -> Result<u64, Error> {
+    client
+        .execute(
+            "delete from invitations where lower(email) = lower($1) and accepted_at is null",
+            &[&email],
+        )
+        .await
+}
+
+/// Who the invitation `token` is for, if it can still be used at `now`
+pub async fn invited_email(
+    client: &Client,
+    token: &str,
+    now: i64,
+) -> Result<Option<String>, Error> {
+    let sql = "
+        select email from invitations
+        where token = $1 and accepted_at is null and expires_at > to_timestamp($2::bigint)
+    ";
+    Ok(client
+        .query_opt(sql, &[&token, &now])
+        .await?
+        .map(|row| row.get(0)))
+}
+
+/// Uses up the invitation `token` if it's for `email` and can still be used at `now`,
+/// returning whether it was
+pub async fn accept(client: &Client, token: &str, email: &str, now: i64) -> Result<bool, Error> {
+    let sql = "
+        update invitations set accepted_at = to_timestamp($3::bigint)
+        where token = $1 and lower(email) = lower($2)
+            and accepted_at is null and expires_at > to_timestamp($3::bigint)
+    ";
+    Ok(client.execute(sql, &[&token, &email, &now]).await? == 1)
+}
+
+/// Holds `email`'s new account until an administrator decides on it
+pub async fn request_join(client: &Client, email: &str, now: i64) -> Result<(), Error> {
+    let sql = "
+        insert into join_requests (email, requested_at)
+        values ($1, to_timestamp($2::bigint))
+        on conflict (email) do update
+            set requested_at = excluded.requested_at,
+                decided_by = null, decided_at = null, approved = null
+    ";
+    client.execute(sql, &[&email, &now]).await?;
+    Ok(())
+}
+
+/// Whether `email`'s account is waiting for approval, so it can't log in
+pub async fn join_pending(client: &Client, email: &str) -> Result<bool, Error> {
+    let rows = client
+        .query(
+            "select 1 from join_requests where lower(email) = lower($1) and approved is null",
+            &[&email],
+        )
+        .await?;
+    Ok(!rows.is_empty())
+}
+
+/// Requests waiting for a decision, oldest first
+pub async fn join_requests(client: &Client) -> Result<Vec<JoinRequest>, Error> {
+    let sql = "
+        select email, extract(epoch from requested_at)::bigint
+        from join_requests
+        where approved is null
+        order by requested_at
+    ";
+    let rows = client.query(sql, &[]).await?;
+    Ok(rows
+        .iter()
+        .map(|row| JoinRequest {
+            email: row.get(0),
+            requested_at: row.get(1),
+        })
+        .collect())
+}
+
+/// Records the decision on `email`'s waiting request, returning whether there was one.
+/// A declined account never logged in, so it has nothing else
+/// and is deleted, letting the address sign up again.
+pub async fn decide(
+    client: &Client,
+    email: &str,
+    approved: bool,
+    admin: &str,
+    now: i64,
+) -> Result<bool, Error> {
+    let sql = "
+        update join_requests
+        set approved = $2, decided_by = $3, decided_at = to_timestamp($4::bigint)
+        where email = $1 and approved is null
+    ";
+    if client
+        .execute(sql, &[&email, &approved, &admin, &now])
+        .await?
+        == 0
+    {
+        return Ok(false);
+    }
+    if !approved {
+        client
+            .execute("delete from users where email = $1", &[&email])
+            .await?;
+    }
+    Ok(true)
+}
+
+/// Who hears about new join requests
+pub async fn admin_emails(client: &Client) -> Result<Vec<String>, Error> {
+    let rows = client
+        .query("select email from users where is_admin order by email", &[])
+        .await?;
+    Ok(rows.iter().map(|row| row.get(0)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{invitation_url, MembershipConfig};
+
+    #[test]
+    fn test_membership_config() {
+        let config: MembershipConfig = toml::from_str("approval = true").unwrap();
+        assert!(config.approval);
+        assert_eq!(config.invitation_days, 14);
+        assert!(!MembershipConfig::default().approval);
+    }
+
+    #[test]
+    fn test_invitation_url() {
+        assert_eq!(
+            invitation_url(Some("https://hallway.example.com/"), "abc"),
+            "https://hallway.example.com/signup?invitation=abc"
+        );
+        assert_eq!(invitation_url(None, "abc"), "/signup?invitation=abc");
+    }
+}
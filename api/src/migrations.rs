@@ -0,0 +1,199 @@
+use std::collections::HashSet;
+
+use anyhow::{Context, Result};
+use deadpool_postgres::Pool;
+use tokio_postgres::Error;
+
+/// A schema change in `api/migrations`, applied once, in order of `version`
+pub struct Migration {
+    pub version: u32,
+    pub file: &'static str,
+    sql: &'static str,
+}
+
+macro_rules! migrations {
+    ($($version:literal => $file:literal,)*) => {
+        [$(Migration {
+            version: $version,
+            file: $file,
+            sql: include_str!(concat!("../migrations/", $file)),
+        },)*]
+    };
+}
+
+// Migrations are never edited once released; a change to the schema gets a new one.
+// Those up to 68 ran on every start before migrations were versioned,
+// so each makes its change only if it isn't already made.
+pub const MIGRATIONS: [Migration; 81] = migrations![
+    1 => "0001_function_n_cohort_peers.sql",
+    2 => "0002_function_epeers.sql",
+    3 => "0003_create_cohort_groups.sql",
+    4 => "0004_index_cohort_groups_meeting_idx.sql",
+    5 => "0005_create_cohort_members.sql",
+    6 => "0006_index_cohort_members_email_idx.sql",
+    7 => "0007_create_meeting_topics.sql",
+    8 => "0008_index_meeting_topics_idx.sql",
+    9 => "0009_add_meeting_topics_cohort.sql",
+    10 => "0010_create_meeting_votes.sql",
+    11 => "0011_add_meeting_votes_cohort.sql",
+    12 => "0012_index_meeting_votes_cohort_idx.sql",
+    13 => "0013_create_meetings.sql",
+    14 => "0014_create_meeting_attendees.sql",
+    15 => "0015_create_meeting_participants.sql",
+    16 => "0016_create_meeting_scores.sql",
+    17 => "0017_index_user_mtg_attendee_idx.sql",
+    18 => "0018_create_user_topics.sql",
+    19 => "0019_index_user_mtg_score_idx.sql",
+    20 => "0020_create_seed_topics.sql",
+    21 => "0021_create_notification_preferences.sql",
+    22 => "0022_create_user_preferences.sql",
+    23 => "0023_create_user_topic_score_history.sql",
+    24 => "0024_index_user_topic_score_history_idx.sql",
+    25 => "0025_create_registration_fields.sql",
+    26 => "0026_create_idempotency_keys.sql",
+    27 => "0027_create_registration_answers.sql",
+    28 => "0028_add_meetings_registration_deadline.sql",
+    29 => "0029_create_room_joins.sql",
+    30 => "0030_create_topic_links.sql",
+    31 => "0031_add_meetings_cohort_size.sql",
+    32 => "0032_add_meetings_owner.sql",
+    33 => "0033_create_meeting_organizers.sql",
+    34 => "0034_add_meetings_veto_policy.sql",
+    35 => "0035_add_meeting_topics_vetoed.sql",
+    36 => "0036_index_meeting_topics_veto_idx.sql",
+    37 => "0037_add_meeting_votes_vetoed.sql",
+    38 => "0038_add_meetings_voting_method.sql",
+    39 => "0039_create_cohort_outcomes.sql",
+    40 => "0040_create_cohort_outcome_topics.sql",
+    41 => "0041_add_meetings_n_winners.sql",
+    42 => "0042_create_topic_aliases.sql",
+    43 => "0043_add_user_topics_private.sql",
+    44 => "0044_add_user_topics_description.sql",
+    45 => "0045_create_election_results.sql",
+    46 => "0046_create_election_result_topics.sql",
+    47 => "0047_add_cohort_groups_started_at.sql",
+    48 => "0048_add_meetings_anonymized_at.sql",
+    49 => "0049_create_disabled_accounts.sql",
+    50 => "0050_function_new_join_code.sql",
+    51 => "0051_add_meetings_join_code.sql",
+    52 => "0052_fill_meetings_join_code.sql",
+    53 => "0053_default_meetings_join_code.sql",
+    54 => "0054_create_notification_deliveries.sql",
+    55 => "0055_add_cohort_groups_round.sql",
+    56 => "0056_create_past_cohort_members.sql",
+    57 => "0057_add_election_results_round.sql",
+    58 => "0058_extension_pg_trgm.sql",
+    59 => "0059_index_meetings_name_trgm_idx.sql",
+    60 => "0060_add_meetings_match_language.sql",
+    61 => "0061_default_user_preferences_meeting_sort.sql",
+    62 => "0062_add_user_preferences_language.sql",
+    63 => "0063_add_meetings_submission_deadline.sql",
+    64 => "0064_add_user_topics_meeting.sql",
+    65 => "0065_create_topic_submission_scores.sql",
+    66 => "0066_create_history_imports.sql",
+    67 => "0067_function_election_status.sql",
+    68 => "0068_create_topic_edits.sql",
+    69 => "0069_create_user_avatars.sql",
+    70 => "0070_add_meetings_topics_per_attendee.sql",
+    71 => "0071_create_attendee_topics.sql",
+    72 => "0072_create_invitations.sql",
+    73 => "0073_create_join_requests.sql",
+    74 => "0074_add_meetings_created_at.sql",
+    75 => "0075_create_digest_deliveries.sql",
+    76 => "0076_add_meeting_attendees_role.sql",
+    77 => "0077_add_meetings_share_contacts.sql",
+    78 => "0078_create_contact_consents.sql",
+    79 => "0079_add_voting_deadlines.sql",
+    80 => "0080_add_meeting_attendees_attended_at.sql",
+    81 => "0081_add_user_preferences_research_consent.sql",
+];
+
+const CREATE_SCHEMA_MIGRATIONS: &str = "
+    create table if not exists schema_migrations (
+        version int primary key,
+        file varchar (254) not null,
+        applied_at timestamptz not null default now()
+    )
+";
+
+// Held by the transaction applying a migration, so API instances starting together
+// apply each one once.  The key is arbitrary but shouldn't change.
+const LOCK: &str = "select pg_advisory_xact_lock(7366827104193045)";
+
+async fn applied_versions(client: &tokio_postgres::Client) -> Result<HashSet<i32>, Error> {
+    let rows = client
+        .query("select version from schema_migrations", &[])
+        .await?;
+    Ok(rows.iter().map(|row| row.get(0)).collect())
+}
+
+/// Applies the migrations the database hasn't had, returning their versions.
+/// Each is applied in a transaction with its record in `schema_migrations`.
+pub async fn run(pool: &Pool) -> Result<Vec<u32>> {
+    let mut client = pool.get().await?;
+    client.batch_execute(CREATE_SCHEMA_MIGRATIONS).await?;
+    let done = applied_versions(&client).await?;
+    let mut applied = vec![];
+    for migration in MIGRATIONS.iter() {
+        let version = migration.version as i32;
+        if done.contains(&version) {
+            continue;
+        }
+        let tx = client.transaction().await?;
+        tx.batch_execute(LOCK).await?;
+        // Another instance may have applied it while this one waited for the lock.
+        let taken = tx
+            .query_opt(
+                "select 1 from schema_migrations where version = $1",
+                &[&version],
+            )
+            .await?;
+        if taken.is_none() {
+            tx.batch_execute(migration.sql)
+                .await
+                .with_context(|| format!("applying migration {}", migration.file))?;
+            tx.execute(
+                "insert into schema_migrations (version, file) values ($1, $2)",
+                &[&version, &migration.file],
+            )
+            .await?;
+            applied.push(migration.version);
+        }
+        tx.commit().await?;
+    }
+    Ok(applied)
+}
+
+/// How many migrations this build has that the database hasn't had,
+/// which is all of them before any have run
+pub async fn n_pending(client: &tokio_postgres::Client) -> Result<usize, Error> {
+    let exists = client
+        .query_one("select to_regclass('schema_migrations') is not null", &[])
+        .await?
+        .get::<_, bool>(0);
+    let done = if exists {
+        applied_versions(client).await?
+    } else {
+        HashSet::new()
+    };
+    Ok(MIGRATIONS
+        .iter()
+        .filter(|m| !done.contains(&(m.version as i32)))
+        .count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MIGRATIONS;
+
+    #[test]
+    fn test_migrations_in_order() {
+        for (i, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.version as usize, i + 1);
+            let prefix = format!("{:04}_", migration.version);
+            assert!(migration.file.starts_with(&prefix), "{}", migration.file);
+            assert!(migration.file.ends_with(".sql"), "{}", migration.file);
+            assert!(!migration.sql.trim().is_empty(), "{}", migration.file);
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use std::path::PathBuf;
+
+use rocket::figment::{value::magic::RelativePathBuf, Figment};
+use rocket::http::RawStr;
+use rocket::response::content::RawHtml;
+use rocket::Responder;
+use rocket_dyn_templates::Template;
+use serde_json::Value;
+
+// As in rocket_dyn_templates, which reads the same setting.
+const DEFAULT_TEMPLATE_DIR: &str = "templates";
+const EXTENSION: &str = ".html.tera";
+// The templates the routes render, and the base they all extend.
+const REQUIRED: [&str; 8] = [
+    "base", "deleted", "index", "login", "logout", "meeting", "signup", "users",
+];
+
+/// Which of the Tera templates were missing at startup
+pub struct Templates {
+    pub dir: PathBuf,
+    pub missing: Vec<&'static str>,
+}
+
+/// A page rendered from its template, or the built-in fallback
+#[derive(Responder)]
+pub enum Page {
+    Rendered(Template),
+    Fallback(RawHtml<String>),
+}
+
+impl Templates {
+    pub fn find(figment: &Figment) -> Self {
+        let dir = figment
+            .extract_inner::<RelativePathBuf>("template_dir")
+            .map(|dir| dir.relative())
+            .unwrap_or_else(|_| DEFAULT_TEMPLATE_DIR.into());
+        let missing = REQUIRED
+            .into_iter()
+            .filter(|name| !dir.join(format!("{name}{EXTENSION}")).is_file())
+            .collect();
+        Templates { dir, missing }
+    }
+
+    pub fn available(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    /// What an operator can do about missing templates
+    pub fn remediation(&self) -> String {
+        let mut hints = format!(
+            "missing templates in {}: {}",
+            self.dir.display(),
+            self.missing.join(", ")
+        );
+        if self.missing.contains(&"base") {
+            hints.push_str(
+                "\n  base.html.tera is generated: run tpt-update.sh after building the UI",
+            );
+        }
+        if !self.dir.is_dir() {
+            hints.push_str(
+                "\n  start the server from the api directory, \
+                or set ROCKET_TEMPLATE_DIR to the api/templates path",
+            );
+        }
+        hints.push_str("\n  serving built-in fallback pages until then");
+        hints
+    }
+
+    pub fn render(&self, name: &'static str, context: Value) -> Page {
+        if self.available() {
+            Page::Rendered(Template::render(name, context))
+        } else {
+            Page::Fallback(fallback(name, &context))
+        }
+    }
+}
+
+fn escape(text: &str) -> String {
+    RawStr::new(text).html_escape().into_owned()
+}
+
+// Links that return to `next` after logging in or signing up
+fn with_next(path: &str, context: &Value) -> String {
+    match context["next"].as_str() {
+        Some(next) => format!("{path}?next={}", RawStr::new(next).percent_encode()),
+        None => path.to_owned(),
+    }
+}
+
+fn credentials_form(action: &str, extra: &str, button: &str) -> String {
+    format!(
+        r#"<form action="{}" method="post">
+<p><label>Email <input type="email" name="email" required></label></p>
+<p><label>Password <input type="password" name="password" required></label></p>
+{extra}<button type="submit">{button}</button>
+</form>"#,
+        escape(action)
+    )
+}
+
+// Loads the UI bundle the way base.html.tera does, minus the service worker.
+fn app_script(context: &Value) -> String {
+    let version = match context["asset_version"].as_str() {
+        Some(version) => version,
+        None => return "".to_owned(),
+    };
+    let mut globals = format!(
+        "var elc_global = {{ asset_version: {} }};",
+        Value::from(version)
+    );
+    if let Some(email) = context["user"]["email"].as_str() {
+        globals.push_str(&format!(" elc_global.user_email = {};", Value::from(email)));
+    }
+    format!(
+        r#"<div id="vhallway"></div>
+<script>{}</script>
+<script type="module">import init from '/index-{version}.js';init('/index-{version}_bg.wasm');</script>"#,
+        globals.replace("</", "<\\/")
+    )
+}
+
+fn fallback(name: &str, context: &Value) -> RawHtml<String> {
+    let body = match name {
+        "index" => {
+            let links = match context["user"]["email"].as_str() {
+                Some(email) => format!(
+                    r#"<p>Logged in as {}. <a href="/logout">Log out</a></p>"#,
+                    escape(email)
+                ),
+                None => r#"<p><a href="/login">Log in</a> or <a href="/signup">sign up</a>.</p>"#
+                    .to_owned(),
+            };
+            format!("{links}\n{}", app_script(context))
+        }
+        "login" => {
+            let disabled = if context["disabled"].as_bool().unwrap_or(false) {
+                "<p>This account is deactivated. Ask an administrator to reactivate it.</p>\n"
+            } else {
+                ""
+            };
+            let pending = if context["pending"].as_bool().unwrap_or(false) {
+                "<p>Thanks for signing up. You can log in once an administrator approves your account.</p>\n"
+            } else {
+                ""
+            };
+            format!(
+                "{disabled}{pending}{}\n<p>No account yet? <a href=\"{}\">Sign up</a></p>",
+                credentials_form(&with_next("/login", context), "", "Log in"),
+                escape(&with_next("/signup", context))
+            )
+        }
+        "signup" => {
+            let failed = if context["captcha_failed"].as_bool().unwrap_or(false) {
+                "<p>The CAPTCHA check failed. Please try again.</p>\n"
+            } else {
+                ""
+            };
+            let captcha = &context["captcha"];
+            let widget = match (captcha["class"].as_str(), captcha["site_key"].as_str()) {
+                (Some(class), Some(site_key)) => format!(
+                    "<p><div class=\"{}\" data-sitekey=\"{}\"></div></p>\n\
+                    <script src=\"{}\" async defer></script>\n",
+                    escape(class),
+                    escape(site_key),
+                    escape(captcha["script_url"].as_str().unwrap_or_default())
+                ),
+                _ => "".to_owned(),
+            };
+            let invitation = match context["invitation"].as_str() {
+                Some(token) => format!(
+                    "<input type=\"hidden\" name=\"invitation\" value=\"{}\">\n",
+                    escape(token)
+                ),
+                None => "".to_owned(),
+            };
+            format!(
+                "{failed}{}",
+                credentials_form(
+                    &with_next("/signup", context),
+                    &format!("{invitation}{widget}"),
+                    "Sign up"
+                )
+            )
+        }
+        "logout" => r#"<p>You have logged out. <a href="/login">Log in</a></p>"#.to_owned(),
+        "deleted" => "<p>Your account has been deleted.</p>".to_owned(),
+        "meeting" => {
+            let next = context["next"].as_str().unwrap_or_default();
+            let action = match context["notice"].as_str() {
+                Some(notice) => format!("<p>{}</p>", escape(notice)),
+                None if context["logged_in"].as_bool().unwrap_or(false) => format!(
+                    r#"<form method="post" action="{}"><button type="submit">Join</button></form>"#,
+                    escape(next)
+                ),
+                None => format!(
+                    r#"<p><a href="{}">Log in to join</a> or <a href="{}">sign up</a>.</p>"#,
+                    escape(&with_next("/login", context)),
+                    escape(&with_next("/signup", context))
+                ),
+            };
+            format!(
+                "<h2>{}</h2>\n<p>{}</p>\n{action}",
+                escape(context["name"].as_str().unwrap_or_default()),
+                escape(context["description"].as_str().unwrap_or_default()),
+            )
+        }
+        _ => "<p>This page is unavailable.</p>".to_owned(),
+    };
+    RawHtml(format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head><meta charset="UTF-8"><title>EHallway</title></head>
+<body>
+<h1><a href="/">EHallway</a></h1>
+{body}
+</body>
+</html>
+"#
+    ))
+}
@@ -0,0 +1,150 @@
+use std::io::Cursor;
+use std::time::Instant;
+
+use rand::Rng;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::{ContentType, Header};
+use rocket::request::{FromRequest, Outcome};
+use rocket::{Data, Request, Response};
+use rocket_auth::Session;
+use serde_json::Value;
+use tracing::Span;
+
+use crate::db;
+use crate::log_privacy::{request_uri, LogEmails};
+
+pub const HEADER: &str = "X-Request-Id";
+const MAX_LEN: usize = 64;
+const BUILD: &str = env!("EHALL_GIT_COMMIT");
+
+/// Identifies one request in the server log, the response headers,
+/// and the body of error responses, so client reports can be correlated.
+/// Error bodies also name the server's build.
+#[derive(Clone, Debug)]
+pub struct RequestId(pub String);
+
+// Honor ids from proxies and clients only when they are safe to log verbatim.
+fn is_valid(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_LEN
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn generate() -> String {
+    format!("{:016x}", rand::thread_rng().gen::<u64>())
+}
+
+fn cached(request: &Request<'_>) -> &RequestId {
+    request.local_cache(|| match request.headers().get_one(HEADER) {
+        Some(id) if is_valid(id) => RequestId(id.to_owned()),
+        _ => RequestId(generate()),
+    })
+}
+
+// When the fairing first saw the request
+struct Started(Instant);
+
+struct RequestSpan(Span);
+
+/// The span of the request's database calls, which names its id.
+/// Like them, it is only logged at the debug level.
+pub fn span<'r>(request: &'r Request<'_>) -> &'r Span {
+    let RequestSpan(span) = request.local_cache(|| {
+        RequestSpan(tracing::debug_span!(
+            "request",
+            request_id = cached(request).0.as_str()
+        ))
+    });
+    span
+}
+
+// The user as the session cookie names them, without the database lookup that checks it,
+// which is enough for the log.
+async fn shown_user(request: &Request<'_>) -> Option<String> {
+    let session = request.guard::<Session>().await.succeeded()?;
+    let emails = request.rocket().state::<LogEmails>()?;
+    Some(emails.show(&session.email))
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for RequestId {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, ()> {
+        Outcome::Success(cached(request).clone())
+    }
+}
+
+/// Assigns every request an id, echoes it in the response,
+/// and logs one event per request with its user, status, and duration,
+/// and how many statements it ran and rows they touched if it used the database.
+pub struct RequestIdFairing;
+
+#[rocket::async_trait]
+impl Fairing for RequestIdFairing {
+    fn info(&self) -> Info {
+        Info {
+            name: "Request ID",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, _: &mut Data<'_>) {
+        request.local_cache(|| Started(Instant::now()));
+        cached(request);
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let RequestId(id) = cached(request);
+        let span = span(request);
+        let Started(started) = request.local_cache(|| Started(Instant::now()));
+        let status = response.status();
+        let stats = db::request_stats(request);
+        let user = shown_user(request).await;
+        // Requests that didn't use the database leave its fields out.
+        let db_statements = Some(stats.statements()).filter(|&n| n > 0);
+        tracing::info!(
+            parent: span,
+            request_id = id.as_str(),
+            method = %request.method(),
+            uri = %request_uri(request),
+            user = user.as_deref(),
+            status = status.code,
+            duration_ms = started.elapsed().as_millis() as u64,
+            db_statements,
+            db_rows = db_statements.map(|_| stats.rows()),
+            "request"
+        );
+        let route = request.route().and_then(|route| route.name.as_deref());
+        if let Some(budget) = route.and_then(|name| stats.over_budget(name)) {
+            tracing::warn!(
+                parent: span,
+                request_id = id.as_str(),
+                "ran {} statements, over its budget of {budget}",
+                stats.statements()
+            );
+        }
+        response.set_header(Header::new(HEADER, id.clone()));
+        if status.code < 400 || response.content_type() != Some(ContentType::JSON) {
+            return;
+        }
+        let body = match response.body_mut().to_string().await {
+            Ok(body) => body,
+            Err(e) => {
+                tracing::warn!(parent: span, request_id = id.as_str(), "unreadable error body: {e}");
+                return;
+            }
+        };
+        let body = match serde_json::from_str(&body) {
+            Ok(Value::Object(mut fields)) => {
+                fields.insert("request_id".to_owned(), Value::from(id.as_str()));
+                fields.insert("build".to_owned(), Value::from(BUILD));
+                Value::Object(fields).to_string()
+            }
+            _ => body,
+        };
+        response.set_sized_body(body.len(), Cursor::new(body));
+    }
+}
@@ -0,0 +1,151 @@
+use std::collections::BTreeMap;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use tokio_postgres::Error;
+
+use ehall::{ResearchCohort, ResearchExport};
+
+use crate::db::Client;
+
+/// The optional `[research]` table of the config file.
+/// With it, users may consent to their ballots being shared,
+/// and admins may export the ballots of those who did, anonymized.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ResearchConfig {
+    /// Cohorts with fewer ballots from consenting users are left out,
+    /// so that no ballot stands out among too few others
+    #[serde(default = "default_min_ballots")]
+    pub min_ballots: usize,
+}
+
+fn default_min_ballots() -> usize {
+    3
+}
+
+// Only cohorts with recorded results, whose ballots are final.
+// Anonymized meetings no longer have their members' emails, so they drop out.
+const GET_CONSENTED_BALLOTS: &str = "
+    select v.meeting, v.cohort, v.email, v.topic, v.score
+    from meeting_votes v
+    join user_preferences p on p.email = v.email and p.research_consent
+    where v.cohort is not null
+        and exists (
+            select 1 from election_results r
+            where r.meeting = v.meeting and r.cohort = v.cohort
+        )
+    order by v.meeting, v.cohort, v.email, v.topic
+";
+
+/// A ballot's points for one topic: meeting, cohort, voter, topic, and points
+pub type BallotRow = (i64, i64, String, i64, u32);
+
+// A voter's points by topic
+type Ballot = BTreeMap<i64, u32>;
+
+/// The ballots of users who consented to research, anonymized
+pub async fn export(client: &Client, config: &ResearchConfig) -> Result<ResearchExport, Error> {
+    let rows = client.query(GET_CONSENTED_BALLOTS, &[]).await?;
+    let rows: Vec<BallotRow> = rows
+        .iter()
+        .map(|row| {
+            let score: i32 = row.get(4);
+            (row.get(0), row.get(1), row.get(2), row.get(3), score as u32)
+        })
+        .collect();
+    let cohorts = anonymize(&rows, config.min_ballots, &mut rand::thread_rng());
+    Ok(ResearchExport { cohorts })
+}
+
+/// Each cohort's ballots as a matrix, leaving out cohorts with fewer than `min_ballots`.
+/// Meetings get numbers in random order, each cohort's topics are columns in random order,
+/// and the ballots are sorted, so that neither voters nor topics can be told from their place.
+pub fn anonymize(
+    rows: &[BallotRow],
+    min_ballots: usize,
+    rng: &mut impl Rng,
+) -> Vec<ResearchCohort> {
+    let mut cohorts: BTreeMap<(i64, i64), BTreeMap<&str, Ballot>> = BTreeMap::new();
+    for (meeting, cohort, voter, topic, score) in rows {
+        cohorts
+            .entry((*meeting, *cohort))
+            .or_default()
+            .entry(voter.as_str())
+            .or_default()
+            .insert(*topic, *score);
+    }
+    cohorts.retain(|_, ballots| ballots.len() >= min_ballots.max(1));
+    let mut meetings: Vec<i64> = cohorts.keys().map(|&(meeting, _)| meeting).collect();
+    meetings.dedup();
+    meetings.shuffle(rng);
+    let mut exported: Vec<ResearchCohort> = cohorts
+        .into_iter()
+        .map(|((meeting, _), ballots)| {
+            let mut topics: Vec<i64> = ballots.values().flat_map(|b| b.keys().copied()).collect();
+            topics.sort_unstable();
+            topics.dedup();
+            topics.shuffle(rng);
+            let mut matrix: Vec<Vec<u32>> = ballots
+                .values()
+                .map(|ballot| {
+                    topics
+                        .iter()
+                        .map(|topic| ballot.get(topic).copied().unwrap_or(0))
+                        .collect()
+                })
+                .collect();
+            matrix.sort_unstable();
+            let number = meetings.iter().position(|&m| m == meeting).unwrap();
+            ResearchCohort {
+                meeting: number as u32 + 1,
+                ballots: matrix,
+            }
+        })
+        .collect();
+    exported.sort_by_key(|cohort| cohort.meeting);
+    exported
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::{anonymize, BallotRow};
+
+    #[test]
+    fn test_anonymize() {
+        let mut rows: Vec<BallotRow> = vec![];
+        // Three voters in a cohort of meeting 40, and one alone in another of its cohorts.
+        for (i, voter) in ["ana", "bo", "cy"].iter().enumerate() {
+            for (j, topic) in [7, 8, 9].iter().enumerate() {
+                rows.push((40, 1, voter.to_string(), *topic, ((i + j) % 3) as u32 + 1));
+            }
+        }
+        rows.push((40, 2, "dee".to_owned(), 7, 3));
+        // Two of meeting 41's voters left a topic unranked.
+        for voter in ["ana", "bo", "eve"] {
+            rows.push((41, 5, voter.to_owned(), 10, 2));
+        }
+        rows.push((41, 5, "ana".to_owned(), 11, 1));
+        let exported = anonymize(&rows, 3, &mut StdRng::seed_from_u64(1));
+        assert_eq!(exported.len(), 2);
+        let mut numbers: Vec<u32> = exported.iter().map(|c| c.meeting).collect();
+        numbers.sort_unstable();
+        assert_eq!(numbers, vec![1, 2]);
+        for cohort in exported.iter() {
+            assert_eq!(cohort.ballots.len(), 3);
+            assert!(cohort.ballots.windows(2).all(|w| w[0] <= w[1]));
+            assert!(cohort
+                .ballots
+                .iter()
+                .all(|ballot| ballot.len() == 2 || ballot.len() == 3));
+        }
+        let unranked = exported.iter().find(|c| c.ballots[0].len() == 2).unwrap();
+        assert_eq!(unranked.ballots.iter().flatten().sum::<u32>(), 7);
+        assert!(unranked.ballots.iter().filter(|b| b.contains(&0)).count() == 2);
+        // Too few ballots anywhere leaves nothing to export.
+        assert!(anonymize(&rows, 4, &mut StdRng::seed_from_u64(1)).is_empty());
+    }
+}
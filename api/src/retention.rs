@@ -0,0 +1,162 @@
+use deadpool_postgres::Pool;
+use rocket::time::Duration;
+use serde::{Deserialize, Serialize};
+use tokio_postgres::Error;
+
+use crate::clock::Clock;
+use crate::db::Client;
+use crate::jobs::Jobs;
+
+/// The optional `[retention]` table of the config file
+#[derive(Clone, Deserialize, Serialize)]
+pub struct RetentionConfig {
+    /// Days after a meeting starts before its members' emails are replaced
+    pub days: u32,
+    /// Minutes between looks for meetings due, while the API runs
+    #[serde(default = "default_every_mins")]
+    pub every_mins: u64,
+}
+
+fn default_every_mins() -> u64 {
+    60
+}
+
+const GET_DUE: &str = "
+    select g.meeting from cohort_groups g
+    join meetings m on m.id = g.meeting
+    where m.anonymized_at is null and g.started_at < to_timestamp($1::bigint)
+    order by g.meeting
+";
+
+// One statement, so a meeting is anonymized whole or not at all,
+// and only once, since every change waits on `due`.
+// Each email gets the same alias throughout the meeting,
+// so the ballots still add up to the elected topics and audits still run.
+// Registration answers are personal, so they go.
+const ANONYMIZE: &str = "
+    with due as (
+        select id from meetings where id = $1 and anonymized_at is null
+    ), aliases as (
+        select email, 'anonymized member ' || row_number() over (order by random()) as alias
+        from (
+            select email from meeting_participants where meeting = $1
+            union select email from meeting_attendees where meeting = $1
+            union select email from meeting_topics where meeting = $1
+            union select email from meeting_votes where meeting = $1
+            union select email from room_joins where meeting = $1
+            union select recorded_by from cohort_outcomes where meeting = $1
+            union select m.email from cohort_members m
+                join cohort_groups g on g.id = m.cohort_group
+                where g.meeting = $1
+            union select email from past_cohort_members where meeting = $1
+            union select email from user_topics where meeting = $1
+            union select email from topic_submission_scores where meeting = $1
+            union select unnest(members) from election_results where meeting = $1
+        ) as e
+    ), participants as (
+        update meeting_participants t set email = a.alias from aliases a
+        where t.meeting = (select id from due) and t.email = a.email
+    ), attendees as (
+        update meeting_attendees t set email = a.alias from aliases a
+        where t.meeting = (select id from due) and t.email = a.email
+    ), ballots as (
+        update meeting_topics t set email = a.alias from aliases a
+        where t.meeting = (select id from due) and t.email = a.email
+    ), votes as (
+        update meeting_votes t set email = a.alias from aliases a
+        where t.meeting = (select id from due) and t.email = a.email
+    ), joins as (
+        update room_joins t set email = a.alias from aliases a
+        where t.meeting = (select id from due) and t.email = a.email
+    ), outcomes as (
+        update cohort_outcomes t set recorded_by = a.alias from aliases a
+        where t.meeting = (select id from due) and t.recorded_by = a.email
+    ), members as (
+        update cohort_members t set email = a.alias from aliases a
+        where t.cohort_group in (select id from cohort_groups where meeting = (select id from due))
+            and t.email = a.email
+    ), past_members as (
+        update past_cohort_members t set email = a.alias from aliases a
+        where t.meeting = (select id from due) and t.email = a.email
+    ), submissions as (
+        update user_topics t set email = a.alias from aliases a
+        where t.meeting = (select id from due) and t.email = a.email
+    ), submission_edits as (
+        update topic_edits t set edited_by = a.alias from aliases a
+        where t.topic in (select id from user_topics where meeting = (select id from due))
+            and t.edited_by = a.email
+    ), submission_scores as (
+        update topic_submission_scores t set email = a.alias from aliases a
+        where t.meeting = (select id from due) and t.email = a.email
+    ), elections as (
+        update election_results e
+        set members = array(
+            select a.alias from unnest(e.members) as m (email)
+            join aliases a using (email)
+            order by a.alias
+        )
+        where e.meeting = (select id from due)
+    ), choices as (
+        delete from attendee_topics where meeting = (select id from due)
+    ), consents as (
+        delete from contact_consents where meeting = (select id from due)
+    ), answers as (
+        delete from registration_answers
+        where field in (select id from registration_fields where meeting = (select id from due))
+    )
+    update meetings set anonymized_at = to_timestamp($2::bigint)
+    where id = (select id from due)
+";
+
+/// Replaces members' emails with aliases in every meeting
+/// that started more than `config.days` ago, returning the meetings' IDs.
+/// Meeting owners and organizers keep their roles.
+pub async fn anonymize_due(
+    client: &Client,
+    clock: &Clock,
+    config: &RetentionConfig,
+) -> Result<Vec<i64>, Error> {
+    let cutoff = (clock.now() - Duration::days(config.days as i64)).unix_timestamp();
+    let rows = client.query(GET_DUE, &[&cutoff]).await?;
+    let mut anonymized = vec![];
+    for row in rows.iter() {
+        let meeting_id: i64 = row.get(0);
+        let n = client
+            .execute(ANONYMIZE, &[&meeting_id, &clock.unix_timestamp()])
+            .await?;
+        // Zero when another run got there first
+        if n > 0 {
+            anonymized.push(meeting_id);
+        }
+    }
+    Ok(anonymized)
+}
+
+/// Anonymizes the meetings due every `config.every_mins`, until the API shuts down.
+/// Every API instance looks, and a meeting is only anonymized once.
+pub fn register(jobs: &Jobs, pool: Pool, clock: Clock, config: RetentionConfig) {
+    let period = std::time::Duration::from_secs(config.every_mins.max(1) * 60);
+    jobs.register("retention", period, move || {
+        let (pool, clock, config) = (pool.clone(), clock.clone(), config.clone());
+        async move {
+            let client = Client::new(pool.get().await?);
+            let meetings = anonymize_due(&client, &clock, &config).await?;
+            if !meetings.is_empty() {
+                tracing::info!(?meetings, "anonymized {} meetings", meetings.len());
+            }
+            Ok(())
+        }
+    });
+}
+
+/// When the meeting was anonymized, in seconds since the Unix epoch,
+/// or None if it has not been
+pub async fn anonymized_at(client: &Client, meeting_id: i64) -> Result<Option<i64>, Error> {
+    let rows = client
+        .query(
+            "select extract(epoch from anonymized_at)::bigint from meetings where id = $1",
+            &[&meeting_id],
+        )
+        .await?;
+    Ok(rows.first().and_then(|row| row.get(0)))
+}
@@ -0,0 +1,144 @@
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+// However long an admin asks for, logging turns itself off within a day.
+const MAX_MINUTES: u32 = 24 * 60;
+// Longer parameters, like uploaded bytes, are cut short.
+const MAX_PARAM_LEN: usize = 64;
+const REDACTED: &str = "<redacted>";
+
+/// The optional `[sql_log]` table of the config file,
+/// for when an admin turns on statement logging with `PUT /admin/sql_log`
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SqlLogConfig {
+    /// How long logging stays on, unless the admin says
+    #[serde(default = "default_minutes")]
+    pub minutes: u32,
+    /// Statements taking at least this long are logged as slow, as warnings
+    #[serde(default = "default_slow_ms")]
+    pub slow_ms: u64,
+}
+
+fn default_minutes() -> u32 {
+    15
+}
+
+fn default_slow_ms() -> u64 {
+    250
+}
+
+impl Default for SqlLogConfig {
+    fn default() -> Self {
+        SqlLogConfig {
+            minutes: default_minutes(),
+            slow_ms: default_slow_ms(),
+        }
+    }
+}
+
+/// Whether each request's statements are logged, kept as Rocket state.
+/// Logging is off until an admin turns it on, and turns itself off when its time is up,
+/// so a forgotten switch can't flood the log.
+#[derive(Clone)]
+pub struct SqlLog(Arc<SqlLogState>);
+
+struct SqlLogState {
+    until: Mutex<Option<Instant>>,
+    minutes: u32,
+    slow: Duration,
+}
+
+impl SqlLog {
+    pub fn new(config: &SqlLogConfig) -> Self {
+        SqlLog(Arc::new(SqlLogState {
+            until: Mutex::new(None),
+            minutes: config.minutes,
+            slow: Duration::from_millis(config.slow_ms),
+        }))
+    }
+
+    /// Logs statements for `minutes`, or the configured time, from now on,
+    /// returning how long
+    pub fn enable(&self, minutes: Option<u32>) -> Duration {
+        let minutes = minutes.unwrap_or(self.0.minutes).clamp(1, MAX_MINUTES);
+        let period = Duration::from_secs(minutes as u64 * 60);
+        *self.0.until.lock().unwrap() = Some(Instant::now() + period);
+        period
+    }
+
+    pub fn disable(&self) {
+        *self.0.until.lock().unwrap() = None;
+    }
+
+    /// How much longer statements are logged, or None while they aren't
+    pub fn remaining(&self) -> Option<Duration> {
+        let mut until = self.0.until.lock().unwrap();
+        let left = (*until)?.checked_duration_since(Instant::now());
+        if left.is_none() {
+            *until = None;
+            tracing::warn!("SQL logging turned itself off");
+        }
+        left
+    }
+
+    /// How long a statement takes to be logged as slow
+    pub fn slow(&self) -> Duration {
+        self.0.slow
+    }
+}
+
+/// The statement on one line, as the log shows it
+pub fn one_line(sql: &str) -> String {
+    sql.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// A parameter as the log shows it.
+/// Numbers, flags, and times are shown, but anything with text in it is not,
+/// since the text could be an email, a password, or what a user wrote.
+pub fn redacted<P: fmt::Debug + ?Sized>(param: &P) -> String {
+    let shown = format!("{param:?}");
+    if shown.contains('"') || shown.contains('\'') {
+        REDACTED.to_owned()
+    } else if shown.chars().count() > MAX_PARAM_LEN {
+        format!(
+            "{}...",
+            shown.chars().take(MAX_PARAM_LEN).collect::<String>()
+        )
+    } else {
+        shown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{one_line, redacted, SqlLog, SqlLogConfig};
+
+    #[test]
+    fn test_redacted() {
+        assert_eq!(redacted(&42i64), "42");
+        assert_eq!(redacted(&Some(true)), "Some(true)");
+        assert_eq!(redacted(&None::<i32>), "None");
+        assert_eq!(redacted("ann@example.com"), "<redacted>");
+        assert_eq!(redacted(&vec!["ann@example.com".to_owned()]), "<redacted>");
+        assert_eq!(redacted(&Some('x')), "<redacted>");
+        assert!(redacted(&vec![0u8; 100]).ends_with("..."));
+        assert_eq!(
+            one_line("\n    select id\n    from meetings\n    where id = $1\n"),
+            "select id from meetings where id = $1"
+        );
+    }
+
+    #[test]
+    fn test_sql_log() {
+        let log = SqlLog::new(&SqlLogConfig::default());
+        assert!(log.remaining().is_none());
+        assert_eq!(log.enable(None).as_secs(), 15 * 60);
+        assert!(log.remaining().is_some());
+        assert_eq!(log.enable(Some(100_000)).as_secs(), 24 * 60 * 60);
+        log.disable();
+        assert!(log.remaining().is_none());
+    }
+}
@@ -0,0 +1,225 @@
+use serde::Serialize;
+
+use ehall::{CohortOutcome, UserTopic, VotingMethod};
+
+/// One cohort's part of a meeting summary
+#[derive(Serialize)]
+pub struct CohortSummary {
+    pub cohort: u32,
+    pub members: Vec<String>,
+    /// The elected topics, or None while the cohort is still voting
+    pub topics: Option<Vec<UserTopic>>,
+    pub outcome: Option<CohortOutcome>,
+}
+
+/// A meeting's results as archived by `GET /meeting/<id>/election_results/export`
+#[derive(Serialize)]
+pub struct ResultsExport<'a> {
+    pub meeting_id: u32,
+    pub meeting_name: &'a str,
+    /// How the topics' scores were counted
+    pub voting_method: VotingMethod,
+    /// When members' emails were replaced with aliases, in seconds since the Unix epoch,
+    /// or None if they have not been
+    pub anonymized_at: Option<i64>,
+    pub cohorts: &'a [CohortSummary],
+}
+
+/// Quotes every value, so commas, quotes, and newlines in answers are safe.
+pub fn csv_row<'a>(values: impl IntoIterator<Item = &'a str>) -> String {
+    let quoted: Vec<_> = values
+        .into_iter()
+        .map(|v| format!("\"{}\"", v.replace('"', "\"\"")))
+        .collect();
+    quoted.join(",") + "\r\n"
+}
+
+/// A row for each elected topic, with its cohort's members,
+/// or a row without a topic for a cohort that is still voting
+pub fn results_csv(cohorts: &[CohortSummary]) -> String {
+    let mut csv = csv_row(["cohort", "members", "rank", "topic", "score", "vetoed"]);
+    for c in cohorts {
+        let cohort = c.cohort.to_string();
+        let members = c.members.join("; ");
+        let topics = match &c.topics {
+            Some(topics) => topics,
+            None => {
+                csv += &csv_row([cohort.as_str(), &members, "", "", "", ""]);
+                continue;
+            }
+        };
+        for (i, topic) in topics.iter().enumerate() {
+            let rank = (i + 1).to_string();
+            let score = topic.score.to_string();
+            let vetoed = if topic.vetoed { "yes" } else { "no" };
+            csv += &csv_row([
+                cohort.as_str(),
+                &members,
+                &rank,
+                &topic.text,
+                &score,
+                vetoed,
+            ]);
+        }
+    }
+    csv
+}
+
+// Backslashes what Markdown would otherwise read as formatting,
+// so names and attendees' text appear as they were typed.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for (i, c) in text.chars().enumerate() {
+        let list_marker = i == 0 && matches!(c, '-' | '+');
+        if list_marker || "\\`*_[]<>#|~".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// A Markdown record of a meeting, giving each cohort's members,
+/// elected topics, and recorded outcome
+pub fn markdown(meeting_name: &str, anonymized: bool, cohorts: &[CohortSummary]) -> String {
+    let mut md = format!("# {}\n", escape(meeting_name));
+    if anonymized {
+        md += "\nMembers' emails were replaced with aliases under the data retention policy.\n";
+    }
+    if cohorts.is_empty() {
+        md += "\nThe meeting has not started.\n";
+    }
+    for c in cohorts {
+        let members: Vec<_> = c.members.iter().map(|m| escape(m)).collect();
+        md += &format!(
+            "\n## Cohort {}\n\nMembers: {}\n",
+            c.cohort,
+            members.join(", ")
+        );
+        match &c.topics {
+            Some(topics) => {
+                md += "\nTopics:\n\n";
+                for (i, topic) in topics.iter().enumerate() {
+                    md += &format!("{}. {}\n", i + 1, escape(&topic.text));
+                }
+            }
+            None => md += "\nThe cohort is still voting.\n",
+        }
+        let outcome = match &c.outcome {
+            Some(outcome) => outcome,
+            None => {
+                md += "\nNo outcome was recorded.\n";
+                continue;
+            }
+        };
+        md += &format!(
+            "\nOutcome, recorded by {}:\n\n",
+            escape(&outcome.recorded_by)
+        );
+        for line in outcome.text.lines() {
+            md += &match line.trim() {
+                "" => ">\n".to_owned(),
+                line => format!("> {}\n", escape(line)),
+            };
+        }
+        if !outcome.topics.is_empty() {
+            let topics: Vec<_> = outcome.topics.iter().map(|t| escape(&t.text)).collect();
+            md += &format!("\nFollowing up on: {}\n", topics.join(", "));
+        }
+    }
+    md
+}
+
+#[cfg(test)]
+mod tests {
+    use ehall::{CohortOutcome, OutcomeTopic, UserTopic};
+
+    use super::{markdown, results_csv, CohortSummary};
+
+    fn topic(text: &str) -> UserTopic {
+        UserTopic {
+            text: text.to_owned(),
+            score: 3,
+            id: 7,
+            seeded: false,
+            position: 0,
+            links: vec![],
+            proposers: vec![],
+            vetoed: false,
+            private: false,
+            description: "".to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_markdown() {
+        let cohorts = [
+            CohortSummary {
+                cohort: 0,
+                members: vec!["a@example.com".to_owned(), "b@example.com".to_owned()],
+                topics: Some(vec![topic("Build times"), topic("*Potato* farming")]),
+                outcome: Some(CohortOutcome {
+                    cohort: 0,
+                    members: vec![],
+                    text: "Profile the CI cache.\n\n- Ask the infra team".to_owned(),
+                    topics: vec![OutcomeTopic {
+                        id: 7,
+                        text: "Build times".to_owned(),
+                    }],
+                    recorded_by: "b@example.com".to_owned(),
+                    recorded_at: 0,
+                }),
+            },
+            CohortSummary {
+                cohort: 1,
+                members: vec!["c@example.com".to_owned()],
+                topics: None,
+                outcome: None,
+            },
+        ];
+        assert_eq!(
+            markdown("Monday #standup", false, &cohorts),
+            "# Monday \\#standup\n\
+             \n## Cohort 0\n\nMembers: a@example.com, b@example.com\n\
+             \nTopics:\n\n1. Build times\n2. \\*Potato\\* farming\n\
+             \nOutcome, recorded by b@example.com:\n\n\
+             > Profile the CI cache.\n>\n> \\- Ask the infra team\n\
+             \nFollowing up on: Build times\n\
+             \n## Cohort 1\n\nMembers: c@example.com\n\
+             \nThe cohort is still voting.\n\
+             \nNo outcome was recorded.\n"
+        );
+        assert_eq!(
+            markdown("Empty", false, &[]),
+            "# Empty\n\nThe meeting has not started.\n"
+        );
+        assert!(markdown("Old", true, &cohorts).starts_with(
+            "# Old\n\nMembers' emails were replaced with aliases under the data retention policy.\n"
+        ));
+    }
+
+    #[test]
+    fn test_results_csv() {
+        let cohorts = [
+            CohortSummary {
+                cohort: 0,
+                members: vec!["a@example.com".to_owned(), "b@example.com".to_owned()],
+                topics: Some(vec![topic("Build times"), topic("\"Potato\" farming")]),
+                outcome: None,
+            },
+            CohortSummary {
+                cohort: 1,
+                members: vec!["c@example.com".to_owned()],
+                topics: None,
+                outcome: None,
+            },
+        ];
+        assert_eq!(
+            results_csv(&cohorts),
+            "\"cohort\",\"members\",\"rank\",\"topic\",\"score\",\"vetoed\"\r\n\
+             \"0\",\"a@example.com; b@example.com\",\"1\",\"Build times\",\"3\",\"no\"\r\n\
+             \"0\",\"a@example.com; b@example.com\",\"2\",\"\"\"Potato\"\" farming\",\"3\",\"no\"\r\n\
+             \"1\",\"c@example.com\",\"\",\"\",\"\",\"\"\r\n"
+        );
+    }
+}
@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use deadpool_postgres::Pool;
+use tokio_postgres::Error;
+
+use crate::clock::Clock;
+use crate::db::Client;
+use crate::jobs::Jobs;
+
+/// How often the deadline job looks for voting that has closed
+const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+// Meetings without a voting time leave the deadline unset.
+const OPEN: &str = "
+    update cohort_groups g
+    set voting_closes_at = to_timestamp($2::bigint) + make_interval(mins => m.voting_minutes)
+    from meetings m
+    where g.meeting = $1 and m.id = g.meeting and m.voting_minutes is not null
+";
+
+// Observers are always counted as voted, so only participants are found.
+const UNVOTED: &str = "
+    select a.meeting, a.email
+    from cohort_groups g
+    join cohort_members cm on cm.cohort_group = g.id
+    join meeting_attendees a on a.meeting = g.meeting and a.email = cm.email
+    where g.voting_closes_at <= to_timestamp($1::bigint) and not a.voted
+    order by a.meeting, a.email
+";
+
+/// Starts the clock on voting in the meeting's current round, if the meeting limits it
+pub async fn open(client: &Client, clock: &Clock, meeting_id: i64) -> Result<(), Error> {
+    client
+        .execute(OPEN, &[&meeting_id, &clock.unix_timestamp()])
+        .await?;
+    Ok(())
+}
+
+/// Submits the ballots of everyone still voting once their meeting's voting closes,
+/// until the API shuts down
+pub fn register(jobs: &Jobs, pool: Pool, clock: Clock) {
+    jobs.register("voting", CHECK_INTERVAL, move || {
+        let (pool, clock) = (pool.clone(), clock.clone());
+        async move { close_due(&pool, &clock).await }
+    });
+}
+
+// Every API instance looks, and submitting a ballot twice changes nothing,
+// so a closed round is finished even if one instance stops part way.
+async fn close_due(pool: &Pool, clock: &Clock) -> Result<()> {
+    let client = Client::new(pool.get().await?);
+    let rows = client.query(UNVOTED, &[&clock.unix_timestamp()]).await?;
+    for row in rows.iter() {
+        let meeting_id = row.get::<_, i64>(0) as u32;
+        let email: &str = row.get(1);
+        // Their ballot is the ranking they have left so far.
+        crate::vote(&client, clock, email, meeting_id)
+            .await
+            .map_err(|e| anyhow!("meeting {meeting_id}: {e:?}"))?;
+    }
+    if !rows.is_empty() {
+        tracing::info!("voting closed for {} attendees", rows.len());
+    }
+    Ok(())
+}
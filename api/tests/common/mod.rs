@@ -0,0 +1,256 @@
+// API processes on a test database, for the tests that need a running server.
+// Each test file uses only some of these.
+#![allow(dead_code)]
+
+use std::env;
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::{header, redirect, Client, Response, StatusCode};
+use rocket::tokio;
+use serde_json::{json, Value};
+use tokio_postgres::NoTls;
+
+// These tests need a Postgres database they may fill,
+// named by EHALLWAY_TEST_POSTGRES_HOST, _USER, and _PASSWORD,
+// so they only run when asked for with `cargo test -- --ignored`.
+const DB_VARS: [(&str, &str); 3] = [
+    ("EHALLWAY_TEST_POSTGRES_HOST", "EHALLWAY_POSTGRES_HOST"),
+    ("EHALLWAY_TEST_POSTGRES_USER", "EHALLWAY_POSTGRES_USER"),
+    (
+        "EHALLWAY_TEST_POSTGRES_PASSWORD",
+        "EHALLWAY_POSTGRES_PASSWORD",
+    ),
+];
+const PASSWORD: &str = "Hallway-test-1";
+
+fn test_db_var(name: &str) -> String {
+    env::var(name).unwrap_or_else(|_| panic!("{name} names the test database"))
+}
+
+/// A connection to the test database, for checking what the API wrote
+pub async fn test_db() -> tokio_postgres::Client {
+    let params = format!(
+        "host={} user={} password={}",
+        test_db_var("EHALLWAY_TEST_POSTGRES_HOST"),
+        test_db_var("EHALLWAY_TEST_POSTGRES_USER"),
+        test_db_var("EHALLWAY_TEST_POSTGRES_PASSWORD"),
+    );
+    let (client, conn) = tokio_postgres::connect(&params, NoTls).await.unwrap();
+    tokio::spawn(conn);
+    client
+}
+
+/// Different for each run, so names and emails don't clash with earlier runs' rows
+pub fn run_id() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos()
+}
+
+/// An API process on its own port, killed when dropped.
+/// It logs as JSON, and its log events are kept for the test to check.
+pub struct Instance {
+    child: Child,
+    pub url: String,
+    events: Arc<Mutex<Vec<Value>>>,
+}
+
+impl Instance {
+    pub async fn start(port: u16) -> Self {
+        Instance::start_with(port, &[]).await
+    }
+
+    /// Like `start`, with more of the config in `vars`, like EHALLWAY_MEMBERSHIP__APPROVAL
+    pub async fn start_with(port: u16, vars: &[(&str, &str)]) -> Self {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_ehallway"));
+        for (test_name, name) in DB_VARS {
+            command.env(name, test_db_var(test_name));
+        }
+        let mut child = command
+            .envs(vars.iter().copied())
+            .env("ROCKET_PORT", port.to_string())
+            .env("EHALLWAY_STATIC_PATH", "../ui/dist")
+            .env("EHALLWAY_MIN_ATTENDEES", "3")
+            .env("EHALLWAY_LOGGING__FORMAT", "json")
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let events = Arc::new(Mutex::new(vec![]));
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+        let kept = events.clone();
+        // Rocket's own lines aren't JSON, so they are skipped.
+        thread::spawn(move || {
+            for line in stdout.lines().map_while(Result::ok) {
+                if let Ok(event) = serde_json::from_str(&line) {
+                    kept.lock().unwrap().push(event);
+                }
+            }
+        });
+        let instance = Instance {
+            child,
+            url: format!("http://127.0.0.1:{port}"),
+            events,
+        };
+        instance.wait_ready().await;
+        instance
+    }
+
+    async fn wait_ready(&self) {
+        for _ in 0..120 {
+            if let Ok(response) = reqwest::get(format!("{}/readyz", self.url)).await {
+                if response.status() == StatusCode::OK {
+                    return;
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+        panic!("{} never became ready", self.url);
+    }
+
+    /// The log events about the request with the `X-Request-Id` `request_id`
+    pub fn request_events(&self, request_id: &str) -> Vec<Value> {
+        let events = self.events.lock().unwrap();
+        events
+            .iter()
+            .filter(|event| event["fields"]["request_id"] == request_id)
+            .cloned()
+            .collect()
+    }
+}
+
+impl Drop for Instance {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// A client that leaves redirects unfollowed, so the login cookie can be taken from them
+pub fn http() -> Client {
+    Client::builder()
+        .redirect(redirect::Policy::none())
+        .build()
+        .unwrap()
+}
+
+// Login sessions live in each instance's memory, so each needs its own.
+fn session_cookie(response: &Response) -> String {
+    assert_eq!(response.status(), StatusCode::SEE_OTHER);
+    let cookie = response
+        .headers()
+        .get(header::SET_COOKIE)
+        .expect("a login sets the session cookie");
+    let cookie = cookie.to_str().unwrap();
+    cookie.split(';').next().unwrap().to_owned()
+}
+
+/// The response to posting the signup form for `email`
+pub async fn sign_up_response(http: &Client, instance: &Instance, email: &str) -> Response {
+    http.post(format!("{}/signup", instance.url))
+        .form(&[("email", email), ("password", PASSWORD)])
+        .send()
+        .await
+        .unwrap()
+}
+
+/// Signs up `email` on `instance`, returning the session cookie
+pub async fn sign_up(http: &Client, instance: &Instance, email: &str) -> String {
+    session_cookie(&sign_up_response(http, instance, email).await)
+}
+
+/// The response to posting the login form for `email`
+pub async fn log_in_response(http: &Client, instance: &Instance, email: &str) -> Response {
+    http.post(format!("{}/login", instance.url))
+        .form(&[("email", email), ("password", PASSWORD)])
+        .send()
+        .await
+        .unwrap()
+}
+
+/// Logs `email`, who signed up before, in on `instance`, returning the session cookie
+pub async fn log_in(http: &Client, instance: &Instance, email: &str) -> String {
+    session_cookie(&log_in_response(http, instance, email).await)
+}
+
+/// Posts `body` as JSON, expecting a 200 and a JSON response
+pub async fn post(
+    http: &Client,
+    instance: &Instance,
+    cookie: &str,
+    path: &str,
+    body: Value,
+) -> Value {
+    let response = http
+        .post(format!("{}{path}", instance.url))
+        .header(header::COOKIE, cookie)
+        .json(&body)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK, "POST {path}");
+    response.json().await.unwrap()
+}
+
+/// Starts `meeting` even with registered users missing, returning the caller's cohort
+pub async fn start_meeting(
+    http: &Client,
+    instance: &Instance,
+    cookie: &str,
+    meeting: i64,
+    key: &str,
+) -> Value {
+    let response = http
+        .put(format!(
+            "{}/meeting/{meeting}/start?confirm=true",
+            instance.url
+        ))
+        .header(header::COOKIE, cookie)
+        .header("Idempotency-Key", key)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(
+        response.status(),
+        StatusCode::OK,
+        "starting on {}",
+        instance.url
+    );
+    response.json().await.unwrap()
+}
+
+/// A meeting with seeded topics, created by the first of `cookies`,
+/// which everyone attends as a participant
+pub async fn attended_meeting(
+    http: &Client,
+    instance: &Instance,
+    cookies: &[String],
+    name: &str,
+) -> i64 {
+    let owner = &cookies[0];
+    let created = post(http, instance, owner, "/meetings", json!({ "name": name })).await;
+    let meeting = created["inserted"].as_i64().unwrap();
+    post(
+        http,
+        instance,
+        owner,
+        &format!("/meeting/{meeting}/seed_topics"),
+        json!({ "topics": ["Rust", "Postgres", "Rocket", "Yew"] }),
+    )
+    .await;
+    for cookie in cookies.iter() {
+        post(
+            http,
+            instance,
+            cookie,
+            &format!("/meeting/{meeting}/attendees"),
+            json!({ "participate_as": "participant" }),
+        )
+        .await;
+    }
+    meeting
+}
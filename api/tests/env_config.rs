@@ -0,0 +1,92 @@
+use std::{env, fs, process::Command};
+
+use serde_json::Value;
+
+// Runs the server's config subcommand, which loads the configuration and exits,
+// with no environment but the given variables.
+fn config(args: &[&str], vars: &[(&str, &str)]) -> Value {
+    let output = Command::new(env!("CARGO_BIN_EXE_ehallway"))
+        .args(args)
+        .arg("config")
+        .env_clear()
+        .envs(vars.iter().copied())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        output.status.success(),
+        "{stdout}{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!stdout.contains("s3cret"));
+    serde_json::from_str(&stdout[stdout.find('{').unwrap()..]).unwrap()
+}
+
+#[test]
+fn test_env_only_config() {
+    let config = config(
+        &[],
+        &[
+            ("EHALLWAY_STATIC_PATH", "/srv/ehallway/dist"),
+            ("EHALLWAY_POSTGRES_HOST", "db"),
+            ("EHALLWAY_POSTGRES_USER", "ehallway"),
+            ("EHALLWAY_POSTGRES_PASSWORD", "s3cret"),
+            ("EHALLWAY_POSTGRES_POOL_SIZE", "32"),
+            ("EHALLWAY_MIN_ATTENDEES", "6"),
+            ("EHALLWAY_MOCK_CLOCK", "true"),
+            ("EHALLWAY_CAPTCHA__PROVIDER", "turnstile"),
+            ("EHALLWAY_CAPTCHA__SITE_KEY", "public"),
+            ("EHALLWAY_CAPTCHA__SECRET_KEY", "s3cret"),
+            ("EHALLWAY_BACKUP__DIRECTORY", "/var/backups/ehallway"),
+            ("EHALLWAY_LOG_PRIVACY__KEY", "s3cret"),
+            ("EHALLWAY_POLLING__MAX_INTERVAL_SECS", "10"),
+            ("EHALLWAY_SMTP__HOST", "smtp.example.com"),
+            ("EHALLWAY_SMTP__USERNAME", "hallway"),
+            ("EHALLWAY_SMTP__PASSWORD", "s3cret"),
+            ("EHALLWAY_SMTP__FROM", "eHallway <hallway@example.com>"),
+            ("EHALLWAY_SMTP__BASE_URL", "https://hallway.example.com"),
+        ],
+    );
+    assert_eq!(config["static_path"], "/srv/ehallway/dist");
+    assert_eq!(config["postgres_host"], "db");
+    assert_eq!(config["postgres_user"], "ehallway");
+    assert_eq!(config["postgres_password"], "<hidden>");
+    assert_eq!(config["postgres_pool_size"], 32);
+    assert_eq!(config["min_attendees"], 6);
+    assert_eq!(config["mock_clock"], true);
+    assert_eq!(config["captcha"]["site_key"], "public");
+    assert_eq!(config["captcha"]["secret_key"], "<hidden>");
+    assert_eq!(config["backup"]["keep"], 7);
+    assert_eq!(config["log_privacy"]["key"], "<hidden>");
+    assert_eq!(config["polling"]["max_interval_secs"], 10);
+    assert_eq!(config["polling"]["busy_requests"], 64);
+    assert_eq!(config["limits"]["expensive_requests"], 8);
+    assert_eq!(config["sql_log"]["minutes"], 15);
+    assert_eq!(config["smtp"]["port"], 587);
+    assert_eq!(config["smtp"]["password"], "<hidden>");
+}
+
+#[test]
+fn test_env_overrides_config_file() {
+    let path = env::temp_dir().join(format!("ehallway-env-config-{}.toml", std::process::id()));
+    fs::write(
+        &path,
+        r#"
+        static_path = "/path/to/ehallway/ui/dist"
+        postgres_user = "ehallway"
+        postgres_password = "s3cret"
+        round_minutes = 45
+        "#,
+    )
+    .unwrap();
+    let config = config(
+        &["--config-file", path.to_str().unwrap()],
+        &[("EHALLWAY_ROUND_MINUTES", "20")],
+    );
+    fs::remove_file(&path).unwrap();
+    assert_eq!(config["static_path"], "/path/to/ehallway/ui/dist");
+    assert_eq!(config["postgres_host"], "localhost");
+    assert_eq!(config["postgres_pool_size"], 16);
+    assert_eq!(config["round_minutes"], 20);
+    assert_eq!(config["mock_clock"], false);
+}
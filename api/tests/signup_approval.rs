@@ -0,0 +1,57 @@
+mod common;
+
+use reqwest::{header, Response, StatusCode};
+
+use common::{http, log_in_response, run_id, sign_up_response, test_db, Instance};
+
+// Join requests for this test's addresses fail to be recorded.
+const FAIL_JOIN_REQUESTS: &str = "
+    create or replace function fail_test_join_requests() returns trigger as $$
+    begin
+        if new.email like 'join-fails-%' then
+            raise exception 'recording join requests fails for this test';
+        end if;
+        return new;
+    end
+    $$ language plpgsql;
+    drop trigger if exists fail_test_join_requests on join_requests;
+    create trigger fail_test_join_requests before insert on join_requests
+        for each row execute function fail_test_join_requests();
+";
+const STOP_FAILING: &str = "
+    drop trigger if exists fail_test_join_requests on join_requests;
+    drop function if exists fail_test_join_requests();
+";
+
+fn redirected_to(response: &Response) -> &str {
+    assert_eq!(response.status(), StatusCode::SEE_OTHER);
+    response.headers()[header::LOCATION].to_str().unwrap()
+}
+
+#[rocket::async_test]
+#[ignore]
+async fn test_signup_without_join_request() {
+    let api = Instance::start_with(18104, &[("EHALLWAY_MEMBERSHIP__APPROVAL", "true")]).await;
+    let db = test_db().await;
+    let http = http();
+    let email = format!("join-fails-{}@example.com", run_id());
+    db.batch_execute(FAIL_JOIN_REQUESTS).await.unwrap();
+    let status = sign_up_response(&http, &api, &email).await.status();
+    // Cleaned up before checking, so a failed check leaves signups working
+    db.batch_execute(STOP_FAILING).await.unwrap();
+
+    // The signup fails whole, leaving no account that could log in unapproved.
+    assert_ne!(status, StatusCode::SEE_OTHER);
+    let rows = db
+        .query("select 1 from users where email = $1", &[&email])
+        .await
+        .unwrap();
+    assert!(rows.is_empty());
+
+    // So the address can sign up again, and then waits for approval.
+    let response = sign_up_response(&http, &api, &email).await;
+    assert!(redirected_to(&response).contains("pending=true"));
+    let response = log_in_response(&http, &api, &email).await;
+    assert!(redirected_to(&response).contains("pending=true"));
+    assert!(response.headers().get(header::SET_COOKIE).is_none());
+}
@@ -0,0 +1,161 @@
+mod common;
+
+use std::time::Duration;
+
+use reqwest::{header, Client, Method, StatusCode};
+use rocket::tokio;
+use serde_json::{json, Value};
+
+use common::{attended_meeting, http, post, run_id, sign_up, start_meeting, Instance};
+
+// A request over its route's budget in STATEMENT_BUDGETS (src/db.rs) logs a warning
+// under its request id, so each request here sends an id naming its route.
+async fn budgeted(
+    http: &Client,
+    api: &Instance,
+    cookie: &str,
+    method: Method,
+    path: &str,
+    body: Option<Value>,
+    request_id: &str,
+) -> Value {
+    let mut request = http
+        .request(method, format!("{}{path}", api.url))
+        .header(header::COOKIE, cookie)
+        .header("X-Request-Id", request_id);
+    if let Some(body) = body {
+        request = request.json(&body);
+    }
+    let response = request.send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK, "{request_id}");
+    response.json().await.unwrap()
+}
+
+// The log is read as the server writes it, so a later request's event
+// means the earlier requests' events are in.
+async fn wait_for_log(api: &Instance, http: &Client, request_id: &str) {
+    http.get(format!("{}/healthz", api.url))
+        .header("X-Request-Id", request_id)
+        .send()
+        .await
+        .unwrap();
+    for _ in 0..50 {
+        if !api.request_events(request_id).is_empty() {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("the log never showed {request_id}");
+}
+
+#[rocket::async_test]
+#[ignore]
+async fn test_statement_budgets() {
+    let api = Instance::start(18103).await;
+    let http = http();
+    let run = run_id();
+    let mut cookies = vec![];
+    for i in 0..3 {
+        let cookie = sign_up(&http, &api, &format!("budgets-{run}-{i}@example.com")).await;
+        let topic = json!({ "new_topic": format!("Budgets {run} {i}") });
+        post(&http, &api, &cookie, "/topics", topic).await;
+        cookies.push(cookie);
+    }
+    let meeting = attended_meeting(&http, &api, &cookies, &format!("Budgets {run}")).await;
+    let cookie = &cookies[0];
+    start_meeting(&http, &api, cookie, meeting, &format!("{run}-start")).await;
+
+    let id = |route: &str| format!("{route}-{run}");
+    let topics = budgeted(
+        &http,
+        &api,
+        cookie,
+        Method::GET,
+        "/user_topics",
+        None,
+        &id("get_user_topics"),
+    )
+    .await;
+    let scores: Vec<(u64, u64)> = topics["topics"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .enumerate()
+        .map(|(i, topic)| (topic["id"].as_u64().unwrap(), i as u64))
+        .collect();
+    let requests = [
+        (Method::GET, "/meetings".to_owned(), None, "get_meetings"),
+        (
+            Method::PUT,
+            "/topics/scores".to_owned(),
+            Some(json!({ "scores": scores })),
+            "store_user_topic_scores",
+        ),
+        (
+            Method::PUT,
+            format!("/meeting/{meeting}/topics/scores"),
+            Some(json!({ "scores": scores })),
+            "store_meeting_topic_scores",
+        ),
+        (
+            Method::GET,
+            format!("/meeting/{meeting}/election_results"),
+            None,
+            "get_election_results",
+        ),
+        (
+            Method::GET,
+            format!("/sync?meeting={meeting}"),
+            None,
+            "sync_state",
+        ),
+    ];
+    for (method, path, body, route) in requests {
+        budgeted(&http, &api, cookie, method, &path, body, &id(route)).await;
+    }
+    // Results cost the most once every ballot is in.
+    for (i, voter) in cookies.iter().enumerate() {
+        let response = http
+            .put(format!("{}/meeting/{meeting}/vote", api.url))
+            .header(header::COOKIE, voter)
+            .header("Idempotency-Key", format!("{run}-vote-{i}"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK, "vote {i}");
+    }
+    budgeted(
+        &http,
+        &api,
+        cookie,
+        Method::GET,
+        &format!("/meeting/{meeting}/election_results"),
+        None,
+        &id("get_election_results-finished"),
+    )
+    .await;
+    wait_for_log(&api, &http, &id("done")).await;
+
+    let routes = [
+        "get_user_topics",
+        "get_meetings",
+        "store_user_topic_scores",
+        "store_meeting_topic_scores",
+        "get_election_results",
+        "get_election_results-finished",
+        "sync_state",
+    ];
+    for route in routes {
+        let events = api.request_events(&id(route));
+        assert!(
+            events.iter().any(|e| e["fields"]["message"] == "request"),
+            "{route} was not logged"
+        );
+        let over: Vec<&str> = events
+            .iter()
+            .filter_map(|e| e["fields"]["message"].as_str())
+            .filter(|message| message.contains("over its budget"))
+            .collect();
+        assert!(over.is_empty(), "{route}: {over:?}");
+    }
+}
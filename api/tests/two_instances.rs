@@ -0,0 +1,57 @@
+mod common;
+
+use rocket::tokio;
+
+use common::{attended_meeting, http, log_in, run_id, sign_up, start_meeting, test_db, Instance};
+
+const N_ATTENDEES: usize = 6;
+
+#[rocket::async_test]
+#[ignore]
+async fn test_start_on_two_instances() {
+    // Instances start one after the other, since each sets up the schema.
+    let a = Instance::start(18101).await;
+    let b = Instance::start(18102).await;
+    let http = http();
+    let run = run_id();
+    let emails: Vec<String> = (0..N_ATTENDEES)
+        .map(|i| format!("two-instances-{run}-{i}@example.com"))
+        .collect();
+    let mut cookies = vec![];
+    for email in emails.iter() {
+        cookies.push(sign_up(&http, &a, email).await);
+    }
+    let meeting = attended_meeting(&http, &a, &cookies, &format!("Two instances {run}")).await;
+    let owner_on_b = log_in(&http, &b, &emails[0]).await;
+
+    // Both instances draw cohorts, but only one draw may be kept.
+    let (on_a, on_b) = tokio::join!(
+        start_meeting(&http, &a, &cookies[0], meeting, &format!("{run}-a")),
+        start_meeting(&http, &b, &owner_on_b, meeting, &format!("{run}-b")),
+    );
+    assert_eq!(on_a["cohort"], on_b["cohort"]);
+
+    let db = test_db().await;
+    let row = db
+        .query_one(
+            "select count(*) from cohort_groups where meeting = $1",
+            &[&meeting],
+        )
+        .await
+        .unwrap();
+    assert_eq!(row.get::<_, i64>(0), 1);
+    let row = db
+        .query_one(
+            "
+            select count(*), count(distinct cm.email)
+            from cohort_members cm
+            join cohort_groups g on g.id = cm.cohort_group
+            where g.meeting = $1
+            ",
+            &[&meeting],
+        )
+        .await
+        .unwrap();
+    assert_eq!(row.get::<_, i64>(0), N_ATTENDEES as i64);
+    assert_eq!(row.get::<_, i64>(1), N_ATTENDEES as i64);
+}
@@ -0,0 +1,148 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use icu_collator::{Collator, CollatorOptions, Numeric};
+use icu_locid::Locale;
+
+/// Orders names and other text as readers of a language expect,
+/// using the Unicode Collation Algorithm with that language's rules,
+/// so accents, case, and other scripts sort sensibly
+/// and numbers in text sort by value, as in "Room 2" before "Room 10".
+pub struct Collation {
+    collator: Collator,
+    language: Option<Locale>,
+}
+
+impl Collation {
+    /// Collation for a BCP 47 language tag, like "de" or "sv-SE".
+    /// Tags that are missing, malformed, or without rules of their own
+    /// get the root order, which suits most languages.
+    pub fn new(language: Option<&str>) -> Self {
+        let language = language.and_then(|tag| tag.parse::<Locale>().ok());
+        let mut options = CollatorOptions::new();
+        options.numeric = Some(Numeric::On);
+        let locale = language.clone().unwrap_or_default();
+        let collator = Collator::try_new(&(&locale).into(), options)
+            .or_else(|_| Collator::try_new(&Default::default(), options))
+            .expect("root collation is compiled in");
+        Collation { collator, language }
+    }
+
+    pub fn compare(&self, a: &str, b: &str) -> Ordering {
+        self.collator.compare(a, b)
+    }
+
+    /// Sorts `items` by the text `key` gives, keeping the order of equal items
+    pub fn sort_by_text<T>(&self, items: &mut [T], key: impl Fn(&T) -> &str) {
+        items.sort_by(|a, b| self.compare(key(a), key(b)));
+    }
+}
+
+impl Default for Collation {
+    fn default() -> Self {
+        Collation::new(None)
+    }
+}
+
+impl fmt::Debug for Collation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.language {
+            Some(language) => write!(f, "Collation({language})"),
+            None => f.write_str("Collation(root)"),
+        }
+    }
+}
+
+/// The language subtag of a BCP 47 tag, like "pt" for "pt-BR",
+/// which is all that matters for who can talk with whom
+pub fn conversation_language(tag: &str) -> Result<String, String> {
+    let locale = tag
+        .trim()
+        .parse::<Locale>()
+        .map_err(|_| format!("{tag:?} is not a language tag, like \"en\" or \"pt-BR\""))?;
+    let language = locale.id.language;
+    if language.is_empty() {
+        return Err(format!("{tag:?} names no language"));
+    }
+    Ok(language.as_str().to_owned())
+}
+
+/// The language an HTTP `Accept-Language` header prefers most, if any
+pub fn preferred_language(accept_language: &str) -> Option<&str> {
+    let mut best: Option<(&str, f32)> = None;
+    for item in accept_language.split(',') {
+        let mut parts = item.split(';').map(str::trim);
+        let tag = parts.next().unwrap_or_default();
+        let quality = parts
+            .find_map(|p| p.strip_prefix("q="))
+            .map_or(Some(1.0), |q| q.parse::<f32>().ok());
+        let Some(q) = quality.filter(|&q| q > 0.0) else {
+            continue;
+        };
+        // Ties go to the earlier language.
+        if !tag.is_empty() && tag != "*" && best.is_none_or(|(_, best_q)| q > best_q) {
+            best = Some((tag, q));
+        }
+    }
+    best.map(|(tag, _)| tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{conversation_language, preferred_language, Collation};
+
+    fn sorted<'a>(language: Option<&str>, words: &[&'a str]) -> Vec<&'a str> {
+        let mut words = words.to_vec();
+        Collation::new(language).sort_by_text(&mut words, |w| w);
+        words
+    }
+
+    #[test]
+    fn test_collation() {
+        assert_eq!(
+            sorted(
+                None,
+                &["zebra", "Ångström", "apple", "Émile", "Apple", "eagle"]
+            ),
+            ["Ångström", "apple", "Apple", "eagle", "Émile", "zebra"]
+        );
+        assert_eq!(
+            sorted(None, &["Room 10", "Room 2", "Room 1"]),
+            ["Room 1", "Room 2", "Room 10"]
+        );
+        // Swedish puts å, ä, and ö after z.
+        assert_eq!(
+            sorted(Some("sv-SE"), &["öl", "zon", "ost"]),
+            ["ost", "zon", "öl"]
+        );
+        assert_eq!(
+            sorted(Some("de"), &["öl", "zon", "ost"]),
+            ["öl", "ost", "zon"]
+        );
+        // Scripts group together instead of by code point.
+        assert_eq!(
+            sorted(None, &["Мир", "beta", "Ωmega", "alpha"]),
+            ["alpha", "beta", "Ωmega", "Мир"]
+        );
+        assert_eq!(sorted(Some("not a tag!"), &["b", "a"]), ["a", "b"]);
+    }
+
+    #[test]
+    fn test_conversation_language() {
+        assert_eq!(conversation_language("pt-BR"), Ok("pt".to_owned()));
+        assert_eq!(conversation_language(" DE "), Ok("de".to_owned()));
+        assert_eq!(conversation_language("zh-Hant-TW"), Ok("zh".to_owned()));
+        assert!(conversation_language("und").is_err());
+        assert!(conversation_language("").is_err());
+        assert!(conversation_language("not a tag!").is_err());
+    }
+
+    #[test]
+    fn test_preferred_language() {
+        assert_eq!(preferred_language("sv-SE,sv;q=0.9,en;q=0.8"), Some("sv-SE"));
+        assert_eq!(preferred_language("en;q=0.5, de"), Some("de"));
+        assert_eq!(preferred_language("*, fr;q=0.1"), Some("fr"));
+        assert_eq!(preferred_language("fr;q=0, *"), None);
+        assert_eq!(preferred_language(""), None);
+    }
+}
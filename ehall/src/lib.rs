@@ -1,14 +1,132 @@
 use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashSet};
 
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize};
+
+mod collation;
+mod page;
+
+pub use collation::{conversation_language, preferred_language, Collation};
+pub use page::{page_limit, paginate, Cursor, PageInfo, DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT};
 
 pub const COHORT_QUORUM: usize = 3;
+/// Cohort sizes an organizer may choose, with `COHORT_QUORUM` the default
+pub const MIN_COHORT_SIZE: u32 = 2;
+pub const MAX_COHORT_SIZE: u32 = 16;
+/// Topics each cohort may elect, with `DEFAULT_N_WINNERS` the default.
+/// Starting a meeting also checks that every cohort's ballot has that many.
+pub const MIN_N_WINNERS: u32 = 1;
+pub const MAX_N_WINNERS: u32 = 10;
+pub const DEFAULT_N_WINNERS: u32 = 2;
+/// Own topics each attendee may bring to a meeting's ballots,
+/// with `DEFAULT_TOPICS_PER_ATTENDEE` the default
+pub const MIN_TOPICS_PER_ATTENDEE: u32 = 1;
+pub const MAX_TOPICS_PER_ATTENDEE: u32 = 10;
+pub const DEFAULT_TOPICS_PER_ATTENDEE: u32 = 3;
+/// Limits on a meeting's optional voting time, which has no default
+pub const MIN_VOTING_MINUTES: u32 = 1;
+pub const MAX_VOTING_MINUTES: u32 = 240;
+pub const MAX_TEXT_LEN: usize = 254; // varchar columns for names and topics
+pub const MAX_TOPIC_LINKS: usize = 5;
+pub const MAX_URL_LEN: usize = 2048; // varchar column for topic links
+pub const MAX_OUTCOME_LEN: usize = 4000; // varchar column for cohort outcomes
+pub const MAX_DESCRIPTION_LEN: usize = 4000; // varchar column for topic descriptions
+pub const MAX_AVATAR_BYTES: usize = 256 * 1024;
+/// Image types `PUT /avatar` accepts, which every browser shows
+pub const AVATAR_CONTENT_TYPES: [&str; 3] = ["image/png", "image/jpeg", "image/webp"];
+pub const MEETING_CONFIG_VERSION: u32 = 1;
+/// Bump when a message changes incompatibly, so stale UI bundles reload themselves.
+pub const SCHEMA_VERSION: u32 = 2;
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// What `GET /admin/backups` reports about this API instance's backups
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct BackupStatus {
+    pub running: bool,
+    /// Unix time of the last backup attempt's end
+    pub last_finished_at: Option<i64>,
+    pub last_error: Option<String>,
+    /// Backup file names in the backup directory, oldest first
+    pub backups: Vec<String>,
+}
+
+/// Use of one deprecated route on one API instance
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DeprecatedRouteUsage {
+    /// The route's handler name
+    pub route: String,
+    /// When the route was deprecated, as YYYY-MM-DD
+    pub deprecated: String,
+    /// When the route may be removed, as YYYY-MM-DD
+    pub sunset: Option<String>,
+    /// The path that replaces the route
+    pub successor: Option<String>,
+    pub uses: u64,
+    /// Unix time of the latest use
+    pub last_used_at: Option<i64>,
+}
+
+/// What `GET /admin/deprecations` reports about this API instance
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DeprecationReport {
+    /// Unix time when this instance started counting
+    pub counting_since: i64,
+    pub routes: Vec<DeprecatedRouteUsage>,
+}
+
+/// What `GET /admin/cohort_waits` reports about this API instance's
+/// waits for election results that began with the cohort pending
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CohortWaitReport {
+    /// Unix time when this instance started counting
+    pub counting_since: i64,
+    /// How long such a wait is held, from the `[polling]` table's `cohort_wait_secs`
+    pub budget_secs: u64,
+    pub waits: u64,
+    /// Waits held for the whole budget with the cohort still pending
+    pub over_budget: u64,
+}
+
+/// Where a binary came from, for triaging reports from self-hosted instances
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct BuildInfo {
+    /// Abbreviated, with a "-dirty" suffix for uncommitted changes
+    pub git_commit: String,
+    pub build_date: String,
+    /// This project's crates in the binary, by name
+    pub crates: BTreeMap<String, String>,
+}
+
+impl BuildInfo {
+    /// Takes the binary's crate versions, adding this crate's own
+    pub fn new(git_commit: &str, build_date: &str, crates: &[(&str, &str)]) -> Self {
+        let mut versions: BTreeMap<String, String> = crates
+            .iter()
+            .map(|(name, version)| (name.to_string(), version.to_string()))
+            .collect();
+        versions.insert(env!("CARGO_PKG_NAME").to_owned(), VERSION.to_owned());
+        BuildInfo {
+            git_commit: git_commit.to_owned(),
+            build_date: build_date.to_owned(),
+            crates: versions,
+        }
+    }
+
+    /// One line for footers and error reports
+    pub fn summary(&self) -> String {
+        format!("{} built {}", self.git_commit, self.build_date)
+    }
+}
 
 /// A None cohort means try again.
 #[derive(Serialize, Deserialize)]
 pub struct CohortMessage {
     /// The cohort that includes the user getting the message
     pub cohort: Option<Vec<String>>,
+    /// Avatar image URLs for each of `cohort`, in the same order, when the server shows avatars
+    #[serde(default)]
+    pub avatars: Option<Vec<Option<String>>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -17,8 +135,260 @@ pub struct ElectionResults {
     pub meeting_name: String,
     pub topics: Option<Vec<UserTopic>>,
     pub users: Option<Vec<String>>,
+    /// Avatar image URLs for each of `users`, in the same order, when the server shows avatars
+    #[serde(default)]
+    pub avatars: Option<Vec<Option<String>>>,
     pub meeting_url: String,
-    pub status: String,
+    pub status: ElectionStatus,
+    /// Suggested discussion minutes for each of `topics`, in the same order
+    #[serde(default)]
+    pub topic_minutes: Option<Vec<u32>>,
+    /// What the user's cohort recorded deciding, once someone has
+    #[serde(default)]
+    pub outcome: Option<CohortOutcome>,
+    /// Whose emails the user can see, once voting is over in a meeting that shares contacts
+    #[serde(default)]
+    pub contact_sharing: Option<ContactSharing>,
+    /// The meeting's hallway round, counting from 1, or 0 before it starts
+    #[serde(default)]
+    pub round: u32,
+    /// The user's ballot is in, though `DELETE /meeting/<id>/vote`
+    /// takes it back until their cohort finishes voting
+    #[serde(default)]
+    pub voted: bool,
+    /// When voting in the current round closes, in seconds since the Unix epoch,
+    /// if the meeting limits it
+    #[serde(default)]
+    pub voting_closes_at: Option<i64>,
+}
+
+/// A cohort's elected topics as recorded when its last member voted,
+/// which outlast the ballots they were counted from
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PastElection {
+    /// The hallway round the cohort met in, counting from 1
+    #[serde(default)]
+    pub round: u32,
+    pub cohort: u32,
+    pub members: Vec<String>,
+    /// How the topics' scores were counted
+    pub voting_method: VotingMethod,
+    /// The elected topics, best first
+    pub topics: Vec<UserTopic>,
+    /// When voting finished, in seconds since the Unix epoch
+    pub recorded_at: i64,
+}
+
+/// Meetings held before eHallway, for `POST /admin/imports`,
+/// which adds each one's cohorts to its history
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct HistoryImport {
+    pub meetings: Vec<ImportedMeeting>,
+}
+
+/// A past meeting, which must not share a name with another meeting
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ImportedMeeting {
+    pub name: String,
+    /// The organizer, who may see every cohort's history
+    #[serde(default)]
+    pub owner: Option<String>,
+    /// When it was held, in seconds since the Unix epoch
+    pub held_at: i64,
+    #[serde(default)]
+    pub voting_method: VotingMethod,
+    pub cohorts: Vec<ImportedCohort>,
+}
+
+/// Who met together and what they chose
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ImportedCohort {
+    /// Emails, which needn't belong to accounts yet
+    pub members: Vec<String>,
+    /// The elected topics, best first
+    #[serde(default)]
+    pub topics: Vec<String>,
+    /// What the cohort decided to do next
+    #[serde(default)]
+    pub outcome: Option<String>,
+}
+
+/// Meetings one import may hold, so one document can't occupy the importer for days
+pub const MAX_IMPORTED_MEETINGS: usize = 10_000;
+
+impl HistoryImport {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.meetings.is_empty() {
+            return Err("the import has no meetings".to_owned());
+        }
+        if self.meetings.len() > MAX_IMPORTED_MEETINGS {
+            return Err(format!(
+                "an import has at most {MAX_IMPORTED_MEETINGS} meetings"
+            ));
+        }
+        let mut names = HashSet::new();
+        for meeting in self.meetings.iter() {
+            let name = meeting.name.as_str();
+            if name.trim().is_empty() || name.len() > MAX_TEXT_LEN {
+                return Err(format!("a meeting name needs 1 to {MAX_TEXT_LEN} bytes"));
+            }
+            if !names.insert(name) {
+                return Err(format!("meeting {name:?} is repeated"));
+            }
+            if meeting.cohorts.is_empty() {
+                return Err(format!("meeting {name:?} has no cohorts"));
+            }
+            let mut emails =
+                (meeting.owner.iter()).chain(meeting.cohorts.iter().flat_map(|c| &c.members));
+            if let Some(email) = emails.find(|e| !e.contains('@') || e.len() > MAX_TEXT_LEN) {
+                return Err(format!("meeting {name:?} has an invalid email {email:?}"));
+            }
+            for cohort in meeting.cohorts.iter() {
+                if cohort.members.is_empty() {
+                    return Err(format!("meeting {name:?} has a cohort without members"));
+                }
+                let invalid = |t: &&String| t.trim().is_empty() || t.len() > MAX_TEXT_LEN;
+                if let Some(topic) = cohort.topics.iter().find(invalid) {
+                    return Err(format!("meeting {name:?} has an invalid topic {topic:?}"));
+                }
+                if cohort
+                    .outcome
+                    .as_ref()
+                    .is_some_and(|o| o.len() > MAX_OUTCOME_LEN)
+                {
+                    return Err(format!(
+                        "meeting {name:?} has an outcome over {MAX_OUTCOME_LEN} bytes"
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Where an import stands
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportState {
+    /// Waiting for the importer, which picks up imports in the order they came
+    Queued,
+    Running,
+    Done,
+    /// Stopped at a meeting that couldn't be added, until resumed
+    Failed,
+}
+
+impl ImportState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ImportState::Queued => "queued",
+            ImportState::Running => "running",
+            ImportState::Done => "done",
+            ImportState::Failed => "failed",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        [
+            ImportState::Queued,
+            ImportState::Running,
+            ImportState::Done,
+            ImportState::Failed,
+        ]
+        .into_iter()
+        .find(|state| state.as_str() == name)
+    }
+}
+
+/// An import's progress, from `GET /admin/imports`
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ImportStatus {
+    pub id: u32,
+    pub state: ImportState,
+    pub n_meetings: u32,
+    /// Meetings added so far, in the document's order
+    pub n_imported: u32,
+    /// Why the import failed, naming the meeting it stopped at
+    pub error: Option<String>,
+    pub submitted_by: String,
+    /// In seconds since the Unix epoch
+    pub submitted_at: i64,
+    pub finished_at: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ImportsMessage {
+    pub imports: Vec<ImportStatus>,
+}
+
+/// The past elections a user may see, oldest first
+#[derive(Serialize, Deserialize)]
+pub struct MeetingHistoryMessage {
+    pub meeting_id: u32,
+    pub elections: Vec<PastElection>,
+}
+
+/// One cohort's committed ballots, tallied as the election does
+/// and recounted independently
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CohortAudit {
+    pub cohort: u32,
+    /// Topic texts, after merging identical proposals
+    pub topics: Vec<String>,
+    /// Each voter's scores for `topics`, without the voter's identity
+    pub ballots: Vec<Vec<u32>>,
+    /// The election's Borda points for `topics`, canonicalized
+    pub reported: Vec<u32>,
+    /// Borda points for `topics` from the independent recount
+    pub recount: Vec<u32>,
+    /// Indices into `topics` of pairs the recount orders differently
+    pub discrepancies: Vec<(u32, u32)>,
+}
+
+/// One cohort's ballots from those who consented to research,
+/// without who cast them or what the meeting and its topics were
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct ResearchCohort {
+    /// A number the export gives the meeting, shared by its cohorts and rounds
+    pub meeting: u32,
+    /// Each row is a ballot, in no particular order,
+    /// giving each topic its points, higher for topics ranked higher.
+    /// The columns are the cohort's topics, in random order.
+    pub ballots: Vec<Vec<u32>>,
+}
+
+/// What `GET /admin/research/ballots` exports
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResearchExport {
+    pub cohorts: Vec<ResearchCohort>,
+}
+
+/// What `GET /admin/meetings/<id>/audit` reports
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ElectionAudit {
+    pub meeting_id: u32,
+    pub cohorts: Vec<CohortAudit>,
+}
+
+impl ElectionAudit {
+    pub fn n_discrepancies(&self) -> usize {
+        self.cohorts.iter().map(|c| c.discrepancies.len()).sum()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct InstanceInfo {
+    pub schema_version: u32,
+    /// The trunk build hash of the UI bundle the server is serving, if found
+    pub asset_version: Option<String>,
+    #[serde(default)]
+    pub build: Option<BuildInfo>,
+    /// Whether the API's `[faults]` table lets requests ask for injected faults
+    #[serde(default)]
+    pub fault_injection: bool,
+    /// Whether the instance shares consenting users' ballots for research
+    #[serde(default)]
+    pub research: bool,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, Hash, PartialEq, Eq)]
@@ -27,32 +397,820 @@ pub struct Meeting {
     pub id: u32,
     pub n_joined: u32,
     pub n_registered: u32,
+    /// Attendees needed before the meeting can start
+    pub min_attendees: u32,
+    /// When registration closes, in seconds since the Unix epoch
+    #[serde(default)]
+    pub registration_deadline: Option<i64>,
+    /// When topic submission closes, in seconds since the Unix epoch.
+    /// Until then, registered users submit and rank topics for the meeting,
+    /// and attendees' ballots start with those instead of their own topics.
+    #[serde(default)]
+    pub submission_deadline: Option<i64>,
+    /// Minutes cohorts have to vote once the meeting starts or begins a round,
+    /// after which ballots not yet submitted count as they stand
+    #[serde(default)]
+    pub voting_minutes: Option<u32>,
+    /// Attendees per cohort when the meeting starts
+    #[serde(default = "default_cohort_size")]
+    pub cohort_size: u32,
+    #[serde(default)]
+    pub veto_policy: VetoPolicy,
+    #[serde(default)]
+    pub voting_method: VotingMethod,
+    /// Topics each cohort elects
+    #[serde(default = "default_n_winners")]
+    pub n_winners: u32,
+    /// Cohorts are drawn from attendees who prefer the same conversation language
+    #[serde(default)]
+    pub match_language: bool,
+    /// Own topics each attendee brings to the ballots,
+    /// chosen with `POST /meeting/<id>/my_topics` or else their top-ranked
+    #[serde(default = "default_topics_per_attendee")]
+    pub topics_per_attendee: u32,
+    /// Cohort members are asked after voting whether to share their emails,
+    /// with `PUT /meeting/<id>/contact_sharing`
+    #[serde(default)]
+    pub share_contacts: bool,
+    /// The code for `POST /join_code`, given to those who may share invites
+    #[serde(default)]
+    pub join_code: Option<String>,
+}
+
+fn default_cohort_size() -> u32 {
+    COHORT_QUORUM as u32
+}
+
+fn default_n_winners() -> u32 {
+    DEFAULT_N_WINNERS
+}
+
+fn default_topics_per_attendee() -> u32 {
+    DEFAULT_TOPICS_PER_ATTENDEE
+}
+
+pub fn validate_n_winners(n_winners: u32) -> Result<(), String> {
+    if (MIN_N_WINNERS..=MAX_N_WINNERS).contains(&n_winners) {
+        Ok(())
+    } else {
+        Err(format!(
+            "the number of winning topics must be from {MIN_N_WINNERS} to {MAX_N_WINNERS}"
+        ))
+    }
+}
+
+pub fn validate_topics_per_attendee(n_topics: u32) -> Result<(), String> {
+    if (MIN_TOPICS_PER_ATTENDEE..=MAX_TOPICS_PER_ATTENDEE).contains(&n_topics) {
+        Ok(())
+    } else {
+        Err(format!(
+            "the number of topics per attendee must be from {MIN_TOPICS_PER_ATTENDEE} to {MAX_TOPICS_PER_ATTENDEE}"
+        ))
+    }
+}
+
+pub fn validate_voting_minutes(minutes: u32) -> Result<(), String> {
+    if (MIN_VOTING_MINUTES..=MAX_VOTING_MINUTES).contains(&minutes) {
+        Ok(())
+    } else {
+        Err(format!(
+            "the voting time must be from {MIN_VOTING_MINUTES} to {MAX_VOTING_MINUTES} minutes"
+        ))
+    }
+}
+
+pub fn validate_cohort_size(size: u32) -> Result<(), String> {
+    if (MIN_COHORT_SIZE..=MAX_COHORT_SIZE).contains(&size) {
+        Ok(())
+    } else {
+        Err(format!(
+            "cohort size must be from {MIN_COHORT_SIZE} to {MAX_COHORT_SIZE}"
+        ))
+    }
+}
+
+impl Meeting {
+    /// Whether people may still register at `now`, in seconds since the Unix epoch
+    pub fn registration_open(&self, now: i64) -> bool {
+        !matches!(self.registration_deadline, Some(deadline) if deadline <= now)
+    }
+
+    /// Whether registered users may still submit and rank topics at `now`
+    pub fn submissions_open(&self, now: i64) -> bool {
+        matches!(self.submission_deadline, Some(deadline) if now < deadline)
+    }
+}
+
+/// A change to a meeting, pushed to clients by `GET /meeting/<id>/events`
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct MeetingEvent {
+    pub meeting: u32,
+    pub kind: MeetingEventKind,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MeetingEventKind {
+    /// Someone joined or left the meeting.
+    AttendanceChanged,
+    /// Someone submitted their ballot, or took it back.
+    Voted,
+    /// The meeting started or began a new round, and its attendees were assigned to cohorts.
+    CohortsCreated,
+    /// Every member of some cohort has voted, so its results are final.
+    ResultsReady,
+    /// Someone opened their cohort's conferencing room.
+    RoomJoined,
+    /// A cohort recorded or revised its outcome.
+    OutcomeRecorded,
+}
+
+/// The portable setup of a meeting, as exported by `GET /meetings/<id>/config_export`
+/// and accepted by `POST /meetings/import`.
+/// Fields added after the first format version have defaults,
+/// so older exports remain importable.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct MeetingConfig {
+    /// Format version, which must not exceed `MEETING_CONFIG_VERSION`
+    pub version: u32,
+    /// Name for the meeting created on import, which must not already be in use
+    pub name: String,
+    /// Host-suggested topics injected into every attendee's ballot
+    #[serde(default)]
+    pub seed_topics: Vec<String>,
+    /// Questions every registrant must answer
+    #[serde(default)]
+    pub registration_fields: Vec<String>,
+    /// Reading material for seed topics, keyed by the topic's text
+    #[serde(default)]
+    pub seed_topic_links: BTreeMap<String, Vec<String>>,
+    /// Attendees per cohort
+    #[serde(default = "default_cohort_size")]
+    pub cohort_size: u32,
+    #[serde(default)]
+    pub veto_policy: VetoPolicy,
+    #[serde(default)]
+    pub voting_method: VotingMethod,
+    /// Topics each cohort elects
+    #[serde(default = "default_n_winners")]
+    pub n_winners: u32,
+    /// Cohorts are drawn from attendees who prefer the same conversation language
+    #[serde(default)]
+    pub match_language: bool,
+    /// Own topics each attendee brings to the ballots
+    #[serde(default = "default_topics_per_attendee")]
+    pub topics_per_attendee: u32,
+    /// Cohort members are asked after voting whether to share their emails
+    #[serde(default)]
+    pub share_contacts: bool,
+}
+
+impl MeetingConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.version == 0 || self.version > MEETING_CONFIG_VERSION {
+            return Err(format!("unsupported config version {}", self.version));
+        }
+        if self.name.trim().is_empty() {
+            return Err("meeting name is empty".to_owned());
+        }
+        if self.name.len() > MAX_TEXT_LEN {
+            return Err(format!("meeting name exceeds {MAX_TEXT_LEN} bytes"));
+        }
+        for topic in self.seed_topics.iter() {
+            if topic.trim().is_empty() {
+                return Err("seed topic is empty".to_owned());
+            }
+            if topic.len() > MAX_TEXT_LEN {
+                return Err(format!("seed topic exceeds {MAX_TEXT_LEN} bytes"));
+            }
+        }
+        for label in self.registration_fields.iter() {
+            validate_registration_text(label, "registration field")?;
+        }
+        for (topic, links) in self.seed_topic_links.iter() {
+            if !self.seed_topics.contains(topic) {
+                return Err(format!("links for \"{topic}\", which is not a seed topic"));
+            }
+            validate_topic_links(links)?;
+        }
+        validate_cohort_size(self.cohort_size)?;
+        validate_topics_per_attendee(self.topics_per_attendee)?;
+        validate_n_winners(self.n_winners)
+    }
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct MeetingMessage {
     pub meeting: Meeting,
     pub score: u32,
+    pub permissions: MeetingPermissions,
+    pub registration_fields: Vec<RegistrationField>,
+    /// Who created the meeting, or None for meetings from before ownership
+    #[serde(default)]
+    pub owner: Option<String>,
+    #[serde(default)]
+    pub role: MeetingRole,
+}
+
+/// The requesting user's part in running a meeting
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MeetingRole {
+    /// Created the meeting and chooses its co-organizers
+    Owner,
+    /// Manages the meeting alongside the owner
+    Organizer,
+    #[default]
+    Participant,
+}
+
+/// What the requesting user may do with a meeting, so the UI can hide
+/// or disable controls that the server would refuse.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct MeetingPermissions {
+    pub can_start: bool,
+    pub can_delete: bool,
+    pub can_rename: bool,
+    /// May add and remove co-organizers
+    pub can_manage_organizers: bool,
+    pub can_seed_topics: bool,
+    /// May fetch the meeting's invite QR code
+    pub can_share_invite: bool,
+    /// May add registration questions and export the answers
+    pub can_manage_registration: bool,
+    /// May see which cohorts have opened their conferencing rooms
+    pub can_view_room_joins: bool,
+    /// May see every cohort's outcome and export the meeting summary
+    #[serde(default)]
+    pub can_view_outcomes: bool,
+    /// May mark the meeting's topics as duplicates until it starts
+    #[serde(default)]
+    pub can_merge_topics: bool,
+}
+
+/// A meeting's owner and co-organizers, from `GET /meeting/<id>/organizers`
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MeetingOrganizersMessage {
+    pub owner: Option<String>,
+    pub organizers: Vec<String>,
+}
+
+/// A meeting as administrators see it, from `GET /admin/meetings`
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AdminMeeting {
+    pub meeting: Meeting,
+    pub owner: Option<String>,
+    /// Whether the attendees have been put into cohorts
+    pub started: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AdminMeetingsMessage {
+    pub meetings: Vec<AdminMeeting>,
+    /// How many meetings there are, of which `meetings` is one page
+    #[serde(default)]
+    pub total: u32,
+    /// Where the page starts among them
+    #[serde(default)]
+    pub offset: u32,
+}
+
+/// An account, from `GET /admin/users`
+#[derive(Debug, Deserialize, Serialize)]
+pub struct UserAccount {
+    pub email: String,
+    pub is_admin: bool,
+    /// Deactivated by an administrator, so it can't log in or join meetings
+    #[serde(default)]
+    pub disabled: bool,
+}
+
+/// Deactivates or reactivates an account, for `PUT /admin/users/<email>/active`
+#[derive(Deserialize, Serialize)]
+pub struct AccountActiveMessage {
+    pub active: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct UserAccountsMessage {
+    pub users: Vec<UserAccount>,
+    /// How many accounts there are, of which `users` is one page
+    #[serde(default)]
+    pub total: u32,
+    /// Where the page starts among them
+    #[serde(default)]
+    pub offset: u32,
+}
+
+/// Invites someone to sign up, for `POST /admin/invitations`
+#[derive(Deserialize, Serialize)]
+pub struct NewInvitationMessage {
+    pub email: String,
+}
+
+/// An invitation not yet used, from `GET /admin/invitations`
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct Invitation {
+    pub email: String,
+    pub invited_by: String,
+    /// The signup link the invitation email carries, to share another way
+    pub url: String,
+    /// Seconds since the Unix epoch
+    pub created_at: i64,
+    /// Seconds since the Unix epoch
+    pub expires_at: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct InvitationsMessage {
+    pub invitations: Vec<Invitation>,
+}
+
+/// A signup waiting for an administrator, from `GET /admin/join_requests`
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct JoinRequest {
+    pub email: String,
+    /// Seconds since the Unix epoch
+    pub requested_at: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct JoinRequestsMessage {
+    pub requests: Vec<JoinRequest>,
+}
+
+/// Approves or declines a join request, for `PUT /admin/join_requests/<email>`
+#[derive(Deserialize, Serialize)]
+pub struct JoinDecisionMessage {
+    pub approved: bool,
+}
+
+/// Adds a co-organizer by the email address of their account
+#[derive(Deserialize, Serialize)]
+pub struct NewOrganizerMessage {
+    pub email: String,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct RenameMeetingMessage {
+    pub name: String,
+}
+
+/// A starting point for new meetings, listed by `GET /meeting_templates`.
+/// The server has built-in templates, and its config file can add more.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct MeetingTemplate {
+    pub title: String,
+    pub description: String,
+    #[serde(default)]
+    pub seed_topics: Vec<String>,
+    #[serde(default)]
+    pub registration_fields: Vec<String>,
+}
+
+impl MeetingTemplate {
+    /// The setup of a new meeting with this template's settings
+    pub fn config(&self, name: &str) -> MeetingConfig {
+        MeetingConfig {
+            version: MEETING_CONFIG_VERSION,
+            name: name.to_owned(),
+            seed_topics: self.seed_topics.clone(),
+            registration_fields: self.registration_fields.clone(),
+            seed_topic_links: BTreeMap::new(),
+            cohort_size: default_cohort_size(),
+            veto_policy: VetoPolicy::default(),
+            voting_method: VotingMethod::default(),
+            n_winners: default_n_winners(),
+            match_language: false,
+            topics_per_attendee: default_topics_per_attendee(),
+            share_contacts: false,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct MeetingTemplatesMessage {
+    pub templates: Vec<MeetingTemplate>,
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct MeetingsMessage {
     pub meetings: Vec<MeetingMessage>,
+    /// The order of `meetings`
+    #[serde(default)]
+    pub sort: MeetingSort,
+    /// How many meetings match the search, of which `meetings` may be one page
+    #[serde(default)]
+    pub total: u32,
+    /// Where the page starts among them
+    #[serde(default)]
+    pub offset: u32,
+}
+
+/// How the meetings list is ordered, kept as each user's preference
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MeetingSort {
+    /// The user's own ranking, highest first
+    #[default]
+    Ranking,
+    Name,
+    /// Most registrations first
+    Registered,
+    /// Soonest registration deadline first, then meetings without one
+    Deadline,
+    /// Newest first
+    Recent,
+}
+
+impl MeetingSort {
+    pub const ALL: [MeetingSort; 5] = [
+        MeetingSort::Ranking,
+        MeetingSort::Name,
+        MeetingSort::Registered,
+        MeetingSort::Deadline,
+        MeetingSort::Recent,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MeetingSort::Ranking => "ranking",
+            MeetingSort::Name => "name",
+            MeetingSort::Registered => "registered",
+            MeetingSort::Deadline => "deadline",
+            MeetingSort::Recent => "recent",
+        }
+    }
+
+    /// Orders two meetings, each with the user's score for it,
+    /// comparing names with `collation`.
+    /// Ties go to the newer meeting, so the order is the same on every fetch.
+    pub fn compare(
+        self,
+        collation: &Collation,
+        (a, a_score): (&Meeting, u32),
+        (b, b_score): (&Meeting, u32),
+    ) -> Ordering {
+        let by = match self {
+            MeetingSort::Ranking => b_score.cmp(&a_score),
+            MeetingSort::Name => collation.compare(&a.name, &b.name),
+            MeetingSort::Registered => b.n_registered.cmp(&a.n_registered),
+            MeetingSort::Deadline => {
+                let soonest =
+                    |m: &Meeting| (m.registration_deadline.is_none(), m.registration_deadline);
+                soonest(a).cmp(&soonest(b))
+            }
+            MeetingSort::Recent => Ordering::Equal,
+        };
+        by.then(b.id.cmp(&a.id))
+    }
+}
+
+/// A user's preferred conversation language, as a language subtag like "de",
+/// or None for no preference
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct LanguageMessage {
+    pub language: Option<String>,
+}
+
+/// Whether the user lets their ballots be shared, anonymized, for research
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct ResearchConsentMessage {
+    pub consent: bool,
+}
+
+/// The user's avatar, from `GET /avatar`
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct AvatarMessage {
+    /// The image cohort members see beside the user's name, if any
+    pub url: Option<String>,
+    /// Whether the server takes images uploaded with `PUT /avatar`
+    pub uploads: bool,
+}
+
+/// Stores the user's preferred `MeetingSort`
+#[derive(Deserialize, Serialize)]
+pub struct MeetingSortMessage {
+    pub sort: MeetingSort,
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct NewMeeting<'r> {
     pub name: Cow<'r, str>,
+    #[serde(default = "default_cohort_size")]
+    pub cohort_size: u32,
+    #[serde(default)]
+    pub veto_policy: VetoPolicy,
+    #[serde(default)]
+    pub voting_method: VotingMethod,
+    #[serde(default = "default_n_winners")]
+    pub n_winners: u32,
+    #[serde(default)]
+    pub match_language: bool,
+    #[serde(default = "default_topics_per_attendee")]
+    pub topics_per_attendee: u32,
+    #[serde(default)]
+    pub share_contacts: bool,
 }
 
 #[derive(Deserialize, Serialize)]
 pub struct NewTopicMessage {
     pub new_topic: String,
+    /// Context for the topic, in Markdown
+    #[serde(default)]
+    pub description: String,
+}
+
+impl NewTopicMessage {
+    pub fn validate(&self) -> Result<(), String> {
+        validate_topic_description(&self.description)
+    }
+}
+
+/// How a notification reaches a user
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationChannel {
+    Email,
+    WebPush,
+    Webhook,
+}
+
+impl NotificationChannel {
+    pub const ALL: [NotificationChannel; 3] = [
+        NotificationChannel::Email,
+        NotificationChannel::WebPush,
+        NotificationChannel::Webhook,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            NotificationChannel::Email => "email",
+            NotificationChannel::WebPush => "web_push",
+            NotificationChannel::Webhook => "webhook",
+        }
+    }
+
+    /// Whether users who never chose get notifications this way
+    pub fn enabled_by_default(self) -> bool {
+        self == NotificationChannel::Email
+    }
+}
+
+/// Something a user can be notified about
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationEvent {
+    /// A meeting the user registered for has started.
+    MeetingStarted,
+    /// The user's cohort has election results.
+    ResultsReady,
+    /// Each day's summary of upcoming and new meetings and popular topics
+    DailyDigest,
+    /// The same summary, once a week
+    WeeklyDigest,
+}
+
+impl NotificationEvent {
+    pub const ALL: [NotificationEvent; 4] = [
+        NotificationEvent::MeetingStarted,
+        NotificationEvent::ResultsReady,
+        NotificationEvent::DailyDigest,
+        NotificationEvent::WeeklyDigest,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            NotificationEvent::MeetingStarted => "meeting_started",
+            NotificationEvent::ResultsReady => "results_ready",
+            NotificationEvent::DailyDigest => "daily_digest",
+            NotificationEvent::WeeklyDigest => "weekly_digest",
+        }
+    }
+
+    /// Whether users who never chose get this event by `channel`.
+    /// Digests go only to those who turn them on.
+    pub fn enabled_by_default(self, channel: NotificationChannel) -> bool {
+        let digest = matches!(
+            self,
+            NotificationEvent::DailyDigest | NotificationEvent::WeeklyDigest
+        );
+        channel.enabled_by_default() && !digest
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct NotificationPreference {
+    pub event: NotificationEvent,
+    pub channel: NotificationChannel,
+    pub enabled: bool,
+}
+
+/// Every event and channel combination from `GET /preferences/notifications`,
+/// or just the changed ones for `PUT`
+#[derive(Deserialize, Serialize)]
+pub struct NotificationPreferencesMessage {
+    pub preferences: Vec<NotificationPreference>,
+}
+
+/// How someone attends a meeting
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AttendeeRole {
+    /// Brings topics and votes with their cohort.
+    #[default]
+    Participant,
+    /// Joins a cohort without bringing topics or voting,
+    /// like a facilitator or note-taker.
+    Observer,
+}
+
+impl AttendeeRole {
+    pub const ALL: [AttendeeRole; 2] = [AttendeeRole::Participant, AttendeeRole::Observer];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AttendeeRole::Participant => "participant",
+            AttendeeRole::Observer => "observer",
+        }
+    }
+}
+
+/// Optionally sent to `POST /meeting/<id>/attendees`, which otherwise attends as a participant
+#[derive(Default, Deserialize, Serialize)]
+pub struct AttendMeetingMessage {
+    #[serde(default)]
+    pub participate_as: AttendeeRole,
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct ParticipateMeetingMessage {
     pub participate: bool,
+    /// Answers to every registration field when registering
+    #[serde(default)]
+    pub answers: Vec<RegistrationAnswer>,
+}
+
+/// Sent to `POST /join_code` with a code as the attendee typed it
+#[derive(Deserialize, Serialize)]
+pub struct JoinCodeMessage {
+    pub code: String,
+}
+
+/// The meeting a join code registered and joined the user to
+#[derive(Debug, Deserialize, Serialize)]
+pub struct JoinedMeetingMessage {
+    pub meeting: u32,
+    pub name: String,
+}
+
+pub const JOIN_CODE_PREFIX: &str = "HALL-";
+
+/// A join code as the API gives them, like "HALL-4921",
+/// from one typed with any case, spacing, or dashes, or without the prefix,
+/// or None when it cannot be a join code
+pub fn normalize_join_code(code: &str) -> Option<String> {
+    let compact = code
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .collect::<String>()
+        .to_uppercase();
+    let prefix = JOIN_CODE_PREFIX.trim_end_matches('-');
+    let digits = compact.strip_prefix(prefix).unwrap_or(&compact);
+    let valid = (4..=11).contains(&digits.len()) && digits.chars().all(|c| c.is_ascii_digit());
+    valid.then(|| format!("{JOIN_CODE_PREFIX}{digits}"))
+}
+
+/// Sets when registration closes, or reopens it when `deadline` is `None`
+#[derive(Deserialize, Serialize)]
+pub struct RegistrationDeadlineMessage {
+    pub deadline: Option<i64>,
+}
+
+/// Opens topic submission until `deadline`, or ends the phase when it is `None`
+#[derive(Deserialize, Serialize)]
+pub struct SubmissionDeadlineMessage {
+    pub deadline: Option<i64>,
+}
+
+/// Limits voting to `minutes` from each start or round, or lifts the limit when it is `None`.
+/// `PUT /meeting/<id>/voting_minutes` takes effect when the next round starts.
+#[derive(Deserialize, Serialize)]
+pub struct VotingMinutesMessage {
+    pub minutes: Option<u32>,
+}
+
+/// The IDs of the user's own topics they bring to a meeting's ballots,
+/// from `GET /meeting/<id>/my_topics` and for `POST` there to replace them.
+/// None means the user's top-ranked topics, up to the meeting's `topics_per_attendee`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct MyTopicsMessage {
+    pub topics: Vec<u32>,
+}
+
+/// The topics submitted to a meeting, from `GET /meeting/<id>/submissions`,
+/// with the user's ranking in their scores, best first
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SubmittedTopicsMessage {
+    pub deadline: Option<i64>,
+    pub topics: Vec<UserTopic>,
+}
+
+/// Why `POST /meeting/<id>/participants` refused a registration
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(tag = "error", rename_all = "snake_case")]
+pub enum RegistrationError {
+    /// Registration closed at `deadline`, in seconds since the Unix epoch.
+    RegistrationClosed { deadline: i64 },
+    /// The answers to the registration fields were unacceptable.
+    InvalidAnswers { reason: String },
+    /// An administrator deactivated the account.
+    AccountDisabled,
+}
+
+/// Which members of a cohort opened its conferencing room link
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct CohortRoomJoins {
+    pub cohort: u32,
+    pub members: Vec<String>,
+    pub joined: Vec<String>,
+}
+
+/// Every cohort of a started meeting, from `GET /meeting/<id>/room_joins`
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct RoomJoinsMessage {
+    pub cohorts: Vec<CohortRoomJoins>,
+}
+
+/// Whether a cohort member shares their email with the rest of the cohort,
+/// for `PUT /meeting/<id>/contact_sharing` in a meeting that shares contacts
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct ContactSharingMessage {
+    pub share: bool,
+}
+
+/// The user's choice about sharing their email, and what sharing has revealed.
+/// Contacts are only revealed among members who each chose to share.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct ContactSharing {
+    /// None until the user chooses
+    pub share: Option<bool>,
+    /// Emails of the other cohort members who chose to share, empty unless the user did
+    pub contacts: Vec<String>,
+}
+
+/// What a cohort decided to do next, recorded by any member after the conversation.
+/// `PUT /meeting/<id>/outcome` replaces the cohort's earlier outcome.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct OutcomeMessage {
+    pub text: String,
+    /// Ids of the cohort's elected topics that the decision follows up on
+    #[serde(default)]
+    pub topics: Vec<u32>,
+}
+
+impl OutcomeMessage {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.text.trim().is_empty() {
+            return Err("outcome is empty".to_owned());
+        }
+        if self.text.len() > MAX_OUTCOME_LEN {
+            return Err(format!("outcome exceeds {MAX_OUTCOME_LEN} bytes"));
+        }
+        for (i, topic) in self.topics.iter().enumerate() {
+            if self.topics[..i].contains(topic) {
+                return Err(format!("topic {topic} is repeated"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An elected topic that an outcome refers to
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct OutcomeTopic {
+    pub id: u32,
+    pub text: String,
+}
+
+/// One cohort's recorded outcome
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct CohortOutcome {
+    pub cohort: u32,
+    pub members: Vec<String>,
+    pub text: String,
+    pub topics: Vec<OutcomeTopic>,
+    pub recorded_by: String,
+    /// Seconds since the epoch of the latest revision
+    pub recorded_at: i64,
+}
+
+/// Every cohort outcome recorded for a meeting, from `GET /meeting/<id>/outcomes`
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct MeetingOutcomesMessage {
+    pub meeting_id: u32,
+    pub meeting_name: String,
+    pub outcomes: Vec<CohortOutcome>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -60,14 +1218,326 @@ pub struct RegisteredMeetingsMessage {
     pub meetings: Vec<u32>,
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct RegistrationAnswer {
+    pub field: u32,
+    pub answer: String,
+}
+
+/// A question the organizer asks everyone who registers for a meeting
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct RegistrationField {
+    pub id: u32,
+    pub label: String,
+}
+
+/// Questions to add to a meeting's registration
+#[derive(Deserialize, Serialize)]
+pub struct RegistrationFieldsMessage {
+    pub labels: Vec<String>,
+}
+
+/// Checks a registration field label or answer.
+pub fn validate_registration_text(text: &str, what: &str) -> Result<(), String> {
+    if text.trim().is_empty() {
+        return Err(format!("{what} is empty"));
+    }
+    if text.len() > MAX_TEXT_LEN {
+        return Err(format!("{what} exceeds {MAX_TEXT_LEN} bytes"));
+    }
+    Ok(())
+}
+
 #[derive(Deserialize, Serialize)]
 pub struct ScoreMessage {
     pub score: u32,
 }
 
+/// Scores for several topics at once, as (topic id, score) pairs,
+/// like the two topics that trade places when one moves in a ranking
+#[derive(Deserialize, Serialize)]
+pub struct ScoresMessage {
+    pub scores: Vec<(u32, u32)>,
+}
+
+impl ScoresMessage {
+    pub fn validate(&self) -> Result<(), String> {
+        for (i, (topic, _)) in self.scores.iter().enumerate() {
+            if self.scores[..i].iter().any(|(t, _)| t == topic) {
+                return Err(format!("topic {topic} is repeated"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Topics an organizer suggests for every attendee's ballot
+#[derive(Serialize, Deserialize)]
+pub struct SeedTopicsMessage {
+    pub topics: Vec<String>,
+}
+
+/// Why `PUT /meeting/<id>/start` refused to start a meeting
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(tag = "error", rename_all = "snake_case")]
+pub enum StartMeetingError {
+    NotAttending,
+    NotOrganizer,
+    TooFewAttendees {
+        min_attendees: u32,
+        n_attendees: u32,
+    },
+    /// Attendees left while the cohorts were being formed.
+    NotEnoughParticipants {
+        needed: u32,
+        have: u32,
+    },
+    /// Some cohort's ballot would have fewer topics than the meeting elects.
+    TooFewTopics {
+        n_winners: u32,
+        n_topics: u32,
+    },
+    /// Fewer than half of those registered have joined,
+    /// so the organizer is asked to start again with `?confirm=true` or wait.
+    ConfirmRequired {
+        n_registered: u32,
+        /// Those registered who are attending
+        n_joined: u32,
+    },
+}
+
+/// What an API refusal was about, sent as the "code" of its error body
+/// beside an "error" that describes it for logs and scripts.
+/// Refusals with structured bodies, like a `StartMeetingError`,
+/// carry their code in "error" instead.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    DatabaseError,
+    InternalError,
+    NotFound,
+    /// The request's values were unacceptable, like an empty meeting name.
+    InvalidRequest,
+    NameTaken,
+    UnknownAccount,
+    NotOrganizer,
+    NotOwner,
+    NotProposer,
+    NotAttendee,
+    NotCohortMember,
+    NotOnBallot,
+    MeetingStarted,
+    CohortVoting,
+    AlreadyVoted,
+    VetoesOff,
+    BackupsOff,
+    BackupRunning,
+    OwnAccount,
+    AccountDisabled,
+    /// An Idempotency-Key came back with a different request.
+    KeyReused,
+    /// An Idempotency-Key came back before its first request finished.
+    RequestInProgress,
+    /// The meeting has no cohorts yet.
+    MeetingNotStarted,
+    NotRegistered,
+    /// The meeting takes no topic submissions, or no longer does.
+    SubmissionsClosed,
+    /// The meeting's ballots hold its submitted topics rather than attendees' own.
+    SubmittedTopicsOnly,
+    /// Someone already has an account with the email address.
+    AccountExists,
+    /// The user attends the meeting as an observer, without a ballot.
+    Observing,
+    /// The meeting doesn't ask its cohorts to share contacts.
+    ContactSharingOff,
+    /// Everyone in the user's cohort has voted, so its ballots are final.
+    CohortFinished,
+    /// The API is handling as many expensive requests as it can,
+    /// and the response's Retry-After says when to try again.
+    Busy,
+    /// The instance doesn't share ballots for research.
+    ResearchOff,
+}
+
+impl ErrorCode {
+    pub const ALL: [ErrorCode; 32] = [
+        ErrorCode::DatabaseError,
+        ErrorCode::InternalError,
+        ErrorCode::NotFound,
+        ErrorCode::InvalidRequest,
+        ErrorCode::NameTaken,
+        ErrorCode::UnknownAccount,
+        ErrorCode::NotOrganizer,
+        ErrorCode::NotOwner,
+        ErrorCode::NotProposer,
+        ErrorCode::NotAttendee,
+        ErrorCode::NotCohortMember,
+        ErrorCode::NotOnBallot,
+        ErrorCode::MeetingStarted,
+        ErrorCode::CohortVoting,
+        ErrorCode::AlreadyVoted,
+        ErrorCode::VetoesOff,
+        ErrorCode::BackupsOff,
+        ErrorCode::BackupRunning,
+        ErrorCode::OwnAccount,
+        ErrorCode::AccountDisabled,
+        ErrorCode::KeyReused,
+        ErrorCode::RequestInProgress,
+        ErrorCode::MeetingNotStarted,
+        ErrorCode::NotRegistered,
+        ErrorCode::SubmissionsClosed,
+        ErrorCode::SubmittedTopicsOnly,
+        ErrorCode::AccountExists,
+        ErrorCode::Observing,
+        ErrorCode::ContactSharingOff,
+        ErrorCode::CohortFinished,
+        ErrorCode::Busy,
+        ErrorCode::ResearchOff,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::DatabaseError => "database_error",
+            ErrorCode::InternalError => "internal_error",
+            ErrorCode::NotFound => "not_found",
+            ErrorCode::InvalidRequest => "invalid_request",
+            ErrorCode::NameTaken => "name_taken",
+            ErrorCode::UnknownAccount => "unknown_account",
+            ErrorCode::NotOrganizer => "not_organizer",
+            ErrorCode::NotOwner => "not_owner",
+            ErrorCode::NotProposer => "not_proposer",
+            ErrorCode::NotAttendee => "not_attendee",
+            ErrorCode::NotCohortMember => "not_cohort_member",
+            ErrorCode::NotOnBallot => "not_on_ballot",
+            ErrorCode::MeetingStarted => "meeting_started",
+            ErrorCode::CohortVoting => "cohort_voting",
+            ErrorCode::AlreadyVoted => "already_voted",
+            ErrorCode::VetoesOff => "vetoes_off",
+            ErrorCode::BackupsOff => "backups_off",
+            ErrorCode::BackupRunning => "backup_running",
+            ErrorCode::OwnAccount => "own_account",
+            ErrorCode::AccountDisabled => "account_disabled",
+            ErrorCode::KeyReused => "key_reused",
+            ErrorCode::RequestInProgress => "request_in_progress",
+            ErrorCode::MeetingNotStarted => "meeting_not_started",
+            ErrorCode::NotRegistered => "not_registered",
+            ErrorCode::SubmissionsClosed => "submissions_closed",
+            ErrorCode::SubmittedTopicsOnly => "submitted_topics_only",
+            ErrorCode::AccountExists => "account_exists",
+            ErrorCode::Observing => "observing",
+            ErrorCode::ContactSharingOff => "contact_sharing_off",
+            ErrorCode::CohortFinished => "cohort_finished",
+            ErrorCode::Busy => "busy",
+            ErrorCode::ResearchOff => "research_off",
+        }
+    }
+}
+
+/// How far the user's cohort has got with its election,
+/// sent as the `status` of `ElectionResults`: a `code`, with a `detail` for some.
+/// A code this build doesn't know, from a newer API, arrives as `Unknown`,
+/// so an older UI still gets the rest of the results and can report it.
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(tag = "code", content = "detail", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ElectionStatus {
+    /// The meeting has not started, so there are no cohorts yet.
+    CohortPending,
+    /// The meeting started without the user in a cohort.
+    NoCohort,
+    /// The cohort is still voting, with `n_voted` of its `n_members` ballots in.
+    WaitingForVotes { n_voted: u32, n_members: u32 },
+    /// Someone in the user's cohort no longer attends the meeting,
+    /// so the cohort's ballots can't all come in.
+    CohortMismatch,
+    /// Every ballot in the cohort is in, and the results hold its elected topics.
+    Finished,
+    /// A code this build doesn't know, kept when deserializing instead of failing.
+    /// The API never sends it.
+    Unknown,
+}
+
+// Read by hand, since serde's fallback variant can't take a detail,
+// and a newer API's code may come with one.
+impl<'de> Deserialize<'de> for ElectionStatus {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Tagged {
+            code: String,
+            #[serde(default)]
+            detail: serde_json::Value,
+        }
+        #[derive(Deserialize)]
+        struct WaitingDetail {
+            n_voted: u32,
+            n_members: u32,
+        }
+        let Tagged { code, detail } = Tagged::deserialize(deserializer)?;
+        Ok(match code.as_str() {
+            "cohort_pending" => ElectionStatus::CohortPending,
+            "no_cohort" => ElectionStatus::NoCohort,
+            "waiting_for_votes" => {
+                let WaitingDetail { n_voted, n_members } =
+                    serde_json::from_value(detail).map_err(de::Error::custom)?;
+                ElectionStatus::WaitingForVotes { n_voted, n_members }
+            }
+            "cohort_mismatch" => ElectionStatus::CohortMismatch,
+            "finished" => ElectionStatus::Finished,
+            _ => ElectionStatus::Unknown,
+        })
+    }
+}
+
+impl ElectionStatus {
+    /// The status's code, as sent
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ElectionStatus::CohortPending => "cohort_pending",
+            ElectionStatus::NoCohort => "no_cohort",
+            ElectionStatus::WaitingForVotes { .. } => "waiting_for_votes",
+            ElectionStatus::CohortMismatch => "cohort_mismatch",
+            ElectionStatus::Finished => "finished",
+            ElectionStatus::Unknown => "unknown",
+        }
+    }
+}
+
+/// Everything the UI needs to reconcile its state after sleeping or regaining focus.
+#[derive(Serialize, Deserialize)]
+pub struct SyncMessage {
+    pub email: String,
+    pub attending_meetings: Vec<u32>,
+    pub registered_meetings: Vec<u32>,
+    pub meetings: Vec<MeetingMessage>,
+    /// The order of `meetings`, which is the user's preference
+    #[serde(default)]
+    pub sort: MeetingSort,
+    pub user_topics: Vec<UserTopic>,
+    pub election_results: Option<ElectionResults>,
+}
+
+/// Score changes ordered by topic and then by time
+#[derive(Deserialize, Serialize)]
+pub struct TopicHistoryMessage {
+    pub changes: Vec<TopicScoreChange>,
+}
+
+/// A user topic's score after one change, from `GET /user_topics/history`
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct TopicScoreChange {
+    pub topic: u32,
+    pub score: u32,
+    /// Seconds since the Unix epoch
+    pub recorded_at: i64,
+}
+
 #[derive(Clone, Deserialize, PartialEq)]
 pub struct UserIdMessage {
     pub email: String,
+    /// Whether the user may use the `/admin` endpoints
+    #[serde(default)]
+    pub is_admin: bool,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -75,18 +1545,596 @@ pub struct UserTopic {
     pub text: String,
     pub score: u32,
     pub id: u32,
+    /// Suggested by the meeting host rather than proposed by an attendee
+    pub seeded: bool,
+    /// Rank within the whole list, with zero for the highest score
+    pub position: u32,
+    /// Reading material, such as an article or RFC
+    #[serde(default)]
+    pub links: Vec<String>,
+    /// Everyone who proposed the topic, filled in for election results,
+    /// where cohort members' identical proposals are merged
+    #[serde(default)]
+    pub proposers: Vec<String>,
+    /// On a ballot, whether its voter vetoed the topic,
+    /// and in election results, whether any cohort member did
+    #[serde(default)]
+    pub vetoed: bool,
+    /// A personal reminder, which is never put on meeting ballots
+    #[serde(default)]
+    pub private: bool,
+    /// The proposer's context for the topic, in Markdown, or empty
+    #[serde(default)]
+    pub description: String,
+}
+
+/// Marks one of the user's topics private, or public again
+#[derive(Deserialize, Serialize)]
+pub struct TopicPrivacyMessage {
+    pub private: bool,
+}
+
+/// What happens to topics that cohort members veto
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum VetoPolicy {
+    /// Attendees cannot veto topics.
+    #[default]
+    Off,
+    /// A vetoed topic is never elected.
+    Exclude,
+    /// A vetoed topic is elected only when too few topics escaped vetoes.
+    Penalize,
+}
+
+impl VetoPolicy {
+    pub const ALL: [VetoPolicy; 3] = [VetoPolicy::Off, VetoPolicy::Exclude, VetoPolicy::Penalize];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            VetoPolicy::Off => "off",
+            VetoPolicy::Exclude => "exclude",
+            VetoPolicy::Penalize => "penalize",
+        }
+    }
+
+    /// Orders tallied topics best first, applying the policy to vetoed ones
+    pub fn rank(self, topics: &mut Vec<UserTopic>) {
+        if self == VetoPolicy::Exclude {
+            topics.retain(|t| !t.vetoed);
+        }
+        let demoted = |t: &UserTopic| self == VetoPolicy::Penalize && t.vetoed;
+        topics.sort_by(|a, b| demoted(a).cmp(&demoted(b)).then(b.score.cmp(&a.score)));
+    }
+}
+
+/// How a cohort's ballots are tallied into elected topics
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum VotingMethod {
+    /// Points for every topic ranked lower, summed over the cohort
+    #[default]
+    Borda,
+    /// Head-to-head majorities, so a topic the cohort prefers to each other topic wins
+    Condorcet,
+    /// Rounds eliminating the topic with the fewest first choices
+    InstantRunoff,
+}
+
+impl VotingMethod {
+    pub const ALL: [VotingMethod; 3] = [
+        VotingMethod::Borda,
+        VotingMethod::Condorcet,
+        VotingMethod::InstantRunoff,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            VotingMethod::Borda => "borda",
+            VotingMethod::Condorcet => "condorcet",
+            VotingMethod::InstantRunoff => "instant_runoff",
+        }
+    }
+}
+
+/// Vetoes one topic on the user's ballot, replacing any earlier veto,
+/// or with None withdraws the veto
+#[derive(Deserialize, Serialize)]
+pub struct VetoMessage {
+    pub topic: Option<u32>,
+}
+
+/// Replaces a topic's links, in the order given
+#[derive(Deserialize, Serialize)]
+pub struct TopicLinksMessage {
+    pub links: Vec<String>,
+}
+
+/// Replaces a topic's description, with an empty one removing it
+#[derive(Deserialize, Serialize)]
+pub struct TopicDescriptionMessage {
+    pub description: String,
+}
+
+/// Checks a topic's description, which may be empty
+pub fn validate_topic_description(description: &str) -> Result<(), String> {
+    if description.len() > MAX_DESCRIPTION_LEN {
+        return Err(format!("description exceeds {MAX_DESCRIPTION_LEN} bytes"));
+    }
+    Ok(())
+}
+
+/// One version of a topic's description
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct TopicEdit {
+    /// Counting from 0, the description the topic was created with
+    pub version: u32,
+    pub description: String,
+    pub edited_by: String,
+    /// When the edit was saved, in seconds since the Unix epoch, or `None` for version 0
+    pub edited_at: Option<i64>,
+}
+
+/// A topic's descriptions from `GET /topic/<id>/description/edits`, newest first,
+/// or none if it was never edited.
+/// Meetings that started before an edit keep the version from before it on their ballots.
+#[derive(Deserialize, Serialize)]
+pub struct TopicEditsMessage {
+    pub edits: Vec<TopicEdit>,
+}
+
+/// Marks a meeting's topics as duplicates of one another,
+/// so each voter's best score among them counts once.
+/// Topics already merged with one of these join the same group.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct MergeTopicsMessage {
+    pub topics: Vec<u32>,
+}
+
+impl MergeTopicsMessage {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.topics.len() < 2 {
+            return Err("merging takes at least two topics".to_owned());
+        }
+        for (i, topic) in self.topics.iter().enumerate() {
+            if self.topics[..i].contains(topic) {
+                return Err(format!("topic {topic} is repeated"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A topic on a meeting's ballots, before the meeting starts
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct CandidateTopic {
+    pub id: u32,
+    pub text: String,
+    pub seeded: bool,
+    /// The id shared by every topic in this one's merged group,
+    /// or None when the topic is not merged
+    pub merged_into: Option<u32>,
+}
+
+/// The topics organizers can merge, from `GET /meeting/<id>/candidate_topics`
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct CandidateTopicsMessage {
+    pub topics: Vec<CandidateTopic>,
+}
+
+/// Checks the links attached to one topic.
+/// Only http and https URLs are allowed, so a link cannot run script.
+pub fn validate_topic_links(links: &[String]) -> Result<(), String> {
+    if links.len() > MAX_TOPIC_LINKS {
+        return Err(format!("a topic has at most {MAX_TOPIC_LINKS} links"));
+    }
+    for (i, link) in links.iter().enumerate() {
+        if link.len() > MAX_URL_LEN {
+            return Err(format!("link exceeds {MAX_URL_LEN} bytes"));
+        }
+        if link.chars().any(|c| c.is_whitespace() || c.is_control()) {
+            return Err(format!("link \"{link}\" contains spaces"));
+        }
+        let lower = link.to_ascii_lowercase();
+        let rest = lower
+            .strip_prefix("https://")
+            .or_else(|| lower.strip_prefix("http://"));
+        match rest {
+            Some(rest) if !rest.starts_with(['/', '?', '#']) && !rest.is_empty() => {}
+            _ => return Err(format!("link \"{link}\" is not an http or https URL")),
+        }
+        if links[..i].contains(link) {
+            return Err(format!("link \"{link}\" is repeated"));
+        }
+    }
+    Ok(())
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct UserTopicsMessage {
     pub topics: Vec<UserTopic>,
+    #[serde(default)]
+    pub page: PageInfo,
 }
 
-pub fn argsort<T>(a: &[T]) -> Vec<usize>
-where
-    T: PartialOrd,
-{
-    let mut indexed: Vec<_> = a.iter().enumerate().collect();
-    indexed.sort_by(|(_i1, v1), (_i2, v2)| v1.partial_cmp(v2).unwrap());
-    indexed.into_iter().map(|(i, _v)| i).collect()
+#[cfg(test)]
+mod tests {
+    use super::{
+        normalize_join_code, validate_topic_links, validate_voting_minutes, AttendeeRole,
+        BuildInfo, Collation, ElectionStatus, ErrorCode, HistoryImport, ImportState, Meeting,
+        MeetingConfig, MeetingSort, MeetingTemplate, MergeTopicsMessage, NewTopicMessage,
+        NotificationChannel, NotificationEvent, OutcomeMessage, RegistrationError, ScoresMessage,
+        UserTopic, VetoPolicy, VotingMethod, COHORT_QUORUM, DEFAULT_N_WINNERS,
+        DEFAULT_TOPICS_PER_ATTENDEE, MAX_COHORT_SIZE, MAX_DESCRIPTION_LEN, MAX_N_WINNERS,
+        MAX_OUTCOME_LEN, MAX_TEXT_LEN, MAX_TOPICS_PER_ATTENDEE, MAX_TOPIC_LINKS,
+        MAX_VOTING_MINUTES, MEETING_CONFIG_VERSION, MIN_COHORT_SIZE, MIN_TOPICS_PER_ATTENDEE,
+        MIN_VOTING_MINUTES, VERSION,
+    };
+
+    fn config() -> MeetingConfig {
+        MeetingConfig {
+            version: MEETING_CONFIG_VERSION,
+            name: "Monday 9am Discord".to_owned(),
+            seed_topics: vec!["potato farming".to_owned()],
+            registration_fields: vec!["What's your team?".to_owned()],
+            seed_topic_links: [(
+                "potato farming".to_owned(),
+                vec!["https://example.com/potatoes".to_owned()],
+            )]
+            .into(),
+            cohort_size: 4,
+            veto_policy: VetoPolicy::Exclude,
+            voting_method: VotingMethod::Condorcet,
+            n_winners: 3,
+            match_language: true,
+            topics_per_attendee: 2,
+            share_contacts: true,
+        }
+    }
+
+    #[test]
+    fn test_meeting_config_validate() {
+        assert!(config().validate().is_ok());
+        let mut c = config();
+        c.version += 1;
+        assert!(c.validate().is_err());
+        c = config();
+        c.name = " ".to_owned();
+        assert!(c.validate().is_err());
+        c = config();
+        c.seed_topics.push("x".repeat(MAX_TEXT_LEN + 1));
+        assert!(c.validate().is_err());
+        c = config();
+        c.registration_fields.push("".to_owned());
+        assert!(c.validate().is_err());
+        c = config();
+        c.seed_topic_links.insert("turnips".to_owned(), vec![]);
+        assert!(c.validate().is_err());
+        for size in [MIN_COHORT_SIZE - 1, MAX_COHORT_SIZE + 1] {
+            c = config();
+            c.cohort_size = size;
+            assert!(c.validate().is_err());
+        }
+        for n_winners in [0, MAX_N_WINNERS + 1] {
+            c = config();
+            c.n_winners = n_winners;
+            assert!(c.validate().is_err());
+        }
+        for n_topics in [MIN_TOPICS_PER_ATTENDEE - 1, MAX_TOPICS_PER_ATTENDEE + 1] {
+            c = config();
+            c.topics_per_attendee = n_topics;
+            assert!(c.validate().is_err());
+        }
+    }
+
+    #[test]
+    fn test_validate_voting_minutes() {
+        assert!(validate_voting_minutes(MIN_VOTING_MINUTES).is_ok());
+        assert!(validate_voting_minutes(MAX_VOTING_MINUTES).is_ok());
+        assert!(validate_voting_minutes(MIN_VOTING_MINUTES - 1).is_err());
+        assert!(validate_voting_minutes(MAX_VOTING_MINUTES + 1).is_err());
+    }
+
+    #[test]
+    fn test_validate_topic_links() {
+        let links = |urls: &[&str]| urls.iter().map(|&u| u.to_owned()).collect::<Vec<_>>();
+        assert!(validate_topic_links(&links(&[
+            "https://www.rfc-editor.org/rfc/rfc9110",
+            "HTTP://example.com/a?b=c#d",
+        ]))
+        .is_ok());
+        for bad in [
+            "javascript:alert(1)",
+            "ftp://example.com/",
+            "https://",
+            "https:///path",
+            "https://example.com/a b",
+            "example.com",
+        ] {
+            assert!(validate_topic_links(&links(&[bad])).is_err(), "{bad}");
+        }
+        assert!(validate_topic_links(&links(&["https://a.example", "https://a.example"])).is_err());
+        let many = vec!["https://example.com/".to_owned(); MAX_TOPIC_LINKS + 1];
+        assert!(validate_topic_links(&many).is_err());
+    }
+
+    #[test]
+    fn test_validate_outcome() {
+        let valid = |text: &str, topics: Vec<u32>| {
+            let text = text.to_owned();
+            OutcomeMessage { text, topics }.validate().is_ok()
+        };
+        assert!(valid("Draft an RFC by Friday", vec![7, 9]));
+        assert!(valid("Nothing to follow up", vec![]));
+        assert!(!valid(" \n", vec![7]));
+        assert!(!valid("Again", vec![7, 7]));
+        assert!(!valid(&"x".repeat(MAX_OUTCOME_LEN + 1), vec![]));
+    }
+
+    #[test]
+    fn test_normalize_join_code() {
+        for typed in ["HALL-4921", "hall-4921", " hall 4921 ", "4921", "49-21"] {
+            assert_eq!(normalize_join_code(typed).as_deref(), Some("HALL-4921"));
+        }
+        assert_eq!(
+            normalize_join_code("HALL-0004921").as_deref(),
+            Some("HALL-0004921")
+        );
+        for typed in [
+            "",
+            "HALL-",
+            "HALL-492",
+            "HALL-49a1",
+            "HALL4921X",
+            "HELLO-4921",
+        ] {
+            assert_eq!(normalize_join_code(typed), None);
+        }
+    }
+
+    #[test]
+    fn test_validate_new_topic() {
+        let valid = |new_topic: &str, description: &str| {
+            let (new_topic, description) = (new_topic.to_owned(), description.to_owned());
+            NewTopicMessage {
+                new_topic,
+                description,
+            }
+            .validate()
+            .is_ok()
+        };
+        assert!(valid("Build times", ""));
+        assert!(valid("Build times", "Our CI takes **40 minutes**."));
+        assert!(!valid("Build times", &"x".repeat(MAX_DESCRIPTION_LEN + 1)));
+    }
+
+    #[test]
+    fn test_validate_merge_topics() {
+        let valid = |topics: Vec<u32>| MergeTopicsMessage { topics }.validate().is_ok();
+        assert!(valid(vec![7, 9]));
+        assert!(valid(vec![9, 7, 12]));
+        assert!(!valid(vec![7]));
+        assert!(!valid(vec![]));
+        assert!(!valid(vec![7, 9, 7]));
+    }
+
+    #[test]
+    fn test_validate_scores() {
+        let valid = |scores: Vec<(u32, u32)>| ScoresMessage { scores }.validate().is_ok();
+        assert!(valid(vec![(7, 1), (9, 0)]));
+        assert!(valid(vec![]));
+        assert!(!valid(vec![(7, 1), (9, 0), (7, 0)]));
+    }
+
+    #[test]
+    fn test_meeting_config_defaults() {
+        let c: MeetingConfig =
+            serde_json::from_str(r#"{"version": 1, "name": "Book club"}"#).unwrap();
+        assert!(c.seed_topics.is_empty());
+        assert!(c.registration_fields.is_empty());
+        assert_eq!(c.cohort_size, COHORT_QUORUM as u32);
+        assert_eq!(c.veto_policy, VetoPolicy::Off);
+        assert_eq!(c.voting_method, VotingMethod::Borda);
+        assert_eq!(c.n_winners, DEFAULT_N_WINNERS);
+        assert!(!c.match_language);
+        assert_eq!(c.topics_per_attendee, DEFAULT_TOPICS_PER_ATTENDEE);
+        assert!(!c.share_contacts);
+        assert!(c.validate().is_ok());
+    }
+
+    #[test]
+    fn test_meeting_template_config() {
+        let t: MeetingTemplate =
+            serde_json::from_str(r#"{"title": "Book club", "description": "Readers"}"#).unwrap();
+        let c = t.config("October book club");
+        assert_eq!(c.name, "October book club");
+        assert!(c.seed_topics.is_empty());
+        assert!(c.validate().is_ok());
+    }
+
+    #[test]
+    fn test_build_info_includes_ehall() {
+        let info = BuildInfo::new("abc123", "2024-02-29", &[("ui", "0.2.0")]);
+        assert_eq!(info.crates["ui"], "0.2.0");
+        assert_eq!(info.crates["ehall"], VERSION);
+        assert_eq!(info.summary(), "abc123 built 2024-02-29");
+    }
+
+    #[test]
+    fn test_registration_deadline() {
+        let mut meeting: Meeting = serde_json::from_str(
+            r#"{"name": "m", "id": 1, "n_joined": 0, "n_registered": 0, "min_attendees": 2}"#,
+        )
+        .unwrap();
+        assert!(meeting.registration_open(i64::MAX));
+        assert_eq!(meeting.cohort_size, COHORT_QUORUM as u32);
+        assert_eq!(meeting.n_winners, DEFAULT_N_WINNERS);
+        meeting.registration_deadline = Some(1000);
+        assert!(meeting.registration_open(999));
+        assert!(!meeting.registration_open(1000));
+        assert!(!meeting.submissions_open(0));
+        meeting.submission_deadline = Some(500);
+        assert!(meeting.submissions_open(499));
+        assert!(!meeting.submissions_open(500));
+        let e = RegistrationError::RegistrationClosed { deadline: 1000 };
+        assert_eq!(
+            serde_json::to_value(&e).unwrap(),
+            serde_json::json!({"error": "registration_closed", "deadline": 1000})
+        );
+    }
+
+    #[test]
+    fn test_notification_names_match_serde() {
+        for channel in NotificationChannel::ALL {
+            assert_eq!(serde_json::to_value(channel).unwrap(), channel.as_str());
+        }
+        for event in NotificationEvent::ALL {
+            assert_eq!(serde_json::to_value(event).unwrap(), event.as_str());
+        }
+        for policy in VetoPolicy::ALL {
+            assert_eq!(serde_json::to_value(policy).unwrap(), policy.as_str());
+        }
+        for role in AttendeeRole::ALL {
+            assert_eq!(serde_json::to_value(role).unwrap(), role.as_str());
+        }
+        for method in VotingMethod::ALL {
+            assert_eq!(serde_json::to_value(method).unwrap(), method.as_str());
+        }
+        for sort in MeetingSort::ALL {
+            assert_eq!(serde_json::to_value(sort).unwrap(), sort.as_str());
+        }
+        for code in ErrorCode::ALL {
+            assert_eq!(serde_json::to_value(code).unwrap(), code.as_str());
+        }
+    }
+
+    #[test]
+    fn test_election_status() {
+        let waiting = ElectionStatus::WaitingForVotes {
+            n_voted: 2,
+            n_members: 5,
+        };
+        for status in [
+            ElectionStatus::CohortPending,
+            ElectionStatus::NoCohort,
+            waiting,
+            ElectionStatus::CohortMismatch,
+            ElectionStatus::Finished,
+        ] {
+            let value = serde_json::to_value(status).unwrap();
+            assert_eq!(value["code"], status.as_str());
+            assert_eq!(
+                serde_json::from_value::<ElectionStatus>(value).unwrap(),
+                status
+            );
+        }
+        let value = serde_json::to_value(waiting).unwrap();
+        assert_eq!(value["detail"]["n_voted"], 2);
+        assert_eq!(value["detail"]["n_members"], 5);
+        let newer = serde_json::json!({ "code": "recounting", "detail": { "round": 2 } });
+        assert_eq!(
+            serde_json::from_value::<ElectionStatus>(newer).unwrap(),
+            ElectionStatus::Unknown
+        );
+    }
+
+    #[test]
+    fn test_meeting_sort() {
+        let meeting = |id: u32, name: &str, n_registered: u32, deadline: Option<i64>| Meeting {
+            name: name.to_owned(),
+            id,
+            n_joined: 0,
+            n_registered,
+            min_attendees: 2,
+            registration_deadline: deadline,
+            submission_deadline: None,
+            voting_minutes: None,
+            cohort_size: COHORT_QUORUM as u32,
+            veto_policy: VetoPolicy::Off,
+            voting_method: VotingMethod::Borda,
+            n_winners: DEFAULT_N_WINNERS,
+            match_language: false,
+            topics_per_attendee: DEFAULT_TOPICS_PER_ATTENDEE,
+            share_contacts: false,
+            join_code: None,
+        };
+        let meetings = [
+            (meeting(1, "standup", 4, None), 2),
+            (meeting(2, "Book club", 9, Some(2000)), 0),
+            (meeting(3, "retro", 4, Some(1000)), 1),
+            (meeting(4, "all hands", 9, None), 0),
+        ];
+        let sorted = |sort: MeetingSort| {
+            let mut sorted: Vec<_> = meetings.iter().map(|(m, score)| (m, *score)).collect();
+            sorted.sort_by(|&a, &b| sort.compare(&Collation::default(), a, b));
+            sorted.iter().map(|(m, _)| m.id).collect::<Vec<_>>()
+        };
+        assert_eq!(sorted(MeetingSort::Ranking), [1, 3, 4, 2]);
+        assert_eq!(sorted(MeetingSort::Name), [4, 2, 3, 1]);
+        assert_eq!(sorted(MeetingSort::Registered), [4, 2, 3, 1]);
+        assert_eq!(sorted(MeetingSort::Deadline), [3, 2, 4, 1]);
+        assert_eq!(sorted(MeetingSort::Recent), [4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn test_veto_policy_rank() {
+        let topics: Vec<UserTopic> = [(3, true), (2, false), (1, false), (0, true)]
+            .into_iter()
+            .map(|(score, vetoed)| UserTopic {
+                text: format!("topic {score}"),
+                score,
+                id: score,
+                seeded: false,
+                position: 0,
+                links: vec![],
+                proposers: vec![],
+                vetoed,
+                private: false,
+                description: "".to_owned(),
+            })
+            .collect();
+        let ranked = |policy: VetoPolicy| {
+            let mut ranked = topics.clone();
+            ranked.reverse();
+            policy.rank(&mut ranked);
+            ranked.iter().map(|t| t.id).collect::<Vec<_>>()
+        };
+        assert_eq!(ranked(VetoPolicy::Off), [3, 2, 1, 0]);
+        assert_eq!(ranked(VetoPolicy::Exclude), [2, 1]);
+        assert_eq!(ranked(VetoPolicy::Penalize), [2, 1, 3, 0]);
+    }
+
+    #[test]
+    fn test_history_import() {
+        let import: HistoryImport = serde_json::from_str(
+            r#"{"meetings": [{"name": "2019 offsite", "held_at": 1560000000, "cohorts": [
+                {"members": ["a@example.com", "b@example.com"], "topics": ["hiring"]},
+                {"members": ["c@example.com"], "outcome": "Write it up"}
+            ]}]}"#,
+        )
+        .unwrap();
+        assert_eq!(import.validate(), Ok(()));
+        assert_eq!(import.meetings[0].voting_method, VotingMethod::Borda);
+        assert_eq!(import.meetings[0].cohorts[1].topics, Vec::<String>::new());
+
+        let invalid = |change: fn(&mut HistoryImport)| {
+            let mut import = import.clone();
+            change(&mut import);
+            import.validate().is_err()
+        };
+        assert!(invalid(|i| i.meetings.clear()));
+        assert!(invalid(|i| i.meetings.push(i.meetings[0].clone())));
+        assert!(invalid(|i| i.meetings[0].name = " ".to_owned()));
+        assert!(invalid(|i| i.meetings[0].cohorts.clear()));
+        assert!(invalid(|i| i.meetings[0].cohorts[0].members.clear()));
+        assert!(invalid(|i| i.meetings[0].owner = Some("nobody".to_owned())));
+        assert!(invalid(
+            |i| i.meetings[0].cohorts[0].topics[0] = "t".repeat(MAX_TEXT_LEN + 1)
+        ));
+        assert!(invalid(
+            |i| i.meetings[0].cohorts[1].outcome = Some("o".repeat(MAX_OUTCOME_LEN + 1))
+        ));
+        assert_eq!(ImportState::parse("failed"), Some(ImportState::Failed));
+        assert_eq!(ImportState::parse("lost"), None);
+    }
 }
@@ -0,0 +1,235 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Items a page holds when the client asks for pages without giving a limit
+pub const DEFAULT_PAGE_LIMIT: u32 = 100;
+pub const MAX_PAGE_LIMIT: u32 = 500;
+
+const CURSOR_VERSION: u8 = 1;
+const CURSOR_LEN: usize = 17; // the version, then the key and the ID
+
+// URL-safe, so cursors go in query strings as they are
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Where a page of a list ordered by (sort key, ID) ends,
+/// so the next page can start after it even when items were added or removed meanwhile.
+/// Clients pass it back as the opaque string it serializes to.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(into = "String", try_from = "String")]
+pub struct Cursor {
+    /// The last item's sort key, such as its score or a timestamp
+    pub key: i64,
+    pub id: i64,
+}
+
+/// What a paginated response says about its page
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct PageInfo {
+    /// Where the next page starts, or None on the last page
+    pub next: Option<Cursor>,
+    /// The most items the page could hold, or None when the whole list was asked for
+    pub limit: Option<u32>,
+}
+
+// Unpadded base64, as in RFC 4648, section 5
+fn base64_encode(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity((bytes.len() * 4).div_ceil(3));
+    for chunk in bytes.chunks(3) {
+        let bits =
+            chunk.iter().fold(0u32, |bits, &b| bits << 8 | b as u32) << (8 * (3 - chunk.len()));
+        for i in 0..=chunk.len() {
+            text.push(BASE64_ALPHABET[(bits >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+    text
+}
+
+fn base64_decode(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(text.len() * 3 / 4);
+    for chunk in text.as_bytes().chunks(4) {
+        if chunk.len() == 1 {
+            return None;
+        }
+        let mut bits = 0u32;
+        for c in chunk {
+            let value = BASE64_ALPHABET.iter().position(|a| a == c)?;
+            bits = bits << 6 | value as u32;
+        }
+        bits <<= 6 * (4 - chunk.len());
+        for i in 0..chunk.len() - 1 {
+            bytes.push((bits >> (16 - 8 * i)) as u8);
+        }
+    }
+    Some(bytes)
+}
+
+impl Cursor {
+    pub fn encode(&self) -> String {
+        let mut bytes = Vec::with_capacity(CURSOR_LEN);
+        bytes.push(CURSOR_VERSION);
+        bytes.extend(self.key.to_be_bytes());
+        bytes.extend(self.id.to_be_bytes());
+        base64_encode(&bytes)
+    }
+
+    pub fn decode(text: &str) -> Result<Self, String> {
+        let invalid = || format!("invalid cursor: {text}");
+        let bytes = base64_decode(text).ok_or_else(invalid)?;
+        if bytes.len() != CURSOR_LEN || bytes[0] != CURSOR_VERSION {
+            return Err(invalid());
+        }
+        Ok(Cursor {
+            key: i64::from_be_bytes(bytes[1..9].try_into().unwrap()),
+            id: i64::from_be_bytes(bytes[9..].try_into().unwrap()),
+        })
+    }
+}
+
+impl fmt::Display for Cursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.encode())
+    }
+}
+
+impl From<Cursor> for String {
+    fn from(cursor: Cursor) -> Self {
+        cursor.encode()
+    }
+}
+
+impl TryFrom<String> for Cursor {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        Cursor::decode(&text)
+    }
+}
+
+impl PageInfo {
+    /// The query string that asks for the next page, or None on the last page
+    pub fn next_query(&self) -> Option<String> {
+        let next = self.next?;
+        Some(match self.limit {
+            Some(limit) => format!("after={next}&limit={limit}"),
+            None => format!("after={next}"),
+        })
+    }
+}
+
+/// The page size for a request's `limit`, within `MAX_PAGE_LIMIT`
+pub fn page_limit(requested: Option<u32>) -> u32 {
+    requested
+        .unwrap_or(DEFAULT_PAGE_LIMIT)
+        .clamp(1, MAX_PAGE_LIMIT)
+}
+
+/// Trims `items`, fetched with one more than `limit` to tell whether another page follows,
+/// to the page, and describes it.
+/// `cursor` gives an item's place in the list's order.
+pub fn paginate<T>(
+    items: &mut Vec<T>,
+    limit: Option<u32>,
+    cursor: impl Fn(&T) -> Cursor,
+) -> PageInfo {
+    let next = match limit {
+        Some(limit) if items.len() > limit as usize => {
+            items.truncate(limit as usize);
+            items.last().map(cursor)
+        }
+        _ => None,
+    };
+    PageInfo { next, limit }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        base64_decode, base64_encode, page_limit, paginate, Cursor, PageInfo, DEFAULT_PAGE_LIMIT,
+        MAX_PAGE_LIMIT,
+    };
+
+    #[test]
+    fn test_base64() {
+        // RFC 4648, section 10, without padding
+        let vectors = [
+            ("", ""),
+            ("f", "Zg"),
+            ("fo", "Zm8"),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg"),
+            ("fooba", "Zm9vYmE"),
+            ("foobar", "Zm9vYmFy"),
+        ];
+        for (plain, encoded) in vectors {
+            assert_eq!(base64_encode(plain.as_bytes()), encoded);
+            assert_eq!(base64_decode(encoded).unwrap(), plain.as_bytes());
+        }
+        assert_eq!(base64_encode(&[0xfb, 0xff]), "-_8");
+        assert_eq!(base64_decode("Z"), None);
+        assert_eq!(base64_decode("Zm9v+A"), None);
+    }
+
+    #[test]
+    fn test_cursor_round_trip() {
+        for cursor in [
+            Cursor { key: 0, id: 0 },
+            Cursor { key: -3, id: 42 },
+            Cursor {
+                key: i64::MAX,
+                id: i64::MIN,
+            },
+        ] {
+            let text = cursor.encode();
+            assert!(text
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+            assert_eq!(Cursor::decode(&text), Ok(cursor));
+            let json = serde_json::to_string(&cursor).unwrap();
+            assert_eq!(json, format!("\"{text}\""));
+            assert_eq!(serde_json::from_str::<Cursor>(&json).unwrap(), cursor);
+        }
+        assert!(Cursor::decode("").is_err());
+        assert!(Cursor::decode("not a cursor").is_err());
+        // The right length, but another version
+        let mut other = Cursor { key: 1, id: 2 }.encode();
+        other.replace_range(..1, "B");
+        assert!(Cursor::decode(&other).is_err());
+        assert!(serde_json::from_str::<Cursor>("\"Zm9v\"").is_err());
+    }
+
+    #[test]
+    fn test_paginate() {
+        let cursor = |&(key, id): &(i64, i64)| Cursor { key, id };
+        let mut items = vec![(9, 1), (7, 2), (7, 3)];
+        let page = paginate(&mut items, Some(2), cursor);
+        assert_eq!(items, vec![(9, 1), (7, 2)]);
+        assert_eq!(page.next, Some(Cursor { key: 7, id: 2 }));
+        let json = serde_json::to_string(&page).unwrap();
+        assert_eq!(serde_json::from_str::<PageInfo>(&json).unwrap(), page);
+        assert_eq!(
+            page.next_query().unwrap(),
+            format!("after={}&limit=2", Cursor { key: 7, id: 2 })
+        );
+
+        let mut items = vec![(7, 3)];
+        let page = paginate(&mut items, Some(2), cursor);
+        assert_eq!(items, vec![(7, 3)]);
+        assert_eq!(page.next, None);
+        assert_eq!(page.next_query(), None);
+
+        let mut items = vec![(9, 1), (7, 2), (7, 3)];
+        let page = paginate(&mut items, None, cursor);
+        assert_eq!(items.len(), 3);
+        assert_eq!(page, PageInfo::default());
+    }
+
+    #[test]
+    fn test_page_limit() {
+        assert_eq!(page_limit(None), DEFAULT_PAGE_LIMIT);
+        assert_eq!(page_limit(Some(0)), 1);
+        assert_eq!(page_limit(Some(20)), 20);
+        assert_eq!(page_limit(Some(u32::MAX)), MAX_PAGE_LIMIT);
+    }
+}
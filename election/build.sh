@@ -0,0 +1,3 @@
+#! /bin/sh
+
+cargo build && cargo build --target wasm32-unknown-unknown
@@ -0,0 +1,82 @@
+use crate::Ranking;
+
+/// Borda points per choice, counted straight from the definition
+/// rather than by sorting, as an independent check on `borda_count`:
+/// each voter gives a choice one point for every choice they scored lower.
+pub fn recount(rankings: &[Ranking]) -> Vec<usize> {
+    let len = rankings.first().map_or(0, |r| r.scores.len());
+    let mut points = vec![0; len];
+    for ranking in rankings {
+        for (choice, score) in ranking.scores.iter().enumerate() {
+            points[choice] += ranking.scores.iter().filter(|&s| s < score).count();
+        }
+    }
+    points
+}
+
+/// Pairs of choices `(a, b)` where the recount puts `a` strictly ahead of `b`
+/// but the reported tally does not
+pub fn discrepancies(recounted: &[usize], reported: &[usize]) -> Vec<(usize, usize)> {
+    let mut pairs = vec![];
+    for (a, points_a) in recounted.iter().enumerate() {
+        for (b, points_b) in recounted.iter().enumerate() {
+            let misordered = !matches!(
+                (reported.get(a), reported.get(b)),
+                (Some(ra), Some(rb)) if ra > rb
+            );
+            if points_a > points_b && misordered {
+                pairs.push((a, b));
+            }
+        }
+    }
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{discrepancies, recount};
+    use crate::{borda_count, Ranking};
+
+    #[test]
+    fn test_recount() {
+        let rankings = [
+            Ranking {
+                scores: vec![1, 2, 0],
+            },
+            Ranking {
+                scores: vec![0, 10, 20],
+            },
+        ];
+        assert_eq!(recount(&rankings), [1, 3, 2]);
+        assert!(recount(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_discrepancies() {
+        assert!(discrepancies(&[1, 3, 2], &[0, 2, 1]).is_empty());
+        // Ties in the recount may be broken either way.
+        assert!(discrepancies(&[2, 2, 0], &[1, 2, 0]).is_empty());
+        assert_eq!(discrepancies(&[1, 3, 2], &[1, 0, 2]), [(1, 0), (1, 2)]);
+        assert_eq!(discrepancies(&[1, 0], &[0]), [(0, 1)]);
+    }
+
+    #[test]
+    fn test_borda_count_matches_recount() {
+        let rankings: Vec<_> = [
+            [3, 1, 4, 0, 2],
+            [0, 4, 3, 2, 1],
+            [4, 3, 2, 1, 0],
+            [1, 0, 4, 2, 3],
+        ]
+        .iter()
+        .map(|scores| Ranking {
+            scores: scores.to_vec(),
+        })
+        .collect();
+        for n in 1..=rankings.len() {
+            let rankings = &rankings[..n];
+            let reported = borda_count(rankings).unwrap();
+            assert!(discrepancies(&recount(rankings), &reported).is_empty());
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use rand::{seq::SliceRandom, thread_rng};
+
+/// Why participants could not be split into cohorts
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CohortError {
+    /// Fewer participants than one full cohort
+    NotEnoughParticipants { needed: usize, have: usize },
+}
+
+impl fmt::Display for CohortError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CohortError::NotEnoughParticipants { needed, have } => {
+                write!(f, "not enough participants: need {needed}, have {have}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CohortError {}
+
+/// Shuffles participants `0..n_participants` into cohorts of `cohort_size`,
+/// with any remainder in a smaller last cohort
+pub fn cohorts(n_participants: usize, cohort_size: usize) -> Result<Vec<Vec<usize>>, CohortError> {
+    if cohort_size > n_participants {
+        return Err(CohortError::NotEnoughParticipants {
+            needed: cohort_size,
+            have: n_participants,
+        });
+    }
+    let rng = &mut thread_rng();
+    let mut order: Vec<usize> = (0..n_participants).collect();
+    order.shuffle(rng);
+    Ok(order
+        .chunks(cohort_size)
+        .map(|cohort| cohort.to_vec())
+        .collect::<Vec<_>>())
+}
+
+/// Like `cohorts`, but participants with the same key, such as a language,
+/// share cohorts as far as full cohorts allow.
+/// Participants without a key, and those of each key too few for another full cohort,
+/// are shuffled into mixed cohorts after them.
+pub fn grouped_cohorts<K: Ord>(
+    keys: &[Option<K>],
+    cohort_size: usize,
+) -> Result<Vec<Vec<usize>>, CohortError> {
+    if cohort_size > keys.len() {
+        return Err(CohortError::NotEnoughParticipants {
+            needed: cohort_size,
+            have: keys.len(),
+        });
+    }
+    let rng = &mut thread_rng();
+    let mut groups: BTreeMap<&K, Vec<usize>> = BTreeMap::new();
+    let mut mixed = vec![];
+    for (participant, key) in keys.iter().enumerate() {
+        match key {
+            Some(key) => groups.entry(key).or_default().push(participant),
+            None => mixed.push(participant),
+        }
+    }
+    let mut grouped = vec![];
+    for mut members in groups.into_values() {
+        members.shuffle(rng);
+        let n_full = members.len() / cohort_size * cohort_size;
+        mixed.extend(members.drain(n_full..));
+        grouped.extend(members.chunks(cohort_size).map(|cohort| cohort.to_vec()));
+    }
+    mixed.shuffle(rng);
+    grouped.extend(mixed.chunks(cohort_size).map(|cohort| cohort.to_vec()));
+    Ok(grouped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cohorts, grouped_cohorts, CohortError};
+    use anyhow::Result;
+
+    #[test]
+    fn test_cohorts() -> Result<()> {
+        let mut c = cohorts(3, 1)?;
+        assert_eq!(c.len(), 3);
+        assert_eq!(c[0].len(), 1);
+        c = cohorts(3, 2)?;
+        println!("{:?}", c);
+        assert_eq!(c.len(), 2);
+        assert_eq!(c[0].len(), 2);
+        assert_eq!(c[1].len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cohorts_too_few() {
+        let e = cohorts(2, 3).unwrap_err();
+        assert_eq!(e, CohortError::NotEnoughParticipants { needed: 3, have: 2 });
+        assert_eq!(e.to_string(), "not enough participants: need 3, have 2");
+    }
+
+    #[test]
+    fn test_cohorts_partition() -> Result<()> {
+        let c = cohorts(10, 3)?;
+        assert_eq!(
+            c.iter().map(|cohort| cohort.len()).collect::<Vec<_>>(),
+            [3, 3, 3, 1]
+        );
+        let mut all: Vec<_> = c.into_iter().flatten().collect();
+        all.sort_unstable();
+        assert_eq!(all, (0..10).collect::<Vec<_>>());
+        Ok(())
+    }
+
+    #[test]
+    fn test_grouped_cohorts() -> Result<()> {
+        let keys = [
+            Some("en"),
+            Some("de"),
+            Some("en"),
+            None,
+            Some("en"),
+            Some("de"),
+            Some("en"),
+            Some("de"),
+            None,
+            Some("fr"),
+        ];
+        let c = grouped_cohorts(&keys, 3)?;
+        assert_eq!(
+            c.iter().map(|cohort| cohort.len()).collect::<Vec<_>>(),
+            [3, 3, 3, 1]
+        );
+        // The German speakers fill one cohort, and the English speakers another
+        // with one left over, who joins the French speaker and those without a preference.
+        assert!(c[0].iter().all(|&i| keys[i] == Some("de")));
+        assert!(c[1].iter().all(|&i| keys[i] == Some("en")));
+        let mut mixed: Vec<_> = c[2..].iter().flatten().map(|&i| keys[i]).collect();
+        mixed.sort_unstable();
+        assert_eq!(mixed, [None, None, Some("en"), Some("fr")]);
+        let mut all: Vec<_> = c.into_iter().flatten().collect();
+        all.sort_unstable();
+        assert_eq!(all, (0..10).collect::<Vec<_>>());
+
+        // Too few of any one language for a cohort, so all are mixed
+        let c = grouped_cohorts(&[Some("en"), Some("de"), Some("fr")], 2)?;
+        assert_eq!(c.len(), 2);
+        assert_eq!(
+            grouped_cohorts::<&str>(&[None], 2).unwrap_err(),
+            CohortError::NotEnoughParticipants { needed: 2, have: 1 }
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,336 @@
+// It would be nice to use tallystick, but I don't want to use nightly.
+use anyhow::{anyhow, Result};
+
+use crate::argsort;
+
+#[derive(Clone, Debug)]
+pub struct Ranking {
+    // Entries are ordered to correspond to an array of choices.
+    // Values are scores, with higher scores preferred.
+    // Only the score order is used to determine the ranking.
+    pub scores: Vec<usize>,
+}
+
+// The number of choices, which every ranking must score
+fn n_choices(rankings: &[Ranking]) -> Result<usize> {
+    let len = rankings.first().map_or(0, |r| r.scores.len());
+    if rankings.iter().any(|r| r.scores.len() != len) {
+        return Err(anyhow!("lengths of rankings differ"));
+    }
+    Ok(len)
+}
+
+pub fn borda_count(rankings: &[Ranking]) -> Result<Vec<usize>> {
+    let len = n_choices(rankings)?;
+
+    // The most esteemed choice has the highest score and the highest rank.
+    // Applying argsort twice provides the conversion
+    // from arbitrary scores to Borda-count points.
+    let rankings: Vec<_> = rankings.iter().map(|r| ranks(&r.scores)).collect();
+    let mut scores: Vec<_> = vec![0; len];
+    for r in &rankings {
+        for j in 0..len {
+            scores[j] += r[j];
+        }
+    }
+    Ok(ranks(&scores)) // canonicalize results
+}
+
+// How many voters prefer choice a to choice b, at [a][b].
+// Tied scores are broken by index, as in `borda_count`.
+fn preferences(rankings: &[Ranking], len: usize) -> Vec<Vec<usize>> {
+    let mut prefer = vec![vec![0; len]; len];
+    for r in rankings.iter().map(|r| ranks(&r.scores)) {
+        for a in 0..len {
+            for b in 0..len {
+                if r[a] > r[b] {
+                    prefer[a][b] += 1;
+                }
+            }
+        }
+    }
+    prefer
+}
+
+/// The choice that a majority prefers to each other choice, if there is one
+pub fn condorcet_winner(rankings: &[Ranking]) -> Result<Option<usize>> {
+    let len = n_choices(rankings)?;
+    let prefer = preferences(rankings, len);
+    Ok((0..len).find(|&a| (0..len).all(|b| a == b || prefer[a][b] > prefer[b][a])))
+}
+
+/// Ranks choices by how many others a majority prefers them to,
+/// which is Copeland's method, with Borda points breaking ties.
+/// The `condorcet_winner`, when there is one, ranks first.
+pub fn condorcet_count(rankings: &[Ranking]) -> Result<Vec<usize>> {
+    let len = n_choices(rankings)?;
+    let prefer = preferences(rankings, len);
+    let borda = borda_count(rankings)?;
+    let keys: Vec<_> = (0..len)
+        .map(|a| {
+            let wins = (0..len).filter(|&b| prefer[a][b] > prefer[b][a]).count();
+            // Borda ranks are below `len`, so they only order equal win counts.
+            wins * len + borda[a]
+        })
+        .collect();
+    Ok(ranks(&keys))
+}
+
+/// Ranks choices in the order instant-runoff voting eliminates them.
+/// Each round, every voter backs their favorite remaining choice,
+/// and the choice with the fewest backers is out,
+/// with Borda points breaking ties.
+/// The last choice standing ranks first.
+pub fn instant_runoff(rankings: &[Ranking]) -> Result<Vec<usize>> {
+    let len = n_choices(rankings)?;
+    let borda = borda_count(rankings)?;
+    let voters: Vec<_> = rankings.iter().map(|r| ranks(&r.scores)).collect();
+    let mut remaining: Vec<usize> = (0..len).collect();
+    let mut result = vec![0; len];
+    for round in 0..len {
+        let mut backers = vec![0; len];
+        for r in voters.iter() {
+            let favorite = *remaining.iter().max_by_key(|&&c| r[c]).unwrap();
+            backers[favorite] += 1;
+        }
+        let (i, &out) = remaining
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, &c)| (backers[c], borda[c]))
+            .unwrap();
+        result[out] = round;
+        remaining.remove(i);
+    }
+    Ok(result)
+}
+
+// The position of each element in ascending order, with ties broken by index
+fn ranks(a: &[usize]) -> Vec<usize> {
+    argsort(&argsort(a))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::{argsort, borda_count, condorcet_count, condorcet_winner, instant_runoff, Ranking};
+
+    // One ranking per row, each listing choices from most to least preferred
+    fn profile(orders: &[&[usize]]) -> Vec<Ranking> {
+        orders
+            .iter()
+            .map(|order| {
+                let mut scores = vec![0; order.len()];
+                for (place, &choice) in order.iter().enumerate() {
+                    scores[choice] = order.len() - place;
+                }
+                Ranking { scores }
+            })
+            .collect()
+    }
+
+    fn winner(count: &[usize]) -> usize {
+        *argsort(count).last().unwrap()
+    }
+
+    #[test]
+    fn test_borda_count_empty() {
+        assert!(borda_count(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_borda_count_length_mismatch() {
+        let rankings = [
+            Ranking {
+                scores: vec![0, 1, 2],
+            },
+            Ranking { scores: vec![0, 1] },
+        ];
+        assert!(borda_count(&rankings).is_err());
+    }
+
+    #[test]
+    fn test_borda_count_majority() {
+        // Two voters prefer choice 2 and one prefers choice 0.
+        let rankings = [
+            Ranking {
+                scores: vec![0, 1, 2],
+            },
+            Ranking {
+                scores: vec![1, 0, 2],
+            },
+            Ranking {
+                scores: vec![2, 1, 0],
+            },
+        ];
+        let count = borda_count(&rankings).unwrap();
+        assert_eq!(argsort(&count).last(), Some(&2));
+    }
+
+    #[test]
+    fn test_borda_count_agree() {
+        let rankings = [
+            Ranking {
+                scores: vec![0, 1, 2],
+            },
+            Ranking {
+                scores: vec![3, 4, 5],
+            },
+            Ranking {
+                scores: vec![6, 7, 8],
+            },
+        ];
+        let count = borda_count(&rankings).unwrap();
+        assert_eq!(count, [0, 1, 2]);
+    }
+
+    #[test]
+    fn test_borda_one_ranking() {
+        let rankings = [
+            Ranking {
+                scores: vec![9, 5, 11, 0, 4, 6, 8, 1, 7, 2, 3, 10],
+            },
+            Ranking {
+                scores: vec![0, 1, 2],
+            },
+            Ranking {
+                scores: vec![3, 5, 4],
+            },
+            Ranking {
+                scores: vec![8, 7, 6],
+            },
+        ];
+        for r in rankings.into_iter() {
+            let rr = std::slice::from_ref(&r);
+            let count = borda_count(rr).unwrap();
+            let i_expected = argsort(&r.scores);
+            let i_observed = argsort(&count);
+            assert_eq!(i_expected, i_observed);
+        }
+    }
+
+    #[test]
+    fn test_borda_count_points() {
+        // Choice 1 is first and second, beating choice 2, which is last and first.
+        let rankings = [
+            Ranking {
+                scores: vec![1, 2, 0],
+            },
+            Ranking {
+                scores: vec![0, 1, 2],
+            },
+        ];
+        assert_eq!(borda_count(&rankings).unwrap(), [0, 2, 1]);
+    }
+
+    #[test]
+    fn test_condorcet_cycle() {
+        // Each choice beats one other by two votes to one.
+        let rankings = profile(&[&[0, 1, 2], &[1, 2, 0], &[2, 0, 1]]);
+        assert_eq!(condorcet_winner(&rankings).unwrap(), None);
+        assert_eq!(condorcet_count(&rankings).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_methods_disagree() {
+        // Choice 1 is everyone's first or second choice, so it beats each rival
+        // head to head, but it has the fewest favorites and goes out first in a runoff,
+        // where its backers then carry choice 2 past choice 0.
+        let mut orders: Vec<&[usize]> = vec![];
+        orders.extend([&[0, 1, 2][..]; 4]);
+        orders.extend([&[2, 1, 0][..]; 3]);
+        orders.extend([&[1, 2, 0][..]; 2]);
+        let rankings = profile(&orders);
+        assert_eq!(condorcet_winner(&rankings).unwrap(), Some(1));
+        assert_eq!(winner(&condorcet_count(&rankings).unwrap()), 1);
+        assert_eq!(winner(&borda_count(&rankings).unwrap()), 1);
+        assert_eq!(instant_runoff(&rankings).unwrap(), [1, 0, 2]);
+    }
+
+    #[test]
+    fn test_borda_differs_from_condorcet() {
+        // A bare majority puts 0 first, but the minority puts 0 last.
+        let rankings = profile(&[&[0, 1, 2], &[0, 1, 2], &[1, 2, 0]]);
+        assert_eq!(condorcet_winner(&rankings).unwrap(), Some(0));
+        assert_eq!(winner(&borda_count(&rankings).unwrap()), 1);
+        assert_eq!(winner(&condorcet_count(&rankings).unwrap()), 0);
+        assert_eq!(winner(&instant_runoff(&rankings).unwrap()), 0);
+    }
+
+    #[test]
+    fn test_methods_empty() {
+        // A cohort whose members had nothing to rank
+        let rankings = [Ranking { scores: vec![] }, Ranking { scores: vec![] }];
+        for rankings in [&rankings[..], &[]] {
+            assert_eq!(condorcet_winner(rankings).unwrap(), None);
+            assert!(condorcet_count(rankings).unwrap().is_empty());
+            assert!(instant_runoff(rankings).unwrap().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_methods_length_mismatch() {
+        let rankings = [Ranking { scores: vec![0, 1] }, Ranking { scores: vec![0] }];
+        assert!(condorcet_winner(&rankings).is_err());
+        assert!(condorcet_count(&rankings).is_err());
+        assert!(instant_runoff(&rankings).is_err());
+    }
+
+    #[test]
+    fn test_method_properties() {
+        let mut rng = StdRng::seed_from_u64(2258);
+        for _ in 0..500 {
+            let n_choices = rng.gen_range(1..7);
+            let rankings: Vec<_> = (0..rng.gen_range(1..9))
+                .map(|_| Ranking {
+                    scores: (0..n_choices).map(|_| rng.gen_range(0..5)).collect(),
+                })
+                .collect();
+            let counts = [
+                borda_count(&rankings).unwrap(),
+                condorcet_count(&rankings).unwrap(),
+                instant_runoff(&rankings).unwrap(),
+            ];
+            // Every method ranks every choice exactly once.
+            for count in counts.iter() {
+                let mut sorted = count.clone();
+                sorted.sort_unstable();
+                assert_eq!(sorted, (0..n_choices).collect::<Vec<_>>());
+            }
+            if let Some(w) = condorcet_winner(&rankings).unwrap() {
+                assert_eq!(winner(&counts[1]), w);
+            }
+            // A majority's shared favorite wins the runoff,
+            // and a unanimous favorite wins every method.
+            let favorites: Vec<_> = rankings.iter().map(|r| winner(&r.scores)).collect();
+            for choice in 0..n_choices {
+                let n = favorites.iter().filter(|&&f| f == choice).count();
+                if 2 * n > rankings.len() {
+                    assert_eq!(winner(&counts[2]), choice);
+                }
+                if n == rankings.len() {
+                    for count in counts.iter() {
+                        assert_eq!(winner(count), choice);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_borda_count_disagree() {
+        let rankings = [
+            Ranking {
+                scores: vec![0, 1, 2],
+            },
+            Ranking {
+                scores: vec![3, 4, 5],
+            },
+            Ranking {
+                scores: vec![8, 7, 6],
+            },
+        ];
+        let count = borda_count(&rankings).unwrap();
+        assert_eq!(count, [0, 1, 2]);
+    }
+}
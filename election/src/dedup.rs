@@ -0,0 +1,195 @@
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+
+use crate::Ranking;
+
+/// The form of a topic's text used to spot duplicates:
+/// lowercase words, with punctuation and extra spaces dropped
+pub fn topic_key(text: &str) -> String {
+    let words: String = text
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .to_lowercase();
+    let key = words.split_whitespace().collect::<Vec<_>>().join(" ");
+    if key.is_empty() {
+        // All punctuation, so only an exact match is a duplicate.
+        text.trim().to_owned()
+    } else {
+        key
+    }
+}
+
+// The first choice that `i` merges with, found by following `first`,
+// which is shortened along the way
+fn root(first: &mut [usize], mut i: usize) -> usize {
+    while first[i] != i {
+        first[i] = first[first[i]];
+        i = first[i];
+    }
+    i
+}
+
+/// Merges choices whose texts share a `topic_key`,
+/// returning the original indices in each merged choice
+/// and the rankings over the merged choices.
+///
+/// Each voter's score for a merged choice is their best score
+/// among its duplicates, so a voter counts once per merged topic
+/// and the order of their other choices is kept.
+pub fn merge_duplicates(
+    texts: &[String],
+    rankings: &[Ranking],
+) -> Result<(Vec<Vec<usize>>, Vec<Ranking>)> {
+    merge_topics(texts, &[], rankings)
+}
+
+/// Like `merge_duplicates`, but also merges each pair of choices in `same`,
+/// like topics an organizer marked as duplicates despite different wording.
+/// Merging is transitive, and each merged choice lists its indices in order,
+/// with the merged choices ordered by their first index.
+pub fn merge_topics(
+    texts: &[String],
+    same: &[(usize, usize)],
+    rankings: &[Ranking],
+) -> Result<(Vec<Vec<usize>>, Vec<Ranking>)> {
+    if rankings.iter().any(|r| r.scores.len() != texts.len()) {
+        return Err(anyhow!("rankings and choices differ in length"));
+    }
+    if same.iter().any(|&(a, b)| a.max(b) >= texts.len()) {
+        return Err(anyhow!("merged choices out of range"));
+    }
+    // Each choice points toward the first choice it merges with.
+    let mut first: Vec<usize> = (0..texts.len()).collect();
+    // Pairs each choice with the first whose text has the same key.
+    let mut first_of_key: HashMap<String, usize> = HashMap::new();
+    let keyed: Vec<_> = texts
+        .iter()
+        .enumerate()
+        .map(|(i, text)| (i, *first_of_key.entry(topic_key(text)).or_insert(i)))
+        .collect();
+    for &(a, b) in keyed.iter().chain(same) {
+        let (a, b) = (root(&mut first, a), root(&mut first, b));
+        first[a.max(b)] = a.min(b);
+    }
+    let mut groups: Vec<Vec<usize>> = vec![];
+    let mut group_of_root: HashMap<usize, usize> = HashMap::new();
+    for i in 0..texts.len() {
+        let group = *group_of_root.entry(root(&mut first, i)).or_insert_with(|| {
+            groups.push(vec![]);
+            groups.len() - 1
+        });
+        groups[group].push(i);
+    }
+    let rankings = rankings
+        .iter()
+        .map(|r| Ranking {
+            scores: groups
+                .iter()
+                .map(|group| group.iter().map(|&i| r.scores[i]).max().unwrap())
+                .collect(),
+        })
+        .collect();
+    Ok((groups, rankings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{merge_duplicates, merge_topics, topic_key};
+    use crate::{argsort, borda_count, Ranking};
+
+    fn texts(texts: &[&str]) -> Vec<String> {
+        texts.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn test_topic_key() {
+        assert_eq!(topic_key("Build times!"), "build times");
+        assert_eq!(topic_key("  build   TIMES "), "build times");
+        assert_eq!(topic_key("build-times?"), "build times");
+        assert_ne!(topic_key("build times"), topic_key("build timer"));
+        assert_eq!(topic_key(" ?! "), "?!");
+    }
+
+    #[test]
+    fn test_merge_no_duplicates() {
+        let rankings = [Ranking {
+            scores: vec![2, 0, 1],
+        }];
+        let (groups, merged) = merge_duplicates(&texts(&["a", "b", "c"]), &rankings).unwrap();
+        assert_eq!(groups, [vec![0], vec![1], vec![2]]);
+        assert_eq!(merged[0].scores, rankings[0].scores);
+    }
+
+    #[test]
+    fn test_merge_takes_best_score() {
+        let rankings = [
+            Ranking {
+                scores: vec![0, 3, 1, 2],
+            },
+            Ranking {
+                scores: vec![3, 0, 2, 1],
+            },
+        ];
+        let choices = texts(&["Rust", "potatoes", "rust.", "Potatoes!"]);
+        let (groups, merged) = merge_duplicates(&choices, &rankings).unwrap();
+        assert_eq!(groups, [vec![0, 2], vec![1, 3]]);
+        assert_eq!(merged[0].scores, [1, 3]);
+        assert_eq!(merged[1].scores, [3, 1]);
+    }
+
+    #[test]
+    fn test_merge_keeps_voter_order() {
+        // Each voter's favorite is still their favorite after merging.
+        let rankings = [
+            Ranking {
+                scores: vec![3, 0, 1, 2],
+            },
+            Ranking {
+                scores: vec![0, 3, 2, 1],
+            },
+            Ranking {
+                scores: vec![1, 2, 3, 0],
+            },
+        ];
+        let choices = texts(&["Build times", "docs", "testing", "build times"]);
+        let (groups, merged) = merge_duplicates(&choices, &rankings).unwrap();
+        assert_eq!(groups, [vec![0, 3], vec![1], vec![2]]);
+        assert_eq!(merged[0].scores, [3, 0, 1]);
+        assert_eq!(merged[1].scores, [1, 3, 2]);
+        assert_eq!(merged[2].scores, [1, 2, 3]);
+        let favorites: Vec<_> = merged
+            .iter()
+            .map(|r| *argsort(&r.scores).last().unwrap())
+            .collect();
+        assert_eq!(favorites, [0, 1, 2]);
+        assert_eq!(borda_count(&merged).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_merge_length_mismatch() {
+        let rankings = [Ranking { scores: vec![0, 1] }];
+        assert!(merge_duplicates(&texts(&["a"]), &rankings).is_err());
+        assert!(merge_topics(&texts(&["a", "b"]), &[(0, 2)], &rankings).is_err());
+    }
+
+    #[test]
+    fn test_merge_topics() {
+        let rankings = [
+            Ranking {
+                scores: vec![0, 4, 1, 2, 3],
+            },
+            Ranking {
+                scores: vec![4, 0, 3, 2, 1],
+            },
+        ];
+        let choices = texts(&["CI", "docs", "build times", "flaky tests", "Build times"]);
+        // Merging 3 with 4 also merges it with 2, whose text matches 4's.
+        let (groups, merged) = merge_topics(&choices, &[(2, 0), (3, 4)], &rankings).unwrap();
+        assert_eq!(groups, [vec![0, 2, 3, 4], vec![1]]);
+        assert_eq!(merged[0].scores, [3, 4]);
+        assert_eq!(merged[1].scores, [4, 0]);
+        let (groups, _) = merge_topics(&choices, &[], &rankings).unwrap();
+        assert_eq!(groups, merge_duplicates(&choices, &rankings).unwrap().0);
+    }
+}
@@ -0,0 +1,50 @@
+//! Election arithmetic shared by the eHallway server and UI,
+//! usable on its own for command-line tallies and simulations.
+
+mod audit;
+mod chance;
+mod cull;
+mod dedup;
+mod timing;
+
+pub use audit::{discrepancies, recount};
+pub use chance::{cohorts, grouped_cohorts, CohortError};
+pub use cull::{borda_count, condorcet_count, condorcet_winner, instant_runoff, Ranking};
+pub use dedup::{merge_duplicates, merge_topics, topic_key};
+pub use timing::split_minutes;
+
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The indices that would sort `a` in ascending order
+pub fn argsort<T>(a: &[T]) -> Vec<usize>
+where
+    T: PartialOrd,
+{
+    let mut indexed: Vec<_> = a.iter().enumerate().collect();
+    indexed.sort_by(|(_i1, v1), (_i2, v2)| v1.partial_cmp(v2).unwrap());
+    indexed.into_iter().map(|(i, _v)| i).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::argsort;
+
+    #[test]
+    fn test_argsort() {
+        let a: Vec<_> = (0..10).collect();
+        let b = a.clone();
+        let i = argsort(&b);
+        let bb: Vec<_> = i.iter().map(|j| b[*j]).collect();
+        assert_eq!(a.len(), bb.len());
+        for (i, j) in a.iter().zip(bb.iter()) {
+            assert_eq!(i, j);
+        }
+    }
+
+    #[test]
+    fn test_argsort_unordered() {
+        assert_eq!(argsort(&[30, 10, 20]), [1, 2, 0]);
+        assert_eq!(argsort(&[0.5, -1.0]), [1, 0]);
+        assert!(argsort::<u32>(&[]).is_empty());
+    }
+}
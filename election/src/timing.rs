@@ -0,0 +1,60 @@
+/// Divides `total` minutes among topics in proportion to their weights,
+/// using the largest remainder method so the parts add up to `total`.
+/// Topics with equal weights get equal time, give or take a minute,
+/// and all-zero weights split the time evenly.
+pub fn split_minutes(weights: &[usize], total: u32) -> Vec<u32> {
+    if weights.is_empty() {
+        return vec![];
+    }
+    let weight_sum: usize = weights.iter().sum();
+    let shares: Vec<f64> = if weight_sum == 0 {
+        vec![total as f64 / weights.len() as f64; weights.len()]
+    } else {
+        weights
+            .iter()
+            .map(|&w| total as f64 * w as f64 / weight_sum as f64)
+            .collect()
+    };
+    let mut minutes: Vec<u32> = shares.iter().map(|s| s.floor() as u32).collect();
+    let mut by_remainder: Vec<usize> = (0..shares.len()).collect();
+    // Stable, so ties go to the earlier, more highly ranked topic.
+    by_remainder.sort_by(|&a, &b| {
+        let ra = shares[a] - shares[a].floor();
+        let rb = shares[b] - shares[b].floor();
+        rb.partial_cmp(&ra).unwrap()
+    });
+    let assigned: u32 = minutes.iter().sum();
+    for &i in by_remainder.iter().take((total - assigned) as usize) {
+        minutes[i] += 1;
+    }
+    minutes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_minutes;
+
+    #[test]
+    fn test_split_minutes_proportional() {
+        assert_eq!(split_minutes(&[3, 1], 20), [15, 5]);
+        assert_eq!(split_minutes(&[2, 1], 30), [20, 10]);
+    }
+
+    #[test]
+    fn test_split_minutes_sums_to_total() {
+        for total in 0..50 {
+            for weights in [&[1, 1, 1][..], &[5, 4], &[7, 0, 2], &[1]] {
+                let minutes = split_minutes(weights, total);
+                assert_eq!(minutes.iter().sum::<u32>(), total);
+            }
+        }
+        // The leftover minute goes to the first of equal remainders.
+        assert_eq!(split_minutes(&[1, 1], 5), [3, 2]);
+    }
+
+    #[test]
+    fn test_split_minutes_degenerate() {
+        assert!(split_minutes(&[], 30).is_empty());
+        assert_eq!(split_minutes(&[0, 0], 30), [15, 15]);
+    }
+}
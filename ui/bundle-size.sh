@@ -0,0 +1,23 @@
+#! /bin/sh
+# Reports the size of each built wasm and js file, raw and gzipped,
+# as trunk's post_build hook.  With EHALLWAY_WASM_BUDGET_KB set,
+# a gzipped wasm file larger than the budget fails the build.
+
+dir="${TRUNK_STAGING_DIR:-dist}"
+status=0
+for f in "$dir"/*.wasm "$dir"/*.js; do
+    test -f "$f" || continue
+    raw=`wc -c < "$f"`
+    gz=`gzip -9 -c "$f" | wc -c`
+    echo "bundle size: `basename "$f"` $raw bytes, $gz gzipped"
+    case "$f" in
+    *.wasm)
+        if test -n "$EHALLWAY_WASM_BUDGET_KB" &&
+            test "$gz" -gt `expr "$EHALLWAY_WASM_BUDGET_KB" \* 1024`; then
+            echo "bundle size: over the budget of $EHALLWAY_WASM_BUDGET_KB KiB gzipped" 1>&2
+            status=1
+        fi
+        ;;
+    esac
+done
+exit $status
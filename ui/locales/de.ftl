@@ -0,0 +1,337 @@
+# Die deutsche Fassung der Oberfläche.
+# Fehlende Meldungen erscheinen auf Englisch, aus en.ftl.
+
+## Tabs
+
+tab-topics = Themen
+tab-meetings = Treffen
+tab-meet = Teilnehmen
+tab-preferences = Einstellungen
+
+## Buttons used throughout
+
+save = speichern
+cancel = abbrechen
+
+## Meeting attendance
+
+joined-count = { $joined } von { $registered } angemeldeten Teilnehmenden sind da
+start-meeting = Treffen jetzt beginnen
+start-anyway = trotzdem beginnen
+start-wait = warten
+ballot-in = Deine Stimme ist abgegeben.
+retract-vote = Stimme zurücknehmen
+done-ranking = FERTIG MIT DER RANGFOLGE!
+new-topic-label = Neues Thema:
+new-topic-placeholder = Eingabetaste fügt hinzu; mehrere Zeilen einfügen fügt mehrere hinzu
+
+## Preferences
+
+display-language = Sprache der Oberfläche
+display-language-help = Die Sprache dieser Seiten. Themen und Namen von Treffen bleiben, wie sie verfasst wurden.
+
+## Why a meeting can't start
+
+start-not-attending = Nur Teilnehmende können das Treffen beginnen.
+start-not-organizer = Nur die Organisation kann das Treffen beginnen.
+start-too-few-attendees = Warten auf mindestens { $min } Personen (bisher { $here } da).
+start-too-few-topics = Jede Gruppe wählt { $winners } Themen, aber ein Stimmzettel hätte nur { $topics }. Füge Themen der Organisation hinzu oder bitte Teilnehmende um weitere Vorschläge.
+start-not-enough-participants = Zu wenige Teilnehmende für eine Gruppe: { $needed } nötig, { $have } da. Versuche es erneut, sobald mehr Leute da sind.
+start-confirm = Erst { $joined } der { $registered } Angemeldeten sind da. Trotzdem beginnen oder auf weitere warten?
+
+## Election status codes
+
+status-cohort-pending = Die Gruppen bilden sich, wenn das Treffen beginnt.
+status-no-cohort = Du bist in keiner Gruppe dieses Treffens.
+status-waiting-for-votes = Warten, bis deine Gruppe fertig abgestimmt hat: { $voted } von { $members } Stimmen sind abgegeben.
+status-cohort-mismatch = Jemand aus deiner Gruppe hat das Treffen verlassen, deshalb kann ihre Abstimmung nicht enden.
+status-finished = Die Abstimmung ist beendet.
+
+## Error codes
+
+error-server = Auf dem Server ist etwas schiefgegangen. Bitte versuche es erneut.
+error-not-found = Das gibt es nicht mehr.
+error-invalid-request = Das wurde nicht angenommen. Bitte prüfe deine Eingabe.
+error-name-taken = Ein anderes Treffen hat schon diesen Namen.
+error-unknown-account = Niemand hat ein Konto mit dieser E-Mail-Adresse.
+error-not-organizer = Nur die Organisation des Treffens kann das tun.
+error-not-owner = Nur die Person, der das Treffen gehört, kann das tun.
+error-not-proposer = Nur wer das Thema vorgeschlagen hat oder die Organisation kann das tun.
+error-not-attendee = Nur Teilnehmende können das tun.
+error-observing = Du beobachtest dieses Treffen und hast daher keinen Stimmzettel.
+error-not-registered = Nur für das Treffen Angemeldete können das tun.
+error-not-cohort-member = Nur Mitglieder einer Gruppe können das tun.
+error-not-on-ballot = Dieses Thema ist nicht auf deinem Stimmzettel.
+error-meeting-started = Das Treffen hat schon begonnen.
+error-meeting-not-started = Das Treffen hat noch nicht begonnen.
+error-cohort-voting = Deine Gruppe stimmt noch ab.
+error-already-voted = Deine Stimme ist schon abgegeben.
+error-cohort-finished = Deine Gruppe hat fertig abgestimmt, die Stimmzettel können sich nicht mehr ändern.
+error-vetoes-off = Dieses Treffen erlaubt keine Vetos.
+error-contact-sharing-off = Dieses Treffen teilt keine Kontakte.
+error-submissions-closed = Für dieses Treffen können keine Themen mehr eingereicht werden.
+error-submitted-topics-only = Die Stimmzettel dieses Treffens enthalten die eingereichten Themen, deine eigenen kommen daher nicht dazu.
+error-backups-off = Sicherungen sind nicht eingerichtet.
+error-backup-running = Eine Sicherung läuft bereits.
+error-own-account = Das kannst du mit deinem eigenen Konto nicht tun.
+error-account-exists = Jemand hat schon ein Konto mit dieser E-Mail-Adresse.
+error-account-disabled = Dein Konto ist deaktiviert. Bitte die Administration, es wieder zu aktivieren.
+error-busy = Der Server ist ausgelastet. Bitte versuche es gleich noch einmal.
+error-research-off = Dieser Server teilt keine Stimmzettel für die Forschung.
+error-in-progress = Diese Anfrage wird schon bearbeitet. Bitte warte einen Moment.
+
+## Refusals the API gives no known code for
+
+refused-my-topics = Deine Themenauswahl wurde nicht gespeichert.
+refused-links = Die Links wurden nicht gespeichert.
+refused-description = Die Beschreibung wurde nicht gespeichert.
+refused-retract = Deine Stimme konnte nicht zurückgenommen werden.
+refused-rename = Das Treffen wurde nicht umbenannt.
+refused-add-organizer = Die mitorganisierende Person wurde nicht hinzugefügt.
+refused-join-code = Kein Treffen hat diesen Code.
+refused-remove-organizer = Die mitorganisierende Person wurde nicht entfernt.
+refused-merge = Die Themen wurden nicht zusammengeführt.
+refused-split = Das Thema wurde nicht abgetrennt.
+refused-contact-sharing = Deine Wahl wurde nicht gespeichert.
+refused-outcome = Das Ergebnis wurde nicht festgehalten.
+refused-avatar = Dieses Bild kann nicht als Avatar verwendet werden.
+refused-invitation = Die Einladung wurde nicht verschickt.
+refused-language = Das ist kein Sprachcode wie „en“ oder „pt-BR“.
+refused-start = Das Treffen konnte nicht beginnen.
+refused-next-round = Die nächste Runde konnte nicht beginnen.
+refused-veto = Das Veto wurde nicht festgehalten.
+refused-submission = Das Thema wurde nicht eingereicht.
+import-failed = Die Treffenkonfiguration wurde nicht importiert.
+
+## Choices and their labels
+
+notify-meeting-started = Ein Treffen, für das ich angemeldet bin, beginnt
+notify-results-ready = Die Themen meiner Gruppe sind gewählt
+notify-daily-digest = Tägliche Übersicht über Treffen und Themen
+notify-weekly-digest = Wöchentliche Übersicht über Treffen und Themen
+channel-email = E-Mail
+channel-web-push = Browser-Benachrichtigung
+channel-webhook = Webhook
+veto-off = keine Vetos
+veto-exclude = Vetos schließen Themen aus
+veto-penalize = Vetos stufen Themen herab
+method-borda = Borda-Zählung
+method-condorcet = Condorcet
+method-instant-runoff = Integrierte Stichwahl
+sort-ranking = meine Rangfolge
+sort-name = Name
+sort-registered = meiste Anmeldungen
+sort-deadline = Anmeldeschluss
+sort-recent = neueste
+
+## Registration
+
+registration-closed-at = Die Anmeldung wurde am { $time } geschlossen.
+registration-closes-at = Die Anmeldung schließt am { $time }.
+registration-open = Die Anmeldung ist offen.
+registration-invalid = Die Anmeldung wurde nicht angenommen: { $reason }.
+badge-registration-closed = Anmeldung geschlossen
+badge-registration-closes = Anmeldung schließt in { $left }
+left-days = { $days } T { $hours } Std.
+left-hours = { $hours } Std. { $minutes } Min.
+left-minutes = { $minutes } Min.
+
+## Results
+
+timer-up = Zeit ist um
+timer-stop = anhalten
+timer-minutes = { $minutes } Min.
+timer-start = Zeitnehmer starten
+badge-vetoed = mit Veto
+proposed-by = vorgeschlagen von { $proposers }
+round = Runde { $round }
+your-group = Deine Gruppe
+your-topics = Deine Themen
+contacts-heading = In Kontakt bleiben
+contacts-question = Deine E-Mail-Adresse mit denen in deiner Gruppe teilen, die ihre teilen?
+contacts-none-yet = Bisher hat niemand sonst in deiner Gruppe die eigene geteilt.
+contacts-declined = Du hast entschieden, deine E-Mail-Adresse nicht zu teilen.
+contacts-share = meine E-Mail-Adresse teilen
+contacts-decline = nicht teilen
+outcome-heading = Euer Ergebnis
+outcome-none = Noch nichts festgehalten.
+outcome-record = festhalten
+outcome-revise = überarbeiten
+outcome-follow-ups = knüpft an { $topics } an
+outcome-recorded-by = festgehalten von { $member }
+outcome-text-label = Was macht deine Gruppe als Nächstes?
+outcome-topics-label = An welche Themen knüpft sie an?
+next-round = Nächste Runde beginnen
+next-round-help = Bildet neue Gruppen, die die Themen erneut ordnen.
+export-summary = Zusammenfassung (Markdown)
+export-results-csv = Ergebnisse (CSV)
+export-results-json = Ergebnisse (JSON)
+past-results = Frühere Ergebnisse
+past-result = Gruppe { $cohort }, { $time }
+past-result-round = Runde { $round }, Gruppe { $cohort }, { $time }
+badge-not-convened = nicht zusammengekommen
+room-joins = Gruppe { $cohort }: { $joined } von { $members } im Raum
+check-rooms = Räume prüfen
+
+## Organizing a meeting
+
+voting-minutes-label = Minuten zum Abstimmen
+voting-minutes-set = Abstimmungszeit festlegen
+voting-unlimited = ohne Zeitlimit
+voting-minutes-help = Die Abstimmung endet { $minutes } Minuten nach Beginn jeder Runde; nicht abgegebene Stimmen zählen, wie sie gerade stehen.
+voting-unlimited-help = Die Abstimmung bleibt offen, bis alle Teilnehmenden ihre Stimme abgegeben haben.
+rename-label = Treffen umbenennen
+rename = umbenennen
+organizers-show = Mitorganisation
+organizers-label = Mitorganisation
+organizers-placeholder = E-Mail-Adresse eines Kontos
+merge-show = doppelte Themen zusammenführen
+merge-label = Themen zusammenführen, die dasselbe meinen
+merge = zusammenführen
+merge-split = abtrennen
+merge-help = Von zusammengeführten Themen zählt für jede Stimme nur der beste Rang, und zwar einmal.
+
+## Attending a meeting
+
+attending-meeting = Im Treffen: { $meeting }
+leave = verlassen
+no-topics-to-share = Du hast noch keine Themen zum Teilen. Füge welche hinzu, damit deine Gruppe mehr zur Auswahl hat als die Vorschläge der Organisation und die Themen anderer.
+add-topics = Themen hinzufügen
+voting-closes-in = Die Abstimmung endet in { $left }
+join-code = Beitrittscode:
+invite-qr = QR-Code zum Einladen
+submissions-label = Themen annehmen bis
+submissions-none = keine Einreichungen
+submissions-closed-at = Die Themeneinreichung wurde am { $time } geschlossen.
+submissions-open-until = Angemeldete können bis { $time } Themen einreichen.
+submissions-off = Teilnehmende bringen bis zu { $limit } eigene Themen mit.
+set-deadline = Frist festlegen
+seed-topic-label = Ein Thema für alle Teilnehmenden vorschlagen
+registration-deadline-label = Anmeldung schließen am
+registration-close-now = jetzt schließen
+registration-reopen = wieder öffnen
+registration-field-label = Alle fragen, die sich anmelden
+export-registrations = Anmeldungen (CSV)
+my-topics-heading = Deine Themen für dieses Treffen
+my-topics-limit = Bring bis zu { $limit } deiner Themen mit.
+my-topics-default = Bring bis zu { $limit } deiner Themen mit. Bis du wählst, kommen deine { $limit } bestplatzierten Themen mit.
+
+## Meetings
+
+new-meeting-label = Neues Treffen
+new-meeting-blank = leeres Treffen
+new-meeting-cohort-size = Gruppengröße
+new-meeting-n-winners = gewählte Themen
+new-meeting-topics-per-attendee = Themen pro Person
+new-meeting-match-language = Gruppen nach Sprache bilden
+new-meeting-share-contacts = nach der Abstimmung Kontaktaustausch anbieten
+import-show = importieren
+import-label = Exportierte Treffenkonfiguration einfügen (JSON)
+import = Importieren
+register-heading = Anmeldung für { $meeting }
+register = Anmelden
+dialog-cancel = Abbrechen
+dialog-close = Schließen
+meetings-sort = sortieren nach
+meetings-search = suchen
+meetings-search-placeholder = Name des Treffens
+meetings-page = { $first }–{ $last } von { $total }
+meetings-page-empty = keine Treffen von { $total }
+page-previous = zurück
+page-next = weiter
+join-code-label = Hast du einen Code?
+join-code-join = beitreten
+submissions-open = Themen für { $meeting } bis { $time } einreichen
+submissions-heading = Themen für { $meeting }
+submissions-help = Ordne die Themen, über die du am liebsten sprechen möchtest, zuerst. Deine Stimme beginnt in dieser Reihenfolge, wenn du teilnimmst.
+submissions-new-label = Thema einreichen
+
+## Topics
+
+links-heading = Links zu { $topic }
+links-done = fertig
+description-heading = Beschreibung von { $topic }
+description-help = Markdown: Leerzeilen zwischen Absätzen, „- “ für Listen, `Code`, **fett**, *kursiv* und [Text](https://...) für Links.
+description-versions = Frühere Fassungen
+description-versions-help = Bereits begonnene Treffen behalten die Fassung von ihrem Beginn.
+description-version = Fassung { $version }, gespeichert am { $time }
+description-first-version = Wie zuerst geschrieben
+description-none = (keine Beschreibung)
+bulk-label = Themen einfügen, eines pro Zeile
+bulk-too-long = zu lang, übersprungen: { $topic }
+bulk-add =
+    { $count ->
+        [one] Ein Thema hinzufügen
+       *[other] { $count } Themen hinzufügen
+    }
+
+## Preferences
+
+notifications = Benachrichtigungen
+notify-when = Benachrichtige mich, wenn
+notify-by = { $event } per { $channel }
+language-heading = Gesprächssprache
+language-label = Ich spreche am liebsten
+language-help = Treffen, die Gruppen nach Sprache bilden, bringen dich mit denen zusammen, die dieselbe gewählt haben. Leer lassen, wenn es dir gleich ist.
+research-heading = Forschung
+research-consent = Meine Stimmen für Forschung zu Treffen wie diesen teilen
+research-help = Geteilte Stimmen lassen weg, wer du bist, die Treffen und die Themen, und behalten nur deine Rangfolge. Ohne Haken bleiben deine Stimmen aus späteren Exporten heraus.
+avatar-heading = Avatar
+avatar-alt = dein Avatar
+avatar-remove = entfernen
+avatar-upload-help = Ein PNG-, JPEG- oder WebP-Bild von höchstens { $kib } KiB, das in deiner Gruppe neben deinem Namen erscheint.
+avatar-gravatar-help = Erscheint in deiner Gruppe neben deinem Namen. Ändern kannst du es auf gravatar.com.
+avatar-wrong-type = Avatare müssen PNG-, JPEG- oder WebP-Bilder sein.
+avatar-too-big = Avatare dürfen höchstens { $kib } KiB groß sein.
+join-requests-heading = Beitrittsanfragen
+join-requests-none = Niemand wartet auf den Beitritt.
+join-request = { $email } ({ $time })
+join-request-approve = annehmen
+join-request-decline = ablehnen
+invite-label = Jemanden per E-Mail einladen
+invite = einladen
+invitation = { $email }, bis { $time }
+invitation-link = Link
+invitation-revoke = zurückziehen
+development-heading = Entwicklung
+development-faults = API-Antworten in diesem Tab verlangsamen und stören, wie die [faults]-Tabelle des Servers es vorgibt
+
+## The page
+
+join-code-invalid = Beitrittscodes sehen aus wie HALL-1234.
+my-topics-too-many = Du kannst bis zu { $limit } Themen mitbringen. Entferne einen Haken, um ein anderes mitzubringen.
+new-topic-bulk = mehrere einfügen
+stale-bundle = Eine neue Version von eHallway ist verfügbar.
+stale-bundle-reload = Neu laden
+offline = Der eHallway-Server ist nicht erreichbar. Neuer Versuch läuft...
+offline-queued =
+    { $count ->
+        [one] Deine letzte Änderung wird gespeichert, sobald er wieder da ist.
+       *[other] Deine letzten { $count } Änderungen werden gespeichert, sobald er wieder da ist.
+    }
+build-ui = eHallway-Oberfläche { $build }
+build-server = Server { $build }
+
+## Ranked lists
+
+ranking-moved-up = { $label } nach oben verschoben
+ranking-moved-down = { $label } nach unten verschoben
+ranking-keys = Die Pfeiltasten nach oben und unten wechseln zwischen den Zeilen.
+ranking-keys-move = Mit gedrückter Strg-Taste verschieben sie den Eintrag.
+ranking-keys-join = Eingabe- oder Leertaste tritt bei oder meldet an.
+ranking-join = jetzt beitreten
+ranking-observe = zuschauen
+ranking-observe-help = Ohne Stimme beitreten, um zuzuhören
+ranking-register = anmelden
+ranking-delete = löschen
+ranking-move-up = nach oben
+ranking-move-down = nach unten
+ranking-registered = angemeldet:{ $count }
+ranking-joined = da:{ $count }
+ranking-host-suggested = von der Organisation vorgeschlagen
+ranking-links = Links
+ranking-describe = beschreiben
+ranking-veto = Veto
+ranking-private = privat
+ranking-private-on = privat: nicht auf den Stimmzetteln von Treffen
+ranking-private-off = mit Treffen geteilt
@@ -0,0 +1,337 @@
+# The UI's English wording, which other locales fall back to message by message.
+# Messages are named by where they appear; see ui/src/i18n.rs.
+
+## Tabs
+
+tab-topics = Topics
+tab-meetings = Meetings
+tab-meet = Meet
+tab-preferences = Preferences
+
+## Buttons used throughout
+
+save = save
+cancel = cancel
+
+## Meeting attendance
+
+joined-count = { $joined } of { $registered } registered participants have joined
+start-meeting = Start Meeting Now
+start-anyway = start anyway
+start-wait = wait
+ballot-in = Your ballot is in.
+retract-vote = take back my vote
+done-ranking = DONE RANKING!
+new-topic-label = Add new topic:
+new-topic-placeholder = Enter adds; paste lines to add several
+
+## Preferences
+
+display-language = Display language
+display-language-help = The language of these pages. Topics and meeting names stay as their authors wrote them.
+
+## Why a meeting can't start
+
+start-not-attending = Only attendees can start the meeting.
+start-not-organizer = Only organizers can start the meeting.
+start-too-few-attendees = Waiting for at least { $min } people ({ $here } here so far).
+start-too-few-topics = Each cohort elects { $winners } topics, but a ballot would have only { $topics }. Add host-suggested topics, or ask attendees to propose more.
+start-not-enough-participants = Not enough participants to form a cohort: need { $needed }, have { $have }. Try again once more people are here.
+start-confirm = Only { $joined } of the { $registered } registered participants have joined. Start anyway, or wait for more?
+
+## Election status codes
+
+status-cohort-pending = Cohorts form when the meeting starts.
+status-no-cohort = You are not in a cohort for this meeting.
+status-waiting-for-votes = Waiting for your cohort to finish voting: { $voted } of { $members } ballots are in.
+status-cohort-mismatch = Someone in your cohort left the meeting, so its vote can't finish.
+status-finished = Voting is finished.
+
+## Error codes
+
+error-server = Something went wrong on the server. Please try again.
+error-not-found = That no longer exists.
+error-invalid-request = That was not accepted. Please check what you entered.
+error-name-taken = Another meeting already has that name.
+error-unknown-account = Nobody has an account with that email address.
+error-not-organizer = Only the meeting's organizers can do that.
+error-not-owner = Only the meeting's owner can do that.
+error-not-proposer = Only the topic's proposer or an organizer can do that.
+error-not-attendee = Only attendees can do that.
+error-observing = You are observing this meeting, so you have no ballot.
+error-not-registered = Only those registered for the meeting can do that.
+error-not-cohort-member = Only members of a cohort can do that.
+error-not-on-ballot = That topic is not on your ballot.
+error-meeting-started = The meeting has already started.
+error-meeting-not-started = The meeting has not started yet.
+error-cohort-voting = Your cohort is still voting.
+error-already-voted = Your ballot is already in.
+error-cohort-finished = Your cohort has finished voting, so ballots can no longer change.
+error-vetoes-off = This meeting does not allow vetoes.
+error-contact-sharing-off = This meeting does not share contacts.
+error-submissions-closed = Topic submission for this meeting is closed.
+error-submitted-topics-only = This meeting's ballots hold its submitted topics, so there are none of yours to bring.
+error-backups-off = Backups are not set up.
+error-backup-running = A backup is already running.
+error-own-account = You cannot do that to your own account.
+error-account-exists = Someone already has an account with that email address.
+error-account-disabled = Your account is deactivated. Ask an administrator to reactivate it.
+error-busy = The server is busy. Please try again in a moment.
+error-research-off = This server does not share ballots for research.
+error-in-progress = That request is already being handled. Please wait a moment.
+
+## Refusals the API gives no known code for
+
+refused-my-topics = Your choice of topics was not saved.
+refused-links = The links were not saved.
+refused-description = The description was not saved.
+refused-retract = Your ballot could not be taken back.
+refused-rename = The meeting was not renamed.
+refused-add-organizer = The co-organizer was not added.
+refused-join-code = No meeting has that code.
+refused-remove-organizer = The co-organizer was not removed.
+refused-merge = The topics were not merged.
+refused-split = The topic was not split off.
+refused-contact-sharing = Your choice was not saved.
+refused-outcome = The outcome was not recorded.
+refused-avatar = That image could not be used as your avatar.
+refused-invitation = The invitation was not sent.
+refused-language = That is not a language code, like "en" or "pt-BR".
+refused-start = The meeting could not start.
+refused-next-round = The next round could not start.
+refused-veto = The veto was not recorded.
+refused-submission = The topic was not submitted.
+import-failed = The meeting configuration was not imported.
+
+## Choices and their labels
+
+notify-meeting-started = A meeting I registered for starts
+notify-results-ready = My cohort's topics are elected
+notify-daily-digest = Daily digest of meetings and topics
+notify-weekly-digest = Weekly digest of meetings and topics
+channel-email = Email
+channel-web-push = Browser push
+channel-webhook = Webhook
+veto-off = no vetoes
+veto-exclude = vetoes exclude topics
+veto-penalize = vetoes demote topics
+method-borda = Borda count
+method-condorcet = Condorcet
+method-instant-runoff = instant runoff
+sort-ranking = my ranking
+sort-name = name
+sort-registered = most registered
+sort-deadline = registration deadline
+sort-recent = newest
+
+## Registration
+
+registration-closed-at = Registration closed at { $time }.
+registration-closes-at = Registration closes at { $time }.
+registration-open = Registration is open.
+registration-invalid = Registration was not accepted: { $reason }.
+badge-registration-closed = registration closed
+badge-registration-closes = registration closes in { $left }
+left-days = { $days }d { $hours }h
+left-hours = { $hours }h { $minutes }m
+left-minutes = { $minutes }m
+
+## Results
+
+timer-up = time's up
+timer-stop = stop
+timer-minutes = { $minutes } min
+timer-start = start timer
+badge-vetoed = vetoed
+proposed-by = proposed by { $proposers }
+round = Round { $round }
+your-group = Your Group
+your-topics = Your Topics
+contacts-heading = Stay in Touch
+contacts-question = Share your email with the others in your group who share theirs?
+contacts-none-yet = Nobody else in your group has shared theirs yet.
+contacts-declined = You chose not to share your email.
+contacts-share = share my email
+contacts-decline = don't share
+outcome-heading = Your Outcome
+outcome-none = Nothing recorded yet.
+outcome-record = record
+outcome-revise = revise
+outcome-follow-ups = following up on { $topics }
+outcome-recorded-by = recorded by { $member }
+outcome-text-label = What will your group do next?
+outcome-topics-label = Which topics does it follow up on?
+next-round = Start Next Round
+next-round-help = Forms new cohorts, who rank the topics again.
+export-summary = Summary (Markdown)
+export-results-csv = Results (CSV)
+export-results-json = Results (JSON)
+past-results = Past results
+past-result = Cohort { $cohort }, { $time }
+past-result-round = Round { $round }, Cohort { $cohort }, { $time }
+badge-not-convened = not convened
+room-joins = Cohort { $cohort }: { $joined } of { $members } in the room
+check-rooms = check rooms
+
+## Organizing a meeting
+
+voting-minutes-label = Minutes for voting
+voting-minutes-set = set voting time
+voting-unlimited = no time limit
+voting-minutes-help = Voting closes { $minutes } minutes after each round starts, counting unsubmitted ballots as they stand.
+voting-unlimited-help = Voting stays open until every attendee submits their ballot.
+rename-label = Rename the meeting
+rename = rename
+organizers-show = co-organizers
+organizers-label = Co-organizers
+organizers-placeholder = email of an account
+merge-show = merge duplicate topics
+merge-label = Merge topics that mean the same thing
+merge = merge
+merge-split = split off
+merge-help = Each voter's best rank among merged topics counts once.
+
+## Attending a meeting
+
+attending-meeting = Attending meeting: { $meeting }
+leave = leave
+no-topics-to-share = You have no topics to share yet. Add some, so your group has more to choose from than the organizers' suggestions and other people's topics.
+add-topics = Add topics
+voting-closes-in = Voting closes in { $left }
+join-code = Join code:
+invite-qr = Invite QR code
+submissions-label = Take topic submissions until
+submissions-none = no submissions
+submissions-closed-at = Topic submission closed at { $time }.
+submissions-open-until = Registered users can submit topics until { $time }.
+submissions-off = Attendees bring up to { $limit } of their own topics.
+set-deadline = set deadline
+seed-topic-label = Suggest a topic for every attendee
+registration-deadline-label = Close registration at
+registration-close-now = close now
+registration-reopen = reopen
+registration-field-label = Ask everyone who registers
+export-registrations = Registrations (CSV)
+my-topics-heading = Your topics for this meeting
+my-topics-limit = Bring up to { $limit } of your topics.
+my-topics-default = Bring up to { $limit } of your topics. Until you choose, your { $limit } top-ranked topics come along.
+
+## Meetings
+
+new-meeting-label = Add new meeting
+new-meeting-blank = blank meeting
+new-meeting-cohort-size = cohort size
+new-meeting-n-winners = winning topics
+new-meeting-topics-per-attendee = topics per attendee
+new-meeting-match-language = match cohorts by language
+new-meeting-share-contacts = offer to share contacts after voting
+import-show = import
+import-label = Paste an exported meeting configuration (JSON)
+import = Import
+register-heading = Register for { $meeting }
+register = Register
+dialog-cancel = Cancel
+dialog-close = Close
+meetings-sort = sort by
+meetings-search = search
+meetings-search-placeholder = meeting name
+meetings-page = { $first }–{ $last } of { $total }
+meetings-page-empty = no meetings of { $total }
+page-previous = previous
+page-next = next
+join-code-label = Have a code?
+join-code-join = join
+submissions-open = submit topics for { $meeting } by { $time }
+submissions-heading = Topics for { $meeting }
+submissions-help = Rank the topics you most want to discuss first. Your ballot starts in this order when you attend.
+submissions-new-label = Submit a topic
+
+## Topics
+
+links-heading = Links for { $topic }
+links-done = done
+description-heading = Description of { $topic }
+description-help = Markdown: blank lines between paragraphs, "- " for lists, `code`, **bold**, *italic*, and [text](https://...) links.
+description-versions = Earlier versions
+description-versions-help = Meetings that had started keep the version from when they started.
+description-version = Version { $version }, saved { $time }
+description-first-version = As first written
+description-none = (no description)
+bulk-label = Paste topics, one per line
+bulk-too-long = too long, skipped: { $topic }
+bulk-add =
+    { $count ->
+        [one] Add one topic
+       *[other] Add { $count } topics
+    }
+
+## Preferences
+
+notifications = Notifications
+notify-when = Notify me when
+notify-by = { $event } by { $channel }
+language-heading = Conversation language
+language-label = I prefer to talk in
+language-help = Meetings that match cohorts by language group you with others who chose the same one. Leave it empty for no preference.
+research-heading = Research
+research-consent = Share my ballots for research on meetings like these
+research-help = Shared ballots leave out who you are, the meetings, and the topics, keeping only how you ranked them. Unchecking leaves your ballots out of later exports.
+avatar-heading = Avatar
+avatar-alt = your avatar
+avatar-remove = remove
+avatar-upload-help = A PNG, JPEG, or WebP image of at most { $kib } KiB, shown beside your name in your group.
+avatar-gravatar-help = Shown beside your name in your group. Change it at gravatar.com.
+avatar-wrong-type = Avatars must be PNG, JPEG, or WebP images.
+avatar-too-big = Avatars must be at most { $kib } KiB.
+join-requests-heading = Join requests
+join-requests-none = Nobody is waiting to join.
+join-request = { $email } ({ $time })
+join-request-approve = approve
+join-request-decline = decline
+invite-label = Invite someone by email
+invite = invite
+invitation = { $email }, until { $time }
+invitation-link = link
+invitation-revoke = revoke
+development-heading = Development
+development-faults = Slow and break API responses in this tab, as the server's [faults] table says
+
+## The page
+
+join-code-invalid = Join codes look like HALL-1234.
+my-topics-too-many = You can bring up to { $limit } topics. Untick one to bring another.
+new-topic-bulk = paste several
+stale-bundle = A new version of eHallway is available.
+stale-bundle-reload = Reload
+offline = Cannot reach the eHallway server. Retrying...
+offline-queued =
+    { $count ->
+        [one] Your last change will be saved once it's back.
+       *[other] Your last { $count } changes will be saved once it's back.
+    }
+build-ui = eHallway ui { $build }
+build-server = server { $build }
+
+## Ranked lists
+
+ranking-moved-up = Moved { $label } up
+ranking-moved-down = Moved { $label } down
+ranking-keys = The up and down arrow keys move between rows.
+ranking-keys-move = With Ctrl held, they move the item.
+ranking-keys-join = Enter or Space joins or registers.
+ranking-join = join now
+ranking-observe = observe
+ranking-observe-help = Join without a ballot, to listen in
+ranking-register = register
+ranking-delete = delete
+ranking-move-up = move up
+ranking-move-down = move down
+ranking-registered = registered:{ $count }
+ranking-joined = joined:{ $count }
+ranking-host-suggested = host suggested
+ranking-links = links
+ranking-describe = describe
+ranking-veto = veto
+ranking-private = private
+ranking-private-on = private: kept off meeting ballots
+ranking-private-off = shared with meetings
@@ -0,0 +1,265 @@
+use std::cell::{Cell, RefCell};
+
+use anyhow::{anyhow, Context, Result};
+use gloo_net::http::{Request, Response};
+use gloo_timers::future::TimeoutFuture;
+use serde::Serialize;
+
+/// Sent with every request and echoed by the API, so errors can be found in its log
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+/// Lets the API recognize a retried request and replay its first response
+const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+/// Seconds to wait before polling again, which the API raises under load
+const RETRY_AFTER_HEADER: &str = "Retry-After";
+/// Asks the API to slow and break its responses, if its `[faults]` table allows
+const FAULTS_HEADER: &str = "X-Ehallway-Faults";
+// In session storage, so faults stay on across reloads of the tab but no longer
+const FAULTS_STORAGE_KEY: &str = "ehallway_faults";
+const MAX_ATTEMPTS: u32 = 4;
+const BASE_BACKOFF_MS: u32 = 250;
+const MAX_BACKOFF_MS: u32 = 4_000;
+// Consecutive failed attempts after which the API is considered unreachable
+const FAILURES_TO_OPEN: u32 = 5;
+// While the circuit is open, requests fail without touching the network.
+const OPEN_COOLDOWN_MS: f64 = 10_000.0;
+
+thread_local! {
+    static CONSECUTIVE_FAILURES: Cell<u32> = Cell::new(0);
+    static OPENED_AT: Cell<Option<f64>> = Cell::new(None);
+    static POLL_HINT_MS: Cell<Option<u32>> = Cell::new(None);
+    static FAULTS: Cell<bool> = Cell::new(stored_faults());
+    // PUTs that couldn't reach the API, as their URLs and JSON bodies, oldest first
+    static QUEUED: RefCell<Vec<(String, String)>> = RefCell::new(vec![]);
+    static FLUSHING: Cell<bool> = Cell::new(false);
+}
+
+fn session_storage() -> Option<web_sys::Storage> {
+    gloo_utils::window().session_storage().ok().flatten()
+}
+
+fn stored_faults() -> bool {
+    session_storage()
+        .and_then(|storage| storage.get_item(FAULTS_STORAGE_KEY).ok().flatten())
+        .is_some()
+}
+
+/// Whether this tab asks the API for injected faults
+pub fn faults_enabled() -> bool {
+    FAULTS.with(|faults| faults.get())
+}
+
+pub fn set_faults_enabled(enabled: bool) {
+    FAULTS.with(|faults| faults.set(enabled));
+    if let Some(storage) = session_storage() {
+        let stored = if enabled {
+            storage.set_item(FAULTS_STORAGE_KEY, "on")
+        } else {
+            storage.remove_item(FAULTS_STORAGE_KEY)
+        };
+        if let Err(e) = stored {
+            gloo_console::console_dbg!(e);
+        }
+    }
+}
+
+/// The interval between polls that the API last suggested
+pub fn poll_hint_ms() -> Option<u32> {
+    POLL_HINT_MS.with(|hint| hint.get())
+}
+
+fn record_poll_hint(resp: &Response) {
+    let secs = resp
+        .headers()
+        .get(RETRY_AFTER_HEADER)
+        .and_then(|secs| secs.trim().parse::<u32>().ok());
+    if let Some(secs) = secs {
+        POLL_HINT_MS.with(|hint| hint.set(Some(secs.saturating_mul(1000))));
+    }
+}
+
+/// True from the time the circuit opens until a request succeeds again
+pub fn is_offline() -> bool {
+    OPENED_AT.with(|opened_at| opened_at.get().is_some())
+}
+
+fn circuit_open() -> bool {
+    OPENED_AT.with(|opened_at| match opened_at.get() {
+        Some(t) => js_sys::Date::now() - t < OPEN_COOLDOWN_MS,
+        None => false,
+    })
+}
+
+fn record_success() {
+    CONSECUTIVE_FAILURES.with(|n| n.set(0));
+    OPENED_AT.with(|opened_at| opened_at.set(None));
+}
+
+// After the cooldown one request is let through, and its failure re-opens the circuit.
+fn record_failure() {
+    let failures = CONSECUTIVE_FAILURES.with(|n| {
+        n.set(n.get() + 1);
+        n.get()
+    });
+    if failures >= FAILURES_TO_OPEN {
+        OPENED_AT.with(|opened_at| opened_at.set(Some(js_sys::Date::now())));
+    }
+}
+
+// Proxies answer with these while the API restarts.
+fn is_transient(status: u16) -> bool {
+    matches!(status, 502 | 503 | 504)
+}
+
+// Exponential backoff with full jitter
+fn backoff_ms(attempt: u32) -> u32 {
+    let cap = BASE_BACKOFF_MS
+        .saturating_mul(1 << attempt.min(16))
+        .min(MAX_BACKOFF_MS);
+    (js_sys::Math::random() * cap as f64) as u32
+}
+
+fn new_request_id() -> String {
+    format!("{:014x}", (js_sys::Math::random() * 2f64.powi(53)) as u64)
+}
+
+/// Sends a request once, without retrying, so it is safe for POST.
+pub async fn send(request: Request) -> Result<Response> {
+    send_with_id(request, &new_request_id()).await
+}
+
+async fn send_with_id(request: Request, id: &str) -> Result<Response> {
+    if circuit_open() {
+        return Err(anyhow!("API unreachable, not sending request_id={id}"));
+    }
+    let request = if faults_enabled() {
+        request.header(FAULTS_HEADER, "on")
+    } else {
+        request
+    };
+    let result = request.header(REQUEST_ID_HEADER, id).send().await;
+    match &result {
+        Ok(resp) if !is_transient(resp.status()) => {
+            record_success();
+            record_poll_hint(resp);
+        }
+        _ => record_failure(),
+    }
+    result.with_context(|| format!("request_id={id}"))
+}
+
+// Retries share one id, so the API log shows every attempt.
+async fn send_with_retry(build: impl Fn() -> Result<Request>) -> Result<Response> {
+    let id = new_request_id();
+    let mut attempt = 0;
+    loop {
+        let result = send_with_id(build()?, &id).await;
+        let retry = match &result {
+            Ok(resp) => is_transient(resp.status()),
+            Err(_) => true,
+        };
+        if !retry || attempt + 1 >= MAX_ATTEMPTS || circuit_open() {
+            return result;
+        }
+        TimeoutFuture::new(backoff_ms(attempt)).await;
+        attempt += 1;
+    }
+}
+
+pub async fn get(url: &str) -> Result<Response> {
+    send_with_retry(|| Ok(Request::get(url))).await
+}
+
+pub async fn put(url: &str) -> Result<Response> {
+    send_with_retry(|| Ok(Request::put(url))).await
+}
+
+/// Like `put`, but retries carry one Idempotency-Key,
+/// so the API acts on the request at most once.
+pub async fn put_idempotent(url: &str) -> Result<Response> {
+    let key = new_request_id();
+    send_with_retry(|| Ok(Request::put(url).header(IDEMPOTENCY_KEY_HEADER, &key))).await
+}
+
+/// Like `put_idempotent`, for POST
+pub async fn post_idempotent(url: &str) -> Result<Response> {
+    let key = new_request_id();
+    send_with_retry(|| Ok(Request::post(url).header(IDEMPOTENCY_KEY_HEADER, &key))).await
+}
+
+pub async fn put_json<T: Serialize>(url: &str, body: &T) -> Result<Response> {
+    send_with_retry(|| Ok(Request::put(url).json(body)?)).await
+}
+
+/// Like `put_json`, but a request that can't reach the API is queued
+/// for `flush_queued` to send once it's back, so the user's change isn't lost.
+/// Requests queue behind any already waiting, to keep their order.
+pub async fn put_json_or_queue<T: Serialize>(url: &str, body: &T) -> Result<Response> {
+    let json = serde_json::to_string(body)?;
+    let result = if n_queued() > 0 {
+        Err(anyhow!("waiting for earlier changes"))
+    } else {
+        send_queued(url, &json).await
+    };
+    match result {
+        Ok(resp) if !is_transient(resp.status()) => Ok(resp),
+        Ok(resp) => {
+            QUEUED.with(|queued| queued.borrow_mut().push((url.to_owned(), json)));
+            Err(anyhow!("response status {}, queued {url}", resp.status()))
+        }
+        Err(e) => {
+            QUEUED.with(|queued| queued.borrow_mut().push((url.to_owned(), json)));
+            Err(e.context(format!("queued {url}")))
+        }
+    }
+}
+
+async fn send_queued(url: &str, json: &str) -> Result<Response> {
+    send_with_retry(|| {
+        Ok(Request::put(url)
+            .header("Content-Type", "application/json")
+            .body(json))
+    })
+    .await
+}
+
+/// How many changes are waiting for the API
+pub fn n_queued() -> usize {
+    QUEUED.with(|queued| queued.borrow().len())
+}
+
+/// Sends the queued changes in order, stopping at one that still can't reach the API.
+/// Changes the API refuses are dropped, as they would be if sent at once.
+pub async fn flush_queued() -> Result<()> {
+    if FLUSHING.with(|flushing| flushing.replace(true)) {
+        return Ok(());
+    }
+    let result = async {
+        while let Some((url, json)) = QUEUED.with(|queued| queued.borrow().first().cloned()) {
+            let resp = send_queued(&url, &json).await?;
+            if is_transient(resp.status()) {
+                return Err(anyhow!(
+                    "response status {}, still queued {url}",
+                    resp.status()
+                ));
+            }
+            QUEUED.with(|queued| queued.borrow_mut().remove(0));
+            if resp.status() != 200 {
+                gloo_console::console_dbg!(format!("queued {url} refused: {}", resp.status()));
+            }
+        }
+        Ok(())
+    }
+    .await;
+    FLUSHING.with(|flushing| flushing.set(false));
+    result
+}
+
+/// Like `put_json`, for data the browser holds, like a chosen file, sent with its type
+pub async fn put_blob(url: &str, blob: &web_sys::Blob) -> Result<Response> {
+    send_with_retry(|| {
+        Ok(Request::put(url)
+            .header("Content-Type", &blob.type_())
+            .body(blob.clone()))
+    })
+    .await
+}
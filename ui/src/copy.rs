@@ -0,0 +1,109 @@
+use ehall::{ElectionStatus, ErrorCode, StartMeetingError};
+
+use crate::i18n;
+
+// What the UI says for the API's codes, as the ids of their messages in ui/locales.
+// Matching every code means a new one can't go without wording.
+
+/// What to tell the user about an error body's "code",
+/// or None for a code this build doesn't know
+pub fn error_text(code: &str) -> Option<String> {
+    let code = ErrorCode::ALL.into_iter().find(|c| c.as_str() == code)?;
+    Some(i18n::text(error_code_id(code)))
+}
+
+/// What to tell the user about an `ElectionResults` status,
+/// or None for a status this build doesn't know
+pub fn election_status_text(status: &ElectionStatus) -> Option<String> {
+    let id = status_id(status)?;
+    Some(match *status {
+        ElectionStatus::WaitingForVotes { n_voted, n_members } => i18n::text_with(
+            id,
+            &[("voted", n_voted.into()), ("members", n_members.into())],
+        ),
+        _ => i18n::text(id),
+    })
+}
+
+/// Why the meeting can't start, or what starting it now needs
+pub fn start_meeting_error_text(e: &StartMeetingError) -> String {
+    match *e {
+        StartMeetingError::NotAttending => i18n::text("start-not-attending"),
+        StartMeetingError::NotOrganizer => i18n::text("start-not-organizer"),
+        StartMeetingError::TooFewAttendees {
+            min_attendees,
+            n_attendees,
+        } => i18n::text_with(
+            "start-too-few-attendees",
+            &[("min", min_attendees.into()), ("here", n_attendees.into())],
+        ),
+        StartMeetingError::TooFewTopics {
+            n_winners,
+            n_topics,
+        } => i18n::text_with(
+            "start-too-few-topics",
+            &[("winners", n_winners.into()), ("topics", n_topics.into())],
+        ),
+        StartMeetingError::NotEnoughParticipants { needed, have } => i18n::text_with(
+            "start-not-enough-participants",
+            &[("needed", needed.into()), ("have", have.into())],
+        ),
+        StartMeetingError::ConfirmRequired {
+            n_registered,
+            n_joined,
+        } => i18n::text_with(
+            "start-confirm",
+            &[
+                ("joined", n_joined.into()),
+                ("registered", n_registered.into()),
+            ],
+        ),
+    }
+}
+
+pub(crate) fn error_code_id(code: ErrorCode) -> &'static str {
+    match code {
+        ErrorCode::DatabaseError | ErrorCode::InternalError => "error-server",
+        ErrorCode::NotFound => "error-not-found",
+        ErrorCode::InvalidRequest => "error-invalid-request",
+        ErrorCode::NameTaken => "error-name-taken",
+        ErrorCode::UnknownAccount => "error-unknown-account",
+        ErrorCode::NotOrganizer => "error-not-organizer",
+        ErrorCode::NotOwner => "error-not-owner",
+        ErrorCode::NotProposer => "error-not-proposer",
+        ErrorCode::NotAttendee => "error-not-attendee",
+        ErrorCode::Observing => "error-observing",
+        ErrorCode::NotRegistered => "error-not-registered",
+        ErrorCode::NotCohortMember => "error-not-cohort-member",
+        ErrorCode::NotOnBallot => "error-not-on-ballot",
+        ErrorCode::MeetingStarted => "error-meeting-started",
+        ErrorCode::MeetingNotStarted => "error-meeting-not-started",
+        ErrorCode::CohortVoting => "error-cohort-voting",
+        ErrorCode::AlreadyVoted => "error-already-voted",
+        ErrorCode::CohortFinished => "error-cohort-finished",
+        ErrorCode::VetoesOff => "error-vetoes-off",
+        ErrorCode::ContactSharingOff => "error-contact-sharing-off",
+        ErrorCode::SubmissionsClosed => "error-submissions-closed",
+        ErrorCode::SubmittedTopicsOnly => "error-submitted-topics-only",
+        ErrorCode::BackupsOff => "error-backups-off",
+        ErrorCode::BackupRunning => "error-backup-running",
+        ErrorCode::OwnAccount => "error-own-account",
+        ErrorCode::AccountExists => "error-account-exists",
+        ErrorCode::AccountDisabled => "error-account-disabled",
+        ErrorCode::Busy => "error-busy",
+        ErrorCode::ResearchOff => "error-research-off",
+        ErrorCode::KeyReused | ErrorCode::RequestInProgress => "error-in-progress",
+    }
+}
+
+pub(crate) fn status_id(status: &ElectionStatus) -> Option<&'static str> {
+    match status {
+        ElectionStatus::CohortPending => Some("status-cohort-pending"),
+        ElectionStatus::NoCohort => Some("status-no-cohort"),
+        ElectionStatus::WaitingForVotes { .. } => Some("status-waiting-for-votes"),
+        ElectionStatus::CohortMismatch => Some("status-cohort-mismatch"),
+        ElectionStatus::Finished => Some("status-finished"),
+        // A newer API's status
+        _ => None,
+    }
+}
@@ -0,0 +1,201 @@
+use std::cell::RefCell;
+
+use fluent::{FluentArgs, FluentBundle, FluentResource, FluentValue};
+use unic_langid::LanguageIdentifier;
+
+// The UI's wording in each locale, as Fluent (https://projectfluent.org) messages
+// in ui/locales.  English has every message, and the others fall back to it.
+
+/// The locales the UI can be shown in, by tag, each named in its own language
+pub const LOCALES: [(&str, &str); 2] = [("en", "English"), ("de", "Deutsch")];
+const FALLBACK_LOCALE: &str = "en";
+// In local storage, so the choice outlasts the tab
+const LOCALE_STORAGE_KEY: &str = "ehallway_locale";
+
+thread_local! {
+    static FALLBACK: FluentBundle<FluentResource> = bundle(FALLBACK_LOCALE);
+    static CURRENT: RefCell<(&'static str, FluentBundle<FluentResource>)> = {
+        let tag = initial_locale();
+        mark_page(tag);
+        RefCell::new((tag, bundle(tag)))
+    };
+}
+
+fn source(tag: &str) -> &'static str {
+    match tag {
+        "de" => include_str!("../locales/de.ftl"),
+        _ => include_str!("../locales/en.ftl"),
+    }
+}
+
+// Regional variants, like pt-BR, get their language's locale.
+fn supported(tag: &str) -> Option<&'static str> {
+    let language = tag.split(['-', '_']).next()?.to_ascii_lowercase();
+    LOCALES
+        .into_iter()
+        .map(|(supported, _)| supported)
+        .find(|&supported| supported == language)
+}
+
+fn local_storage() -> Option<web_sys::Storage> {
+    gloo_utils::window().local_storage().ok().flatten()
+}
+
+// The one chosen before, or else the browser's
+fn initial_locale() -> &'static str {
+    let stored =
+        local_storage().and_then(|storage| storage.get_item(LOCALE_STORAGE_KEY).ok().flatten());
+    stored
+        .into_iter()
+        .chain(gloo_utils::window().navigator().language())
+        .find_map(|tag| supported(&tag))
+        .unwrap_or(FALLBACK_LOCALE)
+}
+
+fn bundle(tag: &'static str) -> FluentBundle<FluentResource> {
+    let langid: LanguageIdentifier = tag.parse().expect("locale tags are language identifiers");
+    let mut bundle = FluentBundle::new(vec![langid]);
+    // Isolation marks around arguments show as boxes in some fonts.
+    bundle.set_use_isolating(false);
+    // Messages that parse are kept, so one typo costs only its own message.
+    let resource =
+        FluentResource::try_new(source(tag).to_owned()).unwrap_or_else(|(resource, errors)| {
+            gloo_console::error!(format!("parsing the {tag} locale: {errors:?}"));
+            resource
+        });
+    if let Err(errors) = bundle.add_resource(resource) {
+        gloo_console::error!(format!("loading the {tag} locale: {errors:?}"));
+    }
+    bundle
+}
+
+fn formatted(
+    bundle: &FluentBundle<FluentResource>,
+    id: &str,
+    args: Option<&FluentArgs>,
+) -> Option<String> {
+    let pattern = bundle.get_message(id)?.value()?;
+    let mut errors = vec![];
+    let text = bundle
+        .format_pattern(pattern, args, &mut errors)
+        .into_owned();
+    if !errors.is_empty() {
+        gloo_console::error!(format!("formatting {id}: {errors:?}"));
+    }
+    Some(text)
+}
+
+fn format(id: &str, args: Option<&FluentArgs>) -> String {
+    CURRENT
+        .with(|current| formatted(&current.borrow().1, id, args))
+        .or_else(|| FALLBACK.with(|fallback| formatted(fallback, id, args)))
+        .unwrap_or_else(|| {
+            gloo_console::error!(format!("no message {id}"));
+            id.to_owned()
+        })
+}
+
+/// The tag of the locale the UI is shown in
+pub fn locale() -> &'static str {
+    CURRENT.with(|current| current.borrow().0)
+}
+
+/// Shows the UI in the locale `tag` from now on, if it is one of `LOCALES`
+pub fn set_locale(tag: &str) {
+    let Some(tag) = supported(tag) else {
+        return;
+    };
+    CURRENT.with(|current| *current.borrow_mut() = (tag, bundle(tag)));
+    if let Some(storage) = local_storage() {
+        if let Err(e) = storage.set_item(LOCALE_STORAGE_KEY, tag) {
+            gloo_console::console_dbg!(e);
+        }
+    }
+    mark_page(tag);
+}
+
+// Screen readers pronounce the page by its language.
+fn mark_page(tag: &str) {
+    if let Some(html) = gloo_utils::document().document_element() {
+        if let Err(e) = html.set_attribute("lang", tag) {
+            gloo_console::console_dbg!(e);
+        }
+    }
+}
+
+/// The message `id` in the current locale
+pub fn text(id: &str) -> String {
+    format(id, None)
+}
+
+/// The message `id` in the current locale, filled in with `args`
+pub fn text_with(id: &str, args: &[(&str, FluentValue)]) -> String {
+    let mut fluent_args = FluentArgs::new();
+    for (name, value) in args {
+        fluent_args.set(*name, value.clone());
+    }
+    format(id, Some(&fluent_args))
+}
+
+#[cfg(test)]
+mod tests {
+    use ehall::{ElectionStatus, ErrorCode};
+    use fluent::FluentResource;
+
+    use super::{bundle, source, supported, LOCALES};
+    use crate::copy::{error_code_id, status_id};
+
+    // Message ids start their lines, as in "status-finished = ...".
+    fn message_ids(tag: &str) -> Vec<&'static str> {
+        source(tag)
+            .lines()
+            .filter(|line| line.starts_with(|c: char| c.is_ascii_lowercase()))
+            .filter_map(|line| line.split_once(" =").map(|(id, _)| id))
+            .collect()
+    }
+
+    #[test]
+    fn test_locales_parse() {
+        for (tag, _) in LOCALES {
+            assert!(
+                FluentResource::try_new(source(tag).to_owned()).is_ok(),
+                "{tag} locale"
+            );
+        }
+    }
+
+    #[test]
+    fn test_locales_complete() {
+        let english = message_ids("en");
+        for code in ErrorCode::ALL {
+            assert!(english.contains(&error_code_id(code)), "{code:?}");
+        }
+        let statuses = [
+            ElectionStatus::CohortPending,
+            ElectionStatus::NoCohort,
+            ElectionStatus::WaitingForVotes {
+                n_voted: 0,
+                n_members: 0,
+            },
+            ElectionStatus::CohortMismatch,
+            ElectionStatus::Finished,
+        ];
+        for status in statuses.iter() {
+            assert!(english.contains(&status_id(status).unwrap()), "{status:?}");
+        }
+        assert_eq!(status_id(&ElectionStatus::Unknown), None);
+        for (tag, _) in LOCALES {
+            let bundle = bundle(tag);
+            for id in english.iter() {
+                assert!(bundle.has_message(id), "{tag} locale lacks {id}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_supported() {
+        assert_eq!(supported("de-AT"), Some("de"));
+        assert_eq!(supported("EN_us"), Some("en"));
+        assert_eq!(supported("fr"), None);
+    }
+}
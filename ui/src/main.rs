@@ -1,67 +1,272 @@
-use std::{borrow::Cow, boxed, collections::HashSet};
+use std::{
+    borrow::Cow,
+    boxed,
+    collections::{HashMap, HashSet},
+};
 
 use anyhow::{anyhow, Error, Result};
 use gloo_console::console_dbg;
 use gloo_net::http;
 use gloo_timers::callback::Interval;
-use web_sys::HtmlInputElement;
+use gloo_timers::future::TimeoutFuture;
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{ClipboardEvent, HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
 use yew::prelude::*;
 
+use copy::start_meeting_error_text;
 use ehall::{
-    ElectionResults, Meeting, MeetingsMessage, NewMeeting, NewTopicMessage,
-    ParticipateMeetingMessage, RegisteredMeetingsMessage, ScoreMessage, UserIdMessage, UserTopic,
-    UserTopicsMessage,
+    validate_topic_description, validate_topic_links, AttendMeetingMessage, AttendeeRole,
+    AvatarMessage, BuildInfo, CandidateTopic, CandidateTopicsMessage, CohortOutcome, Collation,
+    ContactSharing, ContactSharingMessage, ElectionResults, ElectionStatus, InstanceInfo,
+    Invitation, InvitationsMessage, JoinDecisionMessage, JoinRequest, JoinRequestsMessage,
+    LanguageMessage, Meeting, MeetingConfig, MeetingHistoryMessage, MeetingMessage,
+    MeetingOrganizersMessage, MeetingPermissions, MeetingSort, MeetingSortMessage, MeetingTemplate,
+    MeetingTemplatesMessage, MeetingsMessage, MergeTopicsMessage, MyTopicsMessage,
+    NewInvitationMessage, NewMeeting, NewOrganizerMessage, NewTopicMessage, NotificationChannel,
+    NotificationEvent, NotificationPreference, NotificationPreferencesMessage, OutcomeMessage,
+    ParticipateMeetingMessage, RegisteredMeetingsMessage, RegistrationAnswer,
+    RegistrationDeadlineMessage, RegistrationError, RegistrationField, RegistrationFieldsMessage,
+    RenameMeetingMessage, ResearchConsentMessage, RoomJoinsMessage, ScoreMessage, ScoresMessage,
+    SeedTopicsMessage, StartMeetingError, SubmissionDeadlineMessage, SubmittedTopicsMessage,
+    SyncMessage, TopicDescriptionMessage, TopicEdit, TopicEditsMessage, TopicHistoryMessage,
+    TopicLinksMessage, TopicPrivacyMessage, UserIdMessage, UserTopic, UserTopicsMessage,
+    VetoMessage, VetoPolicy, VotingMethod, VotingMinutesMessage, AVATAR_CONTENT_TYPES,
+    COHORT_QUORUM, DEFAULT_N_WINNERS, DEFAULT_PAGE_LIMIT, DEFAULT_TOPICS_PER_ATTENDEE,
+    MAX_AVATAR_BYTES, MAX_COHORT_SIZE, MAX_DESCRIPTION_LEN, MAX_N_WINNERS, MAX_TEXT_LEN,
+    MAX_TOPICS_PER_ATTENDEE, MAX_TOPIC_LINKS, MAX_VOTING_MINUTES, MIN_COHORT_SIZE, MIN_N_WINNERS,
+    MIN_TOPICS_PER_ATTENDEE, MIN_VOTING_MINUTES, SCHEMA_VERSION,
 };
-use svg::add_icon;
+use svg::{add_icon, x_icon};
 
+mod client;
+mod copy;
+mod i18n;
+mod markdown;
 mod ranking;
 mod svg;
 
 const CHECK_ELECTION_MS: u32 = 1_000;
+// How long the API may hold a request for the election status to change
+const ELECTION_WAIT_SECS: u32 = 30;
+// Longer meetings lists, and searches, are shown a page at a time.
+const MEETINGS_PAGE_SIZE: u32 = 20;
+const OFFLINE_PROBE_MS: u32 = 5_000;
+const TOPIC_HISTORY_DAYS: f64 = 90.0;
+const TOPIC_TIMER_TICK_MS: u32 = 1_000;
 
 enum Msg {
+    AddBulkTopics,
     AddMeeting,
+    AddOrganizer,
+    AddRegistrationField,
+    AddSeedTopic,
     AddTopic,
+    AddTopicFailed(String, Error),
+    AddTopicLink,
     AddedMeeting,
+    AddedRegistrationField,
+    AddedSeedTopic(boxed::Box<u32>),
     AddedTopic,
     AttendingMeeting(boxed::Box<u32>),
-    AttendMeeting(u32),
+    AttendMeeting(u32, AttendeeRole),
+    AvatarFailed(String),
     CheckElection,
+    CheckInstance,
     CheckMeetings,
+    CloseSubmissions,
+    DeleteAvatar,
     DeleteMeeting(u32),
     DeleteUserTopic(u32),
     DidFinishVoting,
     DidStoreMeetingScore,
     DidStoreMeetingTopicScore(boxed::Box<u32>),
     DidStoreUserTopicScore,
+    CancelOutcome,
+    CancelRegistration,
     CommitVote,
+    ConfirmStart(Option<(u32, u32)>), // registered and joined, or None to stop asking
+    ContactSharingFailed(String),
+    DecideJoinRequest(String, bool), // the email and whether it's approved
+    EditOutcome,
+    EditTopicDescription(u32),
+    EditTopicLinks(u32),
+    ElectionWaited(Result<ElectionResults>, u32), // and the milliseconds it took
+    FetchAvatar,
+    FetchCandidateTopics,
+    FetchLanguage,
+    FetchMeetingHistory,
+    FetchMeetingPage(u32), // the offset
+    FetchMeetingTemplates,
     FetchMeetingTopics(u32),
+    FetchMembership,
+    FetchMyTopics(u32),
+    FetchNotificationPreferences,
+    FetchOrganizers,
+    FetchResearchConsent,
+    FetchRoomJoins,
+    FetchTopicHistory,
     FetchUserTopics,
+    FlushQueued,
+    FocusTab(Tab), // SetTab from the keyboard, taking focus along
+    HardRefresh,
+    ImportedMeeting,
+    ImportMeeting,
+    ImportMeetingFailed(String),
+    InviteFailed(String),
+    InviteMember,
+    JoinByCode,
+    JoinCodeFailed(String),
+    JoinedByCode(JoinedMeetingMessage),
+    JoinedRoom,
+    LanguageFailed(String),
     LeaveMeeting,
     LeftMeeting(boxed::Box<u32>),
     LogError(Error),
     MeetingRegisteredChanged,
     MeetingToggleRegistered(u32),
+    MergeTopics,
+    MergeTopicsFailed(String),
+    MyTopicsFailed(String),
+    NextRound,
+    NextRoundFailed(String),
     Noop,
+    OpenSubmissions(u32),
+    OrganizersFailed(String),
+    OutcomeFailed(String),
+    PasteTopics(String),
+    RecordOutcome,
+    RecordedOutcome,
+    RegistrationFailed(u32, RegistrationError),
+    RemoveOrganizer(String),
+    RemoveTopicLink(usize),
+    RevokeInvitation(String),
+    RenameMeeting,
+    RenameMeetingFailed(String),
+    RenamedMeeting,
+    RetractedVote,
+    RetractVote,
+    RetractVoteFailed(String),
+    SaveTopicDescription,
+    SetAvatar(AvatarMessage),
+    SetCandidateTopics(Vec<CandidateTopic>),
     SetElectionResults(ElectionResults),
+    SetInstanceInfo(InstanceInfo),
+    SetInvitations(Vec<Invitation>),
+    SetJoinRequests(Vec<JoinRequest>),
+    SetLanguage(Option<String>),
+    SetLocale(String),
     SetRegisteredMeetings(Vec<u32>),
-    SetMeetings(Vec<ScoredMeeting>),
+    SetRegistrationDeadline(Option<i64>),
+    SetSubmissionDeadline(Option<i64>),
+    SetSubmittedTopics(u32, Vec<UserTopic>),
+    SetRoomJoins(RoomJoinsMessage),
+    SetMeetingHistory(MeetingHistoryMessage),
+    SetMeetingPage(MeetingPage),
+    SetMeetings(Vec<ScoredMeeting>, MeetingSort),
+    SetUnlistedMeeting(ScoredMeeting),
+    SetMeetingTemplates(Vec<MeetingTemplate>),
+    SetNewMeetingCohortSize(u32),
+    SetNewMeetingNWinners(u32),
+    SetNewMeetingTemplate(Option<usize>),
+    SetNewMeetingTopicsPerAttendee(u32),
+    SetNewMeetingVetoPolicy(VetoPolicy),
+    SetNewMeetingVotingMethod(VotingMethod),
     SetMeetingTopics(Vec<UserTopic>),
+    SetMyTopics(Vec<u32>),
+    SetNotificationPreferences(Vec<NotificationPreference>),
+    SetOrganizers(MeetingOrganizersMessage),
+    SetResearchConsent(bool),
+    SetSync(SyncMessage),
     SetTab(Tab),
-    SetUserId(String),
+    SetTopicDescription(u32, String),
+    SetTopicEdits(u32, Vec<TopicEdit>),
+    SetTopicHistory(HashMap<u32, Vec<u32>>),
+    SetTopicLinks(u32, Vec<String>),
+    SetUserId(UserIdMessage),
+    SetVotingMinutes(Option<u32>),
     SetUserTopics(Vec<UserTopic>), // set in Model
-    StartMeeting,
-    StoreMeetingScore((u32, u32)), // (id, score) - store to database
-    StoreMeetingTopicScore((u32, u32)), // (id, score)
-    StoreUserTopicScore((u32, u32)), // (id, score)
+    ShareContact(bool),
+    SharedContact,
+    SortMeetings(MeetingSort),
+    SplitTopic(u32),
+    StartMeeting(bool),
+    StartMeetingFailed(String),
+    StartTopicTimer(usize),
+    StopTopicTimer,
+    StoreLanguage,
+    StoredMyTopics(Vec<u32>),
+    StoreMeetingScores(Vec<(u32, u32)>), // (id, score) - store to database
+    StoreMeetingTopicScores(Vec<(u32, u32)>), // (id, score)
+    StoreSubmissionScores(Vec<(u32, u32)>), // (id, score)
+    StoreUserTopicScores(Vec<(u32, u32)>), // (id, score)
+    SubmitRegistration,
+    SubmitTopic,
+    SubmitTopicFailed(String),
+    ToggleNotificationPreference(NotificationEvent, NotificationChannel),
+    Sync,
+    ToggleBulkTopics,
+    ToggleFaults,
+    ToggleImportMeeting,
+    ToggleMergeTopic(u32),
+    ToggleMyTopic(u32),
+    ToggleResearchConsent,
+    ToggleNewMeetingMatchLanguage,
+    ToggleNewMeetingShareContacts,
+    ToggleOutcomeTopic(u32),
+    ToggleTopicPrivate(u32),
+    TopicDescriptionFailed(String),
+    TopicLinksFailed(String),
+    UpdateBulkTopicsText(String),
+    UpdateImportMeetingText(String),
+    UpdateInviteText(String),
+    UploadAvatar(web_sys::File),
+    UpdateJoinCodeText(String),
+    UpdateLanguageText(String),
+    UpdateMeetingSearchText(String),
     UpdateNewMeetingText(String),
+    UpdateNewOrganizerText(String),
+    UpdateNewRegistrationFieldText(String),
+    UpdateNewSeedText(String),
+    UpdateNewSubmissionText(String),
     UpdateNewTopicText(String),
+    UpdateOutcomeText(String),
+    UpdateRegistrationAnswer(usize, String),
+    UpdateRegistrationDeadlineText(String),
+    UpdateRenameMeetingText(String),
+    UpdateSubmissionDeadlineText(String),
+    UpdateTopicDescriptionText(String),
+    UpdateTopicLinkText(String),
+    UpdateVotingMinutesText(String),
+    VetoFailed(String),
+    VetoTopic(u32),
+    WaitForElection,
 }
 
 #[derive(Clone)]
 struct ScoredMeeting {
     meeting: Meeting,
     score: u32,
+    permissions: MeetingPermissions,
+    registration_fields: Vec<RegistrationField>,
+}
+
+impl From<MeetingMessage> for ScoredMeeting {
+    fn from(mm: MeetingMessage) -> Self {
+        ScoredMeeting {
+            meeting: mm.meeting,
+            score: mm.score,
+            permissions: mm.permissions,
+            registration_fields: mm.registration_fields,
+        }
+    }
+}
+
+/// The meetings the API found for a search, or one page of the whole list
+struct MeetingPage {
+    search: String,
+    meetings: Vec<ScoredMeeting>,
+    offset: u32,
+    total: u32,
 }
 
 enum UserIdState {
@@ -76,18 +281,64 @@ impl UserIdState {
     }
 }
 
+// Where focus goes once a tab change is rendered
+enum TabFocus {
+    // The newly selected tab, when the arrow keys moved to it
+    Tab,
+    // The new panel, when the change came from inside the old one
+    Panel,
+}
+
 #[derive(Clone, PartialEq)]
 enum Tab {
     MeetingManagement,
     MeetingPrep,
+    Preferences,
     TopicManagment,
 }
 
 impl Tab {
+    // In the order the tab list shows them
+    const ALL: [Tab; 4] = [
+        Tab::TopicManagment,
+        Tab::MeetingManagement,
+        Tab::MeetingPrep,
+        Tab::Preferences,
+    ];
+
+    fn label(&self) -> String {
+        i18n::text(match self {
+            Tab::MeetingManagement => "tab-meetings",
+            Tab::MeetingPrep => "tab-meet",
+            Tab::Preferences => "tab-preferences",
+            Tab::TopicManagment => "tab-topics",
+        })
+    }
+
+    // Element ids, which tie each tab to its panel for screen readers
+    fn tab_id(&self) -> &'static str {
+        match self {
+            Tab::MeetingManagement => "meetings-tab",
+            Tab::MeetingPrep => "meet-tab",
+            Tab::Preferences => "preferences-tab",
+            Tab::TopicManagment => "topics-tab",
+        }
+    }
+
+    fn panel_id(&self) -> &'static str {
+        match self {
+            Tab::MeetingManagement => "meetings-panel",
+            Tab::MeetingPrep => "meet-panel",
+            Tab::Preferences => "preferences-panel",
+            Tab::TopicManagment => "topics-panel",
+        }
+    }
+
     fn needs_meeting_poll(&self) -> bool {
         match self {
             Tab::MeetingManagement => true,
             Tab::MeetingPrep => true,
+            Tab::Preferences => false,
             Tab::TopicManagment => false,
         }
     }
@@ -95,22 +346,106 @@ impl Tab {
 
 struct Model {
     attending_meeting: Option<u32>, // the meeting the user is currently attending
+    avatar: AvatarMessage,
+    avatar_error: Option<String>,
+    bulk_topics: Option<String>, // pasted lines while the bulk add preview is open
+    candidate_topics: Option<Vec<CandidateTopic>>, // fetched while an organizer merges them
+    collation: Collation,        // sorts names for the browser's language
+    contact_sharing_error: Option<String>,
+    description_form: Option<(u32, String)>, // topic id and description text while editing it
+    description_form_error: Option<String>,
+    description_edits: Vec<TopicEdit>, // past versions of the description being edited
+    fault_injection: bool,             // the API lets this tab ask for injected faults
     election_results: Option<ElectionResults>,
+    import_meeting: Option<String>, // config text while the import dialog is open
+    import_meeting_error: Option<String>,
+    invitations: Vec<Invitation>, // fetched for administrators on the Preferences tab
+    invite_error: Option<String>,
+    invite_text: String,
+    is_admin: bool,
+    join_code_text: String,
+    join_code_error: Option<String>,
+    join_requests: Vec<JoinRequest>, // fetched for administrators on the Preferences tab
+    language_text: String,           // the preferred conversation language, as typed
+    language_error: Option<String>,
+    link_form: Option<(u32, String)>, // topic id and new link text while editing links
+    link_form_error: Option<String>,
     registered_meetings: HashSet<u32>,
+    research: bool,         // the API exports consenting users' ballots for research
+    research_consent: bool, // fetched on the Preferences tab
     meeting_topics: Option<Vec<UserTopic>>,
+    notification_preferences: Vec<NotificationPreference>,
     meetings: Vec<ScoredMeeting>,
+    meeting_page: Option<MeetingPage>, // shown instead of meetings when there is one
+    // The attended meeting, fetched by itself when `meetings` doesn't hold it
+    unlisted_meeting: Option<ScoredMeeting>,
+    meeting_search_text: String,
+    meeting_sort: MeetingSort,
+    meeting_templates: Vec<MeetingTemplate>,
+    meeting_organizers: Option<MeetingOrganizersMessage>, // fetched while the owner edits them
+    meeting_organizers_error: Option<String>,
+    merge_selection: Vec<u32>, // topics checked for merging
+    merge_topics_error: Option<String>,
+    new_meeting_cohort_size: u32,
+    new_meeting_match_language: bool,
+    new_meeting_n_winners: u32,
+    new_meeting_share_contacts: bool,
+    new_meeting_topics_per_attendee: u32,
+    new_meeting_template: Option<usize>, // index into meeting_templates
     new_meeting_text: String,
+    new_meeting_veto_policy: VetoPolicy,
+    new_meeting_voting_method: VotingMethod,
+    new_organizer_text: String,
+    new_registration_field_text: String,
+    new_seed_text: String,
+    new_submission_text: String,
+    new_topic_input: NodeRef,
     new_topic_text: String,
+    outcome_error: Option<String>,
+    outcome_form: Option<OutcomeMessage>, // the cohort's outcome while a member edits it
+    registration_deadline_text: String,   // datetime-local input value
+    rename_meeting_error: Option<String>,
+    rename_meeting_text: String,
+    registration_error: Option<String>,
+    registration_form: Option<(u32, Vec<String>)>, // meeting id and answers while registering
+    room_joins: Option<RoomJoinsMessage>,
+    meeting_history: Option<MeetingHistoryMessage>,
+    next_round_error: Option<String>,
+    start_meeting_error: Option<String>,
+    start_confirm: Option<(u32, u32)>, // registered and joined, while asking the organizer to confirm
+    submission_deadline_text: String,  // datetime-local input value
+    submissions: Option<(u32, Vec<UserTopic>)>, // meeting id and its submitted topics while open
+    submission_error: Option<String>,
+    topic_history: HashMap<u32, Vec<u32>>, // past scores by user topic id
+    topic_timer: Option<(usize, f64)>,     // elected topic index and deadline in ms
+    topic_timer_tick: Option<Interval>,
+    voting_minutes_text: String,
+    voting_tick: Option<Interval>, // re-renders the countdown while voting has a deadline
     user_id: UserIdState,
     user_topics: Vec<UserTopic>,
+    my_topics: Vec<u32>, // own topics chosen for the attended meeting, or none for the top-ranked
+    my_topics_error: Option<String>,
+    veto_error: Option<String>,
+    vote_error: Option<String>,
     active_tab: Tab,
+    active_tab_ref: NodeRef,
+    tab_panel_ref: NodeRef,
+    tab_focus: Option<TabFocus>,
     meeting_poll: Option<Interval>,
     vote_poll: Option<Interval>,
+    election_wait: bool, // a request for the election status to change is in flight
+    poll_ms: u32,        // period of meeting_poll and vote_poll, as the API suggests
+    offline_probe: Option<Interval>, // set while the API is unreachable
+    sync_listeners: Vec<Closure<dyn FnMut()>>,
+    stale_bundle: bool, // the server has moved on to a newer UI or schema
+    server_build: Option<BuildInfo>,
 }
 
 // These are populated by the back-end in template rendering.
 const LOGIN_JS_OBJECT: &str = "elc_global";
 const LOGIN_JS_ATTRIBUTE: &str = "user_email";
+const ASSET_VERSION_JS_ATTRIBUTE: &str = "asset_version";
+const HARD_REFRESH_JS_ATTRIBUTE: &str = "hard_refresh";
 
 fn no_user() -> bool {
     let elc_global = gloo_utils::window().get(LOGIN_JS_OBJECT);
@@ -121,73 +456,155 @@ fn no_user() -> bool {
     }
 }
 
-async fn fetch_user_id() -> Option<String> {
-    let resp = http::Request::get("/user_id")
-        .send()
-        .await
-        .unwrap()
-        .json()
-        .await;
-    match resp {
-        Ok(resp) => {
-            let msg: UserIdMessage = resp;
-            Some(msg.email)
+fn elc_global_get(attribute: &str) -> Option<wasm_bindgen::JsValue> {
+    let elc_global = gloo_utils::window().get(LOGIN_JS_OBJECT)?;
+    js_sys::Reflect::get(&elc_global, &wasm_bindgen::JsValue::from(attribute)).ok()
+}
+
+// The version of the bundle this page loaded, which the server may have replaced.
+fn page_asset_version() -> Option<String> {
+    elc_global_get(ASSET_VERSION_JS_ATTRIBUTE)?.as_string()
+}
+
+fn hard_refresh() {
+    let refresh = elc_global_get(HARD_REFRESH_JS_ATTRIBUTE)
+        .and_then(|f| f.dyn_into::<js_sys::Function>().ok());
+    if let Some(refresh) = refresh {
+        if let Err(e) = refresh.call0(&wasm_bindgen::JsValue::NULL) {
+            console_dbg!(e);
         }
+    } else if let Err(e) = gloo_utils::window().location().reload() {
+        console_dbg!(e);
+    }
+}
+
+async fn fetch_instance_info() -> Result<InstanceInfo> {
+    let resp: std::result::Result<InstanceInfo, gloo_net::Error> =
+        client::get("/instance_info").await?.json().await;
+    match resp {
+        Ok(msg) => Ok(msg),
+        Err(e) => Err(e.into()),
+    }
+}
+
+async fn fetch_user_id() -> Option<UserIdMessage> {
+    let resp = client::get("/user_id").await.ok()?.json().await;
+    match resp {
+        Ok(resp) => Some(resp),
         Err(_e) => None,
     }
 }
 
+fn ui_build() -> BuildInfo {
+    BuildInfo::new(
+        env!("EHALL_GIT_COMMIT"),
+        env!("EHALL_BUILD_DATE"),
+        &[
+            (env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")),
+            ("ehall-election", ehall_election::VERSION),
+        ],
+    )
+}
+
+// Pasted lists often carry bullets, blank lines, and repeats.
+fn bulk_topic_lines(text: &str) -> Vec<String> {
+    let mut topics: Vec<String> = vec![];
+    for line in text.lines() {
+        let topic = line.trim().trim_start_matches(['-', '*', '•']).trim();
+        if !topic.is_empty() && !topics.iter().any(|t| t == topic) {
+            topics.push(topic.to_owned());
+        }
+    }
+    topics
+}
+
+fn fits_topic(topic: &str) -> bool {
+    topic.chars().count() <= MAX_TEXT_LEN
+}
+
+// Every client-side error is logged here, with the build that hit it.
+fn log_error(e: &Error) {
+    console_dbg!(format!("{e:#} ui_build={}", env!("EHALL_GIT_COMMIT")));
+}
+
 fn error_from_response(resp: http::Response) -> Error {
     let status = resp.status();
     assert_ne!(status, 200);
-    anyhow!("response status {status}: {}", resp.status_text())
+    let request_id = resp
+        .headers()
+        .get(client::REQUEST_ID_HEADER)
+        .unwrap_or_default();
+    anyhow!(
+        "response status {status}: {} request_id={request_id}",
+        resp.status_text()
+    )
 }
 
-async fn fetch_meetings() -> Result<Vec<ScoredMeeting>> {
+async fn fetch_meetings() -> Result<(Vec<ScoredMeeting>, MeetingSort)> {
     let resp: std::result::Result<MeetingsMessage, gloo_net::Error> =
-        http::Request::get("/meetings").send().await?.json().await;
+        client::get("/meetings").await?.json().await;
     match resp {
         Ok(msg) => {
-            let mut mtgs: Vec<_> = msg
-                .meetings
-                .into_iter()
-                .map(|mm| ScoredMeeting {
-                    meeting: mm.meeting,
-                    score: mm.score,
-                })
-                .collect();
+            let partial = msg.total as usize > msg.meetings.len();
+            let mut mtgs: Vec<ScoredMeeting> = msg.meetings.into_iter().map(Into::into).collect();
             mtgs.sort_by(
                 |ScoredMeeting { score: a, .. }, ScoredMeeting { score: b, .. }| {
                     a.partial_cmp(b).unwrap()
                 },
             );
+            // Renumbering part of a list too long to send whole
+            // would give its meetings the scores of those left out.
+            if partial {
+                return Ok((mtgs, msg.sort));
+            }
             let mut canonically_scored_meetings: Vec<_> = vec![];
-            for (canonical_score, ScoredMeeting { meeting, score }) in mtgs.into_iter().enumerate()
-            {
+            for (canonical_score, sm) in mtgs.into_iter().enumerate() {
                 let cscore = canonical_score as u32;
-                if score != cscore {
-                    store_meeting_score(boxed::Box::new(meeting.id), boxed::Box::new(cscore))
-                        .await
-                        .unwrap();
+                if sm.score != cscore {
+                    store_meeting_score(boxed::Box::new(sm.meeting.id), boxed::Box::new(cscore))
+                        .await?;
                 }
                 canonically_scored_meetings.push(ScoredMeeting {
-                    meeting,
                     score: cscore,
+                    ..sm
                 });
             }
-            Ok(canonically_scored_meetings)
+            Ok((canonically_scored_meetings, msg.sort))
         }
         Err(e) => Err(e.into()),
     }
 }
 
+async fn fetch_meeting(id: u32) -> Result<ScoredMeeting> {
+    let resp = client::get(&format!("/meetings/{id}")).await?;
+    if resp.status() != 200 {
+        return Err(error_from_response(resp));
+    }
+    let msg: MeetingMessage = resp.json().await?;
+    Ok(msg.into())
+}
+
+/// The page at `offset` of the meetings whose names contain `search`,
+/// or of all meetings when it is empty
+async fn fetch_meeting_page(search: String, sort: MeetingSort, offset: u32) -> Result<MeetingPage> {
+    let q: String = js_sys::encode_uri_component(&search).into();
+    let url = format!(
+        "/meetings?q={q}&sort={}&limit={MEETINGS_PAGE_SIZE}&offset={offset}",
+        sort.as_str()
+    );
+    let msg: MeetingsMessage = client::get(&url).await?.json().await?;
+    Ok(MeetingPage {
+        search,
+        // An older API sends every meeting and no total.
+        total: msg.total.max(msg.meetings.len() as u32),
+        meetings: msg.meetings.into_iter().map(Into::into).collect(),
+        offset: msg.offset,
+    })
+}
+
 async fn fetch_registered_meetings() -> Result<Vec<u32>> {
     let resp: std::result::Result<RegisteredMeetingsMessage, gloo_net::Error> =
-        http::Request::get("/registered_meetings")
-            .send()
-            .await?
-            .json()
-            .await;
+        client::get("/registered_meetings").await?.json().await;
     match resp {
         Ok(msg) => Ok(msg.meetings),
         Err(e) => Err(e.into()),
@@ -197,7 +614,7 @@ async fn fetch_registered_meetings() -> Result<Vec<u32>> {
 async fn fetch_meeting_topics(meeting_id: boxed::Box<u32>) -> Result<Vec<UserTopic>> {
     let url = format!("/meeting/{meeting_id}/topics");
     let resp: std::result::Result<UserTopicsMessage, gloo_net::Error> =
-        http::Request::get(&url).send().await?.json().await;
+        client::get(&url).await?.json().await;
     match resp {
         Ok(msg) => {
             let mut topics = msg.topics;
@@ -209,10 +626,9 @@ async fn fetch_meeting_topics(meeting_id: boxed::Box<u32>) -> Result<Vec<UserTop
             Ok(topics
                 .into_iter()
                 .enumerate()
-                .map(|(score, UserTopic { text, id, .. })| UserTopic {
-                    id,
-                    text,
+                .map(|(score, topic)| UserTopic {
                     score: score as u32,
+                    ..topic
                 })
                 .collect())
         }
@@ -220,200 +636,953 @@ async fn fetch_meeting_topics(meeting_id: boxed::Box<u32>) -> Result<Vec<UserTop
     }
 }
 
+async fn fetch_my_topics(meeting_id: u32) -> Result<Vec<u32>> {
+    let url = format!("/meeting/{meeting_id}/my_topics");
+    let msg: MyTopicsMessage = client::get(&url).await?.json().await?;
+    Ok(msg.topics)
+}
+
+/// The chosen topics as stored, or why they weren't
+async fn store_my_topics(
+    meeting_id: u32,
+    topics: Vec<u32>,
+) -> Result<std::result::Result<Vec<u32>, String>> {
+    let url = format!("/meeting/{meeting_id}/my_topics");
+    let resp = client::send(http::Request::post(&url).json(&MyTopicsMessage { topics })?).await?;
+    if resp.status() == 200 {
+        let msg: MyTopicsMessage = resp.json().await?;
+        Ok(Ok(msg.topics))
+    } else {
+        Ok(Err(refusal_text(resp, "refused-my-topics").await?))
+    }
+}
+
+async fn store_topic_links(
+    topic_id: boxed::Box<u32>,
+    links: Vec<String>,
+) -> Result<std::result::Result<(), String>> {
+    let url = format!("/topic/{}/links", topic_id);
+    let resp = client::put_json(&url, &TopicLinksMessage { links }).await?;
+    if resp.status() == 200 {
+        Ok(Ok(()))
+    } else {
+        Ok(Err(refusal_text(resp, "refused-links").await?))
+    }
+}
+
+async fn fetch_topic_edits(topic_id: boxed::Box<u32>) -> Result<Vec<TopicEdit>> {
+    let url = format!("/topic/{}/description/edits", topic_id);
+    let msg: TopicEditsMessage = client::get(&url).await?.json().await?;
+    Ok(msg.edits)
+}
+
+async fn store_topic_description(
+    topic_id: boxed::Box<u32>,
+    description: String,
+) -> Result<std::result::Result<(), String>> {
+    let url = format!("/topic/{}/description", topic_id);
+    let resp = client::put_json(&url, &TopicDescriptionMessage { description }).await?;
+    if resp.status() == 200 {
+        Ok(Ok(()))
+    } else {
+        Ok(Err(refusal_text(resp, "refused-description").await?))
+    }
+}
+
+async fn store_topic_private(topic_id: boxed::Box<u32>, private: bool) -> Result<()> {
+    let url = format!("/topic/{}/private", topic_id);
+    client::put_json_or_queue(&url, &TopicPrivacyMessage { private }).await?;
+    Ok(())
+}
+
 async fn fetch_user_topics() -> Result<Vec<UserTopic>> {
-    let resp: std::result::Result<UserTopicsMessage, gloo_net::Error> =
-        http::Request::get("/user_topics")
-            .send()
-            .await?
-            .json()
-            .await;
-    match resp {
-        Ok(msg) => {
-            let mut topics = msg.topics;
-            topics.sort_by(|a, b| {
-                let UserTopic { score: a_score, .. } = a;
-                let UserTopic { score: b_score, .. } = b;
-                a_score.partial_cmp(b_score).unwrap()
-            });
-            let orig_scores: Vec<_> = topics.iter().map(|t| t.score).collect();
-            let topics: Vec<_> = topics
-                .into_iter()
-                .enumerate()
-                .map(|(score, UserTopic { text, id, .. })| UserTopic {
-                    id,
-                    text,
-                    score: score as u32,
-                })
-                .collect();
-            let canonical_scores: Vec<_> = topics.iter().map(|t| t.score).collect();
-            if orig_scores != canonical_scores {
-                for t in topics.iter() {
-                    store_user_topic_score(boxed::Box::new(t.id), boxed::Box::new(t.score))
-                        .await
-                        .unwrap();
-                }
-            }
-            Ok(topics)
+    let mut topics = vec![];
+    let mut url = format!("/user_topics?limit={DEFAULT_PAGE_LIMIT}");
+    loop {
+        let msg: UserTopicsMessage = client::get(&url).await?.json().await?;
+        topics.extend(msg.topics);
+        match msg.page.next_query() {
+            Some(query) => url = format!("/user_topics?{query}"),
+            None => return Ok(topics),
         }
-        Err(e) => Err(e.into()),
     }
 }
 
+async fn fetch_meeting_templates() -> Result<Vec<MeetingTemplate>> {
+    let msg: MeetingTemplatesMessage = client::get("/meeting_templates").await?.json().await?;
+    Ok(msg.templates)
+}
+
+async fn fetch_topic_history() -> Result<HashMap<u32, Vec<u32>>> {
+    let since_ms = js_sys::Date::now() - TOPIC_HISTORY_DAYS * 24.0 * 60.0 * 60.0 * 1000.0;
+    let since: String = js_sys::Date::new(&since_ms.into())
+        .to_iso_string()
+        .as_string()
+        .unwrap()
+        .chars()
+        .take("yyyy-mm-dd".len())
+        .collect();
+    let url = format!("/user_topics/history?since={since}");
+    let msg: TopicHistoryMessage = client::get(&url).await?.json().await?;
+    let mut history: HashMap<u32, Vec<u32>> = HashMap::new();
+    for change in msg.changes {
+        history.entry(change.topic).or_default().push(change.score);
+    }
+    Ok(history)
+}
+
 async fn commit_vote(meeting_id: boxed::Box<u32>) -> Result<()> {
     let url = format!("/meeting/{}/vote", meeting_id);
-    gloo_net::http::Request::put(&url).send().await?;
+    client::put_idempotent(&url).await?;
     Ok(())
 }
 
+async fn retract_vote(meeting_id: boxed::Box<u32>) -> Result<std::result::Result<(), String>> {
+    let url = format!("/meeting/{}/vote", meeting_id);
+    let resp = client::send(http::Request::delete(&url)).await?;
+    if resp.status() == 200 {
+        Ok(Ok(()))
+    } else {
+        Ok(Err(refusal_text(resp, "refused-retract").await?))
+    }
+}
+
 async fn delete_meeting(id: boxed::Box<u32>) -> Result<()> {
     let url = format!("/meetings/{}", id);
-    gloo_net::http::Request::delete(&url).send().await?;
+    let resp = client::send(http::Request::delete(&url)).await?;
+    if resp.status() != 200 {
+        return Err(error_from_response(resp));
+    }
     Ok(())
 }
 
+// The API names refusals with a "code", which the UI explains in its own words.
+// Its "error" is meant for logs, so an unknown code gets the message `fallback` instead.
+fn refusal(body: &serde_json::Value, fallback: &str) -> String {
+    let code = body["code"].as_str().unwrap_or_default();
+    match copy::error_text(code) {
+        Some(text) => text,
+        None => {
+            log_error(&anyhow!(
+                "unknown error code {code:?}: {}",
+                body["error"].as_str().unwrap_or_default()
+            ));
+            i18n::text(fallback)
+        }
+    }
+}
+
+async fn refusal_text(resp: http::Response, fallback: &str) -> Result<String> {
+    let body: serde_json::Value = resp.json().await?;
+    Ok(refusal(&body, fallback))
+}
+
+async fn rename_meeting(
+    meeting_id: boxed::Box<u32>,
+    name: String,
+) -> Result<std::result::Result<(), String>> {
+    let url = format!("/meetings/{}", meeting_id);
+    let resp = client::put_json(&url, &RenameMeetingMessage { name }).await?;
+    if resp.status() == 200 {
+        Ok(Ok(()))
+    } else {
+        Ok(Err(refusal_text(resp, "refused-rename").await?))
+    }
+}
+
+async fn fetch_meeting_organizers(meeting_id: boxed::Box<u32>) -> Result<MeetingOrganizersMessage> {
+    let url = format!("/meeting/{}/organizers", meeting_id);
+    let resp = client::get(&url).await?;
+    if resp.status() != 200 {
+        return Err(error_from_response(resp));
+    }
+    Ok(resp.json().await?)
+}
+
+async fn add_meeting_organizer(
+    meeting_id: boxed::Box<u32>,
+    email: String,
+) -> Result<std::result::Result<MeetingOrganizersMessage, String>> {
+    let url = format!("/meeting/{}/organizers", meeting_id);
+    let resp =
+        client::send(http::Request::post(&url).json(&NewOrganizerMessage { email })?).await?;
+    if resp.status() == 200 {
+        Ok(Ok(resp.json().await?))
+    } else {
+        Ok(Err(refusal_text(resp, "refused-add-organizer").await?))
+    }
+}
+
+async fn join_by_code(code: String) -> Result<std::result::Result<JoinedMeetingMessage, String>> {
+    let resp =
+        client::send(http::Request::post("/join_code").json(&JoinCodeMessage { code })?).await?;
+    if resp.status() == 200 {
+        Ok(Ok(resp.json().await?))
+    } else {
+        let body: serde_json::Value = resp.json().await?;
+        // A refused registration says why as `POST /meeting/<id>/participants` does.
+        Ok(Err(
+            match serde_json::from_value::<RegistrationError>(body.clone()) {
+                Ok(e) => registration_error_text(&e),
+                Err(_) => refusal(&body, "refused-join-code"),
+            },
+        ))
+    }
+}
+
+async fn remove_meeting_organizer(
+    meeting_id: boxed::Box<u32>,
+    email: String,
+) -> Result<std::result::Result<MeetingOrganizersMessage, String>> {
+    let email: String = js_sys::encode_uri_component(&email).into();
+    let url = format!("/meeting/{}/organizers/{}", meeting_id, email);
+    let resp = client::send(http::Request::delete(&url)).await?;
+    if resp.status() == 200 {
+        Ok(Ok(resp.json().await?))
+    } else {
+        Ok(Err(refusal_text(resp, "refused-remove-organizer").await?))
+    }
+}
+
+fn organizers_msg(result: Result<std::result::Result<MeetingOrganizersMessage, String>>) -> Msg {
+    match result {
+        Ok(Ok(organizers)) => Msg::SetOrganizers(organizers),
+        Ok(Err(e)) => Msg::OrganizersFailed(e),
+        Err(e) => Msg::LogError(e),
+    }
+}
+
+async fn fetch_candidate_topics(meeting_id: boxed::Box<u32>) -> Result<Vec<CandidateTopic>> {
+    let url = format!("/meeting/{}/candidate_topics", meeting_id);
+    let resp = client::get(&url).await?;
+    if resp.status() != 200 {
+        return Err(error_from_response(resp));
+    }
+    let msg: CandidateTopicsMessage = resp.json().await?;
+    Ok(msg.topics)
+}
+
+async fn merge_meeting_topics(
+    meeting_id: boxed::Box<u32>,
+    topics: Vec<u32>,
+) -> Result<std::result::Result<Vec<CandidateTopic>, String>> {
+    let url = format!("/meeting/{}/topics/merge", meeting_id);
+    let resp =
+        client::send(http::Request::post(&url).json(&MergeTopicsMessage { topics })?).await?;
+    if resp.status() != 200 {
+        return Ok(Err(refusal_text(resp, "refused-merge").await?));
+    }
+    Ok(Ok(fetch_candidate_topics(meeting_id).await?))
+}
+
+async fn split_meeting_topic(
+    meeting_id: boxed::Box<u32>,
+    topic: u32,
+) -> Result<std::result::Result<Vec<CandidateTopic>, String>> {
+    let url = format!("/meeting/{}/topics/merge/{}", meeting_id, topic);
+    let resp = client::send(http::Request::delete(&url)).await?;
+    if resp.status() != 200 {
+        return Ok(Err(refusal_text(resp, "refused-split").await?));
+    }
+    Ok(Ok(fetch_candidate_topics(meeting_id).await?))
+}
+
+fn candidate_topics_msg(result: Result<std::result::Result<Vec<CandidateTopic>, String>>) -> Msg {
+    match result {
+        Ok(Ok(topics)) => Msg::SetCandidateTopics(topics),
+        Ok(Err(e)) => Msg::MergeTopicsFailed(e),
+        Err(e) => Msg::LogError(e),
+    }
+}
+
 async fn delete_user_topic(id: boxed::Box<u32>) -> Result<()> {
     let url = format!("/topics/{}", id);
-    gloo_net::http::Request::delete(&url).send().await?;
+    client::send(http::Request::delete(&url)).await?;
     Ok(())
 }
 
-async fn fetch_election_status(meeting_id: boxed::Box<u32>) -> Result<ElectionResults> {
-    let url = format!("/meeting/{}/election_results", meeting_id);
+/// The election status, or with `wait_secs`, the status once it changes
+/// or that many seconds pass
+async fn fetch_election_status(
+    meeting_id: boxed::Box<u32>,
+    wait_secs: Option<u32>,
+) -> Result<ElectionResults> {
+    let url = match wait_secs {
+        Some(secs) => format!("/meeting/{meeting_id}/election_results?wait={secs}s"),
+        None => format!("/meeting/{}/election_results", meeting_id),
+    };
     let resp: std::result::Result<ElectionResults, gloo_net::Error> =
-        http::Request::get(&url).send().await?.json().await;
+        client::get(&url).await?.json().await;
     match resp {
         Err(e) => Err(e.into()),
         Ok(msg) => Ok(msg),
     }
 }
 
-async fn start_meeting(meeting_id: boxed::Box<u32>) -> Result<()> {
-    let url = format!("/meeting/{}/start", meeting_id);
-    gloo_net::http::Request::put(&url).send().await?;
-    Ok(())
+async fn fetch_room_joins(meeting_id: boxed::Box<u32>) -> Result<RoomJoinsMessage> {
+    let url = format!("/meeting/{}/room_joins", meeting_id);
+    let resp = client::get(&url).await?;
+    if resp.status() != 200 {
+        return Err(error_from_response(resp));
+    }
+    Ok(resp.json().await?)
 }
 
-async fn store_meeting_score(meeting_id: boxed::Box<u32>, score: boxed::Box<u32>) -> Result<()> {
-    let url = format!("/meeting/{}/score", meeting_id);
-    gloo_net::http::Request::put(&url)
-        .json(&ScoreMessage { score: *score })?
-        .send()
-        .await?;
-    Ok(())
+async fn fetch_meeting_history(meeting_id: boxed::Box<u32>) -> Result<MeetingHistoryMessage> {
+    let url = format!("/meeting/{}/history", meeting_id);
+    let resp = client::get(&url).await?;
+    if resp.status() != 200 {
+        return Err(error_from_response(resp));
+    }
+    Ok(resp.json().await?)
 }
 
-async fn store_meeting_topic_score(
+async fn share_contact(
     meeting_id: boxed::Box<u32>,
-    topic_id: boxed::Box<u32>,
-    score: boxed::Box<u32>,
-) -> Result<()> {
-    let url = format!("/meeting/{}/topic/{}/score", meeting_id, topic_id);
-    gloo_net::http::Request::put(&url)
-        .json(&ScoreMessage { score: *score })?
-        .send()
-        .await?;
-    Ok(())
+    share: bool,
+) -> Result<std::result::Result<(), String>> {
+    let url = format!("/meeting/{}/contact_sharing", meeting_id);
+    let resp = client::put_json(&url, &ContactSharingMessage { share }).await?;
+    if resp.status() == 200 {
+        Ok(Ok(()))
+    } else {
+        Ok(Err(refusal_text(resp, "refused-contact-sharing").await?))
+    }
+}
+
+async fn record_outcome(
+    meeting_id: boxed::Box<u32>,
+    outcome: OutcomeMessage,
+) -> Result<std::result::Result<(), String>> {
+    let url = format!("/meeting/{}/outcome", meeting_id);
+    let resp = client::put_json(&url, &outcome).await?;
+    if resp.status() == 200 {
+        Ok(Ok(()))
+    } else {
+        Ok(Err(refusal_text(resp, "refused-outcome").await?))
+    }
 }
 
-async fn store_user_topic_score(topic_id: boxed::Box<u32>, score: boxed::Box<u32>) -> Result<()> {
-    let url = format!("/topic/{}/score", topic_id);
-    gloo_net::http::Request::put(&url)
-        .json(&ScoreMessage { score: *score })?
-        .send()
-        .await?;
+async fn report_joined_room(meeting_id: boxed::Box<u32>) -> Result<()> {
+    let url = format!("/meeting/{}/joined_room", meeting_id);
+    client::send(http::Request::post(&url)).await?;
     Ok(())
 }
 
-async fn attend_meeting(meeting_id: boxed::Box<u32>) -> Result<http::Response> {
-    let url = format!("/meeting/{}/attendees", *meeting_id);
-    Ok(gloo_net::http::Request::post(&url).send().await?)
+async fn fetch_language() -> Result<Option<String>> {
+    let msg: LanguageMessage = client::get("/preferences/language").await?.json().await?;
+    Ok(msg.language)
 }
 
-async fn leave_meeting(meeting_id: boxed::Box<u32>) -> Result<http::Response> {
-    let url = format!("/meeting/{}/attendees", *meeting_id);
-    Ok(gloo_net::http::Request::delete(&url).send().await?)
+async fn fetch_research_consent() -> Result<bool> {
+    let msg: ResearchConsentMessage = client::get("/preferences/research").await?.json().await?;
+    Ok(msg.consent)
 }
 
-async fn add_new_meeting(name: String) -> Result<http::Response> {
-    let new_meeting = NewMeeting {
-        name: Cow::from(name),
-    };
-    Ok(gloo_net::http::Request::post("/meetings")
-        .json(&new_meeting)?
-        .send()
-        .await?)
+async fn store_research_consent(consent: bool) -> Result<bool> {
+    let msg: ResearchConsentMessage =
+        client::put_json("/preferences/research", &ResearchConsentMessage { consent })
+            .await?
+            .json()
+            .await?;
+    Ok(msg.consent)
 }
 
-async fn add_new_topic(topic_text: String) -> Result<http::Response> {
-    let topic = NewTopicMessage {
-        new_topic: topic_text,
-    };
-    Ok(gloo_net::http::Request::post("/topics")
-        .json(&topic)?
-        .send()
-        .await?)
+async fn fetch_avatar() -> Result<AvatarMessage> {
+    Ok(client::get("/avatar").await?.json().await?)
 }
 
-async fn register_for_meeting(id: boxed::Box<u32>, participate: bool) -> Result<http::Response> {
-    let id = *id;
-    let url = format!("/meeting/{id}/participants");
-    Ok(gloo_net::http::Request::post(&url)
-        .json(&ParticipateMeetingMessage { participate })?
-        .send()
+/// The avatar as stored, or why it wasn't
+async fn upload_avatar(file: web_sys::File) -> Result<std::result::Result<AvatarMessage, String>> {
+    let resp = client::put_blob("/avatar", &file).await?;
+    if resp.status() == 200 {
+        Ok(Ok(resp.json().await?))
+    } else {
+        Ok(Err(refusal_text(resp, "refused-avatar").await?))
+    }
+}
+
+async fn fetch_join_requests() -> Result<Vec<JoinRequest>> {
+    let msg: JoinRequestsMessage = client::get("/admin/join_requests").await?.json().await?;
+    Ok(msg.requests)
+}
+
+async fn decide_join_request(email: String, approved: bool) -> Result<()> {
+    let email: String = js_sys::encode_uri_component(&email).into();
+    let url = format!("/admin/join_requests/{}", email);
+    let resp = client::put_json(&url, &JoinDecisionMessage { approved }).await?;
+    if resp.status() != 200 {
+        return Err(error_from_response(resp));
+    }
+    Ok(())
+}
+
+async fn fetch_invitations() -> Result<Vec<Invitation>> {
+    let msg: InvitationsMessage = client::get("/admin/invitations").await?.json().await?;
+    Ok(msg.invitations)
+}
+
+/// The invitation as sent, or why it wasn't
+async fn invite_member(email: String) -> Result<std::result::Result<Invitation, String>> {
+    let resp = client::send(
+        http::Request::post("/admin/invitations").json(&NewInvitationMessage { email })?,
+    )
+    .await?;
+    if resp.status() == 200 {
+        Ok(Ok(resp.json().await?))
+    } else {
+        Ok(Err(refusal_text(resp, "refused-invitation").await?))
+    }
+}
+
+async fn revoke_invitation(email: String) -> Result<()> {
+    let email: String = js_sys::encode_uri_component(&email).into();
+    let url = format!("/admin/invitations/{}", email);
+    let resp = client::send(http::Request::delete(&url)).await?;
+    if resp.status() != 200 {
+        return Err(error_from_response(resp));
+    }
+    Ok(())
+}
+
+async fn delete_avatar() -> Result<AvatarMessage> {
+    Ok(client::send(http::Request::delete("/avatar"))
+        .await?
+        .json()
         .await?)
 }
 
-impl Model {
-    fn meeting_people(&self) -> Option<(usize, usize)> {
-        if let Some(attending_meeting) = self.attending_meeting {
-            self.meetings
-                .iter()
-                .filter(|sm| sm.meeting.id == attending_meeting)
-                .map(|sm| {
-                    (
-                        sm.meeting.n_registered as usize,
-                        sm.meeting.n_joined as usize,
-                    )
-                })
-                .next()
-        } else {
-            None
-        }
+/// The language as stored, or why it wasn't
+async fn store_language(
+    language: Option<String>,
+) -> Result<std::result::Result<Option<String>, String>> {
+    let resp = client::put_json("/preferences/language", &LanguageMessage { language }).await?;
+    if resp.status() == 200 {
+        let msg: LanguageMessage = resp.json().await?;
+        Ok(Ok(msg.language))
+    } else {
+        Ok(Err(refusal_text(resp, "refused-language").await?))
     }
+}
 
-    fn fetch_user(&mut self, tag: &str, ctx: &Context<Self>) {
-        self.user_id = UserIdState::Fetching;
-        console_dbg!(format!("fetch_user in {}", tag));
-        ctx.link().send_future(async {
-            if let Some(uid) = fetch_user_id().await {
-                Msg::SetUserId(uid)
-            } else {
-                Msg::Noop
-            }
-        });
-        ctx.link().send_future(async {
-            if let Ok(topics) = fetch_user_topics().await {
-                Msg::SetUserTopics(topics)
-            } else {
-                Msg::Noop
-            }
-        });
-        ctx.link().send_future(async {
-            if let Ok(meetings) = fetch_registered_meetings().await {
-                Msg::SetRegisteredMeetings(meetings)
-            } else {
-                Msg::Noop
-            }
-        });
+async fn fetch_notification_preferences() -> Result<Vec<NotificationPreference>> {
+    let resp: std::result::Result<NotificationPreferencesMessage, gloo_net::Error> =
+        client::get("/preferences/notifications")
+            .await?
+            .json()
+            .await;
+    match resp {
+        Err(e) => Err(e.into()),
+        Ok(msg) => Ok(msg.preferences),
     }
+}
 
-    fn meeting_election_results_html(&self, _ctx: &Context<Self>) -> Html {
+async fn store_notification_preference(
+    preference: NotificationPreference,
+) -> Result<Vec<NotificationPreference>> {
+    let msg = NotificationPreferencesMessage {
+        preferences: vec![preference],
+    };
+    let resp: std::result::Result<NotificationPreferencesMessage, gloo_net::Error> =
+        client::put_json("/preferences/notifications", &msg)
+            .await?
+            .json()
+            .await;
+    match resp {
+        Err(e) => Err(e.into()),
+        Ok(msg) => Ok(msg.preferences),
+    }
+}
+
+fn notification_event_label(event: NotificationEvent) -> String {
+    i18n::text(match event {
+        NotificationEvent::MeetingStarted => "notify-meeting-started",
+        NotificationEvent::ResultsReady => "notify-results-ready",
+        NotificationEvent::DailyDigest => "notify-daily-digest",
+        NotificationEvent::WeeklyDigest => "notify-weekly-digest",
+    })
+}
+
+fn notification_channel_label(channel: NotificationChannel) -> String {
+    i18n::text(match channel {
+        NotificationChannel::Email => "channel-email",
+        NotificationChannel::WebPush => "channel-web-push",
+        NotificationChannel::Webhook => "channel-webhook",
+    })
+}
+
+async fn fetch_sync(meeting_id: Option<u32>) -> Result<SyncMessage> {
+    let url = match meeting_id {
+        Some(id) => format!("/sync?meeting={id}"),
+        None => "/sync".to_owned(),
+    };
+    let resp: std::result::Result<SyncMessage, gloo_net::Error> =
+        client::get(&url).await?.json().await;
+    match resp {
+        Err(e) => Err(e.into()),
+        Ok(msg) => Ok(msg),
+    }
+}
+
+// Timers are throttled while a laptop sleeps or a tab is hidden,
+// so catch up with one snapshot as soon as the user returns.
+fn add_sync_listeners(ctx: &Context<Model>) -> Vec<Closure<dyn FnMut()>> {
+    let on_focus = {
+        let link = ctx.link().clone();
+        Closure::wrap(boxed::Box::new(move || link.send_message(Msg::Sync)) as Box<dyn FnMut()>)
+    };
+    let on_visibility_change = {
+        let link = ctx.link().clone();
+        Closure::wrap(boxed::Box::new(move || {
+            if !gloo_utils::document().hidden() {
+                link.send_message(Msg::Sync)
+            }
+        }) as Box<dyn FnMut()>)
+    };
+    gloo_utils::window()
+        .add_event_listener_with_callback("focus", on_focus.as_ref().unchecked_ref())
+        .unwrap();
+    gloo_utils::document()
+        .add_event_listener_with_callback(
+            "visibilitychange",
+            on_visibility_change.as_ref().unchecked_ref(),
+        )
+        .unwrap();
+    vec![on_focus, on_visibility_change]
+}
+
+// Refusals the API doesn't describe as a `StartMeetingError`,
+// like a database failure, still get a message in the lobby.
+// Without `confirm`, the API can instead ask the organizer to confirm,
+// giving the registered and joined counts.
+async fn start_meeting(
+    meeting_id: boxed::Box<u32>,
+    confirm: bool,
+) -> Result<std::result::Result<Option<(u32, u32)>, String>> {
+    let url = format!("/meeting/{}/start?confirm={confirm}", meeting_id);
+    let resp = client::put_idempotent(&url).await?;
+    if resp.status() == 200 {
+        return Ok(Ok(None));
+    }
+    let body: serde_json::Value = resp.json().await?;
+    Ok(
+        match serde_json::from_value::<StartMeetingError>(body.clone()) {
+            Ok(StartMeetingError::ConfirmRequired {
+                n_registered,
+                n_joined,
+            }) => Ok(Some((n_registered, n_joined))),
+            Ok(e) => Err(start_meeting_error_text(&e)),
+            Err(_) => Err(refusal(&body, "refused-start")),
+        },
+    )
+}
+
+async fn next_round(meeting_id: boxed::Box<u32>) -> Result<std::result::Result<(), String>> {
+    let url = format!("/meeting/{}/next_round", meeting_id);
+    let resp = client::post_idempotent(&url).await?;
+    if resp.status() == 200 {
+        return Ok(Ok(()));
+    }
+    // The new cohorts are drawn as when the meeting started, and refused alike.
+    let body: serde_json::Value = resp.json().await?;
+    Ok(Err(
+        match serde_json::from_value::<StartMeetingError>(body.clone()) {
+            Ok(e) => start_meeting_error_text(&e),
+            Err(_) => refusal(&body, "refused-next-round"),
+        },
+    ))
+}
+
+async fn veto_meeting_topic(
+    meeting_id: boxed::Box<u32>,
+    topic: Option<u32>,
+) -> Result<std::result::Result<(), String>> {
+    let url = format!("/meeting/{}/veto", meeting_id);
+    let resp = client::put_json(&url, &VetoMessage { topic }).await?;
+    if resp.status() == 200 {
+        Ok(Ok(()))
+    } else {
+        Ok(Err(refusal_text(resp, "refused-veto").await?))
+    }
+}
+
+fn veto_policy_label(policy: VetoPolicy) -> String {
+    i18n::text(match policy {
+        VetoPolicy::Off => "veto-off",
+        VetoPolicy::Exclude => "veto-exclude",
+        VetoPolicy::Penalize => "veto-penalize",
+    })
+}
+
+fn voting_method_label(method: VotingMethod) -> String {
+    i18n::text(match method {
+        VotingMethod::Borda => "method-borda",
+        VotingMethod::Condorcet => "method-condorcet",
+        VotingMethod::InstantRunoff => "method-instant-runoff",
+    })
+}
+
+fn meeting_sort_label(sort: MeetingSort) -> String {
+    i18n::text(match sort {
+        MeetingSort::Ranking => "sort-ranking",
+        MeetingSort::Name => "sort-name",
+        MeetingSort::Registered => "sort-registered",
+        MeetingSort::Deadline => "sort-deadline",
+        MeetingSort::Recent => "sort-recent",
+    })
+}
+
+async fn store_meeting_sort(sort: MeetingSort) -> Result<()> {
+    client::put_json_or_queue("/preferences/meeting_sort", &MeetingSortMessage { sort }).await?;
+    Ok(())
+}
+
+async fn store_meeting_score(meeting_id: boxed::Box<u32>, score: boxed::Box<u32>) -> Result<()> {
+    let url = format!("/meeting/{}/score", meeting_id);
+    client::put_json_or_queue(&url, &ScoreMessage { score: *score }).await?;
+    Ok(())
+}
+
+async fn store_meeting_topic_scores(
+    meeting_id: boxed::Box<u32>,
+    scores: Vec<(u32, u32)>,
+) -> Result<()> {
+    let url = format!("/meeting/{}/topics/scores", meeting_id);
+    client::put_json_or_queue(&url, &ScoresMessage { scores }).await?;
+    Ok(())
+}
+
+async fn store_user_topic_scores(scores: Vec<(u32, u32)>) -> Result<()> {
+    client::put_json_or_queue("/topics/scores", &ScoresMessage { scores }).await?;
+    Ok(())
+}
+
+async fn attend_meeting(
+    meeting_id: boxed::Box<u32>,
+    participate_as: AttendeeRole,
+) -> Result<http::Response> {
+    let url = format!("/meeting/{}/attendees", *meeting_id);
+    let msg = AttendMeetingMessage { participate_as };
+    client::send(http::Request::post(&url).json(&msg)?).await
+}
+
+async fn leave_meeting(meeting_id: boxed::Box<u32>) -> Result<http::Response> {
+    let url = format!("/meeting/{}/attendees", *meeting_id);
+    client::send(http::Request::delete(&url)).await
+}
+
+async fn add_new_meeting(new_meeting: NewMeeting<'_>) -> Result<http::Response> {
+    client::send(http::Request::post("/meetings").json(&new_meeting)?).await
+}
+
+async fn add_new_topic(topic_text: String) -> Result<http::Response> {
+    let topic = NewTopicMessage {
+        new_topic: topic_text,
+        description: "".to_owned(),
+    };
+    client::send(http::Request::post("/topics").json(&topic)?).await
+}
+
+async fn import_meeting_config(config: MeetingConfig) -> Result<()> {
+    let resp = client::send(http::Request::post("/meetings/import").json(&config)?).await?;
+    if resp.status() == 200 {
+        Ok(())
+    } else {
+        let body: serde_json::Value = resp.json().await?;
+        // The API's reason names what in the configuration is wrong.
+        Err(anyhow!(
+            "{}",
+            body["error"]
+                .as_str()
+                .map_or_else(|| i18n::text("import-failed"), str::to_owned)
+        ))
+    }
+}
+
+async fn seed_meeting_topics(
+    meeting_id: boxed::Box<u32>,
+    topics: Vec<String>,
+) -> Result<http::Response> {
+    let url = format!("/meeting/{}/seed_topics", *meeting_id);
+    client::send(http::Request::post(&url).json(&SeedTopicsMessage { topics })?).await
+}
+
+async fn add_registration_field(
+    meeting_id: boxed::Box<u32>,
+    labels: Vec<String>,
+) -> Result<http::Response> {
+    let url = format!("/meeting/{}/registration_fields", *meeting_id);
+    client::send(http::Request::post(&url).json(&RegistrationFieldsMessage { labels })?).await
+}
+
+async fn register_for_meeting(
+    id: boxed::Box<u32>,
+    participate: bool,
+    answers: Vec<RegistrationAnswer>,
+) -> Result<std::result::Result<(), RegistrationError>> {
+    let id = *id;
+    let url = format!("/meeting/{id}/participants");
+    let msg = ParticipateMeetingMessage {
+        participate,
+        answers,
+    };
+    let resp = client::send(http::Request::post(&url).json(&msg)?).await?;
+    if resp.status() == 200 {
+        Ok(Ok(()))
+    } else {
+        Ok(Err(resp.json().await?))
+    }
+}
+
+fn registered_msg(id: u32, result: Result<std::result::Result<(), RegistrationError>>) -> Msg {
+    match result {
+        Ok(Ok(())) => Msg::MeetingRegisteredChanged,
+        Ok(Err(e)) => Msg::RegistrationFailed(id, e),
+        Err(e) => Msg::LogError(e),
+    }
+}
+
+fn registration_error_text(e: &RegistrationError) -> String {
+    match e {
+        RegistrationError::RegistrationClosed { deadline } => i18n::text_with(
+            "registration-closed-at",
+            &[("time", local_time(*deadline).into())],
+        ),
+        RegistrationError::InvalidAnswers { reason } => i18n::text_with(
+            "registration-invalid",
+            &[("reason", reason.as_str().into())],
+        ),
+        RegistrationError::AccountDisabled => i18n::text("error-account-disabled"),
+    }
+}
+
+async fn set_registration_deadline(
+    meeting_id: boxed::Box<u32>,
+    deadline: Option<i64>,
+) -> Result<http::Response> {
+    let url = format!("/meeting/{}/registration_deadline", *meeting_id);
+    client::put_json(&url, &RegistrationDeadlineMessage { deadline }).await
+}
+
+async fn set_submission_deadline(
+    meeting_id: boxed::Box<u32>,
+    deadline: Option<i64>,
+) -> Result<http::Response> {
+    let url = format!("/meeting/{}/submission_deadline", *meeting_id);
+    client::put_json(&url, &SubmissionDeadlineMessage { deadline }).await
+}
+
+async fn set_voting_minutes(
+    meeting_id: boxed::Box<u32>,
+    minutes: Option<u32>,
+) -> Result<http::Response> {
+    let url = format!("/meeting/{}/voting_minutes", *meeting_id);
+    client::put_json(&url, &VotingMinutesMessage { minutes }).await
+}
+
+/// The meeting's submitted topics, ranked as the user left them
+async fn fetch_submitted_topics(meeting_id: u32) -> Result<Vec<UserTopic>> {
+    let url = format!("/meeting/{meeting_id}/submissions");
+    let msg: SubmittedTopicsMessage = client::get(&url).await?.json().await?;
+    Ok(msg.topics)
+}
+
+async fn submit_meeting_topic(
+    meeting_id: u32,
+    new_topic: String,
+) -> Result<std::result::Result<(), String>> {
+    let url = format!("/meeting/{meeting_id}/submissions");
+    let msg = NewTopicMessage {
+        new_topic,
+        description: "".to_owned(),
+    };
+    let resp = client::send(http::Request::post(&url).json(&msg)?).await?;
+    if resp.status() == 200 {
+        Ok(Ok(()))
+    } else {
+        Ok(Err(refusal_text(resp, "refused-submission").await?))
+    }
+}
+
+async fn store_submission_scores(meeting_id: u32, scores: Vec<(u32, u32)>) -> Result<()> {
+    let url = format!("/meeting/{meeting_id}/submissions/scores");
+    client::put_json_or_queue(&url, &ScoresMessage { scores }).await?;
+    Ok(())
+}
+
+fn unix_now() -> i64 {
+    (js_sys::Date::now() / 1000.0) as i64
+}
+
+fn local_time(unix_seconds: i64) -> String {
+    js_sys::Date::new(&((unix_seconds * 1000) as f64).into())
+        .to_locale_string("default", &wasm_bindgen::JsValue::UNDEFINED)
+        .into()
+}
+
+// Counts down to the deadline, rounding up to the minute.
+fn registration_badge(meeting: &Meeting, now: i64) -> String {
+    match meeting.registration_deadline {
+        None => "".to_owned(),
+        Some(_) if !meeting.registration_open(now) => i18n::text("badge-registration-closed"),
+        Some(deadline) => {
+            let minutes = (deadline - now + 59) / 60;
+            let (days, hours, minutes) = (minutes / (24 * 60), minutes / 60 % 24, minutes % 60);
+            let left = if days > 0 {
+                i18n::text_with(
+                    "left-days",
+                    &[("days", days.into()), ("hours", hours.into())],
+                )
+            } else if hours > 0 {
+                i18n::text_with(
+                    "left-hours",
+                    &[("hours", hours.into()), ("minutes", minutes.into())],
+                )
+            } else {
+                i18n::text_with("left-minutes", &[("minutes", minutes.into())])
+            };
+            i18n::text_with("badge-registration-closes", &[("left", left.into())])
+        }
+    }
+}
+
+impl Model {
+    /// Fetches the shown page of meetings again, after the list or search changed.
+    /// Short lists are shown whole until someone searches them.
+    fn refresh_meeting_page(&mut self, ctx: &Context<Self>) {
+        let searching = !self.meeting_search_text.trim().is_empty();
+        if searching || self.meetings.len() > MEETINGS_PAGE_SIZE as usize {
+            let offset = self.meeting_page.as_ref().map_or(0, |page| page.offset);
+            ctx.link().send_message(Msg::FetchMeetingPage(offset));
+        } else {
+            self.meeting_page = None;
+        }
+    }
+
+    fn meeting_people(&self) -> Option<(usize, usize)> {
+        if let Some(attending_meeting) = self.attending_meeting {
+            self.meetings
+                .iter()
+                .filter(|sm| sm.meeting.id == attending_meeting)
+                .map(|sm| {
+                    (
+                        sm.meeting.n_registered as usize,
+                        sm.meeting.n_joined as usize,
+                    )
+                })
+                .next()
+        } else {
+            None
+        }
+    }
+
+    // A list too long to send whole may lack the attended meeting,
+    // and a meeting on the page shown may be past the end of the list.
+    fn scored_meeting(&self, meeting_id: u32) -> Option<&ScoredMeeting> {
+        self.meetings
+            .iter()
+            .chain(
+                self.meeting_page
+                    .iter()
+                    .flat_map(|page| page.meetings.iter()),
+            )
+            .chain(self.unlisted_meeting.iter())
+            .find(|sm| sm.meeting.id == meeting_id)
+    }
+
+    fn fetch_unlisted_meeting(&mut self, ctx: &Context<Self>) {
+        let Some(id) = self.attending_meeting else {
+            return;
+        };
+        if self.meetings.iter().any(|sm| sm.meeting.id == id) {
+            self.unlisted_meeting = None;
+            return;
+        }
+        ctx.link().send_future(async move {
+            match fetch_meeting(id).await {
+                Ok(meeting) => Msg::SetUnlistedMeeting(meeting),
+                Err(e) => Msg::LogError(e),
+            }
+        });
+    }
+
+    fn meeting_permissions(&self, meeting_id: u32) -> MeetingPermissions {
+        self.scored_meeting(meeting_id)
+            .map(|sm| sm.permissions.clone())
+            .unwrap_or_default()
+    }
+
+    fn fetch_user(&mut self, tag: &str, ctx: &Context<Self>) {
+        self.user_id = UserIdState::Fetching;
+        console_dbg!(format!("fetch_user in {}", tag));
+        ctx.link().send_future(async {
+            if let Some(msg) = fetch_user_id().await {
+                Msg::SetUserId(msg)
+            } else {
+                Msg::Noop
+            }
+        });
+        ctx.link().send_future(async {
+            if let Ok(topics) = fetch_user_topics().await {
+                Msg::SetUserTopics(topics)
+            } else {
+                Msg::Noop
+            }
+        });
+        ctx.link().send_future(async {
+            if let Ok(meetings) = fetch_registered_meetings().await {
+                Msg::SetRegisteredMeetings(meetings)
+            } else {
+                Msg::Noop
+            }
+        });
+    }
+
+    fn topic_timer_html(&self, ctx: &Context<Self>, i: usize, minutes: u32) -> Html {
+        match self.topic_timer {
+            Some((running, deadline)) if running == i => {
+                let remaining_s = ((deadline - js_sys::Date::now()) / 1000.0).max(0.0) as u32;
+                let text = if remaining_s == 0 {
+                    i18n::text("timer-up")
+                } else {
+                    format!("{}:{:02}", remaining_s / 60, remaining_s % 60)
+                };
+                html! {
+                    <>
+                        <span class="badge bg-primary me-2">{text}</span>
+                        <button
+                            onclick={ctx.link().callback(|_| Msg::StopTopicTimer)}
+                            type={"button"}
+                            class={"btn btn-sm btn-secondary"}
+                        >{i18n::text("timer-stop")}</button>
+                    </>
+                }
+            }
+            _ => html! {
+                <>
+                    <span class="me-2">{i18n::text_with("timer-minutes", &[("minutes", minutes.into())])}</span>
+                    <button
+                        onclick={ctx.link().callback(move |_| Msg::StartTopicTimer(i))}
+                        type={"button"}
+                        class={"btn btn-sm btn-outline-primary"}
+                    >{i18n::text("timer-start")}</button>
+                </>
+            },
+        }
+    }
+
+    fn meeting_election_results_html(&self, ctx: &Context<Self>) -> Html {
         let ElectionResults {
             meeting_name,
             meeting_url,
             status,
             topics,
             users,
+            avatars,
+            topic_minutes,
+            outcome,
+            contact_sharing,
+            round,
             ..
         } = self.election_results.as_ref().unwrap();
         let topics_html: Vec<_> = if topics.is_none() {
@@ -423,10 +1592,36 @@ impl Model {
                 .as_ref()
                 .unwrap()
                 .iter()
-                .map(|t| {
+                .enumerate()
+                .map(|(i, t)| {
+                    let timer_html = match topic_minutes.as_ref().and_then(|m| m.get(i)) {
+                        Some(&minutes) => self.topic_timer_html(ctx, i, minutes),
+                        None => html! {},
+                    };
+                    // Only a penalizing policy elects vetoed topics.
+                    let vetoed_html = if t.vetoed {
+                        html! { <span class="badge bg-danger ms-2">{i18n::text("badge-vetoed")}</span> }
+                    } else {
+                        html! {}
+                    };
+                    let proposers_html = if t.proposers.is_empty() {
+                        html! {}
+                    } else {
+                        html! {
+                            <div class="small text-muted">
+                                {i18n::text_with("proposed-by", &[("proposers", t.proposers.join(", ").into())])}
+                            </div>
+                        }
+                    };
                     html! {
                         <div class="row">
-                            {t.text.clone()}
+                            <div class="col">
+                                {t.text.clone()}
+                                {vetoed_html}
+                                {ranking::links_html(&t.links)}
+                                {proposers_html}
+                            </div>
+                            <div class="col">{timer_html}</div>
                         </div>
                     }
                 })
@@ -435,10 +1630,20 @@ impl Model {
         let users_html: Vec<_> = if let Some(users) = users {
             users
                 .iter()
-                .map(|u| {
+                .enumerate()
+                .map(|(i, u)| {
+                    let avatar_html = match avatars.as_ref().and_then(|a| a.get(i)) {
+                        Some(Some(url)) => html! {
+                            <img src={url.clone()} alt="" width="32" height="32" class="rounded-circle me-2"/>
+                        },
+                        _ => html! {},
+                    };
                     html! {
                         <div class="row">
-                            {u.clone()}
+                            <div class="col">
+                                {avatar_html}
+                                {u.clone()}
+                            </div>
                         </div>
                     }
                 })
@@ -446,60 +1651,666 @@ impl Model {
         } else {
             vec![]
         };
+        let round_html = if *round > 1 {
+            html! { <p class="text-muted">{i18n::text_with("round", &[("round", (*round).into())])}</p> }
+        } else {
+            html! {}
+        };
         html! {
             <>
                 <h2>{ meeting_name }</h2>
-                <p>{ status }</p>
-                <a href={meeting_url.clone()}>{meeting_url}</a>
-                <h3>{"Your Group"}</h3>
+                {round_html}
+                <p>{ copy::election_status_text(status).unwrap_or_default() }</p>
+                // A new tab keeps the topic timers in view during the conversation.
+                <a
+                    href={meeting_url.clone()}
+                    target="_blank"
+                    rel="noopener"
+                    onclick={ctx.link().callback(|_| Msg::JoinedRoom)}
+                >{meeting_url}</a>
+                <h3>{i18n::text("your-group")}</h3>
                 <div class="container">
                     {users_html}
                 </div>
-                <h3>{"Your Topics"}</h3>
+                <h3>{i18n::text("your-topics")}</h3>
                 <div class="container">
                     {topics_html}
                 </div>
+                {topics.as_ref().map_or(html! {}, |topics| self.outcome_html(ctx, topics, outcome.as_ref()))}
+                {contact_sharing.as_ref().map_or(html! {}, |sharing| self.contact_sharing_html(ctx, sharing))}
+                {self.room_joins_html(ctx)}
+                {self.next_round_html(ctx)}
+                {self.export_links_html()}
+                {self.past_results_html()}
             </>
         }
     }
 
-    fn meeting_attendance_html(&self, ctx: &Context<Self>) -> Html {
-        if let Some(meeting_id) = self.attending_meeting {
-            let meeting_name = &self
-                .meetings
-                .iter()
-                .find_map(|m| {
-                    if m.meeting.id == meeting_id {
-                        Some(m)
-                    } else {
-                        None
+    // Contacts appear only among members who each chose to share,
+    // so declining, or not choosing, reveals nothing.
+    fn contact_sharing_html(&self, ctx: &Context<Self>, sharing: &ContactSharing) -> Html {
+        let contacts_html = match sharing.share {
+            Some(true) if sharing.contacts.is_empty() => html! {
+                <p>{i18n::text("contacts-none-yet")}</p>
+            },
+            Some(true) => html! {
+                <ul>
+                    { for sharing.contacts.iter().map(|email| html! {
+                        <li><a href={format!("mailto:{email}")}>{email.clone()}</a></li>
+                    }) }
+                </ul>
+            },
+            Some(false) => html! { <p>{i18n::text("contacts-declined")}</p> },
+            None => html! {
+                <p>{i18n::text("contacts-question")}</p>
+            },
+        };
+        html! {
+            <div>
+                <h3>{i18n::text("contacts-heading")}</h3>
+                {contacts_html}
+                <button
+                    onclick={ctx.link().callback(|_| Msg::ShareContact(true))}
+                    type={"button"}
+                    class={"btn btn-secondary me-2"}
+                    disabled={sharing.share == Some(true)}
+                >{i18n::text("contacts-share")}</button>
+                <button
+                    onclick={ctx.link().callback(|_| Msg::ShareContact(false))}
+                    type={"button"}
+                    class={"btn btn-outline-secondary"}
+                    disabled={sharing.share == Some(false)}
+                >{i18n::text("contacts-decline")}</button>
+                <div class="form-text text-danger">{self.contact_sharing_error.clone().unwrap_or_default()}</div>
+            </div>
+        }
+    }
+
+    // Once the results are in, any cohort member can record what the group decided.
+    fn outcome_html(
+        &self,
+        ctx: &Context<Self>,
+        topics: &[UserTopic],
+        outcome: Option<&CohortOutcome>,
+    ) -> Html {
+        let error_html = html! {
+            <div class="form-text text-danger">{self.outcome_error.clone().unwrap_or_default()}</div>
+        };
+        let form = match &self.outcome_form {
+            Some(form) => form,
+            None => {
+                let (recorded_html, button_text) = match outcome {
+                    Some(o) => {
+                        let follow_ups: Vec<_> = o.topics.iter().map(|t| t.text.clone()).collect();
+                        let follow_ups_html = if follow_ups.is_empty() {
+                            html! {}
+                        } else {
+                            html! {
+                                <div class="small">{i18n::text_with("outcome-follow-ups", &[("topics", follow_ups.join(", ").into())])}</div>
+                            }
+                        };
+                        let html = html! {
+                            <>
+                                <p style="white-space: pre-wrap">{o.text.clone()}</p>
+                                {follow_ups_html}
+                                <div class="small text-muted">{i18n::text_with("outcome-recorded-by", &[("member", o.recorded_by.as_str().into())])}</div>
+                            </>
+                        };
+                        (html, "outcome-revise")
                     }
-                })
-                .unwrap()
-                .meeting
-                .name;
-            let join_info_html = if let Some((n_registered, n_joined)) = self.meeting_people() {
+                    None => (
+                        html! { <p>{i18n::text("outcome-none")}</p> },
+                        "outcome-record",
+                    ),
+                };
+                return html! {
+                    <div>
+                        <h3>{i18n::text("outcome-heading")}</h3>
+                        {recorded_html}
+                        <button
+                            onclick={ctx.link().callback(|_| Msg::EditOutcome)}
+                            type={"button"}
+                            class={"btn btn-secondary"}
+                        >{i18n::text(button_text)}</button>
+                        {error_html}
+                    </div>
+                };
+            }
+        };
+        let topics_html = topics
+            .iter()
+            .map(|t| {
+                let id = t.id;
                 html! {
-                    <div class="container">
-                        <div class="row">
-                            <div class="col">
-                                <h3>{format!("{n_joined} of {n_registered} registered participants have joined")}</h3>
-                            </div>
-                        </div>
-                        <div class="row">
-                            <div class="col">
-                                <button
-                                    type="button"
-                                    class="btn btn-success"
-                                    onclick={ctx.link().callback(move |_| Msg::StartMeeting)}
-                                >{"Start Meeting Now"}</button>
-                            </div>
-                            <div class="col">
+                    <div class="form-check">
+                        <input
+                            class="form-check-input"
+                            type="checkbox"
+                            id={format!("outcome-topic-{id}")}
+                            checked={form.topics.contains(&id)}
+                            onclick={ctx.link().callback(move |_| Msg::ToggleOutcomeTopic(id))}
+                        />
+                        <label class="form-check-label" for={format!("outcome-topic-{id}")}>
+                            {t.text.clone()}
+                        </label>
+                    </div>
+                }
+            })
+            .collect::<Html>();
+        html! {
+            <div>
+                <h3>{i18n::text("outcome-heading")}</h3>
+                <label for="outcome-text">{i18n::text("outcome-text-label")}</label>
+                <textarea
+                    id="outcome-text"
+                    class="form-control"
+                    rows="4"
+                    value={form.text.clone()}
+                    oninput={ctx.link().callback(|e: InputEvent| {
+                            let input = e.target_unchecked_into::<HtmlTextAreaElement>();
+                            Msg::UpdateOutcomeText(input.value())
+                    })}
+                />
+                <div class="form-text">{i18n::text("outcome-topics-label")}</div>
+                {topics_html}
+                <button
+                    onclick={ctx.link().callback(|_| Msg::RecordOutcome)}
+                    type={"button"}
+                    class={"btn btn-primary"}
+                >{i18n::text("save")}</button>
+                <button
+                    onclick={ctx.link().callback(|_| Msg::CancelOutcome)}
+                    type={"button"}
+                    class={"btn btn-secondary"}
+                >{i18n::text("cancel")}</button>
+                {error_html}
+            </div>
+        }
+    }
+
+    // Organizers can reshuffle everyone for another round once every cohort has voted.
+    fn next_round_html(&self, ctx: &Context<Self>) -> Html {
+        match self.attending_meeting {
+            Some(id) if self.meeting_permissions(id).can_start => html! {
+                <div>
+                    <button
+                        type="button"
+                        class="btn btn-secondary"
+                        onclick={ctx.link().callback(|_| Msg::NextRound)}
+                    >{i18n::text("next-round")}</button>
+                    <div class="form-text">
+                        {self.next_round_error.clone().unwrap_or_else(|| i18n::text("next-round-help"))}
+                    </div>
+                </div>
+            },
+            _ => html! {},
+        }
+    }
+
+    fn export_links_html(&self) -> Html {
+        match self.attending_meeting {
+            Some(id) if self.meeting_permissions(id).can_view_outcomes => html! {
+                <>
+                    <a
+                        href={format!("/meeting/{id}/summary.md")}
+                        class="btn btn-link"
+                    >{i18n::text("export-summary")}</a>
+                    <a
+                        href={format!("/meeting/{id}/election_results/export?format=csv")}
+                        download={format!("meeting-{id}-results.csv")}
+                        class="btn btn-link"
+                    >{i18n::text("export-results-csv")}</a>
+                    <a
+                        href={format!("/meeting/{id}/election_results/export?format=json")}
+                        download={format!("meeting-{id}-results.json")}
+                        class="btn btn-link"
+                    >{i18n::text("export-results-json")}</a>
+                </>
+            },
+            _ => html! {},
+        }
+    }
+
+    // Organizers check this during the conversation, so it shows beside results too.
+    // Kept from when each cohort finished voting, even after attendees leave
+    fn past_results_html(&self) -> Html {
+        let elections = match &self.meeting_history {
+            Some(history) if !history.elections.is_empty() => &history.elections,
+            _ => return html! {},
+        };
+        let elections_html = elections.iter().map(|e| {
+            let topics_html = e.topics.iter().map(|t| {
+                let vetoed_html = if t.vetoed {
+                    html! { <span class="badge bg-danger ms-2">{i18n::text("badge-vetoed")}</span> }
+                } else {
+                    html! {}
+                };
+                html! {
+                    <li>{t.text.clone()}{vetoed_html}</li>
+                }
+            });
+            // Rounds are only worth naming once there is more than one.
+            let time = local_time(e.recorded_at);
+            let heading = if e.round > 1 {
+                i18n::text_with(
+                    "past-result-round",
+                    &[
+                        ("round", e.round.into()),
+                        ("cohort", e.cohort.into()),
+                        ("time", time.into()),
+                    ],
+                )
+            } else {
+                i18n::text_with(
+                    "past-result",
+                    &[("cohort", e.cohort.into()), ("time", time.into())],
+                )
+            };
+            html! {
+                <div class="mb-2">
+                    <div>{heading}</div>
+                    <div class="small text-muted">{e.members.join(", ")}</div>
+                    <ol>{for topics_html}</ol>
+                </div>
+            }
+        });
+        html! {
+            <>
+                <h3>{i18n::text("past-results")}</h3>
+                <div class="container">
+                    {for elections_html}
+                </div>
+            </>
+        }
+    }
+
+    fn room_joins_html(&self, ctx: &Context<Self>) -> Html {
+        let permitted = self
+            .attending_meeting
+            .is_some_and(|id| self.meeting_permissions(id).can_view_room_joins);
+        if !permitted {
+            return html! {};
+        }
+        let cohorts_html = self.room_joins.as_ref().map_or(vec![], |joins| {
+            joins
+                .cohorts
+                .iter()
+                .map(|c| {
+                    let absent: Vec<_> = c
+                        .members
+                        .iter()
+                        .filter(|m| !c.joined.contains(m))
+                        .cloned()
+                        .collect();
+                    let badge = if c.joined.is_empty() {
+                        html! { <span class="badge bg-danger ms-2">{i18n::text("badge-not-convened")}</span> }
+                    } else {
+                        html! {}
+                    };
+                    html! {
+                        <li>
+                            {i18n::text_with(
+                                "room-joins",
+                                &[
+                                    ("cohort", c.cohort.into()),
+                                    ("joined", c.joined.len().into()),
+                                    ("members", c.members.len().into()),
+                                ],
+                            )}
+                            {badge}
+                            <div class="form-text">{absent.join(", ")}</div>
+                        </li>
+                    }
+                })
+                .collect()
+        });
+        html! {
+            <div>
+                <button
+                    onclick={ctx.link().callback(|_| Msg::FetchRoomJoins)}
+                    type={"button"}
+                    class={"btn btn-secondary"}
+                >{i18n::text("check-rooms")}</button>
+                <ul>{cohorts_html}</ul>
+            </div>
+        }
+    }
+
+    fn voting_minutes_html(
+        &self,
+        ctx: &Context<Self>,
+        meeting_id: u32,
+        permissions: &MeetingPermissions,
+    ) -> Html {
+        if !permissions.can_rename {
+            return html! {};
+        }
+        let minutes = self
+            .scored_meeting(meeting_id)
+            .and_then(|sm| sm.meeting.voting_minutes);
+        let minutes_text = match minutes {
+            Some(m) => i18n::text_with("voting-minutes-help", &[("minutes", m.into())]),
+            None => i18n::text("voting-unlimited-help"),
+        };
+        let new_minutes = self
+            .voting_minutes_text
+            .trim()
+            .parse::<u32>()
+            .ok()
+            .filter(|m| (MIN_VOTING_MINUTES..=MAX_VOTING_MINUTES).contains(m));
+        html! {
+            <div>
+                <label for="voting-minutes">{i18n::text("voting-minutes-label")}</label>
+                <input
+                    id="voting-minutes"
+                    type="number"
+                    min={MIN_VOTING_MINUTES.to_string()}
+                    max={MAX_VOTING_MINUTES.to_string()}
+                    value={self.voting_minutes_text.clone()}
+                    oninput={ctx.link().callback(|e: InputEvent| {
+                            let input = e.target_unchecked_into::<HtmlInputElement>();
+                            Msg::UpdateVotingMinutesText(input.value())
+                    })}
+                />
+                <button
+                    onclick={ctx.link().callback(move |_| Msg::SetVotingMinutes(new_minutes))}
+                    type={"button"}
+                    class={"btn btn-secondary"}
+                    disabled={new_minutes.is_none()}
+                >{i18n::text("voting-minutes-set")}</button>
+                <button
+                    onclick={ctx.link().callback(|_| Msg::SetVotingMinutes(None))}
+                    type={"button"}
+                    class={"btn btn-secondary"}
+                    disabled={minutes.is_none()}
+                >{i18n::text("voting-unlimited")}</button>
+                <div class="form-text">{minutes_text}</div>
+            </div>
+        }
+    }
+
+    // Renaming is for every organizer, and choosing co-organizers only for the owner.
+    fn organizers_html(&self, ctx: &Context<Self>, permissions: &MeetingPermissions) -> Html {
+        let rename_html = if permissions.can_rename {
+            let onkeypress = ctx.link().batch_callback(move |e: KeyboardEvent| {
+                (e.key() == "Enter").then(|| Msg::RenameMeeting)
+            });
+            html! {
+                <div>
+                    <label for="rename-meeting">{i18n::text("rename-label")}</label>
+                    <input
+                        id="rename-meeting"
+                        type="text"
+                        value={self.rename_meeting_text.clone()}
+                        { onkeypress }
+                        oninput={ctx.link().callback(|e: InputEvent| {
+                                let input = e.target_unchecked_into::<HtmlInputElement>();
+                                Msg::UpdateRenameMeetingText(input.value())
+                        })}
+                    />
+                    <button
+                        onclick={ctx.link().callback(|_| Msg::RenameMeeting)}
+                        type={"button"}
+                        class={"btn btn-secondary"}
+                        disabled={self.rename_meeting_text.trim().is_empty()}
+                    >{i18n::text("rename")}</button>
+                    <div class="form-text text-danger">
+                        {self.rename_meeting_error.clone().unwrap_or_default()}
+                    </div>
+                </div>
+            }
+        } else {
+            html! {}
+        };
+        if !permissions.can_manage_organizers {
+            return rename_html;
+        }
+        let organizers_html = match &self.meeting_organizers {
+            None => html! {
+                <button
+                    onclick={ctx.link().callback(|_| Msg::FetchOrganizers)}
+                    type={"button"}
+                    class={"btn btn-secondary"}
+                >{i18n::text("organizers-show")}</button>
+            },
+            Some(organizers) => {
+                let onkeypress = ctx.link().batch_callback(move |e: KeyboardEvent| {
+                    (e.key() == "Enter").then(|| Msg::AddOrganizer)
+                });
+                let items: Html = organizers
+                    .organizers
+                    .iter()
+                    .map(|email| {
+                        let remove = email.clone();
+                        html! {
+                            <li>
+                                {email}
+                                <button
+                                    onclick={ctx.link().callback(move |_| Msg::RemoveOrganizer(remove.clone()))}
+                                    type={"button"}
+                                    class={"btn btn-sm"}
+                                >{ x_icon() }</button>
+                            </li>
+                        }
+                    })
+                    .collect();
+                html! {
+                    <div>
+                        <label>{i18n::text("organizers-label")}</label>
+                        <ul>{items}</ul>
+                        <input
+                            id="new-organizer"
+                            type="email"
+                            placeholder={i18n::text("organizers-placeholder")}
+                            value={self.new_organizer_text.clone()}
+                            { onkeypress }
+                            oninput={ctx.link().callback(|e: InputEvent| {
+                                    let input = e.target_unchecked_into::<HtmlInputElement>();
+                                    Msg::UpdateNewOrganizerText(input.value())
+                            })}
+                        />
+                        <button
+                            onclick={ctx.link().callback(|_| Msg::AddOrganizer)}
+                            type={"button"}
+                            class={"btn"}
+                        >{ add_icon() }</button>
+                        <div class="form-text text-danger">
+                            {self.meeting_organizers_error.clone().unwrap_or_default()}
+                        </div>
+                    </div>
+                }
+            }
+        };
+        html! {
+            <>
+                {rename_html}
+                {organizers_html}
+            </>
+        }
+    }
+
+    // Merged topics are listed together, and checking one merges its whole group.
+    fn merge_topics_html(&self, ctx: &Context<Self>, permissions: &MeetingPermissions) -> Html {
+        let started = self.meeting_topics.as_ref().is_some_and(|t| !t.is_empty());
+        if !permissions.can_merge_topics || started {
+            return html! {};
+        }
+        let topics = match &self.candidate_topics {
+            None => {
+                return html! {
+                    <button
+                        onclick={ctx.link().callback(|_| Msg::FetchCandidateTopics)}
+                        type={"button"}
+                        class={"btn btn-secondary"}
+                    >{i18n::text("merge-show")}</button>
+                }
+            }
+            Some(topics) => topics,
+        };
+        let mut groups: Vec<(u32, Vec<&CandidateTopic>)> = vec![];
+        for topic in topics.iter() {
+            let group = topic.merged_into.unwrap_or(topic.id);
+            match groups.iter_mut().find(|(g, _)| *g == group) {
+                Some((_, members)) => members.push(topic),
+                None => groups.push((group, vec![topic])),
+            }
+        }
+        let items: Html = groups
+            .iter()
+            .map(|(_, members)| {
+                let id = members[0].id;
+                let merged = members.len() > 1;
+                let names: Html = members
+                    .iter()
+                    .map(|t| {
+                        let split = t.id;
+                        let split_html = if merged {
+                            html! {
+                                <button
+                                    onclick={ctx.link().callback(move |_| Msg::SplitTopic(split))}
+                                    type={"button"}
+                                    class={"btn btn-sm"}
+                                    title={i18n::text("merge-split")}
+                                >{ x_icon() }</button>
+                            }
+                        } else {
+                            html! {}
+                        };
+                        html! {
+                            <span class="me-2">{t.text.clone()}{split_html}</span>
+                        }
+                    })
+                    .collect();
+                html! {
+                    <li class="form-check">
+                        <input
+                            class="form-check-input"
+                            type="checkbox"
+                            id={format!("merge-topic-{id}")}
+                            checked={self.merge_selection.contains(&id)}
+                            onclick={ctx.link().callback(move |_| Msg::ToggleMergeTopic(id))}
+                        />
+                        <label class="form-check-label" for={format!("merge-topic-{id}")}>
+                            {names}
+                        </label>
+                    </li>
+                }
+            })
+            .collect();
+        html! {
+            <div>
+                <label>{i18n::text("merge-label")}</label>
+                <ul class="list-unstyled">{items}</ul>
+                <button
+                    onclick={ctx.link().callback(|_| Msg::MergeTopics)}
+                    type={"button"}
+                    class={"btn btn-secondary"}
+                    disabled={self.merge_selection.len() < 2}
+                >{i18n::text("merge")}</button>
+                <div class="form-text">
+                    {i18n::text("merge-help")}
+                </div>
+                <div class="form-text text-danger">
+                    {self.merge_topics_error.clone().unwrap_or_default()}
+                </div>
+            </div>
+        }
+    }
+
+    fn meeting_attendance_html(&self, ctx: &Context<Self>) -> Html {
+        if let Some(meeting_id) = self.attending_meeting {
+            // Fetched separately when the list doesn't hold it, so briefly unknown
+            let meeting_name = self
+                .scored_meeting(meeting_id)
+                .map_or("".to_owned(), |sm| sm.meeting.name.clone());
+            let permissions = self.meeting_permissions(meeting_id);
+            let veto_policy = self
+                .scored_meeting(meeting_id)
+                .map_or(VetoPolicy::Off, |sm| sm.meeting.veto_policy);
+            let join_info_html = if let Some((n_registered, n_joined)) = self.meeting_people() {
+                let min_attendees = self
+                    .scored_meeting(meeting_id)
+                    .map_or(COHORT_QUORUM as u32, |sm| sm.meeting.min_attendees);
+                let start_hint = if let Some(e) = &self.start_meeting_error {
+                    e.clone()
+                } else if !permissions.can_start {
+                    start_meeting_error_text(&StartMeetingError::NotOrganizer)
+                } else if (n_joined as u32) < min_attendees {
+                    start_meeting_error_text(&StartMeetingError::TooFewAttendees {
+                        min_attendees,
+                        n_attendees: n_joined as u32,
+                    })
+                } else {
+                    "".to_owned()
+                };
+                let start_confirm_html = match self.start_confirm {
+                    Some((n_registered, n_joined)) => html! {
+                        <div class="alert alert-warning">
+                            <p>{start_meeting_error_text(&StartMeetingError::ConfirmRequired {
+                                n_registered,
+                                n_joined,
+                            })}</p>
+                            <button
+                                type="button"
+                                class="btn btn-warning me-2"
+                                onclick={ctx.link().callback(|_| Msg::StartMeeting(true))}
+                            >{i18n::text("start-anyway")}</button>
+                            <button
+                                type="button"
+                                class="btn btn-secondary"
+                                onclick={ctx.link().callback(|_| Msg::ConfirmStart(None))}
+                            >{i18n::text("start-wait")}</button>
+                        </div>
+                    },
+                    None => html! {},
+                };
+                // Until the rest of the cohort votes, a submitted ballot can come back.
+                let voted = self.election_results.as_ref().is_some_and(|r| {
+                    r.voted && matches!(r.status, ElectionStatus::WaitingForVotes { .. })
+                });
+                let vote_html = if voted {
+                    html! {
+                        <>
+                            <p>{i18n::text("ballot-in")}</p>
+                            <button
+                                type="button"
+                                class="btn btn-outline-secondary"
+                                onclick={ctx.link().callback(|_| Msg::RetractVote)}
+                            >{i18n::text("retract-vote")}</button>
+                        </>
+                    }
+                } else {
+                    html! {
+                        <button
+                            type="button"
+                            class="btn btn-success"
+                            onclick={ctx.link().callback(move |_| Msg::CommitVote)}
+                        >{i18n::text("done-ranking")}</button>
+                    }
+                };
+                html! {
+                    <div class="container">
+                        <div class="row">
+                            <div class="col">
+                                <h3>{i18n::text_with("joined-count", &[("joined", n_joined.into()), ("registered", n_registered.into())])}</h3>
+                            </div>
+                        </div>
+                        <div class="row">
+                            <div class="col">
                                 <button
                                     type="button"
                                     class="btn btn-success"
-                                    onclick={ctx.link().callback(move |_| Msg::CommitVote)}
-                                >{"DONE RANKING!"}</button>
+                                    disabled={!permissions.can_start || (n_joined as u32) < min_attendees}
+                                    onclick={ctx.link().callback(move |_| Msg::StartMeeting(false))}
+                                >{i18n::text("start-meeting")}</button>
+                                <div class="form-text">{start_hint}</div>
+                                {start_confirm_html}
+                            </div>
+                            <div class="col">
+                                {vote_html}
+                                <div class="form-text text-danger">
+                                    {self.vote_error.clone().unwrap_or_default()}
+                                </div>
                             </div>
                         </div>
                     </div>
@@ -513,15 +2324,238 @@ impl Model {
                         ids={topics.iter().map(|t| t.id).collect::<Vec<u32>>()}
                         labels={topics.iter().map(|t| t.text.clone()).collect::<Vec<String>>()}
                         scores={topics.iter().map(|t| t.score).collect::<Vec<u32>>()}
-                        store_score={ctx.link().callback(Msg::StoreMeetingTopicScore)}
+                        host_suggested={Some(topics.iter().map(|t| t.seeded).collect::<Vec<bool>>())}
+                        store_scores={ctx.link().callback(Msg::StoreMeetingTopicScores)}
+                        links={Some(topics.iter().map(|t| t.links.clone()).collect::<Vec<Vec<String>>>())}
+                        edit_links={permissions.can_seed_topics.then(|| ctx.link().callback(Msg::EditTopicLinks))}
+                        linkable={Some(topics.iter().map(|t| t.seeded).collect::<Vec<bool>>())}
+                        descriptions={Some(topics.iter().map(|t| t.description.clone()).collect::<Vec<String>>())}
+                        vetoed={Some(topics.iter().map(|t| t.vetoed).collect::<Vec<bool>>())}
+                        veto={(veto_policy != VetoPolicy::Off).then(|| ctx.link().callback(Msg::VetoTopic))}
                     />
+                    <div class="form-text text-danger">
+                        {self.veto_error.clone().unwrap_or_default()}
+                    </div>
+                }
+            } else {
+                html! {}
+            };
+            // Until cohorts form, proposals can still reach the cohort's ballots.
+            let forming = self
+                .election_results
+                .as_ref()
+                .map_or(true, |r| r.status == ElectionStatus::CohortPending);
+            let no_topics_html = if forming && self.user_topics.iter().all(|t| t.private) {
+                html! {
+                    <div class="alert alert-info">
+                        {i18n::text("no-topics-to-share")}
+                        <button
+                            onclick={ctx.link().callback(|_| Msg::SetTab(Tab::TopicManagment))}
+                            type={"button"}
+                            class={"btn btn-link"}
+                        >{i18n::text("add-topics")}</button>
+                    </div>
                 }
             } else {
                 html! {}
             };
+            let my_topics_html = if forming {
+                self.my_topics_html(ctx, meeting_id)
+            } else {
+                html! {}
+            };
             let status_html = if let Some(results) = &self.election_results {
+                let closes_html = match results.voting_closes_at {
+                    Some(closes_at)
+                        if matches!(results.status, ElectionStatus::WaitingForVotes { .. }) =>
+                    {
+                        let remaining_s = (closes_at - unix_now()).max(0);
+                        let left = format!("{}:{:02}", remaining_s / 60, remaining_s % 60);
+                        html! {
+                            <span class="badge bg-warning text-dark ms-2">
+                                {i18n::text_with("voting-closes-in", &[("left", left.into())])}
+                            </span>
+                        }
+                    }
+                    _ => html! {},
+                };
+                html! {
+                    <p>
+                        { copy::election_status_text(&results.status).unwrap_or_default() }
+                        {closes_html}
+                    </p>
+                }
+            } else {
+                html! {}
+            };
+            let invite_html = if permissions.can_share_invite {
+                let join_code = self
+                    .scored_meeting(meeting_id)
+                    .and_then(|sm| sm.meeting.join_code.clone());
+                let join_code_html = match join_code {
+                    Some(code) => html! {
+                        <span class="ms-2">{i18n::text("join-code")}{" "}<strong>{code}</strong></span>
+                    },
+                    None => html! {},
+                };
+                html! {
+                    <>
+                        <a
+                            href={format!("/meeting/{meeting_id}/qr.svg")}
+                            target="_blank"
+                            class="btn btn-link"
+                        >{i18n::text("invite-qr")}</a>
+                        {join_code_html}
+                    </>
+                }
+            } else {
+                html! {}
+            };
+            let new_seed_html = if permissions.can_seed_topics {
+                let onkeypress = ctx.link().batch_callback(move |e: KeyboardEvent| {
+                    (e.key() == "Enter").then(|| Msg::AddSeedTopic)
+                });
+                let deadline = self
+                    .scored_meeting(meeting_id)
+                    .and_then(|sm| sm.meeting.submission_deadline);
+                let deadline_text = match deadline {
+                    Some(d) if d <= unix_now() => {
+                        i18n::text_with("submissions-closed-at", &[("time", local_time(d).into())])
+                    }
+                    Some(d) => {
+                        i18n::text_with("submissions-open-until", &[("time", local_time(d).into())])
+                    }
+                    None => i18n::text_with(
+                        "submissions-off",
+                        &[("limit", self.topics_per_attendee(meeting_id).into())],
+                    ),
+                };
+                let new_deadline =
+                    js_sys::Date::new(&self.submission_deadline_text.as_str().into()).get_time();
+                html! {
+                    <div>
+                        <label for="submission-deadline">{i18n::text("submissions-label")}</label>
+                        <input
+                            id="submission-deadline"
+                            type="datetime-local"
+                            value={self.submission_deadline_text.clone()}
+                            oninput={ctx.link().callback(|e: InputEvent| {
+                                    let input = e.target_unchecked_into::<HtmlInputElement>();
+                                    Msg::UpdateSubmissionDeadlineText(input.value())
+                            })}
+                        />
+                        <button
+                            onclick={ctx.link().callback(move |_| {
+                                Msg::SetSubmissionDeadline(Some((new_deadline / 1000.0) as i64))
+                            })}
+                            type={"button"}
+                            class={"btn btn-secondary"}
+                            disabled={new_deadline.is_nan()}
+                        >{i18n::text("set-deadline")}</button>
+                        <button
+                            onclick={ctx.link().callback(|_| Msg::SetSubmissionDeadline(None))}
+                            type={"button"}
+                            class={"btn btn-secondary"}
+                            disabled={deadline.is_none()}
+                        >{i18n::text("submissions-none")}</button>
+                        <div class="form-text">{deadline_text}</div>
+                        <label>{i18n::text("seed-topic-label")}</label>
+                        <input
+                            id="new-seed-topic"
+                            type="text"
+                            value={self.new_seed_text.clone()}
+                            { onkeypress }
+                            oninput={ctx.link().callback(|e: InputEvent| {
+                                    let input = e.target_unchecked_into::<HtmlInputElement>();
+                                    Msg::UpdateNewSeedText(input.value())
+                            })}
+                        />
+                        <button
+                            onclick={ctx.link().callback(|_| Msg::AddSeedTopic)}
+                            type={"button"}
+                            class={"btn"}
+                        >{ add_icon() }</button>
+                    </div>
+                }
+            } else {
+                html! {}
+            };
+            let registration_html = if permissions.can_manage_registration {
+                let onkeypress = ctx.link().batch_callback(move |e: KeyboardEvent| {
+                    (e.key() == "Enter").then(|| Msg::AddRegistrationField)
+                });
+                let deadline = self
+                    .scored_meeting(meeting_id)
+                    .and_then(|sm| sm.meeting.registration_deadline);
+                let deadline_text = match deadline {
+                    Some(d) if d <= unix_now() => {
+                        i18n::text_with("registration-closed-at", &[("time", local_time(d).into())])
+                    }
+                    Some(d) => {
+                        i18n::text_with("registration-closes-at", &[("time", local_time(d).into())])
+                    }
+                    None => i18n::text("registration-open"),
+                };
+                let new_deadline =
+                    js_sys::Date::new(&self.registration_deadline_text.as_str().into()).get_time();
+                let deadline_html = html! {
+                    <div>
+                        <label for="registration-deadline">{i18n::text("registration-deadline-label")}</label>
+                        <input
+                            id="registration-deadline"
+                            type="datetime-local"
+                            value={self.registration_deadline_text.clone()}
+                            oninput={ctx.link().callback(|e: InputEvent| {
+                                    let input = e.target_unchecked_into::<HtmlInputElement>();
+                                    Msg::UpdateRegistrationDeadlineText(input.value())
+                            })}
+                        />
+                        <button
+                            onclick={ctx.link().callback(move |_| {
+                                Msg::SetRegistrationDeadline(Some((new_deadline / 1000.0) as i64))
+                            })}
+                            type={"button"}
+                            class={"btn btn-secondary"}
+                            disabled={new_deadline.is_nan()}
+                        >{i18n::text("set-deadline")}</button>
+                        <button
+                            onclick={ctx.link().callback(|_| Msg::SetRegistrationDeadline(Some(unix_now())))}
+                            type={"button"}
+                            class={"btn btn-secondary"}
+                        >{i18n::text("registration-close-now")}</button>
+                        <button
+                            onclick={ctx.link().callback(|_| Msg::SetRegistrationDeadline(None))}
+                            type={"button"}
+                            class={"btn btn-secondary"}
+                            disabled={deadline.is_none()}
+                        >{i18n::text("registration-reopen")}</button>
+                        <div class="form-text">{deadline_text}</div>
+                    </div>
+                };
                 html! {
-                    <p>{ results.status.clone() }</p>
+                    <div>
+                        <label>{i18n::text("registration-field-label")}</label>
+                        <input
+                            id="new-registration-field"
+                            type="text"
+                            value={self.new_registration_field_text.clone()}
+                            { onkeypress }
+                            oninput={ctx.link().callback(|e: InputEvent| {
+                                    let input = e.target_unchecked_into::<HtmlInputElement>();
+                                    Msg::UpdateNewRegistrationFieldText(input.value())
+                            })}
+                        />
+                        <button
+                            onclick={ctx.link().callback(|_| Msg::AddRegistrationField)}
+                            type={"button"}
+                            class={"btn"}
+                        >{ add_icon() }</button>
+                        <a
+                            href={format!("/meeting/{meeting_id}/registrations.csv")}
+                            class="btn btn-link"
+                        >{i18n::text("export-registrations")}</a>
+                        {deadline_html}
+                    </div>
                 }
             } else {
                 html! {}
@@ -529,33 +2563,200 @@ impl Model {
             html! {
                 <div class="container">
                     <div class="row">
-                        <h2>{ format!("Attending meeting: {}", meeting_name) }</h2>
+                        <h2>{ i18n::text_with("attending-meeting", &[("meeting", meeting_name.as_str().into())]) }</h2>
                         {join_info_html}
                         {status_html}
+                        {no_topics_html}
+                        {my_topics_html}
+                        {invite_html}
                         <button
                             onclick={ctx.link().callback(move |_| Msg::LeaveMeeting)}
                             type={"button"}
                             class={"btn btn-secondary"}
-                        >{"leave"}</button>
+                        >{i18n::text("leave")}</button>
                     </div>
                     <div class="row">
+                        { self.link_form_html(ctx) }
                         { meeting_topics_html }
                     </div>
+                    <div class="row">
+                        { new_seed_html }
+                    </div>
+                    <div class="row">
+                        { self.voting_minutes_html(ctx, meeting_id, &permissions) }
+                    </div>
+                    <div class="row">
+                        { self.merge_topics_html(ctx, &permissions) }
+                    </div>
+                    <div class="row">
+                        { registration_html }
+                    </div>
+                    <div class="row">
+                        { self.organizers_html(ctx, &permissions) }
+                    </div>
+                    <div class="row">
+                        { self.room_joins_html(ctx) }
+                        { self.export_links_html() }
+                    </div>
+                    <div class="row">
+                        { self.past_results_html() }
+                    </div>
                 </div>
             }
         } else {
             html! {}
         }
     }
+
+    fn topics_per_attendee(&self, meeting_id: u32) -> u32 {
+        self.scored_meeting(meeting_id)
+            .map_or(DEFAULT_TOPICS_PER_ATTENDEE, |sm| {
+                sm.meeting.topics_per_attendee
+            })
+    }
+
+    /// The own topics the user brings to the attended meeting's ballots,
+    /// as the API picks them when the user hasn't chosen
+    fn brought_topics(&self, meeting_id: u32) -> Vec<u32> {
+        if !self.my_topics.is_empty() {
+            return self.my_topics.clone();
+        }
+        let mut shared: Vec<&UserTopic> = self.user_topics.iter().filter(|t| !t.private).collect();
+        shared.sort_by(|a, b| b.score.cmp(&a.score));
+        shared
+            .iter()
+            .take(self.topics_per_attendee(meeting_id) as usize)
+            .map(|t| t.id)
+            .collect()
+    }
+
+    // Meetings taking submissions put those on the ballots instead.
+    fn my_topics_html(&self, ctx: &Context<Self>, meeting_id: u32) -> Html {
+        let takes_submissions = self
+            .scored_meeting(meeting_id)
+            .is_some_and(|sm| sm.meeting.submission_deadline.is_some());
+        if takes_submissions || self.user_topics.iter().all(|t| t.private) {
+            return html! {};
+        }
+        let limit = self.topics_per_attendee(meeting_id);
+        let brought = self.brought_topics(meeting_id);
+        let hint = if self.my_topics.is_empty() {
+            i18n::text_with("my-topics-default", &[("limit", limit.into())])
+        } else {
+            i18n::text_with("my-topics-limit", &[("limit", limit.into())])
+        };
+        let topics_html = self
+            .user_topics
+            .iter()
+            .filter(|t| !t.private)
+            .map(|t| {
+                let id = t.id;
+                let checkbox_id = format!("my-topic-{id}");
+                html! {
+                    <div class="form-check">
+                        <input
+                            class="form-check-input"
+                            type="checkbox"
+                            id={checkbox_id.clone()}
+                            checked={brought.contains(&id)}
+                            onclick={ctx.link().callback(move |_| Msg::ToggleMyTopic(id))}
+                        />
+                        <label class="form-check-label" for={checkbox_id}>{t.text.clone()}</label>
+                    </div>
+                }
+            })
+            .collect::<Html>();
+        html! {
+            <div class="mb-3">
+                <h3>{i18n::text("my-topics-heading")}</h3>
+                { topics_html }
+                <div class="form-text">{hint}</div>
+                <div class="form-text text-danger">
+                    {self.my_topics_error.clone().unwrap_or_default()}
+                </div>
+            </div>
+        }
+    }
+
     fn meeting_management_html(&self, ctx: &Context<Self>) -> Html {
         let onkeypress = ctx
             .link()
             .batch_callback(move |e: KeyboardEvent| (e.key() == "Enter").then(|| Msg::AddMeeting));
 
         let new_meeting = if let UserIdState::Fetched(_uid) = &self.user_id {
+            let template_options = self
+                .meeting_templates
+                .iter()
+                .enumerate()
+                .map(|(i, t)| {
+                    html! {
+                        <option
+                            value={i.to_string()}
+                            selected={self.new_meeting_template == Some(i)}
+                        >{&t.title}</option>
+                    }
+                })
+                .collect::<Html>();
+            let cohort_size_options = (MIN_COHORT_SIZE..=MAX_COHORT_SIZE)
+                .map(|n| {
+                    html! {
+                        <option
+                            value={n.to_string()}
+                            selected={self.new_meeting_cohort_size == n}
+                        >{n}</option>
+                    }
+                })
+                .collect::<Html>();
+            let n_winners_options = (MIN_N_WINNERS..=MAX_N_WINNERS)
+                .map(|n| {
+                    html! {
+                        <option
+                            value={n.to_string()}
+                            selected={self.new_meeting_n_winners == n}
+                        >{n}</option>
+                    }
+                })
+                .collect::<Html>();
+            let topics_per_attendee_options = (MIN_TOPICS_PER_ATTENDEE..=MAX_TOPICS_PER_ATTENDEE)
+                .map(|n| {
+                    html! {
+                        <option
+                            value={n.to_string()}
+                            selected={self.new_meeting_topics_per_attendee == n}
+                        >{n}</option>
+                    }
+                })
+                .collect::<Html>();
+            let veto_policy_options = VetoPolicy::ALL
+                .into_iter()
+                .map(|policy| {
+                    html! {
+                        <option
+                            value={policy.as_str()}
+                            selected={self.new_meeting_veto_policy == policy}
+                        >{veto_policy_label(policy)}</option>
+                    }
+                })
+                .collect::<Html>();
+            let voting_method_options = VotingMethod::ALL
+                .into_iter()
+                .map(|method| {
+                    html! {
+                        <option
+                            value={method.as_str()}
+                            selected={self.new_meeting_voting_method == method}
+                        >{voting_method_label(method)}</option>
+                    }
+                })
+                .collect::<Html>();
+            let template_description = self
+                .new_meeting_template
+                .and_then(|i| self.meeting_templates.get(i))
+                .map(|t| html! { <div class="form-text">{&t.description}</div> })
+                .unwrap_or_else(|| html! {});
             html! {
                 <div>
-                    <label>{"Add new meeting"}</label>
+                    <label>{i18n::text("new-meeting-label")}</label>
                     <input
                         id="new-meeting"
                         type="text"
@@ -571,134 +2772,1319 @@ impl Model {
                         type={"button"}
                         class={"btn"}
                     >{ add_icon() }</button>
+                    <select
+                        id="new-meeting-template"
+                        onchange={ctx.link().callback(|e: Event| {
+                                let select = e.target_unchecked_into::<HtmlSelectElement>();
+                                Msg::SetNewMeetingTemplate(select.value().parse().ok())
+                        })}
+                    >
+                        <option value="" selected={self.new_meeting_template.is_none()}>{i18n::text("new-meeting-blank")}</option>
+                        { template_options }
+                    </select>
+                    <label for="new-meeting-cohort-size" class="ms-2">{i18n::text("new-meeting-cohort-size")}</label>
+                    <select
+                        id="new-meeting-cohort-size"
+                        onchange={ctx.link().callback(|e: Event| {
+                                let select = e.target_unchecked_into::<HtmlSelectElement>();
+                                Msg::SetNewMeetingCohortSize(select.value().parse().unwrap_or(COHORT_QUORUM as u32))
+                        })}
+                    >
+                        { cohort_size_options }
+                    </select>
+                    <label for="new-meeting-n-winners" class="ms-2">{i18n::text("new-meeting-n-winners")}</label>
+                    <select
+                        id="new-meeting-n-winners"
+                        onchange={ctx.link().callback(|e: Event| {
+                                let select = e.target_unchecked_into::<HtmlSelectElement>();
+                                Msg::SetNewMeetingNWinners(select.value().parse().unwrap_or(DEFAULT_N_WINNERS))
+                        })}
+                    >
+                        { n_winners_options }
+                    </select>
+                    <label for="new-meeting-topics-per-attendee" class="ms-2">{i18n::text("new-meeting-topics-per-attendee")}</label>
+                    <select
+                        id="new-meeting-topics-per-attendee"
+                        onchange={ctx.link().callback(|e: Event| {
+                                let select = e.target_unchecked_into::<HtmlSelectElement>();
+                                Msg::SetNewMeetingTopicsPerAttendee(select.value().parse().unwrap_or(DEFAULT_TOPICS_PER_ATTENDEE))
+                        })}
+                    >
+                        { topics_per_attendee_options }
+                    </select>
+                    <select
+                        id="new-meeting-veto-policy"
+                        class="ms-2"
+                        onchange={ctx.link().callback(|e: Event| {
+                                let select = e.target_unchecked_into::<HtmlSelectElement>();
+                                let policy = VetoPolicy::ALL
+                                    .into_iter()
+                                    .find(|p| p.as_str() == select.value())
+                                    .unwrap_or_default();
+                                Msg::SetNewMeetingVetoPolicy(policy)
+                        })}
+                    >
+                        { veto_policy_options }
+                    </select>
+                    <select
+                        id="new-meeting-voting-method"
+                        class="ms-2"
+                        onchange={ctx.link().callback(|e: Event| {
+                                let select = e.target_unchecked_into::<HtmlSelectElement>();
+                                let method = VotingMethod::ALL
+                                    .into_iter()
+                                    .find(|m| m.as_str() == select.value())
+                                    .unwrap_or_default();
+                                Msg::SetNewMeetingVotingMethod(method)
+                        })}
+                    >
+                        { voting_method_options }
+                    </select>
+                    <div class="form-check form-check-inline ms-2">
+                        <input
+                            class="form-check-input"
+                            type="checkbox"
+                            id="new-meeting-match-language"
+                            checked={self.new_meeting_match_language}
+                            onclick={ctx.link().callback(|_| Msg::ToggleNewMeetingMatchLanguage)}
+                        />
+                        <label class="form-check-label" for="new-meeting-match-language">
+                            {i18n::text("new-meeting-match-language")}
+                        </label>
+                    </div>
+                    <div class="form-check form-check-inline">
+                        <input
+                            class="form-check-input"
+                            type="checkbox"
+                            id="new-meeting-share-contacts"
+                            checked={self.new_meeting_share_contacts}
+                            onclick={ctx.link().callback(|_| Msg::ToggleNewMeetingShareContacts)}
+                        />
+                        <label class="form-check-label" for="new-meeting-share-contacts">
+                            {i18n::text("new-meeting-share-contacts")}
+                        </label>
+                    </div>
+                    <button
+                        onclick={ctx.link().callback(|_| Msg::ToggleImportMeeting)}
+                        type={"button"}
+                        class={"btn btn-link"}
+                    >{i18n::text("import-show")}</button>
+                    { template_description }
+                </div>
+            }
+        } else {
+            html! {}
+        };
+        let import_meeting = if let Some(text) = &self.import_meeting {
+            let error_html = if let Some(e) = &self.import_meeting_error {
+                html! { <div class="alert alert-danger">{e}</div> }
+            } else {
+                html! {}
+            };
+            html! {
+                <div class="container">
+                    <label for="import-meeting">{i18n::text("import-label")}</label>
+                    <textarea
+                        id="import-meeting"
+                        class="form-control"
+                        rows="6"
+                        value={text.clone()}
+                        oninput={ctx.link().callback(|e: InputEvent| {
+                                let input = e.target_unchecked_into::<HtmlTextAreaElement>();
+                                Msg::UpdateImportMeetingText(input.value())
+                        })}
+                    />
+                    {error_html}
+                    <button
+                        onclick={ctx.link().callback(|_| Msg::ImportMeeting)}
+                        type={"button"}
+                        class={"btn btn-primary"}
+                    >{i18n::text("import")}</button>
+                    <button
+                        onclick={ctx.link().callback(|_| Msg::ToggleImportMeeting)}
+                        type={"button"}
+                        class={"btn btn-secondary"}
+                    >{i18n::text("dialog-cancel")}</button>
+                </div>
+            }
+        } else {
+            html! {}
+        };
+        let registration_form = if let Some((id, answers)) = &self.registration_form {
+            let meeting = self.scored_meeting(*id);
+            let fields = meeting.map_or(vec![], |sm| sm.registration_fields.clone());
+            let meeting_name = meeting.map_or("".to_owned(), |sm| sm.meeting.name.clone());
+            let inputs = fields
+                .iter()
+                .zip(answers)
+                .enumerate()
+                .map(|(i, (field, answer))| {
+                    html! {
+                        <div class="mb-2">
+                            <label class="form-label">{field.label.clone()}</label>
+                            <input
+                                class="form-control"
+                                type="text"
+                                value={answer.clone()}
+                                oninput={ctx.link().callback(move |e: InputEvent| {
+                                        let input = e.target_unchecked_into::<HtmlInputElement>();
+                                        Msg::UpdateRegistrationAnswer(i, input.value())
+                                })}
+                            />
+                        </div>
+                    }
+                });
+            html! {
+                <div>
+                    <h4>{i18n::text_with("register-heading", &[("meeting", meeting_name.as_str().into())])}</h4>
+                    {for inputs}
+                    <button
+                        onclick={ctx.link().callback(|_| Msg::SubmitRegistration)}
+                        type={"button"}
+                        class={"btn btn-primary"}
+                        disabled={answers.iter().any(|a| a.trim().is_empty())}
+                    >{i18n::text("register")}</button>
+                    <button
+                        onclick={ctx.link().callback(|_| Msg::CancelRegistration)}
+                        type={"button"}
+                        class={"btn btn-secondary"}
+                    >{i18n::text("dialog-cancel")}</button>
+                </div>
+            }
+        } else {
+            html! {}
+        };
+        let sort = self.meeting_sort;
+        let meetings = self
+            .meeting_page
+            .as_ref()
+            .map_or(&self.meetings, |page| &page.meetings);
+        let mut order: Vec<_> = (0..meetings.len()).collect();
+        order.sort_by(|&a, &b| {
+            let (a, b) = (&meetings[a], &meetings[b]);
+            sort.compare(
+                &self.collation,
+                (&a.meeting, a.score),
+                (&b.meeting, b.score),
+            )
+        });
+        let sort_options = MeetingSort::ALL
+            .into_iter()
+            .map(|s| {
+                html! {
+                    <option value={s.as_str()} selected={s == sort}>{meeting_sort_label(s)}</option>
+                }
+            })
+            .collect::<Html>();
+        let sort_html = html! {
+            <div class="mb-2">
+                <label for="meeting-sort">{i18n::text("meetings-sort")}</label>
+                <select
+                    id="meeting-sort"
+                    class="ms-2"
+                    onchange={ctx.link().callback(|e: Event| {
+                            let select = e.target_unchecked_into::<HtmlSelectElement>();
+                            let sort = MeetingSort::ALL
+                                .into_iter()
+                                .find(|s| s.as_str() == select.value())
+                                .unwrap_or_default();
+                            Msg::SortMeetings(sort)
+                    })}
+                >
+                    { sort_options }
+                </select>
+            </div>
+        };
+        let search_html = html! {
+            <div class="mb-2">
+                <label for="meeting-search">{i18n::text("meetings-search")}</label>
+                <input
+                    id="meeting-search"
+                    class="ms-2"
+                    type="search"
+                    placeholder={i18n::text("meetings-search-placeholder")}
+                    maxlength={MAX_TEXT_LEN.to_string()}
+                    value={self.meeting_search_text.clone()}
+                    oninput={ctx.link().callback(|e: InputEvent| {
+                            let input = e.target_unchecked_into::<HtmlInputElement>();
+                            Msg::UpdateMeetingSearchText(input.value())
+                    })}
+                />
+            </div>
+        };
+        let pager_html = if let Some(page) = &self.meeting_page {
+            let MeetingPage { offset, total, .. } = *page;
+            let shown = if meetings.is_empty() {
+                i18n::text_with("meetings-page-empty", &[("total", total.into())])
+            } else {
+                i18n::text_with(
+                    "meetings-page",
+                    &[
+                        ("first", (offset + 1).into()),
+                        ("last", (offset + meetings.len() as u32).into()),
+                        ("total", total.into()),
+                    ],
+                )
+            };
+            let previous = offset.saturating_sub(MEETINGS_PAGE_SIZE);
+            let next = offset + MEETINGS_PAGE_SIZE;
+            html! {
+                <div class="mb-2">
+                    <button
+                        onclick={ctx.link().callback(move |_| Msg::FetchMeetingPage(previous))}
+                        type={"button"}
+                        class={"btn btn-sm btn-secondary"}
+                        disabled={offset == 0}
+                    >{i18n::text("page-previous")}</button>
+                    <span class="mx-2">{shown}</span>
+                    <button
+                        onclick={ctx.link().callback(move |_| Msg::FetchMeetingPage(next))}
+                        type={"button"}
+                        class={"btn btn-sm btn-secondary"}
+                        disabled={next >= total}
+                    >{i18n::text("page-next")}</button>
+                </div>
+            }
+        } else {
+            html! {}
+        };
+        let join_code_error = if let Some(e) = &self.join_code_error {
+            html! { <div class="form-text text-danger">{e}</div> }
+        } else {
+            html! {}
+        };
+        let join_code_html = html! {
+            <div class="mb-2">
+                <label for="join-code">{i18n::text("join-code-label")}</label>
+                <input
+                    id="join-code"
+                    class="ms-2"
+                    placeholder="HALL-1234"
+                    autocomplete="off"
+                    value={self.join_code_text.clone()}
+                    onkeypress={ctx.link().batch_callback(|e: KeyboardEvent| {
+                        (e.key() == "Enter").then(|| Msg::JoinByCode)
+                    })}
+                    oninput={ctx.link().callback(|e: InputEvent| {
+                            let input = e.target_unchecked_into::<HtmlInputElement>();
+                            Msg::UpdateJoinCodeText(input.value())
+                    })}
+                />
+                <button
+                    onclick={ctx.link().callback(|_| Msg::JoinByCode)}
+                    type={"button"}
+                    class={"btn btn-sm btn-primary ms-2"}
+                >{i18n::text("join-code-join")}</button>
+                {join_code_error}
+            </div>
+        };
+        let registration_error = if let Some(e) = &self.registration_error {
+            html! { <div class="alert alert-warning">{e}</div> }
+        } else {
+            html! {}
+        };
+        let now = unix_now();
+        let submission_buttons = self
+            .meetings
+            .iter()
+            .filter(|sm| {
+                self.registered_meetings.contains(&sm.meeting.id)
+                    && sm.meeting.submissions_open(now)
+            })
+            .map(|sm| {
+                let id = sm.meeting.id;
+                let deadline = local_time(sm.meeting.submission_deadline.unwrap_or_default());
+                html! {
+                    <button
+                        onclick={ctx.link().callback(move |_| Msg::OpenSubmissions(id))}
+                        type={"button"}
+                        class={"btn btn-sm btn-outline-primary me-2 mb-2"}
+                    >{i18n::text_with(
+                        "submissions-open",
+                        &[("meeting", sm.meeting.name.as_str().into()), ("time", deadline.into())],
+                    )}</button>
+                }
+            })
+            .collect::<Html>();
+        let submissions_html = if let Some((meeting_id, topics)) = &self.submissions {
+            let meeting_name = self
+                .scored_meeting(*meeting_id)
+                .map_or("".to_owned(), |sm| sm.meeting.name.clone());
+            let onkeypress = ctx
+                .link()
+                .batch_callback(|e: KeyboardEvent| (e.key() == "Enter").then(|| Msg::SubmitTopic));
+            let error_html = if let Some(e) = &self.submission_error {
+                html! { <div class="form-text text-danger">{e}</div> }
+            } else {
+                html! {}
+            };
+            html! {
+                <div>
+                    <h4>{i18n::text_with("submissions-heading", &[("meeting", meeting_name.as_str().into())])}</h4>
+                    <div class="form-text">
+                        {i18n::text("submissions-help")}
+                    </div>
+                    <ranking::Ranking
+                        ids={topics.iter().map(|t| t.id).collect::<Vec<u32>>()}
+                        labels={topics.iter().map(|t| t.text.clone()).collect::<Vec<String>>()}
+                        scores={topics.iter().map(|t| t.score).collect::<Vec<u32>>()}
+                        store_scores={ctx.link().callback(Msg::StoreSubmissionScores)}
+                        links={Some(topics.iter().map(|t| t.links.clone()).collect::<Vec<Vec<String>>>())}
+                        descriptions={Some(topics.iter().map(|t| t.description.clone()).collect::<Vec<String>>())}
+                    />
+                    <label for="new-submission">{i18n::text("submissions-new-label")}</label>
+                    <input
+                        id="new-submission"
+                        type="text"
+                        maxlength={MAX_TEXT_LEN.to_string()}
+                        value={self.new_submission_text.clone()}
+                        { onkeypress }
+                        oninput={ctx.link().callback(|e: InputEvent| {
+                                let input = e.target_unchecked_into::<HtmlInputElement>();
+                                Msg::UpdateNewSubmissionText(input.value())
+                        })}
+                    />
+                    <button
+                        onclick={ctx.link().callback(|_| Msg::SubmitTopic)}
+                        type={"button"}
+                        class={"btn"}
+                    >{ add_icon() }</button>
+                    {error_html}
+                    <button
+                        onclick={ctx.link().callback(|_| Msg::CloseSubmissions)}
+                        type={"button"}
+                        class={"btn btn-secondary"}
+                    >{i18n::text("dialog-close")}</button>
                 </div>
             }
         } else {
             html! {}
         };
-        let mut meetings = self.meetings.clone();
-        meetings.sort_by(
-            |ScoredMeeting { score: a_score, .. }, ScoredMeeting { score: b_score, .. }| {
-                a_score.partial_cmp(b_score).unwrap()
-            },
-        );
         let meetings_html = {
             let ids = meetings.iter().map(|i| i.meeting.id).collect::<Vec<u32>>();
+            let now = unix_now();
             html! {
                 <ranking::Ranking
                     ids={ids.clone()}
                     labels={meetings.iter().map(|i| i.meeting.name.clone()).collect::<Vec<String>>()}
                     scores={meetings.iter().map(|i| i.score).collect::<Vec<u32>>()}
                     registered_counts={Some(meetings.iter().map(|i| i.meeting.n_registered).collect::<Vec<u32>>())}
+                    cohort_sizes={Some(meetings.iter().map(|i| i.meeting.cohort_size).collect::<Vec<u32>>())}
                     joined_counts={Some(meetings.iter().map(|i| i.meeting.n_joined).collect::<Vec<u32>>())}
-                    store_score={ctx.link().callback(Msg::StoreMeetingScore)}
+                    store_scores={ctx.link().callback(Msg::StoreMeetingScores)}
                     delete={Some(ctx.link().callback(Msg::DeleteMeeting))}
+                    deletable={Some(meetings.iter().map(|i| i.permissions.can_delete).collect::<Vec<bool>>())}
                     is_registered={Some(ids.iter().map(|id| self.registered_meetings.get(id).is_some()).collect::<Vec<bool>>())}
-                    attend_meeting={Some(ctx.link().callback(Msg::AttendMeeting))}
+                    attend_meeting={Some(ctx.link().callback(|id| {
+                        Msg::AttendMeeting(id, AttendeeRole::Participant)
+                    }))}
+                    observe_meeting={Some(ctx.link().callback(|id| {
+                        Msg::AttendMeeting(id, AttendeeRole::Observer)
+                    }))}
                     register_toggle={Some(ctx.link().callback(Msg::MeetingToggleRegistered))}
+                    registration_badges={Some(meetings.iter().map(|i| registration_badge(&i.meeting, now)).collect::<Vec<String>>())}
+                    registration_open={Some(meetings.iter().map(|i| i.meeting.registration_open(now)).collect::<Vec<bool>>())}
+                    order={(sort != MeetingSort::Ranking).then(|| order)}
                 />
             }
         };
         html! {
             <div>
                 {new_meeting}
+                {import_meeting}
+                {registration_form}
+                {registration_error}
+                {submission_buttons}
+                {submissions_html}
                 <hr/>
                 <div class="container">
+                    {join_code_html}
+                    {search_html}
+                    {sort_html}
                     {meetings_html}
+                    {pager_html}
                 </div>
             </div>
         }
     }
 
-    fn tabs_html(&self, ctx: &Context<Self>) -> Html {
-        let link_class = |tag| {
-            if self.active_tab == tag {
-                "nav-link active"
-            } else {
-                "nav-link"
+    // Topics can be linked from the Topics tab or, for seeds, the meeting lobby.
+    fn topic_links(&self, id: u32) -> Vec<String> {
+        self.user_topics
+            .iter()
+            .chain(self.meeting_topics.iter().flatten())
+            .find(|t| t.id == id)
+            .map_or(vec![], |t| t.links.clone())
+    }
+
+    fn save_topic_links(&mut self, ctx: &Context<Self>, id: u32, links: Vec<String>) {
+        if let Err(e) = validate_topic_links(&links) {
+            self.link_form_error = Some(e);
+            return;
+        }
+        self.link_form_error = None;
+        let topic_id = boxed::Box::new(id);
+        ctx.link().send_future(async move {
+            match store_topic_links(topic_id, links.clone()).await {
+                Ok(Ok(())) => Msg::SetTopicLinks(id, links),
+                Ok(Err(e)) => Msg::TopicLinksFailed(e),
+                Err(e) => Msg::LogError(e),
             }
+        });
+    }
+
+    fn link_form_html(&self, ctx: &Context<Self>) -> Html {
+        let (id, text) = match &self.link_form {
+            Some((id, text)) => (*id, text),
+            None => return html! {},
         };
-        // aria-current value
-        let ac = |tag| {
-            if self.active_tab == tag {
-                "page"
-            } else {
-                "false"
+        let topic_text = self
+            .user_topics
+            .iter()
+            .chain(self.meeting_topics.iter().flatten())
+            .find(|t| t.id == id)
+            .map_or("".to_owned(), |t| t.text.clone());
+        let links = self.topic_links(id);
+        let links_html = links.iter().enumerate().map(|(i, url)| {
+            html! {
+                <li>
+                    <a href={url.clone()} target="_blank" rel="noopener">{url}</a>
+                    <button
+                        onclick={ctx.link().callback(move |_| Msg::RemoveTopicLink(i))}
+                        type={"button"}
+                        class={"btn"}
+                    >{ x_icon() }</button>
+                </li>
             }
+        });
+        let error_html = if let Some(e) = &self.link_form_error {
+            html! { <div class="alert alert-danger">{e}</div> }
+        } else {
+            html! {}
         };
-        // https://getbootstrap.com/docs/5.0/components/navs-tabs/
+        let onkeypress = ctx
+            .link()
+            .batch_callback(|e: KeyboardEvent| (e.key() == "Enter").then(|| Msg::AddTopicLink));
         html! {
-            <ul class="nav nav-tabs">
-                <li class="nav-item">
-                    <a class={ link_class(Tab::TopicManagment) }
-                    aria-current={ac(Tab::TopicManagment)}
-                    href="#" onclick={ctx.link().callback(|_| Msg::SetTab(Tab::TopicManagment))}>{ "Topics" }</a>
-                </li>
-                <li class="nav-item">
-                    <a class={ link_class(Tab::MeetingManagement) }
-                    aria-current={ac(Tab::MeetingManagement)}
-                    href="#" onclick={ctx.link().callback(|_| Msg::SetTab(Tab::MeetingManagement))}>{ "Meetings" }</a>
-                </li>
-                <li class="nav-item">
-                    <a class={ link_class(Tab::MeetingPrep) }
-                    aria-current={ac(Tab::MeetingPrep)}
-                    href="#" onclick={ctx.link().callback(|_| Msg::SetTab(Tab::MeetingPrep))}>{ "Meet" }</a>
-                </li>
-            </ul>
+            <div class="container">
+                <h4>{i18n::text_with("links-heading", &[("topic", topic_text.as_str().into())])}</h4>
+                <ul>{for links_html}</ul>
+                <input
+                    type="url"
+                    placeholder="https://"
+                    value={text.clone()}
+                    { onkeypress }
+                    oninput={ctx.link().callback(|e: InputEvent| {
+                            let input = e.target_unchecked_into::<HtmlInputElement>();
+                            Msg::UpdateTopicLinkText(input.value())
+                    })}
+                />
+                <button
+                    onclick={ctx.link().callback(|_| Msg::AddTopicLink)}
+                    type={"button"}
+                    class={"btn"}
+                    disabled={links.len() >= MAX_TOPIC_LINKS}
+                >{ add_icon() }</button>
+                <button
+                    onclick={ctx.link().callback(move |_| Msg::EditTopicLinks(id))}
+                    type={"button"}
+                    class={"btn btn-secondary"}
+                >{i18n::text("links-done")}</button>
+                {error_html}
+            </div>
         }
     }
-}
 
-impl Component for Model {
-    type Message = Msg;
-    type Properties = ();
+    fn description_form_html(&self, ctx: &Context<Self>) -> Html {
+        let (id, text) = match &self.description_form {
+            Some((id, text)) => (*id, text),
+            None => return html! {},
+        };
+        let topic_text = self
+            .user_topics
+            .iter()
+            .find(|t| t.id == id)
+            .map_or("".to_owned(), |t| t.text.clone());
+        let error_html = if let Some(e) = &self.description_form_error {
+            html! { <div class="alert alert-danger">{e}</div> }
+        } else {
+            html! {}
+        };
+        // The newest version is the one being edited.
+        let edits_html =
+            if self.description_edits.len() < 2 {
+                html! {}
+            } else {
+                let versions_html = self.description_edits.iter().skip(1).map(|edit| {
+                let when = match edit.edited_at {
+                    Some(t) => i18n::text_with(
+                        "description-version",
+                        &[("version", edit.version.into()), ("time", local_time(t).into())],
+                    ),
+                    None => i18n::text("description-first-version"),
+                };
+                let text_html = if edit.description.is_empty() {
+                    html! { <div class="small text-muted">{i18n::text("description-none")}</div> }
+                } else {
+                    html! { <div class="small">{markdown::markdown_html(&edit.description)}</div> }
+                };
+                html! {
+                    <li class="list-group-item">
+                        <div class="small text-muted">{when}</div>
+                        {text_html}
+                    </li>
+                }
+            });
+                html! {
+                    <details>
+                        <summary>{i18n::text("description-versions")}</summary>
+                        <div class="form-text">
+                            {i18n::text("description-versions-help")}
+                        </div>
+                        <ul class="list-group">{for versions_html}</ul>
+                    </details>
+                }
+            };
+        html! {
+            <div class="container">
+                <label for="topic-description">
+                    <h4>{i18n::text_with("description-heading", &[("topic", topic_text.as_str().into())])}</h4>
+                </label>
+                <textarea
+                    id="topic-description"
+                    class="form-control"
+                    rows="6"
+                    maxlength={MAX_DESCRIPTION_LEN.to_string()}
+                    value={text.clone()}
+                    oninput={ctx.link().callback(|e: InputEvent| {
+                            let input = e.target_unchecked_into::<HtmlTextAreaElement>();
+                            Msg::UpdateTopicDescriptionText(input.value())
+                    })}
+                />
+                <div class="form-text">
+                    {i18n::text("description-help")}
+                </div>
+                <button
+                    onclick={ctx.link().callback(|_| Msg::SaveTopicDescription)}
+                    type={"button"}
+                    class={"btn btn-primary"}
+                >{i18n::text("save")}</button>
+                <button
+                    onclick={ctx.link().callback(move |_| Msg::EditTopicDescription(id))}
+                    type={"button"}
+                    class={"btn btn-secondary"}
+                >{i18n::text("cancel")}</button>
+                {error_html}
+                {edits_html}
+            </div>
+        }
+    }
 
-    fn create(ctx: &Context<Self>) -> Self {
-        let mut model = Self {
-            attending_meeting: None,
-            election_results: None,
-            registered_meetings: HashSet::new(),
-            meeting_topics: None,
-            meetings: vec![],
-            new_meeting_text: "".to_owned(),
-            new_topic_text: "".to_owned(),
-            user_id: UserIdState::New,
-            user_topics: vec![],
-            active_tab: Tab::TopicManagment,
-            meeting_poll: None,
-            vote_poll: None,
+    fn poll(&self, ctx: &Context<Self>, msg: fn() -> Msg) -> Interval {
+        let link = ctx.link().clone();
+        Interval::new(self.poll_ms, move || link.send_message(msg()))
+    }
+
+    // Under load the API asks for longer intervals, and restarts the polls.
+    fn follow_poll_hint(&mut self, ctx: &Context<Self>) {
+        let poll_ms = client::poll_hint_ms()
+            .unwrap_or(CHECK_ELECTION_MS)
+            .max(CHECK_ELECTION_MS);
+        if poll_ms == self.poll_ms {
+            return;
+        }
+        self.poll_ms = poll_ms;
+        if self.meeting_poll.is_some() {
+            self.meeting_poll = Some(self.poll(ctx, || Msg::CheckMeetings));
+        }
+        if self.vote_poll.is_some() {
+            self.vote_poll = Some(self.poll(ctx, || Msg::CheckElection));
+        }
+    }
+
+    fn focus_new_topic(&self) {
+        if let Some(input) = self.new_topic_input.cast::<HtmlInputElement>() {
+            if let Err(e) = input.focus() {
+                console_dbg!(e);
+            }
+        }
+    }
+
+    fn bulk_topics_html(&self, ctx: &Context<Self>) -> Html {
+        let text = match &self.bulk_topics {
+            Some(text) => text,
+            None => return html! {},
         };
-        model.fetch_user("create", ctx);
-        model
+        let topics = bulk_topic_lines(text);
+        let n_topics = topics.iter().filter(|topic| fits_topic(topic)).count();
+        let preview = topics
+            .iter()
+            .map(|topic| {
+                if fits_topic(topic) {
+                    html! { <li>{topic}</li> }
+                } else {
+                    html! { <li class="text-danger">{i18n::text_with("bulk-too-long", &[("topic", topic.as_str().into())])}</li> }
+                }
+            })
+            .collect::<Html>();
+        html! {
+            <div class="container">
+                <label for="bulk-topics">{i18n::text("bulk-label")}</label>
+                <textarea
+                    id="bulk-topics"
+                    class="form-control"
+                    rows="6"
+                    value={text.clone()}
+                    oninput={ctx.link().callback(|e: InputEvent| {
+                            let input = e.target_unchecked_into::<HtmlTextAreaElement>();
+                            Msg::UpdateBulkTopicsText(input.value())
+                    })}
+                />
+                <ul class="text-start">{preview}</ul>
+                <button
+                    onclick={ctx.link().callback(|_| Msg::AddBulkTopics)}
+                    type={"button"}
+                    class={"btn btn-primary"}
+                    disabled={n_topics == 0}
+                >{i18n::text_with("bulk-add", &[("count", n_topics.into())])}</button>
+                <button
+                    onclick={ctx.link().callback(|_| Msg::ToggleBulkTopics)}
+                    type={"button"}
+                    class={"btn btn-secondary"}
+                >{i18n::text("cancel")}</button>
+                <hr/>
+            </div>
+        }
     }
 
-    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
-        if self.user_id.is_new() {
-            self.fetch_user("update", ctx);
+    // Shown so reports from self-hosted instances can name their builds
+    fn build_footer_html(&self) -> Html {
+        let ui = i18n::text_with("build-ui", &[("build", ui_build().summary().into())]);
+        let builds = match &self.server_build {
+            Some(build) => {
+                let server = i18n::text_with("build-server", &[("build", build.summary().into())]);
+                format!("{ui} · {server}")
+            }
+            None => ui,
+        };
+        html! {
+            <footer class="container text-muted small mt-4">
+                { builds }
+            </footer>
         }
-        match msg {
-            Msg::AddedMeeting => {
-                self.new_meeting_text = "".to_owned();
-                ctx.link().send_future(async {
-                    match fetch_meetings().await {
-                        Ok(meetings) => Msg::SetMeetings(meetings),
-                        Err(e) => Msg::LogError(e),
-                    }
-                });
-                true
+    }
+
+    fn preferences_html(&self, ctx: &Context<Self>) -> Html {
+        let rows = NotificationEvent::ALL.into_iter().map(|event| {
+            let cells = NotificationChannel::ALL.into_iter().map(|channel| {
+                let enabled = self
+                    .notification_preferences
+                    .iter()
+                    .any(|p| p.event == event && p.channel == channel && p.enabled);
+                html! {
+                    <td>
+                        <input
+                            class="form-check-input"
+                            type="checkbox"
+                            aria-label={i18n::text_with(
+                                "notify-by",
+                                &[
+                                    ("event", notification_event_label(event).into()),
+                                    ("channel", notification_channel_label(channel).into()),
+                                ],
+                            )}
+                            checked={enabled}
+                            onclick={ctx.link().callback(move |_| Msg::ToggleNotificationPreference(event, channel))}
+                        />
+                    </td>
+                }
+            });
+            html! {
+                <tr>
+                    <th scope="row">{ notification_event_label(event) }</th>
+                    { for cells }
+                </tr>
             }
-            Msg::AddedTopic => {
-                self.new_topic_text = "".to_owned();
-                ctx.link().send_message(Msg::FetchUserTopics);
-                true
+        });
+        html! {
+            <div class="container">
+                <h3>{ i18n::text("notifications") }</h3>
+                <table class="table">
+                    <thead>
+                        <tr>
+                            <th scope="col">{ i18n::text("notify-when") }</th>
+                            { for NotificationChannel::ALL.into_iter().map(|c| html! {
+                                <th scope="col">{ notification_channel_label(c) }</th>
+                            }) }
+                        </tr>
+                    </thead>
+                    <tbody>
+                        { for rows }
+                    </tbody>
+                </table>
+                { self.locale_html(ctx) }
+                { self.language_html(ctx) }
+                { self.research_html(ctx) }
+                { self.avatar_html(ctx) }
+                { self.membership_html(ctx) }
+                { self.faults_html(ctx) }
+            </div>
+        }
+    }
+
+    // Kept in the browser, unlike the conversation language below
+    fn locale_html(&self, ctx: &Context<Self>) -> Html {
+        let options = i18n::LOCALES.into_iter().map(|(tag, name)| {
+            html! {
+                <option value={tag} selected={tag == i18n::locale()}>{name}</option>
             }
-            Msg::AddMeeting => {
-                let meeting_name = self.new_meeting_text.clone();
-                ctx.link().send_future(async {
-                    match add_new_meeting(meeting_name).await {
-                        Ok(resp) => {
-                            if resp.status() == 200 {
-                                Msg::AddedMeeting
+        });
+        html! {
+            <>
+                <h3>{ i18n::text("display-language") }</h3>
+                <div class="mb-3">
+                    <select
+                        id="display-language"
+                        aria-label={i18n::text("display-language")}
+                        onchange={ctx.link().callback(|e: Event| {
+                                let select = e.target_unchecked_into::<HtmlSelectElement>();
+                                Msg::SetLocale(select.value())
+                        })}
+                    >
+                        { for options }
+                    </select>
+                    <div class="form-text">{ i18n::text("display-language-help") }</div>
+                </div>
+            </>
+        }
+    }
+
+    // Meetings that match cohorts by language put those who share one together.
+    fn language_html(&self, ctx: &Context<Self>) -> Html {
+        let error_html = if let Some(e) = &self.language_error {
+            html! { <div class="form-text text-danger">{e}</div> }
+        } else {
+            html! {}
+        };
+        html! {
+            <>
+                <h3>{ i18n::text("language-heading") }</h3>
+                <div class="mb-3">
+                    <label for="language">{ i18n::text("language-label") }</label>
+                    <input
+                        id="language"
+                        class="ms-2"
+                        placeholder="en, de, pt-BR"
+                        autocomplete="off"
+                        value={self.language_text.clone()}
+                        onkeypress={ctx.link().batch_callback(|e: KeyboardEvent| {
+                            (e.key() == "Enter").then(|| Msg::StoreLanguage)
+                        })}
+                        oninput={ctx.link().callback(|e: InputEvent| {
+                                let input = e.target_unchecked_into::<HtmlInputElement>();
+                                Msg::UpdateLanguageText(input.value())
+                        })}
+                    />
+                    <button
+                        onclick={ctx.link().callback(|_| Msg::StoreLanguage)}
+                        type={"button"}
+                        class={"btn btn-sm btn-primary ms-2"}
+                    >{i18n::text("save")}</button>
+                    <div class="form-text">
+                        { i18n::text("language-help") }
+                    </div>
+                    {error_html}
+                </div>
+            </>
+        }
+    }
+
+    // Only offered when the API has a [research] table
+    fn research_html(&self, ctx: &Context<Self>) -> Html {
+        if !self.research {
+            return html! {};
+        }
+        html! {
+            <>
+                <h3>{ i18n::text("research-heading") }</h3>
+                <div class="form-check mb-3">
+                    <input
+                        class="form-check-input"
+                        type="checkbox"
+                        id="research-consent"
+                        checked={self.research_consent}
+                        onclick={ctx.link().callback(|_| Msg::ToggleResearchConsent)}
+                    />
+                    <label class="form-check-label" for="research-consent">
+                        { i18n::text("research-consent") }
+                    </label>
+                    <div class="form-text">
+                        { i18n::text("research-help") }
+                    </div>
+                </div>
+            </>
+        }
+    }
+
+    // Only offered when the API has an [avatars] table.
+    // Without uploads, a Gravatar is shown but can only be changed at gravatar.com.
+    fn avatar_html(&self, ctx: &Context<Self>) -> Html {
+        let AvatarMessage { url, uploads } = &self.avatar;
+        if url.is_none() && !uploads {
+            return html! {};
+        }
+        let image_html = match url {
+            Some(url) => html! {
+                <img src={url.clone()} alt={i18n::text("avatar-alt")} width="64" height="64" class="rounded-circle me-2"/>
+            },
+            None => html! {},
+        };
+        let upload_html = if *uploads {
+            html! {
+                <>
+                    <input
+                        id="avatar"
+                        type="file"
+                        class="form-control form-control-sm d-inline-block w-auto"
+                        accept={AVATAR_CONTENT_TYPES.join(",")}
+                        onchange={ctx.link().batch_callback(|e: Event| {
+                            let input = e.target_unchecked_into::<HtmlInputElement>();
+                            let file = input.files().and_then(|files| files.get(0));
+                            input.set_value("");
+                            file.map(Msg::UploadAvatar)
+                        })}
+                    />
+                    <button
+                        onclick={ctx.link().callback(|_| Msg::DeleteAvatar)}
+                        type={"button"}
+                        class={"btn btn-sm btn-outline-secondary ms-2"}
+                    >{i18n::text("avatar-remove")}</button>
+                    <div class="form-text">
+                        { i18n::text_with("avatar-upload-help", &[("kib", (MAX_AVATAR_BYTES / 1024).into())]) }
+                    </div>
+                </>
+            }
+        } else {
+            html! {
+                <div class="form-text">{ i18n::text("avatar-gravatar-help") }</div>
+            }
+        };
+        let error_html = if let Some(e) = &self.avatar_error {
+            html! { <div class="form-text text-danger">{e}</div> }
+        } else {
+            html! {}
+        };
+        html! {
+            <>
+                <h3>{ i18n::text("avatar-heading") }</h3>
+                <div class="mb-3">
+                    { image_html }
+                    { upload_html }
+                    { error_html }
+                </div>
+            </>
+        }
+    }
+
+    // Only for administrators, who invite people and approve signups
+    // when the API's [membership] table asks for approval
+    fn membership_html(&self, ctx: &Context<Self>) -> Html {
+        if !self.is_admin {
+            return html! {};
+        }
+        let requests_html = if self.join_requests.is_empty() {
+            html! { <p class="form-text">{ i18n::text("join-requests-none") }</p> }
+        } else {
+            let rows = self.join_requests.iter().map(|request| {
+                let (approve, decline) = (request.email.clone(), request.email.clone());
+                html! {
+                    <li class="mb-1">
+                        { i18n::text_with(
+                            "join-request",
+                            &[
+                                ("email", request.email.as_str().into()),
+                                ("time", local_time(request.requested_at).into()),
+                            ],
+                        ) }
+                        { " " }
+                        <button
+                            onclick={ctx.link().callback(move |_| Msg::DecideJoinRequest(approve.clone(), true))}
+                            type={"button"}
+                            class={"btn btn-sm btn-outline-primary"}
+                        >{i18n::text("join-request-approve")}</button>
+                        <button
+                            onclick={ctx.link().callback(move |_| Msg::DecideJoinRequest(decline.clone(), false))}
+                            type={"button"}
+                            class={"btn btn-sm btn-outline-danger ms-1"}
+                        >{i18n::text("join-request-decline")}</button>
+                    </li>
+                }
+            });
+            html! { <ul>{ for rows }</ul> }
+        };
+        let invitations = self.invitations.iter().map(|invitation| {
+            let revoke = invitation.email.clone();
+            html! {
+                <li>
+                    { i18n::text_with(
+                        "invitation",
+                        &[
+                            ("email", invitation.email.as_str().into()),
+                            ("time", local_time(invitation.expires_at).into()),
+                        ],
+                    ) }
+                    { " " }
+                    <a href={invitation.url.clone()}>{i18n::text("invitation-link")}</a>
+                    <button
+                        onclick={ctx.link().callback(move |_| Msg::RevokeInvitation(revoke.clone()))}
+                        type={"button"}
+                        class={"btn btn-sm"}
+                        aria-label={i18n::text("invitation-revoke")}
+                    >{ x_icon() }</button>
+                </li>
+            }
+        });
+        let onkeypress = ctx.link().batch_callback(move |e: KeyboardEvent| {
+            (e.key() == "Enter").then(|| Msg::InviteMember)
+        });
+        html! {
+            <>
+                <h3>{ i18n::text("join-requests-heading") }</h3>
+                { requests_html }
+                <label for="invite-member">{ i18n::text("invite-label") }</label>
+                <div class="mb-3">
+                    <input
+                        id="invite-member"
+                        type="email"
+                        value={self.invite_text.clone()}
+                        { onkeypress }
+                        oninput={ctx.link().callback(|e: InputEvent| {
+                                let input = e.target_unchecked_into::<HtmlInputElement>();
+                                Msg::UpdateInviteText(input.value())
+                        })}
+                    />
+                    <button
+                        onclick={ctx.link().callback(|_| Msg::InviteMember)}
+                        type={"button"}
+                        class={"btn btn-secondary"}
+                        disabled={self.invite_text.trim().is_empty()}
+                    >{i18n::text("invite")}</button>
+                    <div class="form-text text-danger">
+                        {self.invite_error.clone().unwrap_or_default()}
+                    </div>
+                    <ul>{ for invitations }</ul>
+                </div>
+            </>
+        }
+    }
+
+    // Only offered by a development API with a [faults] table
+    fn faults_html(&self, ctx: &Context<Self>) -> Html {
+        if !self.fault_injection {
+            return html! {};
+        }
+        html! {
+            <>
+                <h3>{ i18n::text("development-heading") }</h3>
+                <div class="form-check">
+                    <input
+                        class="form-check-input"
+                        type="checkbox"
+                        id="fault-injection"
+                        checked={client::faults_enabled()}
+                        onclick={ctx.link().callback(|_| Msg::ToggleFaults)}
+                    />
+                    <label class="form-check-label" for="fault-injection">
+                        { i18n::text("development-faults") }
+                    </label>
+                </div>
+            </>
+        }
+    }
+
+    // https://getbootstrap.com/docs/5.0/components/navs-tabs/
+    // and https://www.w3.org/WAI/ARIA/apg/patterns/tabs/
+    // Only the selected tab is in the Tab key order; the arrow keys move between tabs.
+    fn tabs_html(&self, ctx: &Context<Self>) -> Html {
+        let n = Tab::ALL.len();
+        let tabs = Tab::ALL.iter().enumerate().map(|(i, tab)| {
+            let selected = self.active_tab == *tab;
+            let onkeydown = ctx.link().batch_callback(move |e: KeyboardEvent| {
+                let next = match e.key().as_str() {
+                    "ArrowRight" => (i + 1) % n,
+                    "ArrowLeft" => (i + n - 1) % n,
+                    "Home" => 0,
+                    "End" => n - 1,
+                    _ => return None,
+                };
+                e.prevent_default();
+                Some(Msg::FocusTab(Tab::ALL[next].clone()))
+            });
+            let clicked = tab.clone();
+            html! {
+                <li class="nav-item" role="presentation">
+                    <button
+                        ref={if selected { self.active_tab_ref.clone() } else { NodeRef::default() }}
+                        class={if selected { "nav-link active" } else { "nav-link" }}
+                        id={tab.tab_id()}
+                        type="button"
+                        role="tab"
+                        aria-selected={if selected { "true" } else { "false" }}
+                        aria-controls={tab.panel_id()}
+                        tabindex={if selected { "0" } else { "-1" }}
+                        onclick={ctx.link().callback(move |_| Msg::SetTab(clicked.clone()))}
+                        { onkeydown }
+                    >{ tab.label() }</button>
+                </li>
+            }
+        });
+        html! {
+            <ul class="nav nav-tabs" role="tablist">
+                { for tabs }
+            </ul>
+        }
+    }
+
+    fn focus_tab_change(&mut self) {
+        let target = match self.tab_focus.take() {
+            Some(TabFocus::Tab) => &self.active_tab_ref,
+            Some(TabFocus::Panel) => {
+                // A clicked tab keeps focus, as the keyboard would leave it there.
+                let on_tab = gloo_utils::document()
+                    .active_element()
+                    .and_then(|e| e.get_attribute("role"))
+                    .is_some_and(|role| role == "tab");
+                if on_tab {
+                    return;
+                }
+                &self.tab_panel_ref
+            }
+            None => return,
+        };
+        if let Some(element) = target.cast::<web_sys::HtmlElement>() {
+            if let Err(e) = element.focus() {
+                console_dbg!(e);
+            }
+        }
+    }
+}
+
+impl Component for Model {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let mut model = Self {
+            attending_meeting: None,
+            bulk_topics: None,
+            candidate_topics: None,
+            collation: Collation::new(gloo_utils::window().navigator().language().as_deref()),
+            contact_sharing_error: None,
+            election_results: None,
+            import_meeting: None,
+            import_meeting_error: None,
+            invitations: vec![],
+            invite_error: None,
+            invite_text: "".to_owned(),
+            is_admin: false,
+            link_form: None,
+            link_form_error: None,
+            description_form: None,
+            description_form_error: None,
+            description_edits: vec![],
+            fault_injection: false,
+            registered_meetings: HashSet::new(),
+            meeting_topics: None,
+            notification_preferences: vec![],
+            meetings: vec![],
+            meeting_page: None,
+            unlisted_meeting: None,
+            meeting_search_text: "".to_owned(),
+            meeting_sort: MeetingSort::default(),
+            meeting_templates: vec![],
+            meeting_organizers: None,
+            meeting_organizers_error: None,
+            merge_selection: vec![],
+            merge_topics_error: None,
+            new_meeting_cohort_size: COHORT_QUORUM as u32,
+            new_meeting_match_language: false,
+            new_meeting_n_winners: DEFAULT_N_WINNERS,
+            new_meeting_share_contacts: false,
+            new_meeting_topics_per_attendee: DEFAULT_TOPICS_PER_ATTENDEE,
+            new_meeting_template: None,
+            new_meeting_text: "".to_owned(),
+            new_meeting_veto_policy: VetoPolicy::default(),
+            new_meeting_voting_method: VotingMethod::default(),
+            new_organizer_text: "".to_owned(),
+            join_code_text: "".to_owned(),
+            join_code_error: None,
+            join_requests: vec![],
+            language_text: "".to_owned(),
+            language_error: None,
+            research: false,
+            research_consent: false,
+            avatar: AvatarMessage::default(),
+            avatar_error: None,
+            new_registration_field_text: "".to_owned(),
+            new_seed_text: "".to_owned(),
+            new_submission_text: "".to_owned(),
+            new_topic_input: NodeRef::default(),
+            new_topic_text: "".to_owned(),
+            outcome_error: None,
+            outcome_form: None,
+            registration_deadline_text: "".to_owned(),
+            rename_meeting_error: None,
+            rename_meeting_text: "".to_owned(),
+            registration_error: None,
+            registration_form: None,
+            room_joins: None,
+            meeting_history: None,
+            next_round_error: None,
+            start_meeting_error: None,
+            start_confirm: None,
+            submission_deadline_text: "".to_owned(),
+            submissions: None,
+            submission_error: None,
+            topic_history: HashMap::new(),
+            topic_timer: None,
+            topic_timer_tick: None,
+            voting_minutes_text: "".to_owned(),
+            voting_tick: None,
+            user_id: UserIdState::New,
+            user_topics: vec![],
+            my_topics: vec![],
+            my_topics_error: None,
+            veto_error: None,
+            vote_error: None,
+            active_tab: Tab::TopicManagment,
+            active_tab_ref: NodeRef::default(),
+            tab_panel_ref: NodeRef::default(),
+            tab_focus: None,
+            meeting_poll: None,
+            vote_poll: None,
+            election_wait: false,
+            poll_ms: CHECK_ELECTION_MS,
+            offline_probe: None,
+            sync_listeners: vec![],
+            stale_bundle: false,
+            server_build: None,
+        };
+        if !no_user() {
+            model.sync_listeners = add_sync_listeners(ctx);
+            ctx.link().send_message(Msg::Sync);
+        }
+        model.fetch_user("create", ctx);
+        ctx.link().send_message(Msg::CheckInstance);
+        model
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        if self.user_id.is_new() {
+            self.fetch_user("update", ctx);
+        }
+        match msg {
+            Msg::AddTopicLink => {
+                if let Some((id, text)) = &self.link_form {
+                    let id = *id;
+                    let mut links = self.topic_links(id);
+                    links.push(text.trim().to_owned());
+                    self.save_topic_links(ctx, id, links);
+                }
+                true
+            }
+            Msg::AddedMeeting => {
+                self.new_meeting_text = "".to_owned();
+                self.new_meeting_template = None;
+                self.new_meeting_cohort_size = COHORT_QUORUM as u32;
+                self.new_meeting_match_language = false;
+                self.new_meeting_n_winners = DEFAULT_N_WINNERS;
+                self.new_meeting_share_contacts = false;
+                self.new_meeting_topics_per_attendee = DEFAULT_TOPICS_PER_ATTENDEE;
+                self.new_meeting_veto_policy = VetoPolicy::default();
+                self.new_meeting_voting_method = VotingMethod::default();
+                ctx.link().send_future(async {
+                    match fetch_meetings().await {
+                        Ok((meetings, sort)) => Msg::SetMeetings(meetings, sort),
+                        Err(e) => Msg::LogError(e),
+                    }
+                });
+                true
+            }
+            Msg::AddedTopic => {
+                ctx.link().send_message(Msg::FetchUserTopics);
+                self.focus_new_topic();
+                true
+            }
+            Msg::AddBulkTopics => {
+                let topics: Vec<String> =
+                    bulk_topic_lines(&self.bulk_topics.take().unwrap_or_default())
+                        .into_iter()
+                        .filter(|topic| fits_topic(topic))
+                        .collect();
+                ctx.link().send_future(async {
+                    for topic in topics {
+                        match add_new_topic(topic).await {
+                            Ok(resp) if resp.status() == 200 => {}
+                            Ok(resp) => return Msg::LogError(error_from_response(resp)),
+                            Err(e) => return Msg::LogError(e),
+                        }
+                    }
+                    Msg::AddedTopic
+                });
+                true
+            }
+            Msg::AddOrganizer => {
+                if let Some(meeting_id) = self.attending_meeting {
+                    let meeting_id = boxed::Box::new(meeting_id);
+                    let email = self.new_organizer_text.trim().to_owned();
+                    ctx.link().send_future(async {
+                        organizers_msg(add_meeting_organizer(meeting_id, email).await)
+                    });
+                }
+                true
+            }
+            Msg::AddMeeting => {
+                let meeting_name = self.new_meeting_text.clone();
+                let template = self
+                    .new_meeting_template
+                    .and_then(|i| self.meeting_templates.get(i));
+                if let Some(template) = template {
+                    let name = if meeting_name.trim().is_empty() {
+                        template.title.clone()
+                    } else {
+                        meeting_name
+                    };
+                    let mut config = template.config(&name);
+                    config.cohort_size = self.new_meeting_cohort_size;
+                    config.n_winners = self.new_meeting_n_winners;
+                    config.topics_per_attendee = self.new_meeting_topics_per_attendee;
+                    config.veto_policy = self.new_meeting_veto_policy;
+                    config.voting_method = self.new_meeting_voting_method;
+                    config.match_language = self.new_meeting_match_language;
+                    config.share_contacts = self.new_meeting_share_contacts;
+                    ctx.link().send_future(async {
+                        match import_meeting_config(config).await {
+                            Ok(()) => Msg::AddedMeeting,
+                            Err(e) => Msg::LogError(e),
+                        }
+                    });
+                    return true;
+                }
+                let new_meeting = NewMeeting {
+                    name: Cow::from(meeting_name),
+                    cohort_size: self.new_meeting_cohort_size,
+                    veto_policy: self.new_meeting_veto_policy,
+                    voting_method: self.new_meeting_voting_method,
+                    n_winners: self.new_meeting_n_winners,
+                    match_language: self.new_meeting_match_language,
+                    topics_per_attendee: self.new_meeting_topics_per_attendee,
+                    share_contacts: self.new_meeting_share_contacts,
+                };
+                ctx.link().send_future(async move {
+                    match add_new_meeting(new_meeting).await {
+                        Ok(resp) => {
+                            if resp.status() == 200 {
+                                Msg::AddedMeeting
                             } else {
                                 Msg::LogError(error_from_response(resp))
                             }
@@ -708,31 +4094,93 @@ impl Component for Model {
                 });
                 true
             }
+            Msg::AddSeedTopic => {
+                if let Some(meeting_id) = self.attending_meeting {
+                    let meeting_id = boxed::Box::new(meeting_id);
+                    let topics = vec![self.new_seed_text.clone()];
+                    ctx.link().send_future(async {
+                        match seed_meeting_topics(meeting_id.clone(), topics).await {
+                            Ok(resp) => {
+                                if resp.status() == 200 {
+                                    Msg::AddedSeedTopic(meeting_id)
+                                } else {
+                                    Msg::LogError(error_from_response(resp))
+                                }
+                            }
+                            Err(e) => Msg::LogError(e),
+                        }
+                    });
+                }
+                true
+            }
+            Msg::AddRegistrationField => {
+                if let Some(meeting_id) = self.attending_meeting {
+                    let meeting_id = boxed::Box::new(meeting_id);
+                    let labels = vec![self.new_registration_field_text.clone()];
+                    ctx.link().send_future(async {
+                        match add_registration_field(meeting_id, labels).await {
+                            Ok(resp) => {
+                                if resp.status() == 200 {
+                                    Msg::AddedRegistrationField
+                                } else {
+                                    Msg::LogError(error_from_response(resp))
+                                }
+                            }
+                            Err(e) => Msg::LogError(e),
+                        }
+                    });
+                }
+                true
+            }
+            Msg::AddedRegistrationField => {
+                self.new_registration_field_text = "".to_owned();
+                ctx.link().send_message(Msg::CheckMeetings);
+                true
+            }
+            Msg::AddedSeedTopic(meeting_id) => {
+                self.new_seed_text = "".to_owned();
+                ctx.link()
+                    .send_message(Msg::FetchMeetingTopics(*meeting_id));
+                true
+            }
             Msg::AddTopic => {
-                let topic_text = self.new_topic_text.clone();
+                // Cleared now rather than on success, so typing can go on meanwhile.
+                let topic_text = std::mem::take(&mut self.new_topic_text);
+                self.focus_new_topic();
+                if topic_text.trim().is_empty() {
+                    return false;
+                }
                 ctx.link().send_future(async {
-                    match add_new_topic(topic_text).await {
+                    match add_new_topic(topic_text.clone()).await {
                         Ok(resp) => {
                             if resp.status() == 200 {
                                 Msg::AddedTopic
                             } else {
-                                Msg::LogError(error_from_response(resp))
+                                Msg::AddTopicFailed(topic_text, error_from_response(resp))
                             }
                         }
-                        Err(e) => Msg::LogError(e),
+                        Err(e) => Msg::AddTopicFailed(topic_text, e),
                     }
                 });
                 true
             }
+            Msg::AddTopicFailed(topic_text, e) => {
+                if self.new_topic_text.is_empty() {
+                    self.new_topic_text = topic_text;
+                }
+                ctx.link().send_message(Msg::LogError(e));
+                true
+            }
             Msg::AttendingMeeting(id) => {
                 self.attending_meeting = Some(*id);
+                self.fetch_unlisted_meeting(ctx);
                 ctx.link().send_message(Msg::SetTab(Tab::MeetingPrep));
                 true
             }
-            Msg::AttendMeeting(id) => {
+            Msg::AttendMeeting(id, role) => {
                 let id = boxed::Box::new(id);
-                ctx.link().send_future(async {
-                    match attend_meeting(id.clone()).await {
+                ctx.link().send_future(async move {
+                    match attend_meeting(id.clone(), role).await {
                         Ok(_) => Msg::AttendingMeeting(id),
                         Err(e) => Msg::LogError(e),
                     }
@@ -746,7 +4194,7 @@ impl Component for Model {
                     let meeting_id = boxed::Box::new(self.attending_meeting.unwrap());
                     ctx.link().send_future(async {
                         let m_id = *meeting_id;
-                        match fetch_election_status(meeting_id).await {
+                        match fetch_election_status(meeting_id, None).await {
                             Ok(msg) => {
                                 if msg.meeting_id == m_id {
                                     Msg::SetElectionResults(msg)
@@ -761,286 +4209,1602 @@ impl Component for Model {
                     true
                 }
             }
-            Msg::CheckMeetings => {
-                match self.active_tab {
-                    Tab::MeetingManagement | Tab::MeetingPrep => {
-                        ctx.link().send_future(async {
-                            match fetch_meetings().await {
-                                Ok(meetings) => Msg::SetMeetings(meetings),
-                                Err(e) => Msg::LogError(e),
-                            }
-                        });
+            Msg::FlushQueued => {
+                ctx.link().send_future(async {
+                    match client::flush_queued().await {
+                        // Whatever else failed while offline was dropped, so catch up.
+                        Ok(()) => Msg::Sync,
+                        Err(e) => Msg::LogError(e),
+                    }
+                });
+                false
+            }
+            Msg::CheckInstance => {
+                ctx.link().send_future(async {
+                    match fetch_instance_info().await {
+                        Ok(info) => Msg::SetInstanceInfo(info),
+                        Err(e) => Msg::LogError(e),
+                    }
+                });
+                false
+            }
+            Msg::CloseSubmissions => {
+                self.submissions = None;
+                self.submission_error = None;
+                true
+            }
+            Msg::CheckMeetings => {
+                match self.active_tab {
+                    Tab::MeetingManagement | Tab::MeetingPrep => {
+                        ctx.link().send_future(async {
+                            match fetch_meetings().await {
+                                Ok((meetings, sort)) => Msg::SetMeetings(meetings, sort),
+                                Err(e) => Msg::LogError(e),
+                            }
+                        });
+                    }
+                    _ => self.meeting_poll = None,
+                }
+                true
+            }
+            Msg::CancelOutcome => {
+                self.outcome_form = None;
+                self.outcome_error = None;
+                true
+            }
+            Msg::CancelRegistration => {
+                self.registration_form = None;
+                true
+            }
+            Msg::CommitVote => {
+                if let Some(meeting_id) = self.attending_meeting {
+                    let meeting_id = boxed::Box::new(meeting_id);
+                    ctx.link().send_future(async {
+                        match commit_vote(meeting_id).await {
+                            Ok(()) => Msg::DidFinishVoting,
+                            Err(e) => Msg::LogError(e),
+                        }
+                    });
+                    true
+                } else {
+                    false
+                }
+            }
+            Msg::DeleteMeeting(id) => {
+                let id = boxed::Box::new(id);
+                ctx.link().send_future(async {
+                    match delete_meeting(id).await {
+                        Ok(_) => Msg::AddedMeeting,
+                        Err(e) => Msg::LogError(e),
+                    }
+                });
+                true
+            }
+            Msg::DeleteUserTopic(id) => {
+                let id = boxed::Box::new(id);
+                ctx.link().send_future(async {
+                    match delete_user_topic(id).await {
+                        Ok(_) => Msg::AddedTopic,
+                        Err(e) => Msg::LogError(e),
+                    }
+                });
+                true
+            }
+            Msg::DidFinishVoting => {
+                // At once, so the ballot shows as in and can be taken back.
+                ctx.link().send_message(Msg::CheckElection);
+                ctx.link().send_message(Msg::WaitForElection);
+                true
+            }
+            Msg::DidStoreMeetingScore => {
+                ctx.link().send_future(async {
+                    match fetch_meetings().await {
+                        Ok((meetings, sort)) => Msg::SetMeetings(meetings, sort),
+                        Err(e) => Msg::LogError(e),
+                    }
+                });
+                true
+            }
+            Msg::DidStoreMeetingTopicScore(meeting_id) => {
+                ctx.link()
+                    .send_message(Msg::FetchMeetingTopics(*meeting_id));
+                false
+            }
+            Msg::DidStoreUserTopicScore => {
+                ctx.link().send_message(Msg::FetchUserTopics);
+                false
+            }
+            Msg::ElectionWaited(result, elapsed_ms) => {
+                self.election_wait = false;
+                match result {
+                    Ok(results) => {
+                        let waiting = results.topics.is_none()
+                            && self.attending_meeting == Some(results.meeting_id);
+                        ctx.link().send_message(Msg::SetElectionResults(results));
+                        if waiting {
+                            // An API that answers at once, like an older one,
+                            // is asked no more often than it would be polled.
+                            let delay_ms = CHECK_ELECTION_MS.saturating_sub(elapsed_ms);
+                            ctx.link().send_future(async move {
+                                TimeoutFuture::new(delay_ms).await;
+                                Msg::WaitForElection
+                            });
+                        }
+                    }
+                    Err(e) => {
+                        // Polling still works where held requests are cut off.
+                        log_error(&e);
+                        self.vote_poll = Some(self.poll(ctx, || Msg::CheckElection));
+                    }
+                }
+                false
+            }
+            Msg::FetchMeetingPage(offset) => {
+                let search = self.meeting_search_text.trim().to_owned();
+                let sort = self.meeting_sort;
+                ctx.link().send_future(async move {
+                    match fetch_meeting_page(search, sort, offset).await {
+                        Ok(page) => Msg::SetMeetingPage(page),
+                        Err(e) => Msg::LogError(e),
+                    }
+                });
+                false
+            }
+            Msg::FetchMeetingTopics(meeting_id) => {
+                let id = boxed::Box::new(meeting_id);
+                ctx.link().send_future(async {
+                    match fetch_meeting_topics(id).await {
+                        Ok(topics) => Msg::SetMeetingTopics(topics),
+                        Err(e) => Msg::LogError(e),
+                    }
+                });
+                true
+            }
+            Msg::FetchMyTopics(meeting_id) => {
+                ctx.link().send_future(async move {
+                    match fetch_my_topics(meeting_id).await {
+                        Ok(topics) => Msg::SetMyTopics(topics),
+                        Err(e) => Msg::LogError(e),
+                    }
+                });
+                false
+            }
+            Msg::FetchMeetingTemplates => {
+                ctx.link().send_future(async {
+                    match fetch_meeting_templates().await {
+                        Ok(templates) => Msg::SetMeetingTemplates(templates),
+                        Err(e) => Msg::LogError(e),
+                    }
+                });
+                false
+            }
+            Msg::FetchCandidateTopics => {
+                if let Some(meeting_id) = self.attending_meeting {
+                    let meeting_id = boxed::Box::new(meeting_id);
+                    ctx.link().send_future(async {
+                        match fetch_candidate_topics(meeting_id).await {
+                            Ok(topics) => Msg::SetCandidateTopics(topics),
+                            Err(e) => Msg::LogError(e),
+                        }
+                    });
+                }
+                false
+            }
+            Msg::FetchOrganizers => {
+                if let Some(meeting_id) = self.attending_meeting {
+                    let meeting_id = boxed::Box::new(meeting_id);
+                    ctx.link().send_future(async {
+                        match fetch_meeting_organizers(meeting_id).await {
+                            Ok(organizers) => Msg::SetOrganizers(organizers),
+                            Err(e) => Msg::LogError(e),
+                        }
+                    });
+                }
+                false
+            }
+            Msg::FetchMembership => {
+                ctx.link().send_future(async {
+                    match fetch_join_requests().await {
+                        Ok(requests) => Msg::SetJoinRequests(requests),
+                        Err(e) => Msg::LogError(e),
+                    }
+                });
+                ctx.link().send_future(async {
+                    match fetch_invitations().await {
+                        Ok(invitations) => Msg::SetInvitations(invitations),
+                        Err(e) => Msg::LogError(e),
+                    }
+                });
+                false
+            }
+            Msg::DecideJoinRequest(email, approved) => {
+                ctx.link().send_future(async move {
+                    match decide_join_request(email, approved).await {
+                        Ok(()) => Msg::FetchMembership,
+                        Err(e) => Msg::LogError(e),
+                    }
+                });
+                false
+            }
+            Msg::InviteMember => {
+                let email = self.invite_text.trim().to_owned();
+                ctx.link().send_future(async {
+                    match invite_member(email).await {
+                        Ok(Ok(_)) => Msg::FetchMembership,
+                        Ok(Err(e)) => Msg::InviteFailed(e),
+                        Err(e) => Msg::LogError(e),
+                    }
+                });
+                self.invite_text = "".to_owned();
+                self.invite_error = None;
+                true
+            }
+            Msg::InviteFailed(e) => {
+                self.invite_error = Some(e);
+                true
+            }
+            Msg::RevokeInvitation(email) => {
+                ctx.link().send_future(async {
+                    match revoke_invitation(email).await {
+                        Ok(()) => Msg::FetchMembership,
+                        Err(e) => Msg::LogError(e),
+                    }
+                });
+                false
+            }
+            Msg::SetInvitations(invitations) => {
+                self.invitations = invitations;
+                true
+            }
+            Msg::SetJoinRequests(requests) => {
+                self.join_requests = requests;
+                true
+            }
+            Msg::UpdateInviteText(text) => {
+                self.invite_text = text;
+                true
+            }
+            Msg::FetchAvatar => {
+                ctx.link().send_future(async {
+                    match fetch_avatar().await {
+                        Ok(avatar) => Msg::SetAvatar(avatar),
+                        Err(e) => Msg::LogError(e),
+                    }
+                });
+                false
+            }
+            Msg::FetchLanguage => {
+                ctx.link().send_future(async {
+                    match fetch_language().await {
+                        Ok(language) => Msg::SetLanguage(language),
+                        Err(e) => Msg::LogError(e),
+                    }
+                });
+                false
+            }
+            Msg::FetchResearchConsent => {
+                ctx.link().send_future(async {
+                    match fetch_research_consent().await {
+                        Ok(consent) => Msg::SetResearchConsent(consent),
+                        Err(e) => Msg::LogError(e),
+                    }
+                });
+                false
+            }
+            Msg::FetchNotificationPreferences => {
+                ctx.link().send_future(async {
+                    match fetch_notification_preferences().await {
+                        Ok(preferences) => Msg::SetNotificationPreferences(preferences),
+                        Err(e) => Msg::LogError(e),
+                    }
+                });
+                false
+            }
+            Msg::EditOutcome => {
+                // Revising starts from what the cohort already recorded.
+                let recorded = self
+                    .election_results
+                    .as_ref()
+                    .and_then(|results| results.outcome.as_ref());
+                self.outcome_form =
+                    Some(
+                        recorded.map_or_else(OutcomeMessage::default, |o| OutcomeMessage {
+                            text: o.text.clone(),
+                            topics: o.topics.iter().map(|t| t.id).collect(),
+                        }),
+                    );
+                self.outcome_error = None;
+                true
+            }
+            Msg::EditTopicDescription(id) => {
+                self.description_form = match self.description_form {
+                    Some((editing, _)) if editing == id => None,
+                    _ => self
+                        .user_topics
+                        .iter()
+                        .find(|t| t.id == id)
+                        .map(|t| (id, t.description.clone())),
+                };
+                self.description_form_error = None;
+                self.description_edits = vec![];
+                if self.description_form.is_some() {
+                    let topic_id = boxed::Box::new(id);
+                    ctx.link().send_future(async move {
+                        match fetch_topic_edits(topic_id).await {
+                            Ok(edits) => Msg::SetTopicEdits(id, edits),
+                            Err(e) => Msg::LogError(e),
+                        }
+                    });
+                }
+                true
+            }
+            Msg::EditTopicLinks(id) => {
+                self.link_form = match self.link_form {
+                    Some((editing, _)) if editing == id => None,
+                    _ => Some((id, "".to_owned())),
+                };
+                self.link_form_error = None;
+                true
+            }
+            Msg::FetchRoomJoins => {
+                if let Some(meeting_id) = self.attending_meeting {
+                    let meeting_id = boxed::Box::new(meeting_id);
+                    ctx.link().send_future(async {
+                        match fetch_room_joins(meeting_id).await {
+                            Ok(joins) => Msg::SetRoomJoins(joins),
+                            Err(e) => Msg::LogError(e),
+                        }
+                    });
+                }
+                false
+            }
+            Msg::FetchMeetingHistory => {
+                if let Some(meeting_id) = self.attending_meeting {
+                    let meeting_id = boxed::Box::new(meeting_id);
+                    ctx.link().send_future(async {
+                        match fetch_meeting_history(meeting_id).await {
+                            Ok(history) => Msg::SetMeetingHistory(history),
+                            Err(e) => Msg::LogError(e),
+                        }
+                    });
+                }
+                false
+            }
+            Msg::FetchTopicHistory => {
+                ctx.link().send_future(async {
+                    match fetch_topic_history().await {
+                        Ok(history) => Msg::SetTopicHistory(history),
+                        Err(e) => Msg::LogError(e),
+                    }
+                });
+                false
+            }
+            Msg::FetchUserTopics => {
+                ctx.link().send_future(async {
+                    match fetch_user_topics().await {
+                        Ok(topics) => Msg::SetUserTopics(topics),
+                        Err(e) => Msg::LogError(e),
+                    }
+                });
+                true
+            }
+            Msg::HardRefresh => {
+                hard_refresh();
+                false
+            }
+            Msg::ImportedMeeting => {
+                self.import_meeting = None;
+                self.import_meeting_error = None;
+                ctx.link().send_message(Msg::AddedMeeting);
+                true
+            }
+            Msg::ImportMeeting => {
+                let text = self.import_meeting.clone().unwrap_or_default();
+                // Check locally first, so obvious mistakes need no round trip.
+                let config = serde_json::from_str::<MeetingConfig>(&text)
+                    .map_err(|e| format!("{e}"))
+                    .and_then(|config| config.validate().map(|()| config));
+                match config {
+                    Ok(config) => {
+                        ctx.link().send_future(async {
+                            match import_meeting_config(config).await {
+                                Ok(()) => Msg::ImportedMeeting,
+                                Err(e) => Msg::ImportMeetingFailed(format!("{e}")),
+                            }
+                        });
+                    }
+                    Err(e) => self.import_meeting_error = Some(e),
+                }
+                true
+            }
+            Msg::ImportMeetingFailed(e) => {
+                self.import_meeting_error = Some(e);
+                true
+            }
+            Msg::JoinByCode => {
+                let code = match normalize_join_code(&self.join_code_text) {
+                    Some(code) => code,
+                    None => {
+                        self.join_code_error = Some(i18n::text("join-code-invalid"));
+                        return true;
+                    }
+                };
+                self.join_code_error = None;
+                ctx.link().send_future(async move {
+                    match join_by_code(code).await {
+                        Ok(Ok(joined)) => Msg::JoinedByCode(joined),
+                        Ok(Err(e)) => Msg::JoinCodeFailed(e),
+                        Err(e) => Msg::LogError(e),
+                    }
+                });
+                true
+            }
+            Msg::JoinCodeFailed(e) => {
+                self.join_code_error = Some(e);
+                true
+            }
+            Msg::JoinedByCode(joined) => {
+                self.join_code_text = "".to_owned();
+                self.join_code_error = None;
+                // Also brings in the registration the code made
+                ctx.link().send_message(Msg::Sync);
+                ctx.link()
+                    .send_message(Msg::AttendingMeeting(boxed::Box::new(joined.meeting)));
+                true
+            }
+            Msg::JoinedRoom => {
+                if let Some(meeting_id) = self.attending_meeting {
+                    let meeting_id = boxed::Box::new(meeting_id);
+                    ctx.link().send_future(async {
+                        match report_joined_room(meeting_id).await {
+                            Ok(()) => Msg::Noop,
+                            Err(e) => Msg::LogError(e),
+                        }
+                    });
+                }
+                false
+            }
+            Msg::LeaveMeeting => {
+                if let Some(meeting_to_leave) = self.attending_meeting {
+                    let meeting = Box::new(meeting_to_leave);
+                    ctx.link().send_future(async {
+                        match leave_meeting(meeting.clone()).await {
+                            Ok(_) => Msg::LeftMeeting(meeting),
+                            Err(e) => Msg::LogError(e),
+                        }
+                    });
+                }
+                true
+            }
+            Msg::LeftMeeting(meeting) => {
+                if self.attending_meeting.is_some() && self.attending_meeting.unwrap() == *meeting {
+                    self.attending_meeting = None;
+                    self.election_results = None;
+                    self.room_joins = None;
+                    self.meeting_history = None;
+                    self.link_form = None;
+                    self.meeting_organizers = None;
+                    self.meeting_organizers_error = None;
+                    self.candidate_topics = None;
+                    self.merge_selection.clear();
+                    self.merge_topics_error = None;
+                    self.rename_meeting_error = None;
+                    self.veto_error = None;
+                    self.outcome_form = None;
+                    self.outcome_error = None;
+                    self.start_meeting_error = None;
+                    self.start_confirm = None;
+                    self.vote_poll = None;
+                    self.topic_timer = None;
+                    self.topic_timer_tick = None;
+                    self.voting_tick = None;
+                    self.active_tab = Tab::MeetingManagement;
+                    if self.meeting_templates.is_empty() {
+                        ctx.link().send_message(Msg::FetchMeetingTemplates);
+                    }
+                }
+                true
+            }
+            Msg::AvatarFailed(e) => {
+                self.avatar_error = Some(e);
+                true
+            }
+            Msg::DeleteAvatar => {
+                ctx.link().send_future(async {
+                    match delete_avatar().await {
+                        Ok(avatar) => Msg::SetAvatar(avatar),
+                        Err(e) => Msg::LogError(e),
+                    }
+                });
+                false
+            }
+            Msg::LanguageFailed(e) => {
+                self.language_error = Some(e);
+                true
+            }
+            Msg::LogError(e) => {
+                log_error(&e);
+                let unreachable = client::is_offline() || client::n_queued() > 0;
+                if unreachable && self.offline_probe.is_none() {
+                    let handle = {
+                        let link = ctx.link().clone();
+                        Interval::new(OFFLINE_PROBE_MS, move || {
+                            link.send_message(Msg::CheckInstance)
+                        })
+                    };
+                    self.offline_probe = Some(handle);
+                }
+                true
+            }
+            Msg::MeetingRegisteredChanged => {
+                // could refresh participation info here, but worth it?
+                true
+            }
+            Msg::MeetingToggleRegistered(id) => {
+                let boxed_id = boxed::Box::<u32>::new(id);
+                self.registration_error = None;
+                if self.registered_meetings.contains(&id) {
+                    self.registered_meetings.remove(&id);
+                    ctx.link().send_future(async move {
+                        registered_msg(id, register_for_meeting(boxed_id, false, vec![]).await)
+                    });
+                } else if let Some(n_fields) = self
+                    .scored_meeting(id)
+                    .map(|sm| sm.registration_fields.len())
+                    .filter(|&n| n > 0)
+                {
+                    // The organizer's questions come first.
+                    self.registration_form = Some((id, vec!["".to_owned(); n_fields]));
+                } else {
+                    self.registered_meetings.insert(id);
+                    ctx.link().send_future(async move {
+                        registered_msg(id, register_for_meeting(boxed_id, true, vec![]).await)
+                    });
+                }
+                true
+            }
+            Msg::MergeTopics => {
+                if let Some(meeting_id) = self.attending_meeting {
+                    let meeting_id = boxed::Box::new(meeting_id);
+                    let topics = self.merge_selection.clone();
+                    ctx.link().send_future(async {
+                        candidate_topics_msg(merge_meeting_topics(meeting_id, topics).await)
+                    });
+                }
+                false
+            }
+            Msg::MergeTopicsFailed(e) => {
+                self.merge_topics_error = Some(e);
+                true
+            }
+            Msg::MyTopicsFailed(e) => {
+                self.my_topics_error = Some(e);
+                true
+            }
+            Msg::Noop => true,
+            Msg::OpenSubmissions(meeting_id) => {
+                self.submission_error = None;
+                ctx.link().send_future(async move {
+                    match fetch_submitted_topics(meeting_id).await {
+                        Ok(topics) => Msg::SetSubmittedTopics(meeting_id, topics),
+                        Err(e) => Msg::LogError(e),
+                    }
+                });
+                false
+            }
+            Msg::OrganizersFailed(e) => {
+                self.meeting_organizers_error = Some(e);
+                true
+            }
+            Msg::OutcomeFailed(e) => {
+                self.outcome_error = Some(e);
+                true
+            }
+            Msg::ContactSharingFailed(e) => {
+                self.contact_sharing_error = Some(e);
+                true
+            }
+            Msg::RemoveOrganizer(email) => {
+                if let Some(meeting_id) = self.attending_meeting {
+                    let meeting_id = boxed::Box::new(meeting_id);
+                    ctx.link().send_future(async {
+                        organizers_msg(remove_meeting_organizer(meeting_id, email).await)
+                    });
+                }
+                false
+            }
+            Msg::RenameMeeting => {
+                if let Some(meeting_id) = self.attending_meeting {
+                    let meeting_id = boxed::Box::new(meeting_id);
+                    let name = self.rename_meeting_text.trim().to_owned();
+                    ctx.link().send_future(async {
+                        match rename_meeting(meeting_id, name).await {
+                            Ok(Ok(())) => Msg::RenamedMeeting,
+                            Ok(Err(e)) => Msg::RenameMeetingFailed(e),
+                            Err(e) => Msg::LogError(e),
+                        }
+                    });
+                }
+                false
+            }
+            Msg::RenameMeetingFailed(e) => {
+                self.rename_meeting_error = Some(e);
+                true
+            }
+            Msg::RenamedMeeting => {
+                self.rename_meeting_text = "".to_owned();
+                self.rename_meeting_error = None;
+                ctx.link().send_future(async {
+                    match fetch_meetings().await {
+                        Ok((meetings, sort)) => Msg::SetMeetings(meetings, sort),
+                        Err(e) => Msg::LogError(e),
+                    }
+                });
+                true
+            }
+            Msg::RemoveTopicLink(i) => {
+                if let Some((id, _)) = self.link_form {
+                    let mut links = self.topic_links(id);
+                    if i < links.len() {
+                        links.remove(i);
+                        self.save_topic_links(ctx, id, links);
+                    }
+                }
+                true
+            }
+            Msg::RecordOutcome => {
+                if let (Some(meeting_id), Some(form)) =
+                    (self.attending_meeting, self.outcome_form.clone())
+                {
+                    self.outcome_error = None;
+                    ctx.link().send_future(async move {
+                        match record_outcome(boxed::Box::new(meeting_id), form).await {
+                            Ok(Ok(())) => Msg::RecordedOutcome,
+                            Ok(Err(e)) => Msg::OutcomeFailed(e),
+                            Err(e) => Msg::LogError(e),
+                        }
+                    });
+                }
+                true
+            }
+            Msg::RecordedOutcome => {
+                self.outcome_form = None;
+                ctx.link().send_message(Msg::CheckElection);
+                true
+            }
+            Msg::RegistrationFailed(id, e) => {
+                self.registered_meetings.remove(&id);
+                self.registration_error = Some(registration_error_text(&e));
+                ctx.link().send_message(Msg::CheckMeetings);
+                true
+            }
+            Msg::SetElectionResults(results) => {
+                self.follow_poll_hint(ctx);
+                if let Some(meeting) = self.attending_meeting {
+                    if results.meeting_id == meeting {
+                        let shown = self.election_results.as_ref();
+                        let changed = shown.map_or(true, |r| r.status != results.status);
+                        if changed && copy::election_status_text(&results.status).is_none() {
+                            log_error(&anyhow!("unknown election status from the API"));
+                        }
+                        // A new round reshuffled the cohorts, so there is a new ballot.
+                        if shown.is_some_and(|r| r.round != results.round) {
+                            self.outcome_form = None;
+                            self.next_round_error = None;
+                            ctx.link().send_message(Msg::FetchMeetingTopics(meeting));
+                        }
+                        if results.topics.is_some() {
+                            self.vote_poll = None;
+                            // The cohort's results were just recorded.
+                            if shown.map_or(true, |r| r.topics.is_none()) {
+                                ctx.link().send_message(Msg::FetchMeetingHistory);
+                            }
+                        }
+                        let closing = results.voting_closes_at.is_some()
+                            && matches!(results.status, ElectionStatus::WaitingForVotes { .. });
+                        if !closing {
+                            self.voting_tick = None;
+                        } else if self.voting_tick.is_none() {
+                            // Each tick re-renders the countdown.
+                            let link = ctx.link().clone();
+                            self.voting_tick =
+                                Some(Interval::new(TOPIC_TIMER_TICK_MS, move || {
+                                    link.send_message(Msg::Noop)
+                                }));
+                        }
+                        self.election_results = Some(results);
+                        true
+                    } else {
+                        false
+                    }
+                } else {
+                    false
+                }
+            }
+            Msg::SetInstanceInfo(info) => {
+                if self.offline_probe.take().is_some() {
+                    ctx.link().send_message(Msg::FlushQueued);
+                }
+                let page_version = page_asset_version();
+                self.stale_bundle = info.schema_version != SCHEMA_VERSION
+                    || (info.asset_version.is_some()
+                        && page_version.is_some()
+                        && info.asset_version != page_version);
+                self.server_build = info.build;
+                self.fault_injection = info.fault_injection;
+                if info.research && !self.research && self.active_tab == Tab::Preferences {
+                    ctx.link().send_message(Msg::FetchResearchConsent);
+                }
+                self.research = info.research;
+                true
+            }
+            Msg::SaveTopicDescription => {
+                let (id, description) = match &self.description_form {
+                    Some((id, text)) => (*id, text.trim().to_owned()),
+                    None => return false,
+                };
+                if let Err(e) = validate_topic_description(&description) {
+                    self.description_form_error = Some(e);
+                    return true;
+                }
+                self.description_form_error = None;
+                let topic_id = boxed::Box::new(id);
+                ctx.link().send_future(async move {
+                    match store_topic_description(topic_id, description.clone()).await {
+                        Ok(Ok(())) => Msg::SetTopicDescription(id, description),
+                        Ok(Err(e)) => Msg::TopicDescriptionFailed(e),
+                        Err(e) => Msg::LogError(e),
+                    }
+                });
+                true
+            }
+            Msg::SetCandidateTopics(topics) => {
+                self.candidate_topics = Some(topics);
+                self.merge_selection.clear();
+                self.merge_topics_error = None;
+                true
+            }
+            Msg::SetOrganizers(organizers) => {
+                self.meeting_organizers = Some(organizers);
+                self.meeting_organizers_error = None;
+                self.new_organizer_text = "".to_owned();
+                true
+            }
+            Msg::SetAvatar(avatar) => {
+                self.avatar = avatar;
+                self.avatar_error = None;
+                true
+            }
+            Msg::SetLanguage(language) => {
+                self.language_text = language.unwrap_or_default();
+                self.language_error = None;
+                true
+            }
+            Msg::SetResearchConsent(consent) => {
+                self.research_consent = consent;
+                true
+            }
+            Msg::SetLocale(tag) => {
+                i18n::set_locale(&tag);
+                true
+            }
+            Msg::SetNotificationPreferences(preferences) => {
+                self.notification_preferences = preferences;
+                true
+            }
+            Msg::SetMeetingTopics(topics) => {
+                self.meeting_topics = Some(topics);
+                true
+            }
+            Msg::SetMyTopics(topics) => {
+                self.my_topics = topics;
+                self.my_topics_error = None;
+                true
+            }
+            Msg::SetRoomJoins(joins) => {
+                self.room_joins = Some(joins);
+                true
+            }
+            Msg::SetSync(snapshot) => {
+                let SyncMessage {
+                    email,
+                    attending_meetings,
+                    registered_meetings,
+                    meetings,
+                    sort,
+                    user_topics,
+                    election_results,
+                } = snapshot;
+                self.meeting_sort = sort;
+                self.user_id = UserIdState::Fetched(email);
+                self.registered_meetings = registered_meetings.into_iter().collect();
+                self.meetings = meetings.into_iter().map(Into::into).collect();
+                self.refresh_meeting_page(ctx);
+                self.fetch_unlisted_meeting(ctx);
+                self.user_topics = user_topics;
+                if let Some(meeting) = self.attending_meeting {
+                    if !attending_meetings.contains(&meeting) {
+                        // The server no longer has us in the meeting.
+                        ctx.link()
+                            .send_message(Msg::LeftMeeting(boxed::Box::new(meeting)));
+                    } else if let Some(results) = election_results {
+                        ctx.link().send_message(Msg::SetElectionResults(results));
+                    }
+                } else if let Some(&meeting) = attending_meetings.first() {
+                    // Joined elsewhere, e.g., by scanning a meeting's invite QR code
+                    ctx.link()
+                        .send_message(Msg::AttendingMeeting(boxed::Box::new(meeting)));
+                }
+                true
+            }
+            Msg::SetRegisteredMeetings(meetings) => {
+                self.registered_meetings = meetings.into_iter().collect();
+                true
+            }
+            Msg::SetSubmissionDeadline(deadline) => {
+                if let Some(meeting_id) = self.attending_meeting {
+                    let meeting_id = boxed::Box::new(meeting_id);
+                    ctx.link().send_future(async move {
+                        match set_submission_deadline(meeting_id, deadline).await {
+                            Ok(resp) => {
+                                if resp.status() == 200 {
+                                    Msg::CheckMeetings
+                                } else {
+                                    Msg::LogError(error_from_response(resp))
+                                }
+                            }
+                            Err(e) => Msg::LogError(e),
+                        }
+                    });
+                }
+                self.submission_deadline_text = "".to_owned();
+                true
+            }
+            Msg::SetVotingMinutes(minutes) => {
+                if let Some(meeting_id) = self.attending_meeting {
+                    let meeting_id = boxed::Box::new(meeting_id);
+                    ctx.link().send_future(async move {
+                        match set_voting_minutes(meeting_id, minutes).await {
+                            Ok(resp) => {
+                                if resp.status() == 200 {
+                                    Msg::CheckMeetings
+                                } else {
+                                    Msg::LogError(error_from_response(resp))
+                                }
+                            }
+                            Err(e) => Msg::LogError(e),
+                        }
+                    });
+                }
+                self.voting_minutes_text = "".to_owned();
+                true
+            }
+            Msg::SetSubmittedTopics(meeting_id, topics) => {
+                self.submissions = Some((meeting_id, topics));
+                true
+            }
+            Msg::SetRegistrationDeadline(deadline) => {
+                if let Some(meeting_id) = self.attending_meeting {
+                    let meeting_id = boxed::Box::new(meeting_id);
+                    ctx.link().send_future(async move {
+                        match set_registration_deadline(meeting_id, deadline).await {
+                            Ok(resp) => {
+                                if resp.status() == 200 {
+                                    Msg::CheckMeetings
+                                } else {
+                                    Msg::LogError(error_from_response(resp))
+                                }
+                            }
+                            Err(e) => Msg::LogError(e),
+                        }
+                    });
+                }
+                self.registration_deadline_text = "".to_owned();
+                true
+            }
+            Msg::SetMeetingPage(page) => {
+                // Answers to earlier searches can arrive after later ones.
+                if page.search != self.meeting_search_text.trim() {
+                    return false;
+                }
+                self.meeting_page = Some(page);
+                true
+            }
+            Msg::SetMeetings(meetings, sort) => {
+                self.follow_poll_hint(ctx);
+                self.meetings = meetings;
+                self.meeting_sort = sort;
+                self.refresh_meeting_page(ctx);
+                self.fetch_unlisted_meeting(ctx);
+                true
+            }
+            Msg::SetUnlistedMeeting(meeting) => {
+                self.unlisted_meeting = Some(meeting);
+                true
+            }
+            Msg::SetMeetingHistory(history) => {
+                if self.attending_meeting != Some(history.meeting_id) {
+                    return false;
+                }
+                self.meeting_history = Some(history);
+                true
+            }
+            Msg::SetMeetingTemplates(templates) => {
+                self.meeting_templates = templates;
+                true
+            }
+            Msg::SetNewMeetingCohortSize(size) => {
+                self.new_meeting_cohort_size = size;
+                true
+            }
+            Msg::SetNewMeetingNWinners(n_winners) => {
+                self.new_meeting_n_winners = n_winners;
+                true
+            }
+            Msg::SetNewMeetingTopicsPerAttendee(n_topics) => {
+                self.new_meeting_topics_per_attendee = n_topics;
+                true
+            }
+            Msg::SetNewMeetingVetoPolicy(policy) => {
+                self.new_meeting_veto_policy = policy;
+                true
+            }
+            Msg::SetNewMeetingVotingMethod(method) => {
+                self.new_meeting_voting_method = method;
+                true
+            }
+            Msg::SetNewMeetingTemplate(template) => {
+                self.new_meeting_template = template;
+                true
+            }
+            Msg::FocusTab(tab) => {
+                self.tab_focus = Some(TabFocus::Tab);
+                ctx.link().send_message(Msg::SetTab(tab));
+                false
+            }
+            Msg::SetTab(tab) => {
+                let prev_tab = self.active_tab.clone();
+                self.active_tab = tab.clone();
+                // The old panel, and whatever in it had focus, is going away.
+                if tab != prev_tab && self.tab_focus.is_none() {
+                    self.tab_focus = Some(TabFocus::Panel);
+                }
+                if let Some(meeting_id) = self.attending_meeting {
+                    if tab == Tab::MeetingPrep && tab != prev_tab {
+                        ctx.link().send_message(Msg::CheckMeetings);
+                        ctx.link().send_message(Msg::FetchMeetingTopics(meeting_id));
+                        ctx.link().send_message(Msg::FetchMyTopics(meeting_id));
+                        ctx.link().send_message(Msg::FetchMeetingHistory);
+                    }
+                }
+                if tab == Tab::Preferences && tab != prev_tab {
+                    ctx.link().send_message(Msg::FetchNotificationPreferences);
+                    ctx.link().send_message(Msg::FetchLanguage);
+                    if self.research {
+                        ctx.link().send_message(Msg::FetchResearchConsent);
+                    }
+                    ctx.link().send_message(Msg::FetchAvatar);
+                    if self.is_admin {
+                        ctx.link().send_message(Msg::FetchMembership);
+                    }
+                }
+                // Only organizers creating meetings need templates,
+                // so attendees' first load skips them.
+                if tab == Tab::MeetingManagement && self.meeting_templates.is_empty() {
+                    ctx.link().send_message(Msg::FetchMeetingTemplates);
+                }
+                if tab.needs_meeting_poll() && !prev_tab.needs_meeting_poll() {
+                    self.meeting_poll = Some(self.poll(ctx, || Msg::CheckMeetings));
+                }
+                true
+            }
+            Msg::SetUserId(UserIdMessage { email, is_admin }) => {
+                console_dbg!(format!("got email: {}", &email));
+                self.user_id = UserIdState::Fetched(email);
+                self.is_admin = is_admin;
+                ctx.link().send_future(async {
+                    match fetch_meetings().await {
+                        Ok((meetings, sort)) => Msg::SetMeetings(meetings, sort),
+                        Err(e) => Msg::LogError(e),
+                    }
+                });
+                true
+            }
+            // Ballots keep the description from when their meeting started.
+            Msg::SetTopicDescription(id, description) => {
+                for topic in self.user_topics.iter_mut().filter(|t| t.id == id) {
+                    topic.description = description.clone();
+                }
+                self.description_form = None;
+                true
+            }
+            Msg::SetTopicEdits(id, edits) => {
+                if !matches!(self.description_form, Some((editing, _)) if editing == id) {
+                    return false;
+                }
+                self.description_edits = edits;
+                true
+            }
+            Msg::SetTopicHistory(history) => {
+                self.topic_history = history;
+                true
+            }
+            Msg::SetTopicLinks(id, links) => {
+                for topic in self
+                    .user_topics
+                    .iter_mut()
+                    .chain(self.meeting_topics.iter_mut().flatten())
+                    .filter(|t| t.id == id)
+                {
+                    topic.links = links.clone();
+                }
+                if let Some((_, text)) = &mut self.link_form {
+                    text.clear();
+                }
+                true
+            }
+            Msg::ShareContact(share) => {
+                if let Some(meeting_id) = self.attending_meeting {
+                    self.contact_sharing_error = None;
+                    ctx.link().send_future(async move {
+                        match share_contact(boxed::Box::new(meeting_id), share).await {
+                            Ok(Ok(())) => Msg::SharedContact,
+                            Ok(Err(e)) => Msg::ContactSharingFailed(e),
+                            Err(e) => Msg::LogError(e),
+                        }
+                    });
+                }
+                true
+            }
+            Msg::SharedContact => {
+                ctx.link().send_message(Msg::CheckElection);
+                false
+            }
+            Msg::SetUserTopics(topics) => {
+                self.user_topics = topics;
+                ctx.link().send_message(Msg::FetchTopicHistory);
+                true
+            }
+            Msg::StartMeeting(confirm) => {
+                self.start_meeting_error = None;
+                self.start_confirm = None;
+                if let Some(meeting_id) = self.attending_meeting {
+                    let meeting_id = boxed::Box::new(meeting_id);
+                    ctx.link().send_future(async move {
+                        let m_id = *meeting_id;
+                        match start_meeting(meeting_id, confirm).await {
+                            Ok(Ok(None)) => Msg::FetchMeetingTopics(m_id),
+                            Ok(Ok(counts)) => Msg::ConfirmStart(counts),
+                            Ok(Err(e)) => Msg::StartMeetingFailed(e),
+                            Err(e) => Msg::LogError(e),
+                        }
+                    });
+                }
+                true
+            }
+            Msg::NextRound => {
+                self.next_round_error = None;
+                if let Some(meeting_id) = self.attending_meeting {
+                    let meeting_id = boxed::Box::new(meeting_id);
+                    ctx.link().send_future(async {
+                        match next_round(meeting_id).await {
+                            Ok(Ok(())) => Msg::CheckElection,
+                            Ok(Err(e)) => Msg::NextRoundFailed(e),
+                            Err(e) => Msg::LogError(e),
+                        }
+                    });
+                }
+                true
+            }
+            Msg::NextRoundFailed(e) => {
+                self.next_round_error = Some(e);
+                true
+            }
+            Msg::StartMeetingFailed(e) => {
+                self.start_meeting_error = Some(e);
+                true
+            }
+            Msg::ConfirmStart(counts) => {
+                self.start_confirm = counts;
+                true
+            }
+            Msg::ToggleFaults => {
+                client::set_faults_enabled(!client::faults_enabled());
+                true
+            }
+            Msg::ToggleResearchConsent => {
+                let consent = !self.research_consent;
+                ctx.link().send_future(async move {
+                    match store_research_consent(consent).await {
+                        Ok(consent) => Msg::SetResearchConsent(consent),
+                        Err(e) => Msg::LogError(e),
+                    }
+                });
+                false
+            }
+            Msg::ToggleNotificationPreference(event, channel) => {
+                if let Some(p) = self
+                    .notification_preferences
+                    .iter_mut()
+                    .find(|p| p.event == event && p.channel == channel)
+                {
+                    p.enabled = !p.enabled;
+                    let preference = p.clone();
+                    ctx.link().send_future(async {
+                        match store_notification_preference(preference).await {
+                            Ok(preferences) => Msg::SetNotificationPreferences(preferences),
+                            Err(e) => Msg::LogError(e),
+                        }
+                    });
+                }
+                true
+            }
+            Msg::StartTopicTimer(i) => {
+                let minutes = self
+                    .election_results
+                    .as_ref()
+                    .and_then(|r| r.topic_minutes.as_ref())
+                    .and_then(|m| m.get(i).copied());
+                if let Some(minutes) = minutes {
+                    let deadline = js_sys::Date::now() + minutes as f64 * 60_000.0;
+                    self.topic_timer = Some((i, deadline));
+                    // Each tick re-renders the countdown.
+                    let link = ctx.link().clone();
+                    self.topic_timer_tick = Some(Interval::new(TOPIC_TIMER_TICK_MS, move || {
+                        link.send_message(Msg::Noop)
+                    }));
+                }
+                true
+            }
+            Msg::StopTopicTimer => {
+                self.topic_timer = None;
+                self.topic_timer_tick = None;
+                true
+            }
+            // The API checks these too, but checking first spares uploading a large file.
+            Msg::UploadAvatar(file) => {
+                if !AVATAR_CONTENT_TYPES.contains(&file.type_().as_str()) {
+                    self.avatar_error = Some(i18n::text("avatar-wrong-type"));
+                    return true;
+                }
+                if file.size() > MAX_AVATAR_BYTES as f64 {
+                    self.avatar_error = Some(i18n::text_with(
+                        "avatar-too-big",
+                        &[("kib", (MAX_AVATAR_BYTES / 1024).into())],
+                    ));
+                    return true;
+                }
+                ctx.link().send_future(async {
+                    match upload_avatar(file).await {
+                        Ok(Ok(avatar)) => Msg::SetAvatar(avatar),
+                        Ok(Err(e)) => Msg::AvatarFailed(e),
+                        Err(e) => Msg::LogError(e),
+                    }
+                });
+                false
+            }
+            Msg::StoreLanguage => {
+                let text = self.language_text.trim();
+                let language = (!text.is_empty()).then(|| text.to_owned());
+                ctx.link().send_future(async {
+                    match store_language(language).await {
+                        Ok(Ok(language)) => Msg::SetLanguage(language),
+                        Ok(Err(e)) => Msg::LanguageFailed(e),
+                        Err(e) => Msg::LogError(e),
+                    }
+                });
+                false
+            }
+            // The ballot gains and loses topics along with the choice.
+            Msg::StoredMyTopics(topics) => {
+                if let Some(meeting_id) = self.attending_meeting {
+                    ctx.link().send_message(Msg::FetchMeetingTopics(meeting_id));
+                }
+                ctx.link().send_message(Msg::SetMyTopics(topics));
+                false
+            }
+            Msg::StoreMeetingScores(scores) => {
+                // Meeting scores have no batch route, so the list refreshes once they're all in.
+                ctx.link().send_future(async {
+                    for (meeting_id, score) in scores {
+                        let score = boxed::Box::new(score);
+                        let meeting_id = boxed::Box::new(meeting_id);
+                        if let Err(e) = store_meeting_score(meeting_id, score).await {
+                            return Msg::LogError(e);
+                        }
                     }
-                    _ => self.meeting_poll = None,
-                }
+                    Msg::DidStoreMeetingScore
+                });
                 true
             }
-            Msg::CommitVote => {
-                if let Some(meeting_id) = self.attending_meeting {
-                    let meeting_id = boxed::Box::new(meeting_id);
+            Msg::StoreMeetingTopicScores(scores) => {
+                if self.meeting_topics.is_some() {
+                    let meeting_id = boxed::Box::new(self.attending_meeting.unwrap());
                     ctx.link().send_future(async {
-                        match commit_vote(meeting_id).await {
-                            Ok(()) => Msg::DidFinishVoting,
+                        match store_meeting_topic_scores(meeting_id.clone(), scores).await {
+                            Ok(_) => Msg::DidStoreMeetingTopicScore(meeting_id),
                             Err(e) => Msg::LogError(e),
                         }
                     });
-                    true
-                } else {
-                    false
                 }
+                true
             }
-            Msg::DeleteMeeting(id) => {
-                let id = boxed::Box::new(id);
+            Msg::StoreUserTopicScores(scores) => {
                 ctx.link().send_future(async {
-                    match delete_meeting(id).await {
-                        Ok(_) => Msg::AddedMeeting,
+                    match store_user_topic_scores(scores).await {
+                        Ok(_) => Msg::DidStoreUserTopicScore,
                         Err(e) => Msg::LogError(e),
                     }
                 });
                 true
             }
-            Msg::DeleteUserTopic(id) => {
-                let id = boxed::Box::new(id);
-                ctx.link().send_future(async {
-                    match delete_user_topic(id).await {
-                        Ok(_) => Msg::AddedTopic,
-                        Err(e) => Msg::LogError(e),
-                    }
-                });
+            Msg::StoreSubmissionScores(scores) => {
+                if let Some((meeting_id, _)) = self.submissions {
+                    ctx.link().send_future(async move {
+                        match store_submission_scores(meeting_id, scores).await {
+                            Ok(()) => Msg::OpenSubmissions(meeting_id),
+                            Err(e) => Msg::LogError(e),
+                        }
+                    });
+                }
+                false
+            }
+            Msg::SubmitTopic => {
+                let text = self.new_submission_text.trim().to_owned();
+                if let (Some((meeting_id, _)), false) = (&self.submissions, text.is_empty()) {
+                    let meeting_id = *meeting_id;
+                    ctx.link().send_future(async move {
+                        match submit_meeting_topic(meeting_id, text).await {
+                            Ok(Ok(())) => Msg::OpenSubmissions(meeting_id),
+                            Ok(Err(e)) => Msg::SubmitTopicFailed(e),
+                            Err(e) => Msg::LogError(e),
+                        }
+                    });
+                    self.new_submission_text = "".to_owned();
+                }
                 true
             }
-            Msg::DidFinishVoting => {
-                let handle = {
-                    let link = ctx.link().clone();
-                    Interval::new(CHECK_ELECTION_MS, move || {
-                        link.send_message(Msg::CheckElection)
-                    })
-                };
-                self.vote_poll = Some(handle);
+            Msg::SubmitTopicFailed(e) => {
+                self.submission_error = Some(e);
                 true
             }
-            Msg::DidStoreMeetingScore => {
-                ctx.link().send_future(async {
-                    match fetch_meetings().await {
-                        Ok(meetings) => Msg::SetMeetings(meetings),
+            Msg::SubmitRegistration => {
+                if let Some((id, answers)) = self.registration_form.take() {
+                    let fields = self
+                        .scored_meeting(id)
+                        .map_or(vec![], |sm| sm.registration_fields.clone());
+                    let answers = fields
+                        .iter()
+                        .zip(answers)
+                        .map(|(field, answer)| RegistrationAnswer {
+                            field: field.id,
+                            answer,
+                        })
+                        .collect();
+                    self.registered_meetings.insert(id);
+                    let boxed_id = boxed::Box::new(id);
+                    ctx.link().send_future(async move {
+                        registered_msg(id, register_for_meeting(boxed_id, true, answers).await)
+                    });
+                }
+                true
+            }
+            Msg::Sync => {
+                ctx.link().send_message(Msg::CheckInstance);
+                let meeting_id = self.attending_meeting;
+                ctx.link().send_future(async move {
+                    match fetch_sync(meeting_id).await {
+                        Ok(snapshot) => Msg::SetSync(snapshot),
                         Err(e) => Msg::LogError(e),
                     }
                 });
+                false
+            }
+            Msg::TopicDescriptionFailed(e) => {
+                self.description_form_error = Some(e);
                 true
             }
-            Msg::DidStoreMeetingTopicScore(meeting_id) => {
-                ctx.link()
-                    .send_message(Msg::FetchMeetingTopics(*meeting_id));
-                false
+            Msg::TopicLinksFailed(e) => {
+                self.link_form_error = Some(e);
+                true
             }
-            Msg::DidStoreUserTopicScore => {
-                ctx.link().send_message(Msg::FetchUserTopics);
+            Msg::SplitTopic(topic) => {
+                if let Some(meeting_id) = self.attending_meeting {
+                    let meeting_id = boxed::Box::new(meeting_id);
+                    ctx.link().send_future(async move {
+                        candidate_topics_msg(split_meeting_topic(meeting_id, topic).await)
+                    });
+                }
                 false
             }
-            Msg::FetchMeetingTopics(meeting_id) => {
-                let id = boxed::Box::new(meeting_id);
-                ctx.link().send_future(async {
-                    match fetch_meeting_topics(id).await {
-                        Ok(topics) => Msg::SetMeetingTopics(topics),
+            Msg::SortMeetings(sort) => {
+                self.meeting_sort = sort;
+                if self.meeting_page.is_some() {
+                    ctx.link().send_message(Msg::FetchMeetingPage(0));
+                }
+                ctx.link().send_future(async move {
+                    match store_meeting_sort(sort).await {
+                        Ok(()) => Msg::Noop,
                         Err(e) => Msg::LogError(e),
                     }
                 });
                 true
             }
-            Msg::FetchUserTopics => {
-                ctx.link().send_future(async {
-                    match fetch_user_topics().await {
-                        Ok(topics) => Msg::SetUserTopics(topics),
+            Msg::ToggleMergeTopic(id) => {
+                match self.merge_selection.iter().position(|&t| t == id) {
+                    Some(i) => {
+                        self.merge_selection.remove(i);
+                    }
+                    None => self.merge_selection.push(id),
+                }
+                true
+            }
+            // Choosing starts from what the user already brings, so unticking
+            // one of the top-ranked topics keeps the others.
+            Msg::ToggleMyTopic(id) => {
+                let Some(meeting_id) = self.attending_meeting else {
+                    return false;
+                };
+                let mut topics = self.brought_topics(meeting_id);
+                match topics.iter().position(|&t| t == id) {
+                    Some(i) => {
+                        topics.remove(i);
+                    }
+                    None => topics.push(id),
+                }
+                let limit = self.topics_per_attendee(meeting_id);
+                if topics.len() > limit as usize {
+                    self.my_topics_error = Some(i18n::text_with(
+                        "my-topics-too-many",
+                        &[("limit", limit.into())],
+                    ));
+                    return true;
+                }
+                ctx.link().send_future(async move {
+                    match store_my_topics(meeting_id, topics).await {
+                        Ok(Ok(topics)) => Msg::StoredMyTopics(topics),
+                        Ok(Err(e)) => Msg::MyTopicsFailed(e),
                         Err(e) => Msg::LogError(e),
                     }
                 });
+                false
+            }
+            Msg::ToggleOutcomeTopic(id) => {
+                if let Some(form) = self.outcome_form.as_mut() {
+                    match form.topics.iter().position(|&t| t == id) {
+                        Some(i) => {
+                            form.topics.remove(i);
+                        }
+                        None => form.topics.push(id),
+                    }
+                }
                 true
             }
-            Msg::LeaveMeeting => {
-                if let Some(meeting_to_leave) = self.attending_meeting {
-                    let meeting = Box::new(meeting_to_leave);
-                    ctx.link().send_future(async {
-                        match leave_meeting(meeting.clone()).await {
-                            Ok(_) => Msg::LeftMeeting(meeting),
+            Msg::ToggleTopicPrivate(id) => {
+                if let Some(t) = self.user_topics.iter_mut().find(|t| t.id == id) {
+                    t.private = !t.private;
+                    let private = t.private;
+                    ctx.link().send_future(async move {
+                        match store_topic_private(boxed::Box::new(id), private).await {
+                            Ok(()) => Msg::Noop,
                             Err(e) => Msg::LogError(e),
                         }
                     });
                 }
                 true
             }
-            Msg::LeftMeeting(meeting) => {
-                if self.attending_meeting.is_some() && self.attending_meeting.unwrap() == *meeting {
-                    self.attending_meeting = None;
-                    self.election_results = None;
-                    self.vote_poll = None;
-                    self.active_tab = Tab::MeetingManagement;
-                }
+            Msg::ToggleNewMeetingMatchLanguage => {
+                self.new_meeting_match_language = !self.new_meeting_match_language;
                 true
             }
-            Msg::LogError(e) => {
-                console_dbg!(format!("{e}"));
+            Msg::ToggleNewMeetingShareContacts => {
+                self.new_meeting_share_contacts = !self.new_meeting_share_contacts;
                 true
             }
-            Msg::MeetingRegisteredChanged => {
-                // could refresh participation info here, but worth it?
+            Msg::ToggleImportMeeting => {
+                self.import_meeting = match self.import_meeting {
+                    Some(_) => None,
+                    None => Some("".to_owned()),
+                };
+                self.import_meeting_error = None;
                 true
             }
-            Msg::MeetingToggleRegistered(id) => {
-                let boxed_id = boxed::Box::<u32>::new(id);
-                if self.registered_meetings.contains(&id) {
-                    self.registered_meetings.remove(&id);
-                    ctx.link().send_future(async {
-                        register_for_meeting(boxed_id, false).await.unwrap();
-                        Msg::MeetingRegisteredChanged
-                    });
-                } else {
-                    self.registered_meetings.insert(id);
-                    ctx.link().send_future(async {
-                        register_for_meeting(boxed_id, true).await.unwrap();
-                        Msg::MeetingRegisteredChanged
-                    });
-                }
+            Msg::PasteTopics(text) => {
+                self.bulk_topics = Some(text);
                 true
             }
-            Msg::Noop => true,
-            Msg::SetElectionResults(results) => {
-                if let Some(meeting) = self.attending_meeting {
-                    if results.meeting_id == meeting {
-                        if results.topics.is_some() {
-                            self.vote_poll = None;
-                        }
-                        self.election_results = Some(results);
-                        true
-                    } else {
-                        false
-                    }
-                } else {
-                    false
-                }
+            Msg::ToggleBulkTopics => {
+                self.bulk_topics = match self.bulk_topics {
+                    Some(_) => None,
+                    None => Some("".to_owned()),
+                };
+                true
             }
-            Msg::SetMeetingTopics(topics) => {
-                self.meeting_topics = Some(topics);
+            Msg::UpdateBulkTopicsText(text) => {
+                self.bulk_topics = Some(text);
                 true
             }
-            Msg::SetRegisteredMeetings(meetings) => {
-                self.registered_meetings = meetings.into_iter().collect();
+            Msg::UpdateImportMeetingText(text) => {
+                self.import_meeting = Some(text);
                 true
             }
-            Msg::SetMeetings(meetings) => {
-                self.meetings = meetings;
+            Msg::UpdateJoinCodeText(text) => {
+                self.join_code_text = text;
                 true
             }
-            Msg::SetTab(tab) => {
-                let prev_tab = self.active_tab.clone();
-                self.active_tab = tab.clone();
-                if let Some(meeting_id) = self.attending_meeting {
-                    if tab == Tab::MeetingPrep && tab != prev_tab {
-                        ctx.link().send_message(Msg::CheckMeetings);
-                        ctx.link().send_message(Msg::FetchMeetingTopics(meeting_id));
-                    }
+            Msg::UpdateLanguageText(text) => {
+                self.language_text = text;
+                true
+            }
+            Msg::UpdateMeetingSearchText(text) => {
+                self.meeting_search_text = text;
+                self.meeting_page = None;
+                self.refresh_meeting_page(ctx);
+                true
+            }
+            Msg::UpdateNewMeetingText(text) => {
+                self.new_meeting_text = text;
+                true
+            }
+            Msg::UpdateNewRegistrationFieldText(text) => {
+                self.new_registration_field_text = text;
+                true
+            }
+            Msg::UpdateNewOrganizerText(text) => {
+                self.new_organizer_text = text;
+                true
+            }
+            Msg::UpdateRenameMeetingText(text) => {
+                self.rename_meeting_text = text;
+                true
+            }
+            Msg::UpdateNewSeedText(text) => {
+                self.new_seed_text = text;
+                true
+            }
+            Msg::UpdateNewSubmissionText(text) => {
+                self.new_submission_text = text;
+                true
+            }
+            Msg::UpdateSubmissionDeadlineText(text) => {
+                self.submission_deadline_text = text;
+                true
+            }
+            Msg::UpdateVotingMinutesText(text) => {
+                self.voting_minutes_text = text;
+                true
+            }
+            Msg::UpdateTopicDescriptionText(text) => {
+                if let Some((_, description)) = &mut self.description_form {
+                    *description = text;
                 }
-                if tab.needs_meeting_poll() && !prev_tab.needs_meeting_poll() {
-                    let handle = {
-                        let link = ctx.link().clone();
-                        Interval::new(CHECK_ELECTION_MS, move || {
-                            link.send_message(Msg::CheckMeetings)
-                        })
-                    };
-                    self.meeting_poll = Some(handle);
+                true
+            }
+            Msg::UpdateTopicLinkText(text) => {
+                if let Some((_, link_text)) = &mut self.link_form {
+                    *link_text = text;
                 }
                 true
             }
-            Msg::SetUserId(email) => {
-                console_dbg!(format!("got email: {}", &email));
-                self.user_id = UserIdState::Fetched(email);
-                ctx.link().send_future(async {
-                    match fetch_meetings().await {
-                        Ok(meetings) => Msg::SetMeetings(meetings),
-                        Err(e) => Msg::LogError(e),
-                    }
-                });
+            Msg::UpdateRegistrationDeadlineText(text) => {
+                self.registration_deadline_text = text;
                 true
             }
-            Msg::SetUserTopics(topics) => {
-                self.user_topics = topics;
+            Msg::UpdateRegistrationAnswer(i, text) => {
+                if let Some((_, answers)) = &mut self.registration_form {
+                    answers[i] = text;
+                }
+                true
+            }
+            Msg::UpdateNewTopicText(text) => {
+                self.new_topic_text = text;
                 true
             }
-            Msg::StartMeeting => {
+            Msg::UpdateOutcomeText(text) => {
+                if let Some(form) = self.outcome_form.as_mut() {
+                    form.text = text;
+                }
+                true
+            }
+            Msg::VetoFailed(e) => {
+                self.veto_error = Some(e);
+                true
+            }
+            Msg::RetractVote => {
                 if let Some(meeting_id) = self.attending_meeting {
-                    let meeting_id = boxed::Box::new(meeting_id);
-                    ctx.link().send_future(async {
-                        let m_id = *meeting_id;
-                        match start_meeting(meeting_id).await {
-                            Ok(()) => Msg::FetchMeetingTopics(m_id),
+                    self.vote_error = None;
+                    ctx.link().send_future(async move {
+                        match retract_vote(boxed::Box::new(meeting_id)).await {
+                            Ok(Ok(())) => Msg::RetractedVote,
+                            Ok(Err(e)) => Msg::RetractVoteFailed(e),
                             Err(e) => Msg::LogError(e),
                         }
                     });
                 }
                 true
             }
-            Msg::StoreMeetingScore((meeting_id, score)) => {
-                let score = boxed::Box::new(score);
-                let meeting_id = boxed::Box::new(meeting_id);
-                ctx.link().send_future(async {
-                    match store_meeting_score(meeting_id, score).await {
-                        Ok(_) => Msg::DidStoreMeetingScore,
-                        Err(e) => Msg::LogError(e),
-                    }
-                });
+            Msg::RetractedVote => {
+                ctx.link().send_message(Msg::CheckElection);
+                false
+            }
+            Msg::RetractVoteFailed(e) => {
+                self.vote_error = Some(e);
                 true
             }
-            Msg::StoreMeetingTopicScore((id, score)) => {
-                if self.meeting_topics.is_some() {
-                    let score = boxed::Box::new(score);
-                    let topic_id = boxed::Box::new(id);
-                    let meeting_id = boxed::Box::new(self.attending_meeting.unwrap());
-                    ctx.link().send_future(async {
-                        match store_meeting_topic_score(meeting_id.clone(), topic_id, score).await {
-                            Ok(_) => Msg::DidStoreMeetingTopicScore(meeting_id),
+            Msg::VetoTopic(id) => {
+                if let Some(meeting_id) = self.attending_meeting {
+                    // Vetoing the vetoed topic again withdraws the veto.
+                    let already = self
+                        .meeting_topics
+                        .as_ref()
+                        .is_some_and(|topics| topics.iter().any(|t| t.id == id && t.vetoed));
+                    let topic = (!already).then_some(id);
+                    self.veto_error = None;
+                    ctx.link().send_future(async move {
+                        match veto_meeting_topic(boxed::Box::new(meeting_id), topic).await {
+                            Ok(Ok(())) => Msg::FetchMeetingTopics(meeting_id),
+                            Ok(Err(e)) => Msg::VetoFailed(e),
                             Err(e) => Msg::LogError(e),
                         }
                     });
                 }
                 true
             }
-            Msg::StoreUserTopicScore((id, score)) => {
-                let score = boxed::Box::new(score);
-                let id = boxed::Box::new(id);
-                ctx.link().send_future(async {
-                    match store_user_topic_score(id, score).await {
-                        Ok(_) => Msg::DidStoreUserTopicScore,
-                        Err(e) => Msg::LogError(e),
-                    }
+            // The UI has no event stream, so after voting it asks the API
+            // to answer once the status changes, rather than polling every second.
+            Msg::WaitForElection => {
+                let meeting_id = match self.attending_meeting {
+                    Some(id) if !self.election_wait && self.vote_poll.is_none() => id,
+                    _ => return false,
+                };
+                self.election_wait = true;
+                ctx.link().send_future(async move {
+                    let started_ms = js_sys::Date::now();
+                    let wait = Some(ELECTION_WAIT_SECS);
+                    let result = fetch_election_status(boxed::Box::new(meeting_id), wait).await;
+                    Msg::ElectionWaited(result, (js_sys::Date::now() - started_ms) as u32)
                 });
-                true
-            }
-            Msg::UpdateNewMeetingText(text) => {
-                self.new_meeting_text = text;
-                true
-            }
-            Msg::UpdateNewTopicText(text) => {
-                self.new_topic_text = text;
-                true
+                false
             }
         }
     }
 
+    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
+        self.focus_tab_change();
+    }
+
     fn view(&self, ctx: &Context<Self>) -> Html {
         if no_user() {
             return html! {};
         }
+        // Shift+Enter included, so adding never leaves the keyboard.
         let onkeypress = ctx
             .link()
             .batch_callback(move |e: KeyboardEvent| (e.key() == "Enter").then(|| Msg::AddTopic));
+        // Multi-line pastes open the bulk preview instead of becoming one topic.
+        let onpaste = ctx.link().batch_callback(|e: Event| {
+            let text = e
+                .dyn_ref::<ClipboardEvent>()?
+                .clipboard_data()?
+                .get_data("text")
+                .ok()?;
+            text.trim().contains('\n').then(|| {
+                e.prevent_default();
+                Msg::PasteTopics(text)
+            })
+        });
         let new_topic = if let UserIdState::Fetched(_uid) = &self.user_id {
             html! {
                 <div class="container">
                     <div class="row">
-                        <div class="col text-end">{ "Add new topic:" }</div>
+                        <div class="col text-end">{ i18n::text("new-topic-label") }</div>
                         <div class="col">
                             <input
                                 id="new-topic" type="text" value={self.new_topic_text.clone()}
+                                ref={self.new_topic_input.clone()}
+                                placeholder={i18n::text("new-topic-placeholder")}
                                 { onkeypress }
+                                { onpaste }
                                 oninput={ctx.link().callback(|e: InputEvent| {
                                         let input = e.target_unchecked_into::<HtmlInputElement>();
                                         Msg::UpdateNewTopicText(input.value())
@@ -1051,9 +5815,13 @@ impl Component for Model {
                             <button
                                 type={"button"} class={"btn"}
                                 onclick={ctx.link().callback(|_| Msg::AddTopic)}>{ add_icon() }</button>
+                            <button
+                                type={"button"} class={"btn btn-link"}
+                                onclick={ctx.link().callback(|_| Msg::ToggleBulkTopics)}>{ i18n::text("new-topic-bulk") }</button>
                         </div>
                     </div>
                     <hr/>
+                    { self.bulk_topics_html(ctx) }
                 </div>
             }
         } else {
@@ -1064,19 +5832,67 @@ impl Component for Model {
                 ids={self.user_topics.iter().map(|t| t.id).collect::<Vec<u32>>()}
                 labels={self.user_topics.iter().map(|t| t.text.clone()).collect::<Vec<String>>()}
                 scores={self.user_topics.iter().map(|t| t.score).collect::<Vec<u32>>()}
-                store_score={ctx.link().callback(Msg::StoreUserTopicScore)}
+                store_scores={ctx.link().callback(Msg::StoreUserTopicScores)}
                 delete={Some(ctx.link().callback(Msg::DeleteUserTopic))}
+                histories={Some(self.user_topics.iter().map(|t| self.topic_history.get(&t.id).cloned().unwrap_or_default()).collect::<Vec<Vec<u32>>>())}
+                links={Some(self.user_topics.iter().map(|t| t.links.clone()).collect::<Vec<Vec<String>>>())}
+                edit_links={Some(ctx.link().callback(Msg::EditTopicLinks))}
+                descriptions={Some(self.user_topics.iter().map(|t| t.description.clone()).collect::<Vec<String>>())}
+                edit_description={Some(ctx.link().callback(Msg::EditTopicDescription))}
+                private={Some(self.user_topics.iter().map(|t| t.private).collect::<Vec<bool>>())}
+                toggle_private={Some(ctx.link().callback(Msg::ToggleTopicPrivate))}
             />
         };
+        let stale_bundle_html = if self.stale_bundle {
+            html! {
+                <div class="alert alert-warning" role="alert">
+                    {i18n::text("stale-bundle")}
+                    {" "}
+                    <button
+                        type={"button"} class={"btn btn-warning"}
+                        onclick={ctx.link().callback(|_| Msg::HardRefresh)}>{ i18n::text("stale-bundle-reload") }</button>
+                </div>
+            }
+        } else {
+            html! {}
+        };
+        let offline_html = if self.offline_probe.is_some() {
+            let queued_text = match client::n_queued() {
+                0 => "".to_owned(),
+                n => format!(
+                    " {}",
+                    i18n::text_with("offline-queued", &[("count", n.into())])
+                ),
+            };
+            html! {
+                <div class="alert alert-danger" role="alert">
+                    {i18n::text("offline")}
+                    {queued_text}
+                </div>
+            }
+        } else {
+            html! {}
+        };
         let main_panel = html! {
             <div>
+                { offline_html }
+                { stale_bundle_html }
                 { self.tabs_html(ctx) }
+                <div
+                    ref={self.tab_panel_ref.clone()}
+                    id={self.active_tab.panel_id()}
+                    role="tabpanel"
+                    aria-labelledby={self.active_tab.tab_id()}
+                    tabindex="0"
+                >
                 {
                     match self.active_tab {
                         Tab::TopicManagment => {
                             html! {
                                 <div>
                                     { new_topic }
+                                    { self.link_form_html(ctx) }
+                                    { self.description_form_html(ctx) }
                                     <div class="container">{ topics_html }</div>
                                 </div>
                             }
@@ -1084,6 +5900,9 @@ impl Component for Model {
                         Tab::MeetingManagement => {
                             self.meeting_management_html(ctx)
                         }
+                        Tab::Preferences => {
+                            self.preferences_html(ctx)
+                        }
                         Tab::MeetingPrep => {
                             if self.election_results.is_none() || self.election_results.as_ref().unwrap().topics.is_none() {
                                 self.meeting_attendance_html(ctx)
@@ -1093,6 +5912,8 @@ impl Component for Model {
                         }
                     }
                 }
+                </div>
+                { self.build_footer_html() }
             </div>
         };
         if matches!(self.user_id, UserIdState::Fetched(_)) {
@@ -0,0 +1,202 @@
+use std::mem;
+
+use yew::{html, Html};
+
+// Topic descriptions use a small part of Markdown.
+// Everything is built as nodes, never as markup, so HTML in a description shows as text.
+
+#[derive(Debug, PartialEq)]
+enum Span {
+    Text(String),
+    Code(String),
+    Bold(String),
+    Italic(String),
+    Link(String, String), // text and URL
+}
+
+#[derive(Debug, PartialEq)]
+enum Block {
+    Paragraph(Vec<Span>),
+    List(Vec<Vec<Span>>),
+}
+
+// The text between a delimiter at the start of `text` and the next one,
+// and the text after that.
+// As in Markdown, the text cannot start or end with a space, so "2 * 3 * 4" stays as it is.
+fn delimited<'a>(text: &'a str, delimiter: &str) -> Option<(&'a str, &'a str)> {
+    let start = delimiter.len();
+    let end = start + text[start..].find(delimiter)?;
+    let inner = &text[start..end];
+    let flanked = !inner.is_empty()
+        && !inner.starts_with(char::is_whitespace)
+        && !inner.ends_with(char::is_whitespace);
+    flanked.then(|| (inner, &text[end + delimiter.len()..]))
+}
+
+// Only web links, so a description cannot run script from a `javascript:` URL
+fn link(text: &str) -> Option<(Span, &str)> {
+    let (label, rest) = text[1..].split_once("](")?;
+    let (url, rest) = rest.split_once(')')?;
+    let web = url.starts_with("https://") || url.starts_with("http://");
+    let valid = web && !url.contains(char::is_whitespace) && !label.trim().is_empty();
+    valid.then(|| (Span::Link(label.to_owned(), url.to_owned()), rest))
+}
+
+fn spans(text: &str) -> Vec<Span> {
+    let mut spans = vec![];
+    let mut plain = String::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        let span = match c {
+            '\\' => {
+                // A backslash shows the next character as it is.
+                let after = &rest[1..];
+                if let Some(escaped) = after.chars().next() {
+                    plain.push(escaped);
+                    rest = &after[escaped.len_utf8()..];
+                    continue;
+                }
+                None
+            }
+            '`' => delimited(rest, "`").map(|(inner, after)| (Span::Code(inner.to_owned()), after)),
+            '*' if rest.starts_with("**") => {
+                delimited(rest, "**").map(|(inner, after)| (Span::Bold(inner.to_owned()), after))
+            }
+            '*' => {
+                delimited(rest, "*").map(|(inner, after)| (Span::Italic(inner.to_owned()), after))
+            }
+            '[' => link(rest),
+            _ => None,
+        };
+        match span {
+            Some((span, after)) => {
+                if !plain.is_empty() {
+                    spans.push(Span::Text(mem::take(&mut plain)));
+                }
+                spans.push(span);
+                rest = after;
+            }
+            None => {
+                plain.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    if !plain.is_empty() {
+        spans.push(Span::Text(plain));
+    }
+    spans
+}
+
+// Paragraphs are separated by blank lines, and list items start with "- " or "* ".
+fn blocks(text: &str) -> Vec<Block> {
+    let mut blocks = vec![];
+    let mut paragraph: Vec<&str> = vec![];
+    let mut items: Vec<Vec<Span>> = vec![];
+    let end_paragraph = |blocks: &mut Vec<Block>, paragraph: &mut Vec<&str>| {
+        if !paragraph.is_empty() {
+            blocks.push(Block::Paragraph(spans(&paragraph.join(" "))));
+            paragraph.clear();
+        }
+    };
+    let end_list = |blocks: &mut Vec<Block>, items: &mut Vec<Vec<Span>>| {
+        if !items.is_empty() {
+            blocks.push(Block::List(mem::take(items)));
+        }
+    };
+    for line in text.lines().map(str::trim) {
+        if let Some(item) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+            end_paragraph(&mut blocks, &mut paragraph);
+            items.push(spans(item.trim()));
+        } else if line.is_empty() {
+            end_paragraph(&mut blocks, &mut paragraph);
+            end_list(&mut blocks, &mut items);
+        } else {
+            end_list(&mut blocks, &mut items);
+            paragraph.push(line);
+        }
+    }
+    end_paragraph(&mut blocks, &mut paragraph);
+    end_list(&mut blocks, &mut items);
+    blocks
+}
+
+fn spans_html(spans: Vec<Span>) -> Html {
+    spans
+        .into_iter()
+        .map(|span| match span {
+            Span::Text(text) => html! { <>{text}</> },
+            Span::Code(text) => html! { <code>{text}</code> },
+            Span::Bold(text) => html! { <strong>{text}</strong> },
+            Span::Italic(text) => html! { <em>{text}</em> },
+            Span::Link(text, url) => html! {
+                <a href={url} target="_blank" rel="noopener">{text}</a>
+            },
+        })
+        .collect()
+}
+
+/// A topic description, rendered from its Markdown
+pub fn markdown_html(text: &str) -> Html {
+    blocks(text)
+        .into_iter()
+        .map(|block| match block {
+            Block::Paragraph(spans) => html! { <p class="mb-1">{spans_html(spans)}</p> },
+            Block::List(items) => html! {
+                <ul class="mb-1">
+                    {for items.into_iter().map(|spans| html! { <li>{spans_html(spans)}</li> })}
+                </ul>
+            },
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{blocks, spans, Block, Span};
+
+    fn text(t: &str) -> Span {
+        Span::Text(t.to_owned())
+    }
+
+    #[test]
+    fn test_spans() {
+        assert_eq!(
+            spans("Read **this** and *that*, then run `make`"),
+            vec![
+                text("Read "),
+                Span::Bold("this".to_owned()),
+                text(" and "),
+                Span::Italic("that".to_owned()),
+                text(", then run "),
+                Span::Code("make".to_owned()),
+            ]
+        );
+        assert_eq!(
+            spans("See [the doc](https://example.com/a) first"),
+            vec![
+                text("See "),
+                Span::Link("the doc".to_owned(), "https://example.com/a".to_owned()),
+                text(" first"),
+            ]
+        );
+        assert_eq!(
+            spans("[run](javascript:alert(1)) <b>hi</b>"),
+            vec![text("[run](javascript:alert(1)) <b>hi</b>")]
+        );
+        assert_eq!(spans("2 * 3 \\*not\\* **"), vec![text("2 * 3 *not* **")]);
+    }
+
+    #[test]
+    fn test_blocks() {
+        assert_eq!(
+            blocks("First line\nsecond line\n\n- one\n* two\nAfter"),
+            vec![
+                Block::Paragraph(vec![text("First line second line")]),
+                Block::List(vec![vec![text("one")], vec![text("two")]]),
+                Block::Paragraph(vec![text("After")]),
+            ]
+        );
+        assert_eq!(blocks("\n  \n"), vec![]);
+    }
+}
@@ -1,40 +1,201 @@
-use yew::{html, Callback, Component, Context, Html, Properties};
+use gloo_console::console_dbg;
+use yew::events::{Event, FocusEvent, KeyboardEvent, TargetCast};
+use yew::{html, Callback, Component, Context, Html, NodeRef, Properties};
 
-use ehall::{argsort, COHORT_QUORUM};
+use ehall::COHORT_QUORUM;
+use ehall_election::argsort;
 
-use crate::svg::{down_arrow, up_arrow, x_icon};
+use crate::i18n;
+use crate::markdown::markdown_html;
+use crate::svg::{down_arrow, lock_icon, sparkline, unlock_icon, up_arrow, x_icon};
 
+/// What a `Ranking` lists and which of its controls appear.
+/// Items are given as parallel lists indexed alike, so `labels[i]` names `ids[i]`.
+/// Optional lists and callbacks add their column or button only where given:
+/// the Topics tab passes links, descriptions, and privacy,
+/// the Meetings tab passes registration and joining,
+/// and the meeting lobby passes host suggestions and vetoes.
 #[derive(Clone, Debug, PartialEq, Properties)]
 pub struct Props {
     pub ids: Vec<u32>,
     pub labels: Vec<String>,
     pub scores: Vec<u32>,
     pub registered_counts: Option<Vec<u32>>,
+    /// Registrations each item needs before anyone can join it
+    pub cohort_sizes: Option<Vec<u32>>,
     pub joined_counts: Option<Vec<u32>>,
-    pub store_score: Callback<(u32, u32)>,
+    /// New (id, score) pairs, sent together for the items whose scores change
+    pub store_scores: Callback<Vec<(u32, u32)>>,
     pub delete: Option<Callback<u32>>,
+    pub deletable: Option<Vec<bool>>,
     pub is_registered: Option<Vec<bool>>,
     pub attend_meeting: Option<Callback<u32>>,
+    /// Joins the meeting without a ballot, shown beside "join now"
+    pub observe_meeting: Option<Callback<u32>>,
     pub register_toggle: Option<Callback<u32>>,
+    pub host_suggested: Option<Vec<bool>>,
+    /// A note about each item's registration deadline, empty when there is none
+    pub registration_badges: Option<Vec<String>>,
+    /// Whether people may still register for each item
+    pub registration_open: Option<Vec<bool>>,
+    /// Past scores of each item, oldest first
+    pub histories: Option<Vec<Vec<u32>>>,
+    /// Reading material for each item, shown after its label
+    pub links: Option<Vec<Vec<String>>>,
+    pub edit_links: Option<Callback<u32>>,
+    pub linkable: Option<Vec<bool>>,
+    /// Context for each item in Markdown, shown under its label when not empty
+    pub descriptions: Option<Vec<String>>,
+    pub edit_description: Option<Callback<u32>>,
+    /// Items the user has vetoed, which `veto` toggles
+    pub vetoed: Option<Vec<bool>>,
+    pub veto: Option<Callback<u32>>,
+    /// Items kept off meeting ballots, which `toggle_private` toggles
+    pub private: Option<Vec<bool>>,
+    pub toggle_private: Option<Callback<u32>>,
+    /// Indices of the items in display order, in place of highest score first.
+    /// The up and down buttons are hidden, since they would move items by score.
+    pub order: Option<Vec<usize>>,
+}
+
+// The host is enough to recognize a link, and keeps long URLs from crowding the list.
+pub fn link_label(url: &str) -> &str {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    rest.split(['/', '?', '#']).next().unwrap_or(rest)
+}
+
+pub fn links_html(links: &[String]) -> Html {
+    links
+        .iter()
+        .map(|url| {
+            html! {
+                <a class="ms-2" href={url.clone()} target="_blank" rel="noopener">
+                    {link_label(url)}
+                </a>
+            }
+        })
+        .collect()
+}
+
+/// New scores that move item `id` one place up the list, toward the highest score,
+/// or down, as (id, score) pairs for the items whose scores change,
+/// or None when the item is already at that end of the list
+pub fn moved_scores(ids: &[u32], scores: &[u32], id: u32, up: bool) -> Option<Vec<(u32, u32)>> {
+    // In the order the list shows them
+    let mut shown: Vec<usize> = argsort(scores).into_iter().rev().collect();
+    let place = shown.iter().position(|&i| ids[i] == id)?;
+    let other = if up { place.checked_sub(1)? } else { place + 1 };
+    let (i, j) = (shown[place], *shown.get(other)?);
+    if scores[i] != scores[j] {
+        return Some(vec![(ids[j], scores[i]), (ids[i], scores[j])]);
+    }
+    // Trading tied scores would change nothing, so every item is scored by its new place.
+    shown.swap(place, other);
+    let n = shown.len() as u32;
+    Some(
+        shown
+            .into_iter()
+            .zip((0..n).rev())
+            .filter(|&(i, score)| scores[i] != score)
+            .map(|(i, score)| (ids[i], score))
+            .collect(),
+    )
+}
+
+// Events from the row's buttons and checkboxes are theirs.
+fn on_row(e: &Event) -> bool {
+    e.target_unchecked_into::<web_sys::Element>().tag_name() == "TR"
 }
 
 pub enum Msg {
     AttendMeeting(u32),
     Delete(u32),
     Down(u32),
+    EditDescription(u32),
+    EditLinks(u32),
+    Focus(u32),   // moves keyboard focus to the item's row
+    Focused(u32), // the item's row took focus, from a click or the Tab key
+    ObserveMeeting(u32),
     RegisterToggle(u32),
+    TogglePrivate(u32),
     Up(u32),
+    Veto(u32),
 }
 
-pub struct Ranking {}
+/// The list every ranked thing in the UI uses, whether user topics, meetings,
+/// or a meeting's ballot, with each row moved up and down to change its score.
+/// Each control reports through its own callback in `Props`, by item ID.
+// https://www.w3.org/WAI/ARIA/apg/practices/keyboard-interface/
+// Only the focused row is in the Tab key order; the arrow keys move between rows,
+// and with Ctrl held, they move the focused item up or down the list.
+pub struct Ranking {
+    focused: Option<u32>, // id of the item whose row has, or last had, keyboard focus
+    focused_ref: NodeRef,
+    refocus: bool,        // the focused row should take focus once rendered
+    announcement: String, // read out by screen readers after an item moves
+}
+
+impl Ranking {
+    fn move_item(&mut self, ctx: &Context<Self>, id: u32, up: bool) -> bool {
+        let Props {
+            ids,
+            labels,
+            scores,
+            store_scores,
+            ..
+        } = ctx.props();
+        match moved_scores(ids, scores, id, up) {
+            Some(moved) => {
+                store_scores.emit(moved);
+                self.focused = Some(id);
+                let label = ids.iter().position(|&i| i == id).map(|i| &labels[i]);
+                let label = label.map_or("", |l| l.as_str());
+                let moved = if up {
+                    "ranking-moved-up"
+                } else {
+                    "ranking-moved-down"
+                };
+                self.announcement = i18n::text_with(moved, &[("label", label.into())]);
+                true
+            }
+            None => false,
+        }
+    }
+}
 
 impl Component for Ranking {
     type Message = Msg;
     type Properties = Props;
 
     fn create(_ctx: &Context<Self>) -> Self {
-        Self {}
+        Self {
+            focused: None,
+            focused_ref: NodeRef::default(),
+            refocus: false,
+            announcement: "".to_owned(),
+        }
+    }
+
+    fn changed(&mut self, _ctx: &Context<Self>) -> bool {
+        // Moving an item gives its row a new place, so keyboard focus follows it there.
+        let active = gloo_utils::document()
+            .active_element()
+            .map(web_sys::Node::from);
+        self.refocus |= active.is_some() && self.focused_ref.get() == active;
+        true
     }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
+        if !std::mem::take(&mut self.refocus) {
+            return;
+        }
+        if let Some(row) = self.focused_ref.cast::<web_sys::HtmlElement>() {
+            if let Err(e) = row.focus() {
+                console_dbg!(e);
+            }
+        }
+    }
+
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::AttendMeeting(id) => {
@@ -53,24 +214,30 @@ impl Component for Ranking {
                     false
                 }
             }
-            Msg::Down(id) => {
-                let scores = &ctx.props().scores;
-                let ids = &ctx.props().ids;
-                let order = argsort(scores);
-                if let Some(pos) = ids.iter().position(|&i| i == id) {
-                    if order[pos] == 0 {
-                        false
-                    } else {
-                        let i_below = order.iter().position(|&i| i == order[pos] - 1).unwrap();
-                        ctx.props()
-                            .store_score
-                            .emit((ids[i_below], scores[pos] as u32));
-                        ctx.props().store_score.emit((id, (scores[i_below]) as u32));
-                        true
-                    }
-                } else {
-                    false
+            Msg::Down(id) => self.move_item(ctx, id, false),
+            Msg::EditDescription(id) => {
+                if let Some(edit_description) = &ctx.props().edit_description {
+                    edit_description.emit(id);
+                }
+                false
+            }
+            Msg::EditLinks(id) => {
+                if let Some(edit_links) = &ctx.props().edit_links {
+                    edit_links.emit(id);
                 }
+                false
+            }
+            Msg::Focus(id) => {
+                self.focused = Some(id);
+                self.refocus = true;
+                true
+            }
+            Msg::Focused(id) => self.focused.replace(id) != Some(id),
+            Msg::ObserveMeeting(id) => {
+                if let Some(observe_meeting) = &ctx.props().observe_meeting {
+                    observe_meeting.emit(id);
+                }
+                false
             }
             Msg::RegisterToggle(id) => {
                 if ctx.props().register_toggle.is_some() {
@@ -80,24 +247,18 @@ impl Component for Ranking {
                     false
                 }
             }
-            Msg::Up(id) => {
-                let scores = &ctx.props().scores;
-                let ids = &ctx.props().ids;
-                let order = argsort(scores);
-                if let Some(pos) = ids.iter().position(|&i| i == id) {
-                    if order[pos] == ids.len() - 1 {
-                        false
-                    } else {
-                        let i_above = order.iter().position(|&i| i == order[pos] + 1).unwrap();
-                        ctx.props()
-                            .store_score
-                            .emit((ids[i_above], scores[pos] as u32));
-                        ctx.props().store_score.emit((id, (scores[i_above]) as u32));
-                        true
-                    }
-                } else {
-                    false
+            Msg::TogglePrivate(id) => {
+                if let Some(toggle_private) = &ctx.props().toggle_private {
+                    toggle_private.emit(id);
+                }
+                false
+            }
+            Msg::Up(id) => self.move_item(ctx, id, true),
+            Msg::Veto(id) => {
+                if let Some(veto) = &ctx.props().veto {
+                    veto.emit(id);
                 }
+                false
             }
         }
     }
@@ -105,40 +266,115 @@ impl Component for Ranking {
     fn view(&self, ctx: &Context<Self>) -> Html {
         let Props {
             delete,
+            deletable,
             ids,
             labels,
             scores,
             registered_counts,
+            cohort_sizes,
             joined_counts,
             is_registered,
             attend_meeting,
+            observe_meeting,
             register_toggle,
+            host_suggested,
+            registration_badges,
+            registration_open,
+            histories,
+            links,
+            edit_links,
+            linkable,
+            descriptions,
+            edit_description,
+            vetoed,
+            veto,
+            private,
+            toggle_private,
+            order,
             ..
         } = ctx.props();
-        let order = argsort(scores);
+        let reorderable = order.is_none();
+        let order = match order {
+            Some(order) => order.clone(),
+            None => argsort(scores).into_iter().rev().collect(),
+        };
+        let shown_ids: Vec<u32> = order.iter().map(|&i| ids[i]).collect();
+        // A row stays in the Tab key order after its item is deleted or hidden.
+        let tab_stop = self
+            .focused
+            .filter(|id| shown_ids.contains(id))
+            .or_else(|| shown_ids.first().copied());
         let mut items: Vec<_> = vec![];
 
-        for (list_item_offset, i) in order.into_iter().rev().enumerate() {
+        for (list_item_offset, i) in order.into_iter().enumerate() {
             let id = ids[i];
-            let attend_meeting_html = if attend_meeting.is_some()
+            let can_join = attend_meeting.is_some()
                 && is_registered.as_ref().unwrap()[i]
                 && registered_counts.is_some()
-                && registered_counts.as_ref().unwrap()[i] >= COHORT_QUORUM as u32
-            {
+                && registered_counts.as_ref().unwrap()[i]
+                    >= cohort_sizes.as_ref().map_or(COHORT_QUORUM as u32, |s| s[i]);
+            let is_reg = is_registered.as_ref().is_some_and(|r| r[i]);
+            // Registrants can still withdraw after registration closes.
+            let locked = !is_reg && registration_open.as_ref().is_some_and(|o| !o[i]);
+            let activate: Option<fn(u32) -> Msg> = if can_join {
+                Some(Msg::AttendMeeting)
+            } else if register_toggle.is_some() && !locked {
+                Some(Msg::RegisterToggle)
+            } else {
+                None
+            };
+            let previous = list_item_offset
+                .checked_sub(1)
+                .map(|offset| shown_ids[offset]);
+            let next = shown_ids.get(list_item_offset + 1).copied();
+            let (first, last) = (shown_ids[0], shown_ids[shown_ids.len() - 1]);
+            let onfocus = ctx
+                .link()
+                .batch_callback(move |e: FocusEvent| on_row(&e).then(|| Msg::Focused(id)));
+            let onkeydown = ctx.link().batch_callback(move |e: KeyboardEvent| {
+                if !on_row(&e) {
+                    return None;
+                }
+                let msg = match (e.key().as_str(), e.ctrl_key()) {
+                    ("ArrowUp", true) if reorderable => Msg::Up(id),
+                    ("ArrowDown", true) if reorderable => Msg::Down(id),
+                    ("ArrowUp", false) => Msg::Focus(previous?),
+                    ("ArrowDown", false) => Msg::Focus(next?),
+                    ("Home", _) => Msg::Focus(first),
+                    ("End", _) => Msg::Focus(last),
+                    ("Enter" | " ", _) => activate?(id),
+                    _ => return None,
+                };
+                e.prevent_default();
+                Some(msg)
+            });
+            let observe_meeting_html = if observe_meeting.is_some() {
+                html! {
+                    <button
+                        onclick={ctx.link().callback(move |_| Msg::ObserveMeeting(id))}
+                        type={"button"}
+                        class={"btn btn-outline-secondary ms-1"}
+                        title={i18n::text("ranking-observe-help")}
+                    >{i18n::text("ranking-observe")}</button>
+                }
+            } else {
+                html! {}
+            };
+            let attend_meeting_html = if can_join {
                 html! {
                     <td>
                         <button
                             onclick={ctx.link().callback(move |_| Msg::AttendMeeting(id))}
                             type={"button"}
                             class={"btn btn-secondary"}
-                        >{"join now"}</button>
+                        >{i18n::text("ranking-join")}</button>
+                        {observe_meeting_html}
                     </td>
                 }
             } else {
                 html! { <td></td> }
             };
             let register_toggle_html = if register_toggle.is_some() {
-                let is_reg = is_registered.as_ref().unwrap()[i];
                 let register_id = format!("register{id}");
                 html! {
                     <td>
@@ -149,12 +385,13 @@ impl Component for Ranking {
                                 type={"checkbox"}
                                 value=""
                                 checked={ is_reg }
+                                disabled={ locked }
                                 autocomplete={"off"}
                                 onclick={ctx.link().callback(move |_| Msg::RegisterToggle(id))}
                             />
                             <label
                                 class="form-check-label"
-                                for={register_id}>{"register"}
+                                for={register_id}>{i18n::text("ranking-register")}
                             </label>
                         </div>
                     </td>
@@ -162,20 +399,21 @@ impl Component for Ranking {
             } else {
                 html! { <td></td> }
             };
-            let delete_html = if delete.is_some() {
+            let delete_html = if delete.is_some() && deletable.as_ref().map_or(true, |d| d[i]) {
                 html! {
                     <td>
                         <button
                         onclick={ctx.link().callback(move |_| Msg::Delete(id))}
                         type={"button"}
                         class={"btn"}
+                        aria-label={i18n::text("ranking-delete")}
                         >{ x_icon() }</button>
                     </td>
                 }
             } else {
                 html! { <td></td> }
             };
-            let up_button = if list_item_offset == 0 {
+            let up_button = if !reorderable || list_item_offset == 0 {
                 html! {}
             } else {
                 html! {
@@ -183,10 +421,12 @@ impl Component for Ranking {
                     onclick={ctx.link().callback(move |_| Msg::Up(id))}
                     type={"button"}
                     class={"btn"}
+                    aria-label={i18n::text("ranking-move-up")}
+                    aria-keyshortcuts={"Control+ArrowUp"}
                     >{ up_arrow() }</button>
                 }
             };
-            let down_button = if list_item_offset == scores.len() - 1 {
+            let down_button = if !reorderable || list_item_offset == scores.len() - 1 {
                 html! {}
             } else {
                 html! {
@@ -194,6 +434,8 @@ impl Component for Ranking {
                     onclick={ctx.link().callback(move |_| Msg::Down(id))}
                     type={"button"}
                     class={"btn"}
+                    aria-label={i18n::text("ranking-move-down")}
+                    aria-keyshortcuts={"Control+ArrowDown"}
                     >{ down_arrow() }</button>
                 }
             };
@@ -202,19 +444,116 @@ impl Component for Ranking {
                 let j = joined_counts.as_ref().unwrap()[i];
                 html! {
                     <>
-                        <td>{format!("registered:{r}")}</td>
-                        <td>{format!("joined:{j}")}</td>
+                        <td>{i18n::text_with("ranking-registered", &[("count", r.into())])}</td>
+                        <td>{i18n::text_with("ranking-joined", &[("count", j.into())])}</td>
                     </>
                 }
             } else {
                 html! {}
             };
+            let host_suggested_html = if host_suggested.is_some()
+                && host_suggested.as_ref().unwrap()[i]
+            {
+                html! {
+                    <span class="badge bg-info ms-2">{i18n::text("ranking-host-suggested")}</span>
+                }
+            } else {
+                html! {}
+            };
+            let registration_badge_html = match registration_badges {
+                Some(badges) if !badges[i].is_empty() => html! {
+                    <span class="badge bg-secondary ms-2">{badges[i].clone()}</span>
+                },
+                _ => html! {},
+            };
+            let links_html = match links {
+                Some(links) => links_html(&links[i]),
+                None => html! {},
+            };
+            let edit_links_html =
+                if edit_links.is_some() && linkable.as_ref().map_or(true, |l| l[i]) {
+                    html! {
+                        <button
+                            onclick={ctx.link().callback(move |_| Msg::EditLinks(id))}
+                            type={"button"}
+                            class={"btn btn-sm btn-link"}
+                        >{i18n::text("ranking-links")}</button>
+                    }
+                } else {
+                    html! {}
+                };
+            let edit_description_html = if edit_description.is_some() {
+                html! {
+                    <button
+                        onclick={ctx.link().callback(move |_| Msg::EditDescription(id))}
+                        type={"button"}
+                        class={"btn btn-sm btn-link"}
+                    >{i18n::text("ranking-describe")}</button>
+                }
+            } else {
+                html! {}
+            };
+            let description_html = match descriptions {
+                Some(descriptions) if !descriptions[i].is_empty() => html! {
+                    <div class="small text-muted">{markdown_html(&descriptions[i])}</div>
+                },
+                _ => html! {},
+            };
+            let is_vetoed = vetoed.as_ref().is_some_and(|v| v[i]);
+            let veto_html = if veto.is_some() {
+                html! {
+                    <button
+                        onclick={ctx.link().callback(move |_| Msg::Veto(id))}
+                        type={"button"}
+                        aria-pressed={if is_vetoed { "true" } else { "false" }}
+                        class={if is_vetoed { "btn btn-sm btn-danger ms-2" } else { "btn btn-sm btn-outline-danger ms-2" }}
+                    >{i18n::text(if is_vetoed { "badge-vetoed" } else { "ranking-veto" })}</button>
+                }
+            } else if is_vetoed {
+                html! { <span class="badge bg-danger ms-2">{i18n::text("badge-vetoed")}</span> }
+            } else {
+                html! {}
+            };
+            let is_private = private.as_ref().is_some_and(|p| p[i]);
+            let private_html = if toggle_private.is_some() {
+                html! {
+                    <button
+                        onclick={ctx.link().callback(move |_| Msg::TogglePrivate(id))}
+                        type={"button"}
+                        class={"btn btn-sm ms-2"}
+                        title={i18n::text(if is_private { "ranking-private-on" } else { "ranking-private-off" })}
+                        aria-label={i18n::text("ranking-private")}
+                        aria-pressed={if is_private { "true" } else { "false" }}
+                    >{if is_private { lock_icon() } else { unlock_icon() }}</button>
+                }
+            } else {
+                html! {}
+            };
+            let history_html = if let Some(histories) = histories {
+                html! { <td>{sparkline(&histories[i])}</td> }
+            } else {
+                html! {}
+            };
+            let focused = tab_stop == Some(id);
             items.push(html! {
-                <tr>
+                <tr
+                    ref={if focused { self.focused_ref.clone() } else { NodeRef::default() }}
+                    tabindex={if focused { "0" } else { "-1" }}
+                    { onfocus }
+                    { onkeydown }
+                >
                     {attend_meeting_html}
                     {register_toggle_html}
                     <td>
                         {labels[i].clone()}
+                        {host_suggested_html}
+                        {registration_badge_html}
+                        {links_html}
+                        {edit_links_html}
+                        {edit_description_html}
+                        {veto_html}
+                        {private_html}
+                        {description_html}
                     </td>
                     <td>
                         {up_button}
@@ -223,16 +562,65 @@ impl Component for Ranking {
                         {down_button}
                     </td>
                     {participants_html}
+                    {history_html}
                     {delete_html}
                 </tr>
             });
         }
+        let mut keys = vec![i18n::text("ranking-keys")];
+        if reorderable {
+            keys.push(i18n::text("ranking-keys-move"));
+        }
+        if attend_meeting.is_some() || register_toggle.is_some() {
+            keys.push(i18n::text("ranking-keys-join"));
+        }
         html! {
-            <table class="table table-striped">
-                <tbody>
-                    {items}
-                </tbody>
-            </table>
+            <>
+                <table class="table table-striped">
+                    <caption class="visually-hidden">{keys.join(" ")}</caption>
+                    <tbody>
+                        {items}
+                    </tbody>
+                </table>
+                <div class="visually-hidden" aria-live="polite">{self.announcement.clone()}</div>
+            </>
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::moved_scores;
+
+    #[test]
+    fn test_moved_scores() {
+        // Shown as 20, 30, 10
+        let ids = [10, 20, 30];
+        let scores = [0, 5, 3];
+        assert_eq!(
+            moved_scores(&ids, &scores, 30, true),
+            Some(vec![(20, 3), (30, 5)])
+        );
+        assert_eq!(
+            moved_scores(&ids, &scores, 30, false),
+            Some(vec![(10, 3), (30, 0)])
+        );
+        assert_eq!(moved_scores(&ids, &scores, 20, true), None);
+        assert_eq!(moved_scores(&ids, &scores, 10, false), None);
+        assert_eq!(moved_scores(&ids, &scores, 40, true), None);
+    }
+
+    #[test]
+    fn test_moved_scores_tied() {
+        // Ties show the later item first, so this is shown as 30, 20, 10.
+        let ids = [10, 20, 30];
+        let scores = [0, 1, 1];
+        assert_eq!(moved_scores(&ids, &scores, 20, true), Some(vec![(20, 2)]));
+        assert_eq!(moved_scores(&ids, &scores, 30, false), Some(vec![(20, 2)]));
+        assert_eq!(moved_scores(&[10, 20], &[0, 0], 10, false), None);
+        assert_eq!(
+            moved_scores(&[10, 20], &[0, 0], 20, false),
+            Some(vec![(10, 1)])
+        );
+    }
+}
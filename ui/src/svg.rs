@@ -57,3 +57,70 @@ pub fn up_arrow() -> Html {
         </svg>
     }
 }
+
+// https://icons.getbootstrap.com/icons/lock-fill/
+pub fn lock_icon() -> Html {
+    html! {
+        <svg
+            xmlns="http://www.w3.org/2000/svg"
+            width="16"
+            height="16"
+            fill="currentColor"
+            class="bi bi-lock-fill"
+            viewBox="0 0 16 16"
+        >
+            <path d="M8 1a2 2 0 0 1 2 2v4H6V3a2 2 0 0 1 2-2zm3 6V3a3 3 0 0 0-6 0v4a2 2 0 0 0-2 2v5a2 2 0 0 0 2 2h6a2 2 0 0 0 2-2V9a2 2 0 0 0-2-2z"/>
+        </svg>
+    }
+}
+
+// https://icons.getbootstrap.com/icons/unlock/
+pub fn unlock_icon() -> Html {
+    html! {
+        <svg
+            xmlns="http://www.w3.org/2000/svg"
+            width="16"
+            height="16"
+            fill="currentColor"
+            class="bi bi-unlock"
+            viewBox="0 0 16 16"
+        >
+            <path d="M11 1a2 2 0 0 0-2 2v4a2 2 0 0 1 2 2v5a2 2 0 0 1-2 2H3a2 2 0 0 1-2-2V9a2 2 0 0 1 2-2h5V3a3 3 0 0 1 6 0v4a.5.5 0 0 1-1 0V3a2 2 0 0 0-2-2zM3 8a1 1 0 0 0-1 1v5a1 1 0 0 0 1 1h6a1 1 0 0 0 1-1V9a1 1 0 0 0-1-1H3z"/>
+        </svg>
+    }
+}
+
+// A line with no axes, for showing a trend next to a label
+pub fn sparkline(values: &[u32]) -> Html {
+    const WIDTH: f64 = 80.0;
+    const HEIGHT: f64 = 16.0;
+    if values.len() < 2 {
+        return html! {};
+    }
+    let min = *values.iter().min().unwrap() as f64;
+    let max = *values.iter().max().unwrap() as f64;
+    let step = WIDTH / (values.len() - 1) as f64;
+    let points = values
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| {
+            let y = if max > min {
+                HEIGHT - (v as f64 - min) / (max - min) * HEIGHT
+            } else {
+                HEIGHT / 2.0
+            };
+            format!("{:.1},{y:.1}", i as f64 * step)
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    html! {
+        <svg
+            xmlns="http://www.w3.org/2000/svg"
+            width={WIDTH.to_string()}
+            height={HEIGHT.to_string()}
+            viewBox={format!("-1 -1 {} {}", WIDTH + 2.0, HEIGHT + 2.0)}
+        >
+            <polyline points={points} fill="none" stroke="currentColor" stroke-width="1.5"/>
+        </svg>
+    }
+}
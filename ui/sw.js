@@ -0,0 +1,37 @@
+// Serves the hashed wasm and js bundles from a cache named for their version.
+// The page registers this worker as /sw.js?v=<hash>, so a new UI build
+// installs a new worker whose activation drops the previous version's cache.
+const VERSION = new URL(self.location).searchParams.get('v');
+const CACHE = 'ehallway-' + VERSION;
+
+self.addEventListener('install', (event) => {
+  event.waitUntil(
+    caches.open(CACHE).then((cache) =>
+      cache.addAll(['/index-' + VERSION + '.js', '/index-' + VERSION + '_bg.wasm'])
+    )
+  );
+});
+
+self.addEventListener('activate', (event) => {
+  event.waitUntil(
+    caches.keys().then((keys) =>
+      Promise.all(keys.filter((key) => key !== CACHE).map((key) => caches.delete(key)))
+    )
+  );
+});
+
+// The UI asks for this when /instance_info reports a newer version.
+self.addEventListener('message', (event) => {
+  if (event.data && event.data.type === 'SKIP_WAITING') {
+    self.skipWaiting();
+  }
+});
+
+self.addEventListener('fetch', (event) => {
+  const url = new URL(event.request.url);
+  if (url.pathname.startsWith('/index-')) {
+    event.respondWith(
+      caches.match(event.request).then((hit) => hit || fetch(event.request))
+    );
+  }
+});